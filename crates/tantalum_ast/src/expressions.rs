@@ -6,13 +6,20 @@ use crate::{Literal, Type};
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression<'file_name, 'source> {
     Variable(#[cfg_attr(feature = "serde", serde(borrow))] Variable<'file_name, 'source>),
+    Path(Path<'file_name, 'source>),
     Literal(Literal<'file_name, 'source>),
     FunctionCall(FunctionCall<'file_name, 'source>),
+    StructLiteral(StructLiteral<'file_name, 'source>),
+    ArrayLiteral(ArrayLiteral<'file_name, 'source>),
+    ArrayRepeat(ArrayRepeat<'file_name, 'source>),
     MemberAccess(MemberAccess<'file_name, 'source>),
     Index(Index<'file_name, 'source>),
     UnaryOperation(UnaryOperation<'file_name, 'source>),
     BinaryOperation(BinaryOperation<'file_name, 'source>),
     TypeCast(TypeCast<'file_name, 'source>),
+    VariantCheck(VariantCheck<'file_name, 'source>),
+    /// An expression that could not be parsed, the error has already been reported
+    Error,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -22,6 +29,14 @@ pub struct Variable<'file_name, 'source> {
     pub name: Spanned<'file_name, &'source str>,
 }
 
+/// A name made of several segments, e.g. `Color::Red`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub segments: Vec<Spanned<'file_name, &'source str>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionCall<'file_name, 'source> {
@@ -30,6 +45,24 @@ pub struct FunctionCall<'file_name, 'source> {
     pub arguments: Vec<Spanned<'file_name, Expression<'file_name, 'source>>>,
 }
 
+/// A value of a struct, e.g. `Point { x: 1, y: 2 }`, or of a variant of an
+/// enum with named fields, e.g. `Shape::Rect { w: 1.0, h: 2.0 }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructLiteral<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, Path<'file_name, 'source>>,
+    pub fields: Vec<Spanned<'file_name, FieldInitializer<'file_name, 'source>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldInitializer<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub value: Spanned<'file_name, Expression<'file_name, 'source>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemberAccess<'file_name, 'source> {
@@ -38,6 +71,23 @@ pub struct MemberAccess<'file_name, 'source> {
     pub member: Spanned<'file_name, &'source str>,
 }
 
+/// An array of the values of its elements, e.g. `[1, 2, 3]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayLiteral<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub elements: Vec<Spanned<'file_name, Expression<'file_name, 'source>>>,
+}
+
+/// An array of a value repeated a number of times, e.g. `[0; 8]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayRepeat<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub value: Box<Spanned<'file_name, Expression<'file_name, 'source>>>,
+    pub length: Spanned<'file_name, usize>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Index<'file_name, 'source> {
@@ -108,3 +158,12 @@ pub struct TypeCast<'file_name, 'source> {
     pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
     pub value: Box<Spanned<'file_name, Expression<'file_name, 'source>>>,
 }
+
+/// A check of which variant of an enum a value is, e.g. `shape is Shape::Circle`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariantCheck<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub value: Box<Spanned<'file_name, Expression<'file_name, 'source>>>,
+    pub variant: Spanned<'file_name, Path<'file_name, 'source>>,
+}
@@ -1,6 +1,6 @@
 use tantalum_span::Spanned;
 
-use crate::{Statement, Type};
+use crate::{Expression, Path, Statement, Type};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -9,6 +9,20 @@ pub enum Item<'file_name, 'source> {
     ExternalFunction(
         #[cfg_attr(feature = "serde", serde(borrow))] ExternalFunction<'file_name, 'source>,
     ),
+    Struct(#[cfg_attr(feature = "serde", serde(borrow))] Struct<'file_name, 'source>),
+    Enum(#[cfg_attr(feature = "serde", serde(borrow))] Enum<'file_name, 'source>),
+    Trait(#[cfg_attr(feature = "serde", serde(borrow))] Trait<'file_name, 'source>),
+    Impl(#[cfg_attr(feature = "serde", serde(borrow))] Impl<'file_name, 'source>),
+    Module(#[cfg_attr(feature = "serde", serde(borrow))] Module<'file_name, 'source>),
+    Use(#[cfg_attr(feature = "serde", serde(borrow))] Use<'file_name, 'source>),
+    TypeAlias(#[cfg_attr(feature = "serde", serde(borrow))] TypeAlias<'file_name, 'source>),
+    Constant(#[cfg_attr(feature = "serde", serde(borrow))] Constant<'file_name, 'source>),
+    Static(#[cfg_attr(feature = "serde", serde(borrow))] Static<'file_name, 'source>),
+    ExternalStatic(
+        #[cfg_attr(feature = "serde", serde(borrow))] ExternalStatic<'file_name, 'source>,
+    ),
+    /// An item that could not be parsed, the error has already been reported
+    Error,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -16,6 +30,9 @@ pub enum Item<'file_name, 'source> {
 pub struct Function<'file_name, 'source> {
     #[cfg_attr(feature = "serde", serde(borrow))]
     pub name: Spanned<'file_name, &'source str>,
+    /// The type parameters of a generic function, e.g. `<T>` in
+    /// `fn max<T>(a: T, b: T): T`
+    pub generics: Vec<Spanned<'file_name, GenericParameter<'file_name, 'source>>>,
     pub parameters: Spanned<'file_name, Vec<Spanned<'file_name, Parameter<'file_name, 'source>>>>,
     pub return_type: Option<Spanned<'file_name, Type<'file_name, 'source>>>,
     pub body: Spanned<'file_name, Statement<'file_name, 'source>>,
@@ -37,6 +54,17 @@ pub enum Parameter<'file_name, 'source> {
     Variadic,
 }
 
+/// A type parameter of a generic item, replaced by a concrete type wherever
+/// the item is used
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericParameter<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    /// The traits the type must implement, e.g. `Display` in `<T: Display>`
+    pub bounds: Vec<Spanned<'file_name, &'source str>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamedParameter<'file_name, 'source> {
@@ -44,3 +72,166 @@ pub struct NamedParameter<'file_name, 'source> {
     pub name: Spanned<'file_name, &'source str>,
     pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Struct<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub generics: Vec<Spanned<'file_name, GenericParameter<'file_name, 'source>>>,
+    pub fields: Spanned<'file_name, Vec<Spanned<'file_name, StructField<'file_name, 'source>>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructField<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
+}
+
+/// An enum, e.g. `enum Color: u8 { Red = 1, Green, Blue }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Enum<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub generics: Vec<Spanned<'file_name, GenericParameter<'file_name, 'source>>>,
+    /// The integer type the discriminants are stored as
+    pub ty: Option<Spanned<'file_name, Type<'file_name, 'source>>>,
+    pub variants: Spanned<'file_name, Vec<Spanned<'file_name, EnumVariant<'file_name, 'source>>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumVariant<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub fields: VariantFields<'file_name, 'source>,
+    pub discriminant: Option<Spanned<'file_name, Expression<'file_name, 'source>>>,
+}
+
+/// The values carried by a variant of an enum
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariantFields<'file_name, 'source> {
+    /// A variant without any values, e.g. `Empty`
+    Unit,
+    /// A variant with unnamed values, e.g. `Circle(f32)`
+    Tuple(
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        Spanned<'file_name, Vec<Spanned<'file_name, Type<'file_name, 'source>>>>,
+    ),
+    /// A variant with named values, e.g. `Rect { w: f32, h: f32 }`
+    Struct(
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        Spanned<'file_name, Vec<Spanned<'file_name, StructField<'file_name, 'source>>>>,
+    ),
+}
+
+/// A trait, e.g. `trait Shape { fn area(self: *Self): f32; }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trait<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub methods: Spanned<'file_name, Vec<Spanned<'file_name, TraitMethod<'file_name, 'source>>>>,
+}
+
+/// The signature of a method of a trait, which every implementation of the
+/// trait must define, e.g. `fn area(self: *Self): f32;`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraitMethod<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub parameters: Spanned<'file_name, Vec<Spanned<'file_name, Parameter<'file_name, 'source>>>>,
+    pub return_type: Option<Spanned<'file_name, Type<'file_name, 'source>>>,
+}
+
+/// An implementation of a trait for a type, e.g.
+/// `impl Shape for Circle { fn area(self: *Self): f32 { ... } }`, or
+/// `impl Circle { fn new(radius: f32): Circle { ... } }` without a trait
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Impl<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub trait_name: Option<Spanned<'file_name, &'source str>>,
+    pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
+    pub methods: Spanned<'file_name, Vec<Spanned<'file_name, Function<'file_name, 'source>>>>,
+}
+
+/// A module, e.g. `mod shapes { struct Circle { radius: f32 } }`, whose items
+/// are named from outside of it by their path, e.g. `shapes::Circle`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Module<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    /// The items of the module, which are in a file of their own for
+    /// `mod shapes;` until the file is loaded into the package
+    pub items: Option<Spanned<'file_name, Vec<Spanned<'file_name, Item<'file_name, 'source>>>>>,
+}
+
+/// An import of items from another module, e.g. `use shapes::Circle;` or
+/// `use shapes::{Circle, Square as Quad};`, so that they can be named without
+/// their path
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Use<'file_name, 'source> {
+    /// The path of the module the items are in, e.g. `shapes`, which has no
+    /// segments for items at the root of the package
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub prefix: Path<'file_name, 'source>,
+    pub imports: Vec<Spanned<'file_name, UseImport<'file_name, 'source>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UseImport<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    /// The name the item is imported as, e.g. `Quad` in `Square as Quad`
+    pub alias: Option<Spanned<'file_name, &'source str>>,
+}
+
+/// Another name for a type, e.g. `type Meters = f32;`, which can also be
+/// declared inside a block
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeAlias<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
+}
+
+/// A value computed at compile time, e.g. `const LIMIT: i32 = 4 * 16;`, which
+/// is copied wherever it is used
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Constant<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
+    pub value: Spanned<'file_name, Expression<'file_name, 'source>>,
+}
+
+/// A variable stored once for the whole program, e.g.
+/// `static COUNTER: i32 = 0;`, whose initial value is computed at compile time
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Static<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
+    pub value: Spanned<'file_name, Expression<'file_name, 'source>>,
+}
+
+/// A variable defined outside of the package, e.g. `extern static errno: i32;`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalStatic<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    pub ty: Spanned<'file_name, Type<'file_name, 'source>>,
+}
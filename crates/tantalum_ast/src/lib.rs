@@ -5,12 +5,14 @@
 mod expressions;
 mod items;
 mod literals;
+mod patterns;
 mod statements;
 mod types;
 
 pub use expressions::*;
 pub use items::*;
 pub use literals::*;
+pub use patterns::*;
 pub use statements::*;
 use tantalum_span::Spanned;
 pub use types::*;
@@ -40,6 +42,17 @@ pub trait ASTVisitor<'file_name, 'source> {
             Item::ExternalFunction(external_function) => {
                 self.visit_external_function(external_function);
             }
+            Item::Struct(structure) => self.visit_struct(structure),
+            Item::Enum(enumeration) => self.visit_enum(enumeration),
+            Item::Trait(trait_) => self.visit_trait(trait_),
+            Item::Impl(impl_) => self.visit_impl(impl_),
+            Item::Module(module) => self.visit_module(module),
+            Item::Use(use_) => self.visit_use(use_),
+            Item::TypeAlias(alias) => self.visit_type_alias(alias),
+            Item::Constant(constant) => self.visit_constant(constant),
+            Item::Static(static_) => self.visit_static(static_),
+            Item::ExternalStatic(external_static) => self.visit_external_static(external_static),
+            Item::Error => self.visit_error_item(),
         }
     }
 
@@ -49,6 +62,21 @@ pub trait ASTVisitor<'file_name, 'source> {
         external_function: &ExternalFunction<'file_name, 'source>,
     ) {
     }
+    fn visit_struct(&mut self, structure: &Struct<'file_name, 'source>) {}
+    fn visit_enum(&mut self, enumeration: &Enum<'file_name, 'source>) {}
+    fn visit_trait(&mut self, trait_: &Trait<'file_name, 'source>) {}
+    fn visit_impl(&mut self, impl_: &Impl<'file_name, 'source>) {}
+    fn visit_module(&mut self, module: &Module<'file_name, 'source>) {
+        for item in module.items.iter().flat_map(Spanned::data) {
+            self.visit_item(item.data());
+        }
+    }
+    fn visit_use(&mut self, use_: &Use<'file_name, 'source>) {}
+    fn visit_type_alias(&mut self, alias: &TypeAlias<'file_name, 'source>) {}
+    fn visit_constant(&mut self, constant: &Constant<'file_name, 'source>) {}
+    fn visit_static(&mut self, static_: &Static<'file_name, 'source>) {}
+    fn visit_external_static(&mut self, external_static: &ExternalStatic<'file_name, 'source>) {}
+    fn visit_error_item(&mut self) {}
 
     ////////////////////////////////////////////////////////////////////////////
     // Parameters
@@ -76,6 +104,7 @@ pub trait ASTVisitor<'file_name, 'source> {
             Type::SizedArray(array) => self.visit_sized_array_type(array),
             Type::UnsizedArray(array) => self.visit_unsized_array_type(array),
             Type::Const(constant) => self.visit_const_type(constant),
+            Type::Dyn(dyn_) => self.visit_dyn_type(dyn_),
         }
     }
 
@@ -85,6 +114,7 @@ pub trait ASTVisitor<'file_name, 'source> {
     fn visit_sized_array_type(&mut self, array: &SizedArrayType<'file_name, 'source>) {}
     fn visit_unsized_array_type(&mut self, array: &UnsizedArrayType<'file_name, 'source>) {}
     fn visit_const_type(&mut self, constant: &ConstType<'file_name, 'source>) {}
+    fn visit_dyn_type(&mut self, dyn_: &DynType<'file_name, 'source>) {}
 
     ////////////////////////////////////////////////////////////////////////////
     // Statements
@@ -98,6 +128,7 @@ pub trait ASTVisitor<'file_name, 'source> {
             }
             Statement::If(if_statement) => self.visit_if(if_statement),
             Statement::While(while_statement) => self.visit_while(while_statement),
+            Statement::Match(match_statement) => self.visit_match(match_statement),
             Statement::ForInitCondUpdate(for_statement) => {
                 self.visit_for_init_cond_update(for_statement);
             }
@@ -105,6 +136,8 @@ pub trait ASTVisitor<'file_name, 'source> {
             Statement::Continue => self.visit_continue(),
             Statement::Return(return_statement) => self.visit_return(return_statement),
             Statement::Expression(expression) => self.visit_expression_statement(expression),
+            Statement::TypeAlias(alias) => self.visit_type_alias_statement(alias),
+            Statement::Error => self.visit_error_statement(),
         }
     }
 
@@ -116,6 +149,7 @@ pub trait ASTVisitor<'file_name, 'source> {
     }
     fn visit_if(&mut self, if_statement: &If<'file_name, 'source>) {}
     fn visit_while(&mut self, while_statement: &While<'file_name, 'source>) {}
+    fn visit_match(&mut self, match_statement: &Match<'file_name, 'source>) {}
     fn visit_for_init_cond_update(
         &mut self,
         for_statement: &ForInitCondUpdate<'file_name, 'source>,
@@ -127,6 +161,8 @@ pub trait ASTVisitor<'file_name, 'source> {
     fn visit_expression_statement(&mut self, expression: &Expression<'file_name, 'source>) {
         self.visit_expression(expression);
     }
+    fn visit_type_alias_statement(&mut self, alias: &TypeAlias<'file_name, 'source>) {}
+    fn visit_error_statement(&mut self) {}
 
     ////////////////////////////////////////////////////////////////////////////
     // Expressions
@@ -135,23 +171,35 @@ pub trait ASTVisitor<'file_name, 'source> {
     fn visit_expression(&mut self, expression: &Expression<'file_name, 'source>) {
         match expression {
             Expression::Variable(variable) => self.visit_variable(variable),
+            Expression::Path(path) => self.visit_path(path),
             Expression::Literal(literal) => self.visit_literal(literal),
             Expression::FunctionCall(call) => self.visit_function_call(call),
+            Expression::StructLiteral(literal) => self.visit_struct_literal(literal),
+            Expression::ArrayLiteral(literal) => self.visit_array_literal(literal),
+            Expression::ArrayRepeat(repeat) => self.visit_array_repeat(repeat),
             Expression::MemberAccess(access) => self.visit_member_access(access),
             Expression::Index(index) => self.visit_array_access(index),
             Expression::UnaryOperation(unary) => self.visit_unary_operation(unary),
             Expression::BinaryOperation(binary) => self.visit_binary_operation(binary),
             Expression::TypeCast(cast) => self.visit_type_cast(cast),
+            Expression::VariantCheck(check) => self.visit_variant_check(check),
+            Expression::Error => self.visit_error_expression(),
         }
     }
 
     fn visit_variable(&mut self, variable: &Variable<'file_name, 'source>) {}
+    fn visit_path(&mut self, path: &Path<'file_name, 'source>) {}
     fn visit_function_call(&mut self, call: &FunctionCall<'file_name, 'source>) {}
+    fn visit_struct_literal(&mut self, literal: &StructLiteral<'file_name, 'source>) {}
+    fn visit_array_literal(&mut self, literal: &ArrayLiteral<'file_name, 'source>) {}
+    fn visit_array_repeat(&mut self, repeat: &ArrayRepeat<'file_name, 'source>) {}
     fn visit_member_access(&mut self, access: &MemberAccess<'file_name, 'source>) {}
     fn visit_array_access(&mut self, index: &Index<'file_name, 'source>) {}
     fn visit_unary_operation(&mut self, unary: &UnaryOperation<'file_name, 'source>) {}
     fn visit_binary_operation(&mut self, binary: &BinaryOperation<'file_name, 'source>) {}
     fn visit_type_cast(&mut self, cast: &TypeCast<'file_name, 'source>) {}
+    fn visit_variant_check(&mut self, check: &VariantCheck<'file_name, 'source>) {}
+    fn visit_error_expression(&mut self) {}
 
     ////////////////////////////////////////////////////////////////////////////
     // Literals
@@ -0,0 +1,77 @@
+use crate::{Literal, Path};
+use tantalum_span::Spanned;
+
+/// A pattern that values are matched against in a `match` statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pattern<'file_name, 'source> {
+    /// Matches any value, `_`
+    Wildcard,
+    /// Matches any value and gives it a name, e.g. `radius`
+    Binding(#[cfg_attr(feature = "serde", serde(borrow))] Spanned<'file_name, &'source str>),
+    /// Matches a single value, e.g. `1`, `-1`, `'a'` or `true`
+    Literal(LiteralPattern<'file_name, 'source>),
+    /// Matches the values between two literals, e.g. `'a'..='z'` or `0..10`
+    Range(RangePattern<'file_name, 'source>),
+    /// Matches a variant of an enum and the values it carries, e.g.
+    /// `Shape::Circle(radius)`
+    Variant(VariantPattern<'file_name, 'source>),
+    /// Matches a value that any of the patterns match, e.g. `1 | 2`
+    Or(Vec<Spanned<'file_name, Pattern<'file_name, 'source>>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiteralPattern<'file_name, 'source> {
+    /// Whether the literal is preceded by a `-`
+    pub negative: bool,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub literal: Literal<'file_name, 'source>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangePattern<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub start: Spanned<'file_name, LiteralPattern<'file_name, 'source>>,
+    pub end: Spanned<'file_name, LiteralPattern<'file_name, 'source>>,
+    /// Whether `end` is part of the range, written `..=` instead of `..`
+    pub inclusive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariantPattern<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub path: Spanned<'file_name, Path<'file_name, 'source>>,
+    pub fields: VariantPatternFields<'file_name, 'source>,
+}
+
+/// The patterns the values carried by a variant are matched against
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariantPatternFields<'file_name, 'source> {
+    /// A variant without values, e.g. `Color::Red`
+    Unit,
+    /// The unnamed values of a variant, e.g. `Shape::Circle(radius)`
+    Tuple(
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        Spanned<'file_name, Vec<Spanned<'file_name, Pattern<'file_name, 'source>>>>,
+    ),
+    /// The named values of a variant, e.g. `Shape::Rect { w, h: 0.. }`.
+    /// Fields that are left out must be allowed with a trailing `..`
+    Struct {
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        fields: Spanned<'file_name, Vec<Spanned<'file_name, FieldPattern<'file_name, 'source>>>>,
+        rest: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldPattern<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<'file_name, &'source str>,
+    /// The pattern of the field, binds the field to its own name when left out
+    pub pattern: Option<Spanned<'file_name, Pattern<'file_name, 'source>>>,
+}
@@ -1,6 +1,6 @@
 use tantalum_span::Spanned;
 
-use crate::{Expression, Type};
+use crate::{Expression, Pattern, Type, TypeAlias};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -9,11 +9,15 @@ pub enum Statement<'file_name, 'source> {
     VariableDeclaration(VariableDeclaration<'file_name, 'source>),
     If(If<'file_name, 'source>),
     While(While<'file_name, 'source>),
+    Match(Match<'file_name, 'source>),
     ForInitCondUpdate(ForInitCondUpdate<'file_name, 'source>),
     Break,
     Continue,
     Return(Return<'file_name, 'source>),
     Expression(Expression<'file_name, 'source>),
+    TypeAlias(TypeAlias<'file_name, 'source>),
+    /// A statement that could not be parsed, the error has already been reported
+    Error,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -49,6 +53,23 @@ pub struct While<'file_name, 'source> {
     pub body: Box<Spanned<'file_name, Statement<'file_name, 'source>>>,
 }
 
+/// Runs the first arm whose pattern matches the scrutinee
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub scrutinee: Spanned<'file_name, Expression<'file_name, 'source>>,
+    pub arms: Vec<Spanned<'file_name, MatchArm<'file_name, 'source>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchArm<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub pattern: Spanned<'file_name, Pattern<'file_name, 'source>>,
+    pub body: Spanned<'file_name, Statement<'file_name, 'source>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ForInitCondUpdate<'file_name, 'source> {
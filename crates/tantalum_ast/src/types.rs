@@ -1,5 +1,7 @@
 use tantalum_span::Spanned;
 
+use crate::Path;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type<'file_name, 'source> {
@@ -10,13 +12,17 @@ pub enum Type<'file_name, 'source> {
     UnsizedArray(UnsizedArrayType<'file_name, 'source>),
 
     Const(ConstType<'file_name, 'source>),
+    Dyn(DynType<'file_name, 'source>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamedType<'file_name, 'source> {
+    /// The path of the type, e.g. `shapes::Circle`
     #[cfg_attr(feature = "serde", serde(borrow))]
-    pub name: Spanned<'file_name, &'source str>,
+    pub name: Spanned<'file_name, Path<'file_name, 'source>>,
+    /// The type arguments of a generic type, e.g. `i32` in `Option<i32>`
+    pub arguments: Vec<Spanned<'file_name, Type<'file_name, 'source>>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -56,3 +62,12 @@ pub struct ConstType<'file_name, 'source> {
     #[cfg_attr(feature = "serde", serde(borrow))]
     pub ty: Box<Spanned<'file_name, Type<'file_name, 'source>>>,
 }
+
+/// A value of any type implementing a trait, e.g. `dyn Shape`, only used
+/// behind a pointer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynType<'file_name, 'source> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub trait_name: Spanned<'file_name, &'source str>,
+}
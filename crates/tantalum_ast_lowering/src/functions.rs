@@ -1,47 +1,198 @@
+use std::collections::HashSet;
 use tantalum_ast::{
-    BinaryOperator, Boolean, Character, ConstType, Float, FunctionCall, FunctionType, Index,
-    Integer, MemberAccess, NamedType, PointerType, Return, SizedArrayType, UnaryOperation,
-    UnsizedArrayType, Variable,
+    ArrayLiteral, ArrayRepeat, BinaryOperator, Boolean, Character, ConstType, FieldInitializer,
+    FieldPattern, Float, ForInitCondUpdate, FunctionCall, FunctionType, Index, Integer,
+    LiteralPattern, MemberAccess, NamedType, PointerType, RangePattern, Return, SizedArrayType,
+    StructLiteral, UnaryOperation, UnsizedArrayType, Variable, VariantCheck, VariantPattern,
+    VariantPatternFields, While,
 };
-use tantalum_hlir::{Expression, HLIRPackage, Path, PathSegment, Statement, TypeId};
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_hlir::{
+    Expression, FunctionId, GlobalKind, HLIRPackage, InferenceId, Literal, LiteralValue, MatchArm,
+    Path, Pattern, Place, Statement, StructField, TraitId, TypeId, VariableId, VariantKind,
+};
+use tantalum_span::{Span, Spanned};
 
-pub struct FunctionLoweringContext<'a> {
-    package: &'a mut HLIRPackage,
+pub struct FunctionLoweringContext<'a, 'file_name> {
+    package: &'a mut HLIRPackage<'file_name>,
     type_stack: Vec<TypeId>,
     statement_stack: Vec<Statement>,
     expression_stack: Vec<Expression>,
+
+    /// The number of loops enclosing the statement being lowered
+    loop_depth: usize,
+    /// The number of or-patterns enclosing the pattern being lowered
+    or_pattern_depth: usize,
+
+    /// The trait, if any, and type of each `impl` block not lowered yet, if
+    /// both exist
+    impls: std::vec::IntoIter<Option<(Option<TraitId>, TypeId)>>,
+    /// The functions and methods lowered so far, to skip the ones defined
+    /// more than once
+    functions: HashSet<FunctionId>,
+    /// The constants and statics whose values have been lowered so far, for
+    /// the same reason
+    globals: HashSet<VariableId>,
+
+    /// The span of the type, statement, or expression currently being lowered
+    span: Option<Span<'file_name>>,
 }
 
-impl<'a> FunctionLoweringContext<'a> {
+impl<'a, 'file_name> FunctionLoweringContext<'a, 'file_name> {
     #[must_use]
-    pub fn new(package: &'a mut HLIRPackage) -> Self {
+    pub fn new(package: &'a mut HLIRPackage<'file_name>) -> Self {
         Self {
             package,
             type_stack: Vec::new(),
             statement_stack: Vec::new(),
             expression_stack: Vec::new(),
+            loop_depth: 0,
+            or_pattern_depth: 0,
+            impls: Vec::new().into_iter(),
+            functions: HashSet::new(),
+            globals: HashSet::new(),
+            span: None,
         }
     }
 
-    pub fn lower(mut self, ast: &tantalum_ast::AST) {
+    /// Lower the body of every function, `impls` being the trait and type of
+    /// each `impl` block as lowered with the prototypes.
+    pub fn lower(
+        mut self,
+        ast: &tantalum_ast::AST<'file_name, '_>,
+        impls: Vec<Option<(Option<TraitId>, TypeId)>>,
+    ) {
+        self.impls = impls.into_iter();
         <Self as tantalum_ast::ASTVisitor>::visit_ast(&mut self, ast);
     }
-}
 
-impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
-    fn visit_function(&mut self, function: &tantalum_ast::Function<'_, '_>) {
-        let path = Path::new(vec![PathSegment::from(*(function.name.data()))]);
+    fn span(&self) -> Span<'file_name> {
+        self.span.expect("expected a node to be being lowered")
+    }
 
-        let function_id = self
-            .package
-            .get_function_id(&path)
-            .expect("expected function to exist in package");
+    fn lower_type(
+        &mut self,
+        ty: &Spanned<'file_name, tantalum_ast::Type<'file_name, '_>>,
+    ) -> TypeId {
+        let span = self.span.replace(ty.span());
+        <Self as tantalum_ast::ASTVisitor>::visit_type(self, ty.data());
+        self.span = span;
+
+        self.type_stack
+            .pop()
+            .expect("expected type to exist in stack")
+    }
+
+    /// Lower a call of a method, e.g. `shape.area()`.
+    fn lower_method_call(
+        &mut self,
+        access: &MemberAccess<'file_name, '_>,
+        arguments: &[Spanned<'file_name, tantalum_ast::Expression<'file_name, '_>>],
+    ) {
+        let receiver = self.lower_expression(&access.object);
+        let arguments = arguments
+            .iter()
+            .map(|argument| self.lower_expression(argument))
+            .collect();
+
+        let expression = self.package.build_method_call(
+            receiver,
+            access.member.data(),
+            arguments,
+            access.member.span(),
+            self.span(),
+        );
+
+        self.expression_stack.push(expression);
+    }
+
+    /// Lower a call of a path, which is either a method called through the
+    /// path of its type, e.g. `Point::new(1, 2)`, where every argument is
+    /// passed explicitly, a function in a module, e.g. `shapes::area(circle)`,
+    /// or a tuple variant, e.g. `Shape::Circle(1.0)`.
+    fn lower_path_call(
+        &mut self,
+        path: &tantalum_ast::Path<'file_name, '_>,
+        arguments: &[Spanned<'file_name, tantalum_ast::Expression<'file_name, '_>>],
+    ) {
+        let (name, prefix) = path
+            .segments
+            .split_last()
+            .expect("expected a path to have segments");
+        let ty = self.package.resolve_type_id(&crate::lower_path(prefix));
+
+        let function = match ty {
+            Some(ty) => self.package.get_method(ty, name.data()),
+            None => self
+                .package
+                .resolve_function_id(&crate::lower_path(&path.segments)),
+        };
+        let is_variant = ty.is_none_or(|ty| {
+            self.package.get_enum(ty).is_some() || self.package.get_union(ty).is_some()
+        });
+
+        if function.is_none() && self.package.is_module(&crate::lower_path(prefix)) {
+            let module = crate::display_path(prefix);
+            self.package.report(Diagnostic::error(
+                codes::UNKNOWN_FUNCTION,
+                format!(
+                    "cannot find function `{}` in module `{module}`",
+                    name.data()
+                ),
+                Label::new(name.span(), format!("not found in `{module}`")),
+            ));
+
+            // Lower the arguments anyway to report the errors inside of them
+            for argument in arguments {
+                self.lower_expression(argument);
+            }
+
+            let expression = self.package.build_expression_error();
+            self.expression_stack.push(expression);
+            return;
+        }
+
+        if function.is_none() && is_variant {
+            self.lower_tuple_variant(path, arguments);
+            return;
+        }
+
+        let arguments = arguments
+            .iter()
+            .map(|argument| self.lower_expression(argument))
+            .collect();
+
+        let expression = if let Some(function) = function {
+            self.package
+                .build_function_call(function, arguments, self.span())
+        } else {
+            let ty = self
+                .package
+                .type_name(ty.expect("expected the path to name a type"));
+            self.package.report(Diagnostic::error(
+                codes::UNKNOWN_FUNCTION,
+                format!("no function named `{}` found for `{ty}`", name.data()),
+                Label::new(name.span(), format!("not found in `{ty}`")),
+            ));
+            self.package.build_expression_error()
+        };
+
+        self.expression_stack.push(expression);
+    }
+
+    /// Lower the body of `function`, whose prototype is `function_id`.
+    fn lower_function(
+        &mut self,
+        function_id: FunctionId,
+        function: &tantalum_ast::Function<'file_name, '_>,
+    ) {
         let function_prototype = self
             .package
             .get_prototype(function_id)
             .expect("expected prototype to exist in package");
 
-        self.package.start_function_impl(function_id);
+        self.package
+            .start_function_impl(function_id, function.name.span());
 
         // Add parameters to variable scope
         for (parameter, ty) in function
@@ -54,6 +205,8 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
             match parameter {
                 tantalum_ast::Parameter::Named(named) => {
                     let inference_id = self.package.create_type_inference_resolved(*ty);
+                    self.package
+                        .set_type_inference_origin(inference_id, named.ty.span());
                     let variable_id = self
                         .package
                         .create_variable(named.name.data(), inference_id);
@@ -64,77 +217,927 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
             }
         }
 
-        self.visit_statement(function.body.data());
-
-        let statement = self
-            .statement_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+        let statement = self.lower_statement(&function.body);
 
         self.package.set_function_body(function_id, statement);
 
         self.package.finish_function_impl(function_id);
     }
 
-    fn visit_named_type(&mut self, named: &NamedType<'_, '_>) {
-        let path = Path::from(*(named.name.data()));
-        let type_id = self
+    fn lower_statement(
+        &mut self,
+        statement: &Spanned<'file_name, tantalum_ast::Statement<'file_name, '_>>,
+    ) -> Statement {
+        let span = self.span.replace(statement.span());
+        <Self as tantalum_ast::ASTVisitor>::visit_statement(self, statement.data());
+        self.span = span;
+
+        self.statement_stack
+            .pop()
+            .expect("expected statement to exist in stack")
+    }
+
+    fn lower_expression(
+        &mut self,
+        expression: &Spanned<'file_name, tantalum_ast::Expression<'file_name, '_>>,
+    ) -> Expression {
+        let span = self.span.replace(expression.span());
+        <Self as tantalum_ast::ASTVisitor>::visit_expression(self, expression.data());
+        self.span = span;
+
+        let lowered = self
+            .expression_stack
+            .pop()
+            .expect("expected expression to exist in stack");
+
+        let ty = lowered.ty(self.package);
+        self.package
+            .set_type_inference_origin(ty, expression.span());
+
+        lowered
+    }
+
+    /// Lower the body of a loop, allowing `break` and `continue` inside of it
+    fn lower_loop_body(
+        &mut self,
+        body: &Spanned<'file_name, tantalum_ast::Statement<'file_name, '_>>,
+    ) -> Statement {
+        self.loop_depth += 1;
+        let body = self.lower_statement(body);
+        self.loop_depth -= 1;
+
+        body
+    }
+
+    /// Lower a `break` or `continue`, reporting an error if it is not inside of
+    /// a loop
+    fn lower_loop_control(&mut self, keyword: &str, statement: Statement) {
+        if self.loop_depth == 0 {
+            self.package.report(Diagnostic::error(
+                codes::BREAK_OUTSIDE_OF_LOOP,
+                format!("`{keyword}` outside of a loop"),
+                Label::new(self.span(), format!("cannot `{keyword}` outside of a loop")),
+            ));
+            self.push_error_statement();
+        } else {
+            self.statement_stack.push(statement);
+        }
+    }
+
+    /// Lower an assignment, reporting an error if the left side is not a place
+    /// that can be assigned to
+    fn lower_assignment(&mut self, binary: &tantalum_ast::BinaryOperation<'file_name, '_>) {
+        let target = self.lower_expression(&binary.left);
+        let value = self.lower_expression(&binary.right);
+
+        let expression = match Place::try_from(target) {
+            Ok(Place::Variable(variable)) if self.is_constant(variable) => {
+                let name = self
+                    .package
+                    .get_global(variable)
+                    .expect("expected a global")
+                    .name()
+                    .to_string();
+
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_ASSIGNMENT_TARGET,
+                    format!("cannot assign to constant `{name}`"),
+                    Label::new(binary.left.span(), "cannot assign to a constant"),
+                ));
+                self.package.build_expression_error()
+            }
+            Ok(place) => self.package.build_expression_assign(place, value),
+            // The error has already been reported
+            Err(Expression::Error(_)) => self.package.build_expression_error(),
+            Err(_) => {
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_ASSIGNMENT_TARGET,
+                    "invalid left-hand side of assignment",
+                    Label::new(binary.left.span(), "cannot assign to this expression"),
+                ));
+                self.package.build_expression_error()
+            }
+        };
+
+        self.expression_stack.push(expression);
+    }
+
+    /// Lower the value of the constant or static called `name` in the current
+    /// module, which is computed once every function has been lowered.
+    fn lower_global_value(
+        &mut self,
+        name: &str,
+        value: &Spanned<'file_name, tantalum_ast::Expression<'file_name, '_>>,
+    ) {
+        let id = self
             .package
-            .get_type_id(&path)
-            .expect("expected type to exist in package");
+            .get_global_id(&self.package.item_path(name))
+            .filter(|id| self.globals.insert(*id));
 
-        self.type_stack.push(type_id);
+        // Globals defined more than once are reported with the prototypes
+        let Some(id) = id else {
+            return;
+        };
+
+        self.package.start_global_initializer(value.span());
+        let value = self.lower_expression(value);
+        self.package.finish_global_initializer(id, value);
     }
 
-    fn visit_function_type(&mut self, function: &FunctionType<'_, '_>) {
-        todo!("function types not yet implemented, did not lower {function:?}");
+    fn is_constant(&self, variable: VariableId) -> bool {
+        self.package
+            .get_global(variable)
+            .is_some_and(|global| global.kind == GlobalKind::Constant)
     }
 
-    fn visit_pointer_type(&mut self, pointer: &PointerType<'_, '_>) {
-        self.visit_type(pointer.ty.data());
+    /// Find the enum or tagged union a path such as `Shape::Circle` refers
+    /// to, along with the position of the variant in it, reporting it if there
+    /// is none.
+    fn resolve_variant(
+        &mut self,
+        segments: &[Spanned<'file_name, &str>],
+    ) -> Option<(TypeId, usize)> {
+        let (variant, prefix) = segments
+            .split_last()
+            .expect("expected a path to have segments");
+
+        let name = crate::display_path(prefix);
         let ty = self
-            .type_stack
-            .pop()
-            .expect("expected type to exist in stack");
+            .package
+            .resolve_type_id(&crate::lower_path(prefix))
+            .filter(|ty| {
+                self.package.get_enum(*ty).is_some() || self.package.get_union(*ty).is_some()
+            });
+
+        let Some(ty) = ty else {
+            let span = Span::new(
+                prefix.first().map_or(variant.start(), Spanned::start),
+                prefix.last().map_or(variant.end(), Spanned::end),
+            );
+
+            self.package.report(Diagnostic::error(
+                codes::UNKNOWN_TYPE,
+                format!("cannot find enum `{name}` in this scope"),
+                Label::new(span, "not found in this scope"),
+            ));
+
+            return None;
+        };
+
+        let index = if let Some(definition) = self.package.get_enum(ty) {
+            definition.variant(variant.data()).map(|(index, _)| index)
+        } else {
+            self.package
+                .get_union(ty)
+                .and_then(|definition| definition.variant(variant.data()))
+                .map(|(index, _)| index)
+        };
+
+        if index.is_none() {
+            self.package.report(Diagnostic::error(
+                codes::UNKNOWN_VARIANT,
+                format!("no variant `{}` in enum `{name}`", variant.data()),
+                Label::new(variant.span(), "unknown variant"),
+            ));
+        }
+
+        index.map(|index| (ty, index))
+    }
+
+    /// Check that the variant at position `index` of the enum or tagged union
+    /// `ty` is written with the syntax matching the values it carries,
+    /// reporting it if it is not.
+    fn expect_variant_kind(
+        &mut self,
+        ty: TypeId,
+        index: usize,
+        used: VariantKind,
+        segments: &[Spanned<'file_name, &str>],
+    ) -> bool {
+        let kind = self
+            .package
+            .get_union(ty)
+            .map_or(VariantKind::Unit, |union| union.variants[index].kind);
+
+        if kind == used {
+            return true;
+        }
+
+        let path = segments
+            .iter()
+            .map(|segment| *(segment.data()))
+            .collect::<Vec<_>>()
+            .join("::");
+        let (kind, syntax) = match kind {
+            VariantKind::Unit => ("a unit", path.clone()),
+            VariantKind::Tuple => ("a tuple", format!("{path}(..)")),
+            VariantKind::Struct => ("a struct", format!("{path} {{ .. }}")),
+        };
+
+        self.package.report(Diagnostic::error(
+            codes::MISMATCHED_VARIANT_KIND,
+            format!("`{path}` is {kind} variant"),
+            Label::new(self.span(), format!("expected `{syntax}`")),
+        ));
+
+        false
+    }
+
+    /// Lower a variant with unnamed values, e.g. `Shape::Circle(1.0)`.
+    fn lower_tuple_variant(
+        &mut self,
+        path: &tantalum_ast::Path<'file_name, '_>,
+        arguments: &[Spanned<'file_name, tantalum_ast::Expression<'file_name, '_>>],
+    ) {
+        let fields = arguments
+            .iter()
+            .map(|argument| self.lower_expression(argument))
+            .collect();
+
+        let expression = match self.resolve_variant(&path.segments) {
+            Some((ty, index))
+                if self.package.get_union(ty).is_some()
+                    && self.expect_variant_kind(ty, index, VariantKind::Tuple, &path.segments) =>
+            {
+                self.package
+                    .build_expression_union(ty, index, fields, self.span())
+            }
+            Some((ty, _)) if self.package.get_enum(ty).is_some() => {
+                self.package.report(Diagnostic::error(
+                    codes::NOT_CALLABLE,
+                    "variants of enums without values cannot be called",
+                    Label::new(self.span(), "not a tuple variant"),
+                ));
+                self.package.build_expression_error()
+            }
+            _ => self.package.build_expression_error(),
+        };
+
+        self.expression_stack.push(expression);
+    }
+
+    /// Lower a variant with named values, e.g. `Shape::Rect { w: 1.0, h: 2.0 }`.
+    fn lower_struct_variant(&mut self, literal: &StructLiteral<'file_name, '_>) {
+        let segments = &literal.name.data().segments;
+
+        let variant = self
+            .resolve_variant(segments)
+            .filter(|(ty, _)| self.package.get_union(*ty).is_some())
+            .filter(|(ty, index)| {
+                self.expect_variant_kind(*ty, *index, VariantKind::Struct, segments)
+            });
+
+        let Some((ty, index)) = variant else {
+            // Lower the values anyway to report the errors inside of them
+            for field in &literal.fields {
+                self.lower_expression(&field.data().value);
+            }
+
+            let expression = self.package.build_expression_error();
+            self.expression_stack.push(expression);
+            return;
+        };
+
+        let name = segments
+            .iter()
+            .map(|segment| *(segment.data()))
+            .collect::<Vec<_>>()
+            .join("::");
+        let fields = self
+            .package
+            .get_union(ty)
+            .expect("expected type to be a tagged union")
+            .variants[index]
+            .fields
+            .clone();
+
+        let values = self.lower_field_initializers(&name, &fields, &literal.fields);
+
+        let expression = self
+            .package
+            .build_expression_union(ty, index, values, self.span());
+
+        self.expression_stack.push(expression);
+    }
+
+    /// Lower the values given to the fields of a struct or of a variant,
+    /// ordered as the fields are declared. Fields that are unknown, missing
+    /// or given more than once are reported, with missing fields lowered as
+    /// errors.
+    fn lower_field_initializers(
+        &mut self,
+        name: &str,
+        fields: &[StructField],
+        initializers: &[Spanned<'file_name, FieldInitializer<'file_name, '_>>],
+    ) -> Vec<Expression> {
+        let mut values: Vec<Option<Expression>> = vec![None; fields.len()];
+
+        for initializer in initializers {
+            let initializer = initializer.data();
+            let field_name = *(initializer.name.data());
+            let value = self.lower_expression(&initializer.value);
+
+            match fields.iter().position(|field| field.name == field_name) {
+                Some(index) if values[index].is_some() => {
+                    self.package.report(Diagnostic::error(
+                        codes::DUPLICATE_DEFINITION,
+                        format!("field `{field_name}` is initialized more than once"),
+                        Label::new(initializer.name.span(), "already initialized"),
+                    ));
+                }
+                Some(index) => values[index] = Some(value),
+                None => {
+                    self.package.report(Diagnostic::error(
+                        codes::UNKNOWN_FIELD,
+                        format!("no field `{field_name}` on type `{name}`"),
+                        Label::new(initializer.name.span(), "unknown field"),
+                    ));
+                }
+            }
+        }
+
+        let missing = fields
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(field, _)| format!("`{}`", field.name))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            self.package.report(Diagnostic::error(
+                codes::MISSING_FIELDS,
+                format!(
+                    "missing {} {} in initializer of `{name}`",
+                    if missing.len() == 1 {
+                        "field"
+                    } else {
+                        "fields"
+                    },
+                    missing.join(", ")
+                ),
+                Label::new(self.span(), "missing fields"),
+            ));
+        }
+
+        values
+            .into_iter()
+            .map(|value| value.unwrap_or_else(|| self.package.build_expression_error()))
+            .collect()
+    }
+
+    /// Lower a pattern that values of type `scrutinee` are matched against.
+    fn lower_pattern(
+        &mut self,
+        pattern: &Spanned<'file_name, tantalum_ast::Pattern<'file_name, '_>>,
+        scrutinee: InferenceId,
+    ) -> Pattern {
+        let span = self.span.replace(pattern.span());
+
+        let lowered = match pattern.data() {
+            tantalum_ast::Pattern::Wildcard => self.package.build_pattern_wildcard(),
+            tantalum_ast::Pattern::Binding(name) => {
+                if self.or_pattern_depth > 0 {
+                    crate::report_unsupported(self.package, "bindings in or-patterns", name.span());
+                    self.package.build_pattern_error()
+                } else {
+                    self.package.build_pattern_binding(name.data(), scrutinee)
+                }
+            }
+            tantalum_ast::Pattern::Literal(literal) => {
+                match self.lower_pattern_literal(literal, pattern.span()) {
+                    Some((literal, value)) => self
+                        .package
+                        .build_pattern_literal(&literal, value, scrutinee),
+                    None => self.package.build_pattern_error(),
+                }
+            }
+            tantalum_ast::Pattern::Range(range) => self.lower_pattern_range(range, scrutinee),
+            tantalum_ast::Pattern::Variant(variant) => {
+                self.lower_pattern_variant(variant, scrutinee)
+            }
+            tantalum_ast::Pattern::Or(alternatives) => {
+                self.or_pattern_depth += 1;
+                let alternatives = alternatives
+                    .iter()
+                    .map(|alternative| self.lower_pattern(alternative, scrutinee))
+                    .collect();
+                self.or_pattern_depth -= 1;
+
+                self.package.build_pattern_or(alternatives)
+            }
+        };
+
+        self.span = span;
+
+        lowered
+    }
+
+    /// Lower a literal in a pattern, found at `span`, to the literal and its
+    /// value, reporting it if it is not an integer, a character or a boolean.
+    fn lower_pattern_literal(
+        &mut self,
+        pattern: &LiteralPattern<'file_name, '_>,
+        span: Span<'file_name>,
+    ) -> Option<(Literal, i128)> {
+        let (literal, value) = match &pattern.literal {
+            tantalum_ast::Literal::Integer(integer) => {
+                let sign = if pattern.negative { "-" } else { "" };
+                let value = format!("{sign}{}", integer.value.data());
+                let parsed = LiteralValue::parse_integer(&value, integer.radix).ok()?;
+                let literal = self.package.build_integer_literal(value, integer.radix);
+
+                (literal, parsed)
+            }
+            tantalum_ast::Literal::Boolean(boolean) if !pattern.negative => {
+                let value = (*boolean.value.data())
+                    .parse::<bool>()
+                    .expect("expected boolean value to be valid and verified by parser");
+
+                (self.package.build_boolean_literal(value), i128::from(value))
+            }
+            tantalum_ast::Literal::Character(character) if !pattern.negative => {
+                let value = self.unescape(character.value.data(), '\'');
+
+                // Characters are a single byte
+                let Ok(byte) = u8::try_from(value.chars().next().unwrap_or_default()) else {
+                    crate::report_unsupported(self.package, "non-ASCII character literals", span);
+                    return None;
+                };
+
+                (
+                    self.package.build_character_literal(value),
+                    i128::from(byte),
+                )
+            }
+            tantalum_ast::Literal::Float(_) | tantalum_ast::Literal::String(_) => {
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_PATTERN,
+                    "only integers, characters and booleans can be matched by a literal",
+                    Label::new(span, "cannot be used in a pattern"),
+                ));
+                return None;
+            }
+            tantalum_ast::Literal::Boolean(_) | tantalum_ast::Literal::Character(_) => {
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_PATTERN,
+                    "only integers can be negated in a pattern",
+                    Label::new(span, "cannot be negated"),
+                ));
+                return None;
+            }
+        };
+
+        self.package.set_type_inference_origin(literal.ty(), span);
+
+        Some((literal, value))
+    }
+
+    /// Lower a range pattern, e.g. `0..10`, reporting it if it matches no
+    /// values.
+    fn lower_pattern_range(
+        &mut self,
+        range: &RangePattern<'file_name, '_>,
+        scrutinee: InferenceId,
+    ) -> Pattern {
+        let start = self.lower_pattern_literal(range.start.data(), range.start.span());
+        let end = self.lower_pattern_literal(range.end.data(), range.end.span());
+
+        let (Some(start), Some((end, mut end_value))) = (start, end) else {
+            return self.package.build_pattern_error();
+        };
+
+        if !range.inclusive {
+            end_value -= 1;
+        }
+
+        if start.1 > end_value {
+            self.package.report(Diagnostic::error(
+                codes::INVALID_PATTERN,
+                "range patterns must not be empty",
+                Label::new(self.span(), "this range matches no values"),
+            ));
+            return self.package.build_pattern_error();
+        }
+
+        self.package
+            .build_pattern_range((&start.0, start.1), (&end, end_value), scrutinee)
+    }
+
+    /// Lower a pattern matching a variant, e.g. `Shape::Rect { w, h: 0 }`.
+    fn lower_pattern_variant(
+        &mut self,
+        pattern: &VariantPattern<'file_name, '_>,
+        scrutinee: InferenceId,
+    ) -> Pattern {
+        let segments = &pattern.path.data().segments;
+        let used = match pattern.fields {
+            VariantPatternFields::Unit => VariantKind::Unit,
+            VariantPatternFields::Tuple(_) => VariantKind::Tuple,
+            VariantPatternFields::Struct { .. } => VariantKind::Struct,
+        };
+
+        let variant = self
+            .resolve_variant(segments)
+            .filter(|(ty, index)| self.expect_variant_kind(*ty, *index, used, segments));
+
+        let Some((ty, index)) = variant else {
+            // Lower the patterns of the values anyway to report the errors
+            // inside of them
+            let patterns = match &pattern.fields {
+                VariantPatternFields::Unit => Vec::new(),
+                VariantPatternFields::Tuple(patterns) => patterns.data().iter().collect(),
+                VariantPatternFields::Struct { fields, .. } => fields
+                    .data()
+                    .iter()
+                    .filter_map(|field| field.data().pattern.as_ref())
+                    .collect(),
+            };
+
+            for pattern in patterns {
+                let ty = self.error_pattern_type();
+                self.lower_pattern(pattern, ty);
+            }
+
+            return self.package.build_pattern_error();
+        };
+
+        let fields = self
+            .package
+            .get_union(ty)
+            .map(|union| union.variants[index].fields.clone())
+            .unwrap_or_default();
+        let field_types = self
+            .package
+            .build_pattern_field_types(ty, index, scrutinee, self.span());
+
+        let patterns = match &pattern.fields {
+            VariantPatternFields::Unit => Vec::new(),
+            VariantPatternFields::Tuple(patterns) => patterns
+                .data()
+                .iter()
+                .enumerate()
+                .map(|(position, pattern)| {
+                    let ty = match field_types.get(position) {
+                        Some(ty) => *ty,
+                        None => self.error_pattern_type(),
+                    };
+
+                    self.lower_pattern(pattern, ty)
+                })
+                .collect(),
+            VariantPatternFields::Struct {
+                fields: patterns,
+                rest,
+            } => {
+                let name = segments
+                    .iter()
+                    .map(|segment| *(segment.data()))
+                    .collect::<Vec<_>>()
+                    .join("::");
+
+                self.lower_field_patterns(&name, &fields, &field_types, patterns.data(), *rest)
+            }
+        };
+
+        self.package
+            .build_pattern_variant(ty, index, patterns, scrutinee, self.span())
+    }
+
+    /// The type of a pattern matched against a value that could not be
+    /// found, which is an error so the pattern causes no further errors.
+    fn error_pattern_type(&mut self) -> InferenceId {
+        let ty = self.package.build_type_error();
+        self.package.create_type_inference_resolved(ty)
+    }
+
+    /// Lower the patterns of the fields of a variant, whose types are
+    /// `field_types`, ordered as the fields are declared. Fields that are
+    /// unknown or given more than once are reported, as are missing fields
+    /// unless `rest` allows them to be left out.
+    fn lower_field_patterns(
+        &mut self,
+        name: &str,
+        fields: &[StructField],
+        field_types: &[InferenceId],
+        patterns: &[Spanned<'file_name, FieldPattern<'file_name, '_>>],
+        rest: bool,
+    ) -> Vec<Pattern> {
+        let mut lowered: Vec<Option<Pattern>> = vec![None; fields.len()];
+
+        for pattern in patterns {
+            let pattern = pattern.data();
+            let field_name = *(pattern.name.data());
+            let position = fields.iter().position(|field| field.name == field_name);
+
+            let ty = match position {
+                Some(position) => field_types[position],
+                None => self.error_pattern_type(),
+            };
+            let value = match &pattern.pattern {
+                Some(pattern) => self.lower_pattern(pattern, ty),
+                // `name` is short for `name: name`
+                None => self.lower_pattern(
+                    &Spanned::new(
+                        pattern.name.span(),
+                        tantalum_ast::Pattern::Binding(pattern.name),
+                    ),
+                    ty,
+                ),
+            };
+
+            match position {
+                Some(position) if lowered[position].is_some() => {
+                    self.package.report(Diagnostic::error(
+                        codes::DUPLICATE_DEFINITION,
+                        format!("field `{field_name}` is matched more than once"),
+                        Label::new(pattern.name.span(), "already matched"),
+                    ));
+                }
+                Some(position) => lowered[position] = Some(value),
+                None => {
+                    self.package.report(Diagnostic::error(
+                        codes::UNKNOWN_FIELD,
+                        format!("no field `{field_name}` on type `{name}`"),
+                        Label::new(pattern.name.span(), "unknown field"),
+                    ));
+                }
+            }
+        }
+
+        let missing = fields
+            .iter()
+            .zip(&lowered)
+            .filter(|(_, pattern)| pattern.is_none())
+            .map(|(field, _)| format!("`{}`", field.name))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() && !rest {
+            self.package.report(Diagnostic::error(
+                codes::MISSING_FIELDS,
+                format!(
+                    "pattern does not mention {} {}",
+                    if missing.len() == 1 {
+                        "field"
+                    } else {
+                        "fields"
+                    },
+                    missing.join(", ")
+                ),
+                Label::new(self.span(), "missing fields, or use `..` to ignore them"),
+            ));
+        }
+
+        lowered
+            .into_iter()
+            .map(|pattern| match pattern {
+                Some(pattern) => pattern,
+                None if rest => self.package.build_pattern_wildcard(),
+                None => self.package.build_pattern_error(),
+            })
+            .collect()
+    }
+
+    /// Lower a statement that cannot be lowered to an error expression, so the
+    /// rest of the function can still be checked
+    fn push_error_statement(&mut self) {
+        let expression = self.package.build_expression_error();
+        let statement = self.package.build_statement_expression(expression);
+        self.statement_stack.push(statement);
+    }
+
+    /// Report the expression being lowered as unsupported
+    fn unsupported_expression(&mut self, feature: &str) {
+        crate::report_unsupported(self.package, feature, self.span());
+
+        let expression = self.package.build_expression_error();
+        self.expression_stack.push(expression);
+    }
+
+    /// Get the contents of a string or character literal from its lexeme,
+    /// decoding escape sequences and reporting the unknown ones
+    fn unescape(&mut self, lexeme: &str, quote: char) -> String {
+        let contents = lexeme.strip_prefix(quote).unwrap_or(lexeme);
+        let contents = contents.strip_suffix(quote).unwrap_or(contents);
+
+        let mut value = String::with_capacity(contents.len());
+        let mut characters = contents.chars();
+
+        while let Some(character) = characters.next() {
+            if character != '\\' {
+                value.push(character);
+                continue;
+            }
+
+            match characters.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('0') => value.push('\0'),
+                Some(escaped @ ('\\' | '\'' | '"')) => value.push(escaped),
+                Some(unknown) => {
+                    self.package.report(Diagnostic::error(
+                        codes::UNKNOWN_ESCAPE_SEQUENCE,
+                        format!("unknown escape sequence `\\{unknown}`"),
+                        Label::new(self.span(), "contains an unknown escape sequence"),
+                    ));
+                    value.push(unknown);
+                }
+                // The literal is unterminated, which the lexer has reported
+                None => {}
+            }
+        }
+
+        value
+    }
 
-        let type_id = self.package.build_type_pointer(ty);
+    /// Report the type being lowered as unsupported
+    fn unsupported_type(&mut self, feature: &str) {
+        crate::report_unsupported(self.package, feature, self.span());
 
+        let type_id = self.package.build_type_error();
         self.type_stack.push(type_id);
     }
+}
+
+impl<'file_name> tantalum_ast::ASTVisitor<'file_name, '_>
+    for FunctionLoweringContext<'_, 'file_name>
+{
+    fn visit_function(&mut self, function: &tantalum_ast::Function<'file_name, '_>) {
+        let path = self.package.item_path(function.name.data());
+
+        let function_id = self
+            .package
+            .get_function_id(&path)
+            .expect("expected function to exist in package");
 
-    fn visit_sized_array_type(&mut self, array: &SizedArrayType<'_, '_>) {
-        todo!("sized arrays not yet implemented, did not lower {array:?}");
+        // Functions defined more than once were reported along with the
+        // prototypes
+        if self.functions.insert(function_id) {
+            self.lower_function(function_id, function);
+        }
     }
 
-    fn visit_unsized_array_type(&mut self, array: &UnsizedArrayType<'_, '_>) {
-        self.visit_type(array.ty.data());
+    fn visit_constant(&mut self, constant: &tantalum_ast::Constant<'file_name, '_>) {
+        self.lower_global_value(constant.name.data(), &constant.value);
+    }
 
-        let ty = self
-            .type_stack
-            .pop()
-            .expect("expected type to exist in stack");
+    fn visit_static(&mut self, static_: &tantalum_ast::Static<'file_name, '_>) {
+        self.lower_global_value(static_.name.data(), &static_.value);
+    }
+
+    // Imports are declared along with the types, and checked here once every
+    // item they can refer to is declared
+    fn visit_use(&mut self, use_: &tantalum_ast::Use<'file_name, '_>) {
+        for import in &use_.imports {
+            let import = import.data();
+            let name = import.alias.unwrap_or(import.name);
+
+            // An item of the module itself would be found instead of the import
+            let item = self.package.item_path(name.data());
+            if self.package.get_type_id(&item).is_some()
+                || self.package.get_function_id(&item).is_some()
+                || self.package.get_trait_id(&item).is_some()
+                || self.package.get_global_id(&item).is_some()
+            {
+                crate::report_duplicate_definition(self.package, "name", name.data(), name.span());
+                continue;
+            }
+
+            let mut segments = use_.prefix.segments.clone();
+            segments.push(import.name);
+            let path = crate::lower_path(&segments);
+
+            if self.package.resolve_type_id(&path).is_some()
+                || self.package.resolve_function_id(&path).is_some()
+                || self.package.resolve_trait_id(&path).is_some()
+                || self.package.resolve_global_id(&path).is_some()
+                || self.package.is_module(&path)
+            {
+                continue;
+            }
+
+            let scope = if use_.prefix.segments.is_empty() {
+                "this scope".to_string()
+            } else {
+                format!("`{}`", crate::display_path(&use_.prefix.segments))
+            };
+            self.package.report(Diagnostic::error(
+                codes::UNRESOLVED_IMPORT,
+                format!("cannot find `{}` in {scope}", import.name.data()),
+                Label::new(import.name.span(), format!("not found in {scope}")),
+            ));
+        }
+    }
+
+    fn visit_module(&mut self, module: &tantalum_ast::Module<'file_name, '_>) {
+        self.package.enter_module(module.name.data());
+        for item in module.items.iter().flat_map(Spanned::data) {
+            self.visit_item(item.data());
+        }
+        self.package.exit_module();
+    }
+
+    fn visit_impl(&mut self, impl_: &tantalum_ast::Impl<'file_name, '_>) {
+        let lowered = self
+            .impls
+            .next()
+            .expect("expected impl to have been lowered with the prototypes");
+        let Some((trait_id, ty)) = lowered else {
+            return;
+        };
+
+        self.package.push_self_type(ty);
+
+        // Methods the trait does not declare, or that are defined more than
+        // once, were reported along with the prototypes
+        for method in impl_.methods.data() {
+            let method = method.data();
+            let name = method.name.data();
+            let function_id = match trait_id {
+                Some(trait_id) => self.package.get_trait_impl_method(trait_id, ty, name),
+                None => self.package.get_method(ty, name),
+            }
+            .filter(|id| self.functions.insert(*id));
+
+            if let Some(function_id) = function_id {
+                self.lower_function(function_id, method);
+            }
+        }
+
+        self.package.pop_type_parameters();
+    }
+
+    fn visit_named_type(&mut self, named: &NamedType<'file_name, '_>) {
+        let arguments = named
+            .arguments
+            .iter()
+            .map(|argument| {
+                let ty = self.lower_type(argument);
+                crate::lower_value_type(self.package, ty, "a type argument", argument.span())
+            })
+            .collect();
+        let type_id = crate::lower_named_type(self.package, named, arguments, self.span());
+
+        self.type_stack.push(type_id);
+    }
+
+    fn visit_function_type(&mut self, _function: &FunctionType<'file_name, '_>) {
+        self.unsupported_type("function types");
+    }
+
+    fn visit_pointer_type(&mut self, pointer: &PointerType<'file_name, '_>) {
+        // A pointer to a trait object is a type of its own
+        let type_id = if let tantalum_ast::Type::Dyn(dyn_) = pointer.ty.data() {
+            crate::lower_trait_object(self.package, dyn_)
+        } else {
+            let ty = self.lower_type(&pointer.ty);
+            self.package.build_type_pointer(ty)
+        };
+
+        self.type_stack.push(type_id);
+    }
+
+    fn visit_sized_array_type(&mut self, array: &SizedArrayType<'file_name, '_>) {
+        let ty = self.lower_type(&array.ty);
+        let ty = crate::lower_value_type(
+            self.package,
+            ty,
+            "the type of an array element",
+            array.ty.span(),
+        );
+
+        let type_id = self.package.build_type_array(ty, *(array.size.data()));
+
+        self.type_stack.push(type_id);
+    }
+
+    fn visit_unsized_array_type(&mut self, array: &UnsizedArrayType<'file_name, '_>) {
+        let ty = self.lower_type(&array.ty);
 
         let type_id = self.package.build_type_unsized_array(ty);
 
         self.type_stack.push(type_id);
     }
 
-    fn visit_const_type(&mut self, constant: &ConstType<'_, '_>) {
-        todo!("const types not yet implemented, did not lower {constant:?}");
+    // Constness is not checked yet, so a `const` type is the type itself
+    fn visit_const_type(&mut self, constant: &ConstType<'file_name, '_>) {
+        let ty = self.lower_type(&constant.ty);
+
+        self.type_stack.push(ty);
     }
 
-    fn visit_block(&mut self, block: &tantalum_ast::Block<'_, '_>) {
+    fn visit_dyn_type(&mut self, _dyn: &tantalum_ast::DynType<'file_name, '_>) {
+        self.unsupported_type("trait objects not behind a pointer");
+    }
+
+    fn visit_block(&mut self, block: &tantalum_ast::Block<'file_name, '_>) {
         self.package.build_block_start();
 
         let mut statements = Vec::new();
 
         for statement in &block.statements {
-            self.visit_statement(statement.data());
-            statements.push(
-                self.statement_stack
-                    .pop()
-                    .expect("expected statement to exist in stack"),
-            );
+            let statement = self.lower_statement(statement);
+            statements.push(statement);
         }
 
         let statement = self.package.build_block_end(statements);
@@ -144,59 +1147,48 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
 
     fn visit_variable_declaration(
         &mut self,
-        variable_declaration: &tantalum_ast::VariableDeclaration<'_, '_>,
+        variable_declaration: &tantalum_ast::VariableDeclaration<'file_name, '_>,
     ) {
         let name = *(variable_declaration.name.data());
-        let value = variable_declaration.value.data();
 
-        self.visit_expression(value);
+        let value = self.lower_expression(&variable_declaration.value);
+
+        let (inference_id, span) = if let Some(ty) = &variable_declaration.ty {
+            let type_id = self.lower_type(ty);
+            let inference_id = self.package.create_type_inference_resolved(type_id);
 
-        let inference_id = if let Some(ty) = &variable_declaration.ty {
-            let ty = ty.data();
-            self.visit_type(ty);
+            // Type errors show the type by the name it is written with, which
+            // may be an alias of it
+            if let tantalum_ast::Type::Named(named) = ty.data() {
+                if named.arguments.is_empty() {
+                    let name = crate::display_path(&named.name.data().segments);
+                    self.package.set_type_inference_name(inference_id, name);
+                }
+            }
 
-            let type_id = self
-                .type_stack
-                .pop()
-                .expect("expected type to exist in stack");
-            self.package.create_type_inference_resolved(type_id)
+            (inference_id, ty.span())
         } else {
-            self.package.create_type_inference_variable()
+            (
+                self.package.create_type_inference_variable(),
+                variable_declaration.name.span(),
+            )
         };
-
-        let value = self
-            .expression_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+        self.package.set_type_inference_origin(inference_id, span);
 
         let statement = self.package.build_statement_let(name, inference_id, value);
 
         self.statement_stack.push(statement);
     }
 
-    fn visit_if(&mut self, if_statement: &tantalum_ast::If<'_, '_>) {
-        self.visit_expression(if_statement.condition.data());
-        let condition = self
-            .expression_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+    fn visit_if(&mut self, if_statement: &tantalum_ast::If<'file_name, '_>) {
+        let condition = self.lower_expression(&if_statement.condition);
 
-        self.visit_statement(if_statement.body.data());
-        let then_branch = self
-            .statement_stack
-            .pop()
-            .expect("expected statement to exist in stack");
-
-        let else_branch = if let Some(else_branch) = &if_statement.else_branch {
-            self.visit_statement(else_branch.data());
-            Some(
-                self.statement_stack
-                    .pop()
-                    .expect("expected statement to exist in stack"),
-            )
-        } else {
-            None
-        };
+        let then_branch = self.lower_statement(&if_statement.body);
+
+        let else_branch = if_statement
+            .else_branch
+            .as_ref()
+            .map(|else_branch| self.lower_statement(else_branch));
 
         let statement = self
             .package
@@ -205,13 +1197,92 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         self.statement_stack.push(statement);
     }
 
-    fn visit_return(&mut self, return_statement: &Return<'_, '_>) {
+    fn visit_match(&mut self, statement: &tantalum_ast::Match<'file_name, '_>) {
+        let scrutinee = self.lower_expression(&statement.scrutinee);
+        let ty = scrutinee.ty(self.package);
+
+        let arms = statement
+            .arms
+            .iter()
+            .map(|arm| {
+                let arm = arm.data();
+
+                // Each arm is a block holding the variables bound by its
+                // pattern
+                self.package.build_block_start();
+                let pattern = self.lower_pattern(&arm.pattern, ty);
+                let body = self.lower_statement(&arm.body);
+                let body = self.package.build_block_end(vec![body]);
+
+                (MatchArm { pattern, body }, arm.pattern.span())
+            })
+            .collect();
+
+        let statement =
+            self.package
+                .build_statement_match(scrutinee, arms, statement.scrutinee.span());
+
+        self.statement_stack.push(statement);
+    }
+
+    fn visit_while(&mut self, while_statement: &While<'file_name, '_>) {
+        let condition = self.lower_expression(&while_statement.condition);
+
+        let body = self.lower_loop_body(&while_statement.body);
+
+        let statement = self.package.build_statement_while(condition, body);
+
+        self.statement_stack.push(statement);
+    }
+
+    fn visit_for_init_cond_update(&mut self, for_statement: &ForInitCondUpdate<'file_name, '_>) {
+        // The loop is wrapped in a block so that variables declared by the
+        // initializer are only visible inside of the loop
+        self.package.build_block_start();
+
+        let init = self.lower_statement(&for_statement.init);
+
+        let condition = if let Statement::Expression(condition) =
+            self.lower_statement(&for_statement.condition)
+        {
+            condition
+        } else {
+            self.package.report(Diagnostic::error(
+                codes::INVALID_LOOP_CONDITION,
+                "expected an expression as the condition of the loop",
+                Label::new(for_statement.condition.span(), "expected an expression"),
+            ));
+            self.package.build_expression_error()
+        };
+
+        let update = self.lower_statement(&for_statement.update);
+
+        let body = self.lower_loop_body(&for_statement.body);
+
+        let for_loop = self.package.build_statement_for(condition, update, body);
+
+        let statement = self.package.build_block_end(vec![init, for_loop]);
+
+        self.statement_stack.push(statement);
+    }
+
+    fn visit_break(&mut self) {
+        let statement = self.package.build_statement_break();
+        self.lower_loop_control("break", statement);
+    }
+
+    fn visit_continue(&mut self) {
+        let statement = self.package.build_statement_continue();
+        self.lower_loop_control("continue", statement);
+    }
+
+    fn visit_error_statement(&mut self) {
+        self.push_error_statement();
+    }
+
+    fn visit_return(&mut self, return_statement: &Return<'file_name, '_>) {
         if let Some(value) = &return_statement.value {
-            self.visit_expression(value.data());
-            let value = self
-                .expression_stack
-                .pop()
-                .expect("expected statement to exist in stack");
+            let value = self.lower_expression(value);
 
             let statement = self.package.build_statement_return(value);
 
@@ -223,67 +1294,237 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         }
     }
 
-    fn visit_expression_statement(&mut self, expression: &tantalum_ast::Expression<'_, '_>) {
+    fn visit_expression_statement(
+        &mut self,
+        expression: &tantalum_ast::Expression<'file_name, '_>,
+    ) {
         self.visit_expression(expression);
 
         let expression = self
             .expression_stack
             .pop()
-            .expect("expected statement to exist in stack");
+            .expect("expected expression to exist in stack");
+
+        let ty = expression.ty(self.package);
+        self.package.set_type_inference_origin(ty, self.span());
 
         let statement = self.package.build_statement_expression(expression);
 
         self.statement_stack.push(statement);
     }
 
-    fn visit_variable(&mut self, variable: &Variable<'_, '_>) {
+    fn visit_type_alias_statement(&mut self, alias: &tantalum_ast::TypeAlias<'file_name, '_>) {
+        let ty = self.lower_type(&alias.ty);
+        let path = self.package.item_path(alias.name.data());
+        self.package.add_type_alias(path, ty);
+
+        // The alias is only a name in the scope of the enclosing block, so
+        // there is nothing to run
+        self.package.build_block_start();
+        let statement = self.package.build_block_end(Vec::new());
+        self.statement_stack.push(statement);
+    }
+
+    fn visit_variable(&mut self, variable: &Variable<'file_name, '_>) {
         let name = *(variable.name.data());
 
-        let expression = self.package.build_expression_variable(name);
+        let expression = self
+            .package
+            .build_expression_variable(name, variable.name.span());
 
         self.expression_stack.push(expression);
     }
 
-    fn visit_function_call(&mut self, call: &FunctionCall<'_, '_>) {
-        let function = match call.function.data() {
-            tantalum_ast::Expression::Variable(variable) => {
-                let name = *(variable.name.data());
-                self.package
-                    .get_function_id(&Path::from(name))
-                    .expect("expected function to exist")
+    fn visit_path(&mut self, path: &tantalum_ast::Path<'file_name, '_>) {
+        if let Some(global) = self
+            .package
+            .resolve_global_id(&crate::lower_path(&path.segments))
+        {
+            self.expression_stack.push(Expression::Variable(global));
+            return;
+        }
+
+        let Some((ty, index)) = self.resolve_variant(&path.segments) else {
+            let expression = self.package.build_expression_error();
+            self.expression_stack.push(expression);
+            return;
+        };
+
+        let expression = if self.package.get_enum(ty).is_some() {
+            self.package.build_expression_enum_value(ty, index)
+        } else if self.expect_variant_kind(ty, index, VariantKind::Unit, &path.segments) {
+            self.package
+                .build_expression_union(ty, index, Vec::new(), self.span())
+        } else {
+            self.package.build_expression_error()
+        };
+
+        self.expression_stack.push(expression);
+    }
+
+    fn visit_function_call(&mut self, call: &FunctionCall<'file_name, '_>) {
+        if let tantalum_ast::Expression::Path(path) = call.function.data() {
+            self.lower_path_call(path, &call.arguments);
+            return;
+        }
+
+        if let tantalum_ast::Expression::MemberAccess(access) = call.function.data() {
+            self.lower_method_call(access, &call.arguments);
+            return;
+        }
+
+        let function = if let tantalum_ast::Expression::Variable(variable) = call.function.data() {
+            let name = *(variable.name.data());
+            let function = self.package.resolve_function_id(&Path::from(name));
+
+            if function.is_none() {
+                self.package.report(Diagnostic::error(
+                    codes::UNKNOWN_FUNCTION,
+                    format!("cannot find function `{name}` in this scope"),
+                    Label::new(variable.name.span(), "not found in this scope"),
+                ));
             }
-            _ => panic!("expected function to be a variable"),
+
+            function
+        } else {
+            self.package.report(Diagnostic::error(
+                codes::NOT_CALLABLE,
+                "expected a function name",
+                Label::new(call.function.span(), "only named functions can be called"),
+            ));
+
+            None
         };
 
         let mut arguments = Vec::new();
         for argument in &call.arguments {
-            self.visit_expression(argument.data());
-            arguments.push(
-                self.expression_stack
-                    .pop()
-                    .expect("expected statement to exist in stack"),
-            );
+            let argument = self.lower_expression(argument);
+            arguments.push(argument);
+        }
+
+        let expression = if let Some(function) = function {
+            self.package
+                .build_function_call(function, arguments, self.span())
+        } else {
+            self.package.build_expression_error()
+        };
+
+        self.expression_stack.push(expression);
+    }
+
+    fn visit_struct_literal(&mut self, literal: &StructLiteral<'file_name, '_>) {
+        let segments = &literal.name.data().segments;
+
+        let ty = self.package.resolve_type_id(&crate::lower_path(segments));
+        let definition = ty.and_then(|ty| Some((ty, self.package.get_struct(ty)?.clone())));
+
+        // Any other path, e.g. `Shape::Circle`, names a variant
+        if segments.len() > 1 && definition.is_none() {
+            self.lower_struct_variant(literal);
+            return;
+        }
+
+        let name = crate::display_path(segments);
+
+        let Some((ty, definition)) = definition else {
+            self.package.report(Diagnostic::error(
+                codes::UNKNOWN_TYPE,
+                format!("cannot find struct `{name}` in this scope"),
+                Label::new(literal.name.span(), "not a struct"),
+            ));
+
+            // Lower the values anyway to report the errors inside of them
+            for field in &literal.fields {
+                self.lower_expression(&field.data().value);
+            }
+
+            let expression = self.package.build_expression_error();
+            self.expression_stack.push(expression);
+            return;
+        };
+
+        let values = self.lower_field_initializers(&name, &definition.fields, &literal.fields);
+
+        let expression = self
+            .package
+            .build_expression_struct(ty, values, self.span());
+
+        self.expression_stack.push(expression);
+    }
+
+    fn visit_array_literal(&mut self, literal: &ArrayLiteral<'file_name, '_>) {
+        let mut elements = Vec::new();
+        for element in &literal.elements {
+            let element = self.lower_expression(element);
+            elements.push(element);
         }
 
-        let expression = self.package.build_function_call(function, arguments);
+        let expression = self.package.build_expression_array(elements, self.span());
 
         self.expression_stack.push(expression);
     }
 
-    fn visit_member_access(&mut self, access: &MemberAccess<'_, '_>) {
-        todo!("member access not yet implemented, did not lower {access:?}");
+    fn visit_array_repeat(&mut self, repeat: &ArrayRepeat<'file_name, '_>) {
+        let value = self.lower_expression(&repeat.value);
+
+        let expression = self
+            .package
+            .build_expression_array_repeat(value, *(repeat.length.data()));
+
+        self.expression_stack.push(expression);
     }
 
-    fn visit_array_access(&mut self, index: &Index<'_, '_>) {
-        todo!("array access not yet implemented, did not lower {index:?}");
+    fn visit_variant_check(&mut self, check: &VariantCheck<'file_name, '_>) {
+        let value = self.lower_expression(&check.value);
+
+        let expression =
+            if let Some((ty, index)) = self.resolve_variant(&check.variant.data().segments) {
+                self.package
+                    .build_expression_variant_check(ty, value, index)
+            } else {
+                self.package.build_expression_error()
+            };
+
+        self.expression_stack.push(expression);
     }
 
-    fn visit_unary_operation(&mut self, unary: &UnaryOperation<'_, '_>) {
-        self.visit_expression(unary.operand.data());
-        let operand = self
-            .expression_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+    fn visit_member_access(&mut self, access: &MemberAccess<'file_name, '_>) {
+        let base = self.lower_expression(&access.object);
+
+        let expression =
+            self.package
+                .build_expression_field(base, access.member.data(), access.member.span());
+
+        self.expression_stack.push(expression);
+    }
+
+    fn visit_array_access(&mut self, index: &Index<'file_name, '_>) {
+        let base = self.lower_expression(&index.object);
+        let index = self.lower_expression(&index.index);
+
+        let expression = self.package.build_expression_index(base, index);
+
+        self.expression_stack.push(expression);
+    }
+
+    fn visit_unary_operation(&mut self, unary: &UnaryOperation<'file_name, '_>) {
+        // A negated integer literal is a literal of its own, so that it is
+        // checked against the range of its type with its sign
+        if let (
+            tantalum_ast::UnaryOperator::Negation,
+            tantalum_ast::Expression::Literal(tantalum_ast::Literal::Integer(integer)),
+        ) = (unary.operator.data(), unary.operand.data())
+        {
+            let literal = self
+                .package
+                .build_integer_literal(format!("-{}", integer.value.data()), integer.radix);
+
+            let expression = self.package.build_expression_literal(literal);
+            self.expression_stack.push(expression);
+            return;
+        }
+
+        let operand = self.lower_expression(&unary.operand);
 
         let operator = match unary.operator.data() {
             tantalum_ast::UnaryOperator::Negation => self.package.build_unary_operator_negation(),
@@ -302,18 +1543,14 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         self.expression_stack.push(expression);
     }
 
-    fn visit_binary_operation(&mut self, binary: &tantalum_ast::BinaryOperation<'_, '_>) {
-        self.visit_expression(binary.left.data());
-        let left = self
-            .expression_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+    fn visit_binary_operation(&mut self, binary: &tantalum_ast::BinaryOperation<'file_name, '_>) {
+        if let BinaryOperator::Assignment = binary.operator.data() {
+            self.lower_assignment(binary);
+            return;
+        }
 
-        self.visit_expression(binary.right.data());
-        let right = self
-            .expression_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+        let left = self.lower_expression(&binary.left);
+        let right = self.lower_expression(&binary.right);
 
         let operator = match binary.operator.data() {
             BinaryOperator::Addition => self.package.build_binary_operator_addition(),
@@ -338,7 +1575,7 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
             BinaryOperator::NotEqual => self.package.build_binary_operator_not_equal(),
             BinaryOperator::LogicalAnd => self.package.build_binary_operator_logical_and(),
             BinaryOperator::LogicalOr => self.package.build_binary_operator_logical_or(),
-            BinaryOperator::Assignment => todo!("assignment operator not yet implemented"),
+            BinaryOperator::Assignment => unreachable!("assignments are lowered separately"),
         };
 
         let expression = self.package.build_expression_binary(operator, left, right);
@@ -346,25 +1583,21 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         self.expression_stack.push(expression);
     }
 
-    fn visit_type_cast(&mut self, cast: &tantalum_ast::TypeCast<'_, '_>) {
-        self.visit_type(cast.ty.data());
-        let ty = self
-            .type_stack
-            .pop()
-            .expect("expected type to exist in stack");
-
-        self.visit_expression(cast.value.data());
-        let expression = self
-            .expression_stack
-            .pop()
-            .expect("expected statement to exist in stack");
+    fn visit_type_cast(&mut self, cast: &tantalum_ast::TypeCast<'file_name, '_>) {
+        let ty = self.lower_type(&cast.ty);
+        let expression = self.lower_expression(&cast.value);
 
         let expression = self.package.build_expression_type_cast(ty, expression);
 
         self.expression_stack.push(expression);
     }
 
-    fn visit_integer_literal(&mut self, integer: &Integer<'_, '_>) {
+    fn visit_error_expression(&mut self) {
+        let expression = self.package.build_expression_error();
+        self.expression_stack.push(expression);
+    }
+
+    fn visit_integer_literal(&mut self, integer: &Integer<'file_name, '_>) {
         let literal = self
             .package
             .build_integer_literal((*integer.value.data()).to_string(), integer.radix);
@@ -374,7 +1607,7 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         self.expression_stack.push(expression);
     }
 
-    fn visit_float_literal(&mut self, float: &Float<'_, '_>) {
+    fn visit_float_literal(&mut self, float: &Float<'file_name, '_>) {
         let literal = self
             .package
             .build_float_literal((*float.value.data()).to_string());
@@ -384,7 +1617,7 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         self.expression_stack.push(expression);
     }
 
-    fn visit_boolean_literal(&mut self, boolean: &Boolean<'_, '_>) {
+    fn visit_boolean_literal(&mut self, boolean: &Boolean<'file_name, '_>) {
         let literal = self.package.build_boolean_literal(
             (*boolean.value.data())
                 .parse::<bool>()
@@ -396,14 +1629,25 @@ impl tantalum_ast::ASTVisitor<'_, '_> for FunctionLoweringContext<'_> {
         self.expression_stack.push(expression);
     }
 
-    fn visit_character_literal(&mut self, character: &Character<'_, '_>) {
-        todo!("character literals not yet implemented, did not lower {character:?}");
+    fn visit_character_literal(&mut self, character: &Character<'file_name, '_>) {
+        let value = self.unescape(character.value.data(), '\'');
+
+        // Characters are a single byte
+        if !value.is_ascii() {
+            self.unsupported_expression("non-ASCII character literals");
+            return;
+        }
+
+        let literal = self.package.build_character_literal(value);
+
+        let expression = self.package.build_expression_literal(literal);
+
+        self.expression_stack.push(expression);
     }
 
-    fn visit_string_literal(&mut self, string: &tantalum_ast::String<'_, '_>) {
-        let literal = self
-            .package
-            .build_string_literal((*string.value.data()).to_string());
+    fn visit_string_literal(&mut self, string: &tantalum_ast::String<'file_name, '_>) {
+        let value = self.unescape(string.value.data(), '"');
+        let literal = self.package.build_string_literal(value);
 
         let expression = self.package.build_expression_literal(literal);
 
@@ -1,47 +1,255 @@
-use tantalum_hlir::HLIRPackage;
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_hlir::{HLIRPackage, Path, PathSegment, TraitId, TypeId};
+use tantalum_span::{Span, Spanned};
 
 mod functions;
 mod prototypes;
+mod types;
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ASTLoweringContext {
-    package: HLIRPackage,
-    errors: Vec<String>,
+pub struct ASTLoweringContext<'file_name> {
+    package: HLIRPackage<'file_name>,
 }
 
-impl ASTLoweringContext {
+impl<'file_name> ASTLoweringContext<'file_name> {
     #[must_use]
     pub fn new() -> Self {
         Self {
             package: HLIRPackage::new(),
-            errors: Vec::new(),
         }
     }
 
-    pub fn lower(&mut self, ast: &tantalum_ast::AST) {
-        // TODO: Process types in AST to produce types
-
-        // TODO: Process traits in AST to produce traits
-
-        // TODO: Process functions in AST to produce prototypes
+    pub fn lower(&mut self, ast: &tantalum_ast::AST<'file_name, '_>) {
+        types::TypeDeclarationContext::new(&mut self.package).lower(ast);
 
-        prototypes::PrototypeLoweringContext::new(&mut self.package).lower(ast);
+        let impls = prototypes::PrototypeLoweringContext::new(&mut self.package).lower(ast);
 
-        // TODO: Process functions in AST to produce implementations
+        functions::FunctionLoweringContext::new(&mut self.package).lower(ast, impls);
 
-        functions::FunctionLoweringContext::new(&mut self.package).lower(ast);
+        // The values of constants may refer to constants declared after them
+        self.package.evaluate_globals();
 
-        eprintln!("{:#?}", self.package);
+        // Generic functions whose bodies have errors cannot be instantiated
+        if !self.package.has_errors() {
+            self.package.monomorphize();
+        }
     }
 
+    /// Finish lowering and get the package.
+    ///
+    /// Any problems found while lowering are available from
+    /// [`HLIRPackage::diagnostics`].
     #[must_use]
-    pub fn finish(self) -> HLIRPackage {
+    pub fn finish(self) -> HLIRPackage<'file_name> {
         self.package
     }
 }
 
-impl Default for ASTLoweringContext {
+impl Default for ASTLoweringContext<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Report a type name that is not defined.
+fn report_unknown_type<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    name: &str,
+    span: Span<'file_name>,
+) {
+    package.report(Diagnostic::error(
+        codes::UNKNOWN_TYPE,
+        format!("cannot find type `{name}` in this scope"),
+        Label::new(span, "not found in this scope"),
+    ));
+}
+
+/// Find the trait called `name`, reporting it if there is none.
+fn lower_trait<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    name: &Spanned<'file_name, &str>,
+) -> Option<TraitId> {
+    let trait_id = package.resolve_trait_id(&Path::from(*(name.data())));
+
+    if trait_id.is_none() {
+        package.report(Diagnostic::error(
+            codes::UNKNOWN_TRAIT,
+            format!("cannot find trait `{}` in this scope", name.data()),
+            Label::new(name.span(), "not found in this scope"),
+        ));
+    }
+
+    trait_id
+}
+
+/// Lower a pointer to a value of any type implementing a trait, e.g.
+/// `*dyn Shape`.
+fn lower_trait_object<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    dyn_: &tantalum_ast::DynType<'file_name, '_>,
+) -> TypeId {
+    match lower_trait(package, &dyn_.trait_name) {
+        Some(trait_id) => package.build_type_trait_object(trait_id),
+        None => package.build_type_error(),
+    }
+}
+
+/// Find the type `named` refers to, instantiated with `arguments` if it is
+/// generic, reporting it if there is none or if the number of type arguments
+/// does not match.
+fn lower_named_type<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    named: &tantalum_ast::NamedType<'file_name, '_>,
+    arguments: Vec<TypeId>,
+    span: Span<'file_name>,
+) -> TypeId {
+    let segments = &named.name.data().segments;
+    let name = display_path(segments);
+    let Some(ty) = package.resolve_type_id(&lower_path(segments)) else {
+        report_unknown_type(package, &name, named.name.span());
+        return package.build_type_error();
+    };
+
+    let expected = package.get_type_parameters(ty).len();
+    if arguments.len() != expected {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+        package.report(Diagnostic::error(
+            codes::ARGUMENT_COUNT_MISMATCH,
+            format!(
+                "type `{name}` takes {expected} type argument{} but {} type argument{} {} supplied",
+                plural(expected),
+                arguments.len(),
+                plural(arguments.len()),
+                if arguments.len() == 1 { "was" } else { "were" },
+            ),
+            Label::new(
+                span,
+                format!("expected {expected} type argument{}", plural(expected)),
+            ),
+        ));
+        return package.build_type_error();
+    }
+
+    if arguments.is_empty() {
+        ty
+    } else {
+        package.build_type_instance(ty, arguments, span)
+    }
+}
+
+/// Get the path written as `segments`, e.g. `shapes::Circle`.
+fn lower_path(segments: &[Spanned<'_, &str>]) -> Path {
+    Path::new(
+        segments
+            .iter()
+            .map(|segment| PathSegment::from(*(segment.data())))
+            .collect(),
+    )
+}
+
+/// Get a path written as `segments` as it appears in the source, to name it
+/// in diagnostics.
+fn display_path(segments: &[Spanned<'_, &str>]) -> String {
+    segments
+        .iter()
+        .map(|segment| *(segment.data()))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Get the names of the type parameters of a generic item, reporting the ones
+/// declared more than once.
+fn lower_type_parameter_names<'file_name, 'source>(
+    package: &mut HLIRPackage<'file_name>,
+    generics: &[Spanned<'file_name, tantalum_ast::GenericParameter<'file_name, 'source>>],
+) -> Vec<&'source str> {
+    let mut names: Vec<&str> = Vec::new();
+
+    for generic in generics {
+        let name = generic.data().name;
+        if names.contains(name.data()) {
+            report_duplicate_definition(package, "type parameter", name.data(), name.span());
+            continue;
+        }
+        names.push(name.data());
+    }
+
+    names
+}
+
+/// Get the traits each of the type parameters called `names` is bounded by,
+/// reporting the ones that do not exist.
+fn lower_trait_bounds<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    generics: &[Spanned<'file_name, tantalum_ast::GenericParameter<'file_name, '_>>],
+    names: &[&str],
+) -> Vec<Vec<TraitId>> {
+    names
+        .iter()
+        .map(|name| {
+            let generic = generics
+                .iter()
+                .find(|generic| generic.data().name.data() == name)
+                .expect("expected type parameter to be declared");
+
+            generic
+                .data()
+                .bounds
+                .iter()
+                .filter_map(|bound| lower_trait(package, bound))
+                .collect()
+        })
+        .collect()
+}
+
+/// Report a name that is defined more than once, `kind` is what the name
+/// refers to (e.g. `type` or `field`).
+fn report_duplicate_definition<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    kind: &str,
+    name: &str,
+    span: Span<'file_name>,
+) {
+    package.report(Diagnostic::error(
+        codes::DUPLICATE_DEFINITION,
+        format!("{kind} `{name}` is defined multiple times"),
+        Label::new(span, "defined again here"),
+    ));
+}
+
+/// Check that `ty`, written at `span` to be used as `what`, is not `void`,
+/// which has no values, reporting it if it is. Gives the type to use.
+fn lower_value_type<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    ty: TypeId,
+    what: &str,
+    span: Span<'file_name>,
+) -> TypeId {
+    if !package.is_void_type(ty) {
+        return ty;
+    }
+
+    package.report(Diagnostic::error(
+        codes::VOID_VALUE,
+        format!("`void` cannot be used as {what}"),
+        Label::new(span, "has no values"),
+    ));
+    package.build_type_error()
+}
+
+/// Report a language feature that cannot be lowered yet.
+fn report_unsupported<'file_name>(
+    package: &mut HLIRPackage<'file_name>,
+    feature: &str,
+    span: Span<'file_name>,
+) {
+    package.report(Diagnostic::error(
+        codes::UNSUPPORTED,
+        format!("{feature} are not supported yet"),
+        Label::new(span, "not supported yet"),
+    ));
+}
@@ -1,16 +1,58 @@
-use tantalum_hlir::{HLIRPackage, Path, PathSegment, TypeId};
+use std::collections::{HashMap, HashSet};
+use tantalum_ast::{ConstType, FunctionType, NamedType, Type};
 
-pub struct PrototypeLoweringContext<'a> {
-    package: &'a mut HLIRPackage,
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_hlir::{
+    FunctionPrototype, GlobalKind, HLIRPackage, Path, PathSegment, StructField, TraitId, TypeId,
+    UnionVariant,
+};
+use tantalum_span::{Span, Spanned};
+
+pub struct PrototypeLoweringContext<'a, 'file_name> {
+    package: &'a mut HLIRPackage<'file_name>,
 
     types: Vec<TypeId>,
+
+    /// The structs whose fields have been lowered, to find the ones defined
+    /// more than once
+    structs: HashSet<TypeId>,
+
+    /// The tagged unions whose variants have been lowered, for the same reason
+    unions: HashSet<TypeId>,
+
+    /// The traits whose methods have been lowered, for the same reason
+    traits: HashSet<TraitId>,
+
+    /// The span of the type currently being lowered
+    span: Option<Span<'file_name>>,
+}
+
+/// The type aliases declared in modules, which are lowered as soon as one
+/// refers to another, since an alias can be used before it is defined.
+#[derive(Default)]
+struct TypeAliases<'ast, 'file_name, 'source> {
+    /// The path and definition of each alias, and whether it has been lowered
+    aliases: Vec<(
+        Path,
+        &'ast tantalum_ast::TypeAlias<'file_name, 'source>,
+        bool,
+    )>,
+    /// The index of each alias by its path
+    indices: HashMap<Path, usize>,
+    /// The aliases being lowered, innermost last, to find the ones referring
+    /// to themselves
+    lowering: Vec<usize>,
 }
 
-impl<'a> PrototypeLoweringContext<'a> {
-    pub fn new(package: &'a mut HLIRPackage) -> Self {
+impl<'a, 'file_name> PrototypeLoweringContext<'a, 'file_name> {
+    pub fn new(package: &'a mut HLIRPackage<'file_name>) -> Self {
         Self {
             package,
             types: Vec::new(),
+            structs: HashSet::new(),
+            unions: HashSet::new(),
+            traits: HashSet::new(),
+            span: None,
         }
     }
 
@@ -20,28 +62,98 @@ impl<'a> PrototypeLoweringContext<'a> {
             .expect("expected void type to exist in package")
     }
 
-    pub fn lower(mut self, ast: &tantalum_ast::AST) {
-        <Self as tantalum_ast::ASTVisitor>::visit_ast(&mut self, ast);
+    fn span(&self) -> Span<'file_name> {
+        self.span.expect("expected a type to be being lowered")
     }
-}
 
-impl tantalum_ast::ASTVisitor<'_, '_> for PrototypeLoweringContext<'_> {
-    fn visit_function(&mut self, function: &tantalum_ast::Function<'_, '_>) {
-        let mut parameters = Vec::new();
+    /// Whether a function called `name` is already defined in the current
+    /// module, reporting it if it is.
+    fn is_function_defined(&mut self, name: &Spanned<'file_name, &str>) -> bool {
+        let defined = self
+            .package
+            .get_function_id(&self.package.item_path(name.data()))
+            .is_some();
+
+        if defined {
+            crate::report_duplicate_definition(self.package, "function", name.data(), name.span());
+        }
+
+        defined
+    }
+
+    fn lower_type(&mut self, ty: &Spanned<'file_name, Type<'file_name, '_>>) -> TypeId {
+        let span = self.span.replace(ty.span());
+        <Self as tantalum_ast::ASTVisitor>::visit_type(self, ty.data());
+        self.span = span;
+
+        self.types
+            .pop()
+            .expect("expected type to have been visited")
+    }
+
+    /// Lower the fields of a struct or of a variant of a tagged union,
+    /// reporting the ones defined more than once.
+    fn lower_fields(
+        &mut self,
+        fields: &[Spanned<'file_name, tantalum_ast::StructField<'file_name, '_>>],
+    ) -> Vec<StructField> {
+        let mut lowered: Vec<StructField> = Vec::new();
+
+        for field in fields {
+            let field = field.data();
+            let field_type = self.lower_type(&field.ty);
+            let field_type = crate::lower_value_type(
+                self.package,
+                field_type,
+                "the type of a field",
+                field.ty.span(),
+            );
+
+            if lowered.iter().any(|other| other.name == *field.name.data()) {
+                crate::report_duplicate_definition(
+                    self.package,
+                    "field",
+                    field.name.data(),
+                    field.name.span(),
+                );
+                continue;
+            }
+
+            lowered.push(StructField {
+                name: (*field.name.data()).to_string(),
+                ty: field_type,
+            });
+        }
+
+        lowered
+    }
+
+    /// Lower the signature of a function, whose type parameters can only be
+    /// named in the signature and in the body.
+    fn lower_prototype(
+        &mut self,
+        type_parameter_names: &[&str],
+        bounds: Vec<Vec<TraitId>>,
+        parameters: &[Spanned<'file_name, tantalum_ast::Parameter<'file_name, '_>>],
+        return_type: Option<&Spanned<'file_name, Type<'file_name, '_>>>,
+    ) -> FunctionPrototype {
+        let type_parameters = self.package.push_type_parameters(type_parameter_names);
+
+        let mut lowered = Vec::new();
         let mut variadic = false;
 
-        for parameter in function.parameters.data() {
-            let parameter = parameter.data();
-            match parameter {
+        for parameter in parameters {
+            match parameter.data() {
                 tantalum_ast::Parameter::Named(named) => {
-                    self.visit_type(named.ty.data());
+                    let parameter_type = self.lower_type(&named.ty);
+                    let parameter_type = crate::lower_value_type(
+                        self.package,
+                        parameter_type,
+                        "the type of a parameter",
+                        named.ty.span(),
+                    );
 
-                    let parameter_type = self
-                        .types
-                        .pop()
-                        .expect("expected type to have been visited");
-
-                    parameters.push(parameter_type);
+                    lowered.push(parameter_type);
                 }
                 tantalum_ast::Parameter::Variadic => {
                     variadic = true;
@@ -49,39 +161,408 @@ impl tantalum_ast::ASTVisitor<'_, '_> for PrototypeLoweringContext<'_> {
             }
         }
 
-        let return_type = if let Some(return_type) = &function.return_type {
-            self.visit_type(return_type.data());
-            self.types
-                .pop()
-                .expect("expected type to have been visited")
+        let return_type = if let Some(return_type) = return_type {
+            self.lower_type(return_type)
         } else {
             self.void_type()
         };
 
-        let prototype = self
+        self.package.pop_type_parameters();
+
+        self.package.build_function_prototype(
+            type_parameters,
+            bounds,
+            lowered,
+            variadic,
+            return_type,
+        )
+    }
+
+    fn lower_function_prototype(
+        &mut self,
+        function: &tantalum_ast::Function<'file_name, '_>,
+    ) -> FunctionPrototype {
+        let type_parameter_names =
+            crate::lower_type_parameter_names(self.package, &function.generics);
+        let bounds =
+            crate::lower_trait_bounds(self.package, &function.generics, &type_parameter_names);
+
+        self.lower_prototype(
+            &type_parameter_names,
+            bounds,
+            function.parameters.data(),
+            function.return_type.as_ref(),
+        )
+    }
+
+    /// Lower the signatures of the methods of an `impl` block, getting the
+    /// trait it implements, if any, and the type it is for, if both exist.
+    fn lower_impl(
+        &mut self,
+        impl_: &tantalum_ast::Impl<'file_name, '_>,
+    ) -> Option<(Option<TraitId>, TypeId)> {
+        match &impl_.trait_name {
+            Some(trait_name) => self
+                .lower_trait_impl(impl_, trait_name)
+                .map(|(trait_id, ty)| (Some(trait_id), ty)),
+            None => self.lower_inherent_impl(impl_).map(|ty| (None, ty)),
+        }
+    }
+
+    /// Lower the signatures of the methods of an implementation of a trait,
+    /// getting the trait and the type it is implemented for, if both exist.
+    fn lower_trait_impl(
+        &mut self,
+        impl_: &tantalum_ast::Impl<'file_name, '_>,
+        trait_name: &Spanned<'file_name, &str>,
+    ) -> Option<(TraitId, TypeId)> {
+        let trait_id = crate::lower_trait(self.package, trait_name);
+        let (trait_name, trait_span) = (*(trait_name.data()), trait_name.span());
+
+        let ty = self.lower_type(&impl_.ty);
+        let trait_id = trait_id?;
+        if self.package.is_error_type(ty) {
+            return None;
+        }
+
+        if self.package.get_trait_impl(trait_id, ty).is_some() {
+            crate::report_duplicate_definition(
+                self.package,
+                "implementation",
+                &format!("{trait_name} for {}", self.package.type_name(ty)),
+                impl_.ty.span(),
+            );
+            return None;
+        }
+
+        self.package.create_trait_impl(trait_id, ty);
+
+        // `Self` is the type the trait is implemented for in the signatures
+        // and bodies of the methods
+        self.package.push_self_type(ty);
+
+        let mut implemented = HashSet::new();
+        for method in impl_.methods.data() {
+            let method = method.data();
+            let name = *(method.name.data());
+
+            let trait_ = self
+                .package
+                .get_trait(trait_id)
+                .expect("expected trait to exist");
+            let Some(method_id) = trait_.get_method(name) else {
+                self.package.report(Diagnostic::error(
+                    codes::UNKNOWN_TRAIT_METHOD,
+                    format!("method `{name}` is not a member of trait `{trait_name}`"),
+                    Label::new(
+                        method.name.span(),
+                        format!("not a member of `{trait_name}`"),
+                    ),
+                ));
+                continue;
+            };
+
+            if !implemented.insert(method_id) {
+                crate::report_duplicate_definition(
+                    self.package,
+                    "method",
+                    name,
+                    method.name.span(),
+                );
+                continue;
+            }
+
+            let prototype = self.lower_function_prototype(method);
+
+            self.package.create_trait_impl_method(
+                trait_id,
+                ty,
+                method_id,
+                prototype,
+                method.name.span(),
+            );
+        }
+
+        self.package.pop_type_parameters();
+
+        let trait_ = self
             .package
-            .build_function_prototype(parameters, variadic, return_type);
+            .get_trait(trait_id)
+            .expect("expected trait to exist");
+        let missing = trait_
+            .method_ids()
+            .into_iter()
+            .filter(|id| !implemented.contains(id))
+            .map(|id| format!("`{}`", trait_.method_definitions[&id].name))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            self.package.report(Diagnostic::error(
+                codes::MISSING_TRAIT_METHODS,
+                format!("not all methods of trait `{trait_name}` are implemented"),
+                Label::new(trait_span, format!("missing {}", missing.join(", "))),
+            ));
+        }
+
+        Some((trait_id, ty))
+    }
+
+    /// Lower the signatures of the methods of an `impl` block of a type
+    /// itself, getting the type if it exists.
+    fn lower_inherent_impl(
+        &mut self,
+        impl_: &tantalum_ast::Impl<'file_name, '_>,
+    ) -> Option<TypeId> {
+        let ty = self.lower_type(&impl_.ty);
+        if self.package.is_error_type(ty) {
+            return None;
+        }
+
+        self.package.push_self_type(ty);
+
+        for method in impl_.methods.data() {
+            let method = method.data();
+            let name = *(method.name.data());
+
+            // Methods can be defined across several `impl` blocks of the type
+            if self.package.get_method(ty, name).is_some() {
+                crate::report_duplicate_definition(
+                    self.package,
+                    "method",
+                    name,
+                    method.name.span(),
+                );
+                continue;
+            }
+
+            let prototype = self.lower_function_prototype(method);
+            self.package.create_method(ty, name, prototype);
+        }
+
+        self.package.pop_type_parameters();
+
+        Some(ty)
+    }
+
+    /// Lower the contents of every item, getting the trait, if any, and type
+    /// of each `impl` block in the order they are defined.
+    ///
+    /// Type aliases are lowered first, so that they can be used by the other
+    /// items. The methods of `impl` blocks are lowered after every trait,
+    /// since an implementation can come before its trait.
+    pub fn lower(
+        mut self,
+        ast: &tantalum_ast::AST<'file_name, '_>,
+    ) -> Vec<Option<(Option<TraitId>, TypeId)>> {
+        let mut aliases = TypeAliases::default();
+        self.collect_type_aliases(&ast.0, &mut aliases);
+        for index in 0..aliases.aliases.len() {
+            self.lower_type_alias(index, &mut aliases);
+        }
+
+        <Self as tantalum_ast::ASTVisitor>::visit_ast(&mut self, ast);
+
+        let mut lowered = Vec::new();
+        self.lower_impls(&ast.0, &mut lowered);
+        lowered
+    }
+
+    /// Lower the `impl` blocks among `items`, including the ones in modules,
+    /// in the order they are defined.
+    fn lower_impls(
+        &mut self,
+        items: &[Spanned<'file_name, tantalum_ast::Item<'file_name, '_>>],
+        lowered: &mut Vec<Option<(Option<TraitId>, TypeId)>>,
+    ) {
+        for item in items {
+            match item.data() {
+                tantalum_ast::Item::Impl(impl_) => lowered.push(self.lower_impl(impl_)),
+                // The items of a module whose file could not be loaded are
+                // left out, the missing file has already been reported
+                tantalum_ast::Item::Module(tantalum_ast::Module {
+                    name,
+                    items: Some(items),
+                }) => {
+                    self.package.enter_module(name.data());
+                    self.lower_impls(items.data(), lowered);
+                    self.package.exit_module();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Find the type aliases among `items`, including the ones in modules,
+    /// reporting the ones whose name is already taken by a type.
+    fn collect_type_aliases<'ast, 'source>(
+        &mut self,
+        items: &'ast [Spanned<'file_name, tantalum_ast::Item<'file_name, 'source>>],
+        aliases: &mut TypeAliases<'ast, 'file_name, 'source>,
+    ) {
+        for item in items {
+            match item.data() {
+                tantalum_ast::Item::TypeAlias(alias) => {
+                    let name = *(alias.name.data());
+                    let path = self.package.item_path(name);
+
+                    if aliases.indices.contains_key(&path)
+                        || self.package.get_type_id(&path).is_some()
+                    {
+                        crate::report_duplicate_definition(
+                            self.package,
+                            "type",
+                            name,
+                            alias.name.span(),
+                        );
+                        continue;
+                    }
+
+                    aliases.indices.insert(path.clone(), aliases.aliases.len());
+                    aliases.aliases.push((path, alias, false));
+                }
+                tantalum_ast::Item::Module(tantalum_ast::Module {
+                    name,
+                    items: Some(items),
+                }) => {
+                    self.package.enter_module(name.data());
+                    self.collect_type_aliases(items.data(), aliases);
+                    self.package.exit_module();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Lower the type alias at `index` unless it already has been, first
+    /// lowering the aliases its type refers to.
+    fn lower_type_alias(&mut self, index: usize, aliases: &mut TypeAliases<'_, 'file_name, '_>) {
+        let (path, alias, lowered) = aliases.aliases[index].clone();
+        if lowered {
+            return;
+        }
+
+        if let Some(position) = aliases.lowering.iter().position(|other| *other == index) {
+            self.report_type_alias_cycle(&aliases.lowering[position..], aliases);
+
+            // Every alias in the cycle is then lowered as an error
+            let error = self.package.build_type_error();
+            self.package.add_type_alias(path, error);
+            aliases.aliases[index].2 = true;
+            return;
+        }
+
+        // The type is written in the module the alias is declared in
+        let mut module = path.clone();
+        module.pop();
+        let previous = self.package.switch_module(module);
+
+        aliases.lowering.push(index);
+        let mut named = Vec::new();
+        named_types(alias.ty.data(), &mut named);
+        for named in named {
+            let dependency = self
+                .package
+                .resolve_path(&crate::lower_path(&named.name.data().segments), |path| {
+                    aliases.indices.contains_key(path) || self.package.get_type_id(path).is_some()
+                })
+                .and_then(|path| aliases.indices.get(&path).copied());
+
+            if let Some(dependency) = dependency {
+                self.lower_type_alias(dependency, aliases);
+            }
+        }
+        aliases.lowering.pop();
+
+        // An alias in a cycle has already been lowered by the time the cycle
+        // gets back to it
+        if !aliases.aliases[index].2 {
+            let ty = self.lower_type(&alias.ty);
+            self.package.add_type_alias(path, ty);
+            aliases.aliases[index].2 = true;
+        }
+
+        self.package.switch_module(previous);
+    }
+
+    /// Report the type aliases at `cycle`, each of which refers to the next
+    /// one, the last one referring to the first.
+    /// Declare a constant or static in the current module, reporting it if a
+    /// value is already declared with the same name.
+    fn declare_global(&mut self, name: &Spanned<'file_name, &str>, kind: GlobalKind, ty: TypeId) {
+        let path = self.package.item_path(name.data());
+
+        if self.package.add_global(path, kind, ty).is_none() {
+            crate::report_duplicate_definition(
+                self.package,
+                &kind.to_string(),
+                name.data(),
+                name.span(),
+            );
+        }
+    }
+
+    fn report_type_alias_cycle(
+        &mut self,
+        cycle: &[usize],
+        aliases: &TypeAliases<'_, 'file_name, '_>,
+    ) {
+        let name = |index: &usize| *(aliases.aliases[*index].1.name.data());
+        let first = aliases.aliases[cycle[0]].1;
+
+        let references = cycle
+            .iter()
+            .skip(1)
+            .chain([&cycle[0]])
+            .map(|index| format!("`{}`", name(index)))
+            .collect::<Vec<_>>()
+            .join(", which refers to ");
+
+        self.package.report(
+            Diagnostic::error(
+                codes::CYCLIC_TYPE_ALIAS,
+                format!("type alias `{}` refers to itself", first.name.data()),
+                Label::new(first.name.span(), "defined in terms of itself"),
+            )
+            .with_note(format!("`{}` refers to {references}", name(&cycle[0]))),
+        );
+    }
+}
+
+impl<'file_name> tantalum_ast::ASTVisitor<'file_name, '_>
+    for PrototypeLoweringContext<'_, 'file_name>
+{
+    fn visit_function(&mut self, function: &tantalum_ast::Function<'file_name, '_>) {
+        if self.is_function_defined(&function.name) {
+            return;
+        }
+
+        let prototype = self.lower_function_prototype(function);
 
         self.package
-            .create_function(Path::from(*(function.name.data())), prototype);
+            .create_function(self.package.item_path(function.name.data()), prototype);
     }
 
     fn visit_external_function(
         &mut self,
-        external_function: &tantalum_ast::ExternalFunction<'_, '_>,
+        external_function: &tantalum_ast::ExternalFunction<'file_name, '_>,
     ) {
+        if self.is_function_defined(&external_function.name) {
+            return;
+        }
+
         let mut is_variadic = false;
         let mut parameters = Vec::new();
 
         for parameter in external_function.parameters.data() {
             match parameter.data() {
                 tantalum_ast::Parameter::Named(named) => {
-                    self.visit_type(named.ty.data());
-
-                    let parameter_type = self
-                        .types
-                        .pop()
-                        .expect("expected type to have been visited");
+                    let parameter_type = self.lower_type(&named.ty);
+                    let parameter_type = crate::lower_value_type(
+                        self.package,
+                        parameter_type,
+                        "the type of a parameter",
+                        named.ty.span(),
+                    );
 
                     parameters.push(parameter_type);
                 }
@@ -92,58 +573,260 @@ impl tantalum_ast::ASTVisitor<'_, '_> for PrototypeLoweringContext<'_> {
         }
 
         let return_type = if let Some(return_type) = &external_function.return_type {
-            self.visit_type(return_type.data());
-            self.types
-                .pop()
-                .expect("expected type to have been visited")
+            self.lower_type(return_type)
         } else {
             self.void_type()
         };
 
-        let prototype = self
+        let prototype = self.package.build_function_prototype(
+            Vec::new(),
+            Vec::new(),
+            parameters,
+            is_variadic,
+            return_type,
+        );
+
+        self.package.create_function(
+            self.package.item_path(external_function.name.data()),
+            prototype,
+        );
+    }
+
+    fn visit_constant(&mut self, constant: &tantalum_ast::Constant<'file_name, '_>) {
+        let ty = self.lower_type(&constant.ty);
+        self.declare_global(&constant.name, GlobalKind::Constant, ty);
+    }
+
+    fn visit_static(&mut self, static_: &tantalum_ast::Static<'file_name, '_>) {
+        let ty = self.lower_type(&static_.ty);
+        self.declare_global(&static_.name, GlobalKind::Static, ty);
+    }
+
+    fn visit_external_static(
+        &mut self,
+        external_static: &tantalum_ast::ExternalStatic<'file_name, '_>,
+    ) {
+        let ty = self.lower_type(&external_static.ty);
+        self.declare_global(&external_static.name, GlobalKind::ExternalStatic, ty);
+    }
+
+    fn visit_module(&mut self, module: &tantalum_ast::Module<'file_name, '_>) {
+        self.package.enter_module(module.name.data());
+        for item in module.items.iter().flat_map(Spanned::data) {
+            self.visit_item(item.data());
+        }
+        self.package.exit_module();
+    }
+
+    fn visit_trait(&mut self, trait_: &tantalum_ast::Trait<'file_name, '_>) {
+        let trait_id = self
+            .package
+            .get_trait_id(&self.package.item_path(trait_.name.data()))
+            .filter(|id| self.traits.insert(*id));
+
+        // Traits defined more than once are reported when they are declared
+        let Some(trait_id) = trait_id else {
+            return;
+        };
+
+        let mut names = HashSet::new();
+        for method in trait_.methods.data() {
+            let method = method.data();
+            let name = *(method.name.data());
+
+            if !names.insert(name) {
+                crate::report_duplicate_definition(
+                    self.package,
+                    "method",
+                    name,
+                    method.name.span(),
+                );
+                continue;
+            }
+
+            // Each method is generic over `Self`, the type implementing the
+            // trait
+            let prototype = self.lower_prototype(
+                &["Self"],
+                vec![Vec::new()],
+                method.parameters.data(),
+                method.return_type.as_ref(),
+            );
+
+            self.package.add_trait_method(trait_id, name, prototype);
+        }
+    }
+
+    fn visit_struct(&mut self, structure: &tantalum_ast::Struct<'file_name, '_>) {
+        let name = *(structure.name.data());
+
+        let ty = self
             .package
-            .build_function_prototype(parameters, is_variadic, return_type);
+            .get_type_id(&self.package.item_path(name))
+            .filter(|ty| self.package.get_struct(*ty).is_some() && self.structs.insert(*ty));
+
+        let Some(ty) = ty else {
+            crate::report_duplicate_definition(self.package, "type", name, structure.name.span());
+            return;
+        };
 
+        // The type parameters of a generic struct can only be named in its
+        // fields
+        self.package.push_type_parameters_of(ty);
+        let fields = self.lower_fields(structure.fields.data());
+        self.package.pop_type_parameters();
+
+        self.package.set_struct_fields(ty, fields);
         self.package
-            .create_function(Path::from(*(external_function.name.data())), prototype);
+            .report_type_recursion_limit(structure.name.span());
     }
 
-    fn visit_named_type(&mut self, named: &tantalum_ast::NamedType<'_, '_>) {
-        let path = Path::from(*(named.name.data()));
-        let type_id = self
+    // Enums defined more than once are reported when they are declared, and
+    // the variants left out there are skipped here
+    fn visit_enum(&mut self, enumeration: &tantalum_ast::Enum<'file_name, '_>) {
+        let ty = self
             .package
-            .get_type_id(&path)
-            .expect("expected type to exist in package");
+            .get_type_id(&self.package.item_path(enumeration.name.data()))
+            .filter(|ty| self.package.get_union(*ty).is_some() && self.unions.insert(*ty));
+
+        let Some(ty) = ty else {
+            return;
+        };
+
+        let mut variants: Vec<UnionVariant> = self
+            .package
+            .get_union(ty)
+            .expect("expected type to be a tagged union")
+            .variants
+            .clone();
+        let mut lowered = HashSet::new();
+
+        self.package.push_type_parameters_of(ty);
+
+        for variant in enumeration.variants.data() {
+            let variant = variant.data();
+            let name = *(variant.name.data());
+
+            if !lowered.insert(name) {
+                continue;
+            }
+
+            let fields = match &variant.fields {
+                tantalum_ast::VariantFields::Unit => Vec::new(),
+                tantalum_ast::VariantFields::Tuple(types) => types
+                    .data()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ty)| {
+                        let field_type = self.lower_type(ty);
+                        StructField {
+                            name: index.to_string(),
+                            ty: crate::lower_value_type(
+                                self.package,
+                                field_type,
+                                "the type of a field",
+                                ty.span(),
+                            ),
+                        }
+                    })
+                    .collect(),
+                tantalum_ast::VariantFields::Struct(fields) => self.lower_fields(fields.data()),
+            };
+
+            if let Some(lowered) = variants.iter_mut().find(|other| other.name == name) {
+                lowered.fields = fields;
+            }
+        }
+
+        self.package.pop_type_parameters();
+
+        self.package.set_union_variants(ty, variants);
+        self.package
+            .report_type_recursion_limit(enumeration.name.span());
+    }
+
+    fn visit_named_type(&mut self, named: &tantalum_ast::NamedType<'file_name, '_>) {
+        let arguments = named
+            .arguments
+            .iter()
+            .map(|argument| {
+                let ty = self.lower_type(argument);
+                crate::lower_value_type(self.package, ty, "a type argument", argument.span())
+            })
+            .collect();
+        let type_id = crate::lower_named_type(self.package, named, arguments, self.span());
         self.types.push(type_id);
     }
 
-    fn visit_pointer_type(&mut self, pointer: &tantalum_ast::PointerType<'_, '_>) {
-        self.visit_type(pointer.ty.data());
-        let ty = self
-            .types
-            .pop()
-            .expect("expected type to have been visited");
-        let pointer_type = self.package.build_type_pointer(ty);
+    fn visit_function_type(&mut self, _function: &FunctionType<'file_name, '_>) {
+        crate::report_unsupported(self.package, "function types", self.span());
+        let type_id = self.package.build_type_error();
+        self.types.push(type_id);
+    }
+
+    fn visit_pointer_type(&mut self, pointer: &tantalum_ast::PointerType<'file_name, '_>) {
+        // A pointer to a trait object is a type of its own
+        let pointer_type = if let Type::Dyn(dyn_) = pointer.ty.data() {
+            crate::lower_trait_object(self.package, dyn_)
+        } else {
+            let ty = self.lower_type(&pointer.ty);
+            self.package.build_type_pointer(ty)
+        };
         self.types.push(pointer_type);
     }
 
-    fn visit_sized_array_type(&mut self, array: &tantalum_ast::SizedArrayType<'_, '_>) {
-        self.visit_type(array.ty.data());
-        let ty = self
-            .types
-            .pop()
-            .expect("expected type to have been visited");
+    fn visit_sized_array_type(&mut self, array: &tantalum_ast::SizedArrayType<'file_name, '_>) {
+        let ty = self.lower_type(&array.ty);
+        let ty = crate::lower_value_type(
+            self.package,
+            ty,
+            "the type of an array element",
+            array.ty.span(),
+        );
         let array_type = self.package.build_type_array(ty, *(array.size.data()));
         self.types.push(array_type);
     }
 
-    fn visit_unsized_array_type(&mut self, array: &tantalum_ast::UnsizedArrayType<'_, '_>) {
-        self.visit_type(array.ty.data());
-        let ty = self
-            .types
-            .pop()
-            .expect("expected type to have been visited");
+    fn visit_unsized_array_type(&mut self, array: &tantalum_ast::UnsizedArrayType<'file_name, '_>) {
+        let ty = self.lower_type(&array.ty);
         let array_type = self.package.build_type_unsized_array(ty);
         self.types.push(array_type);
     }
+
+    // Constness is not checked yet, so a `const` type is the type itself
+    fn visit_const_type(&mut self, constant: &ConstType<'file_name, '_>) {
+        let ty = self.lower_type(&constant.ty);
+        self.types.push(ty);
+    }
+
+    fn visit_dyn_type(&mut self, _dyn: &tantalum_ast::DynType<'file_name, '_>) {
+        crate::report_unsupported(
+            self.package,
+            "trait objects not behind a pointer",
+            self.span(),
+        );
+        let type_id = self.package.build_type_error();
+        self.types.push(type_id);
+    }
+}
+
+/// Find the named types `ty` is built from, e.g. `Meters` in `*[Meters; 3]`.
+fn named_types<'ast, 'file_name, 'source>(
+    ty: &'ast Type<'file_name, 'source>,
+    found: &mut Vec<&'ast NamedType<'file_name, 'source>>,
+) {
+    match ty {
+        Type::Named(named) => {
+            found.push(named);
+            for argument in &named.arguments {
+                named_types(argument.data(), found);
+            }
+        }
+        Type::Pointer(pointer) => named_types(pointer.ty.data(), found),
+        Type::SizedArray(array) => named_types(array.ty.data(), found),
+        Type::UnsizedArray(array) => named_types(array.ty.data(), found),
+        Type::Const(constant) => named_types(constant.ty.data(), found),
+        // Function types are not supported yet, and trait objects name traits
+        Type::Function(_) | Type::Dyn(_) => {}
+    }
 }
@@ -0,0 +1,1824 @@
+use tantalum_diagnostics::{Renderer, SourceMap};
+
+use crate::ASTLoweringContext;
+
+/// Lower `source` and render every diagnostic reported along the way
+fn lower(source: &str) -> String {
+    let (rendered, syntax_errors) = lower_with_syntax_errors(source);
+    assert_eq!(syntax_errors, 0, "failed to parse source");
+
+    rendered
+}
+
+/// Lower `source` even if it contains syntax errors, returning the rendered
+/// lowering diagnostics and the number of syntax errors
+fn lower_with_syntax_errors(source: &str) -> (String, usize) {
+    let lexer = tantalum_lexer::Lexer::new("test.ta", source);
+    let mut parser = tantalum_parser::Parser::new(lexer);
+    let (ast, errors) = parser.parse();
+
+    let mut context = ASTLoweringContext::new();
+    context.lower(&ast);
+    let package = context.finish();
+
+    let mut sources = SourceMap::new();
+    sources.add("test.ta", source);
+    let renderer = Renderer::new(&sources);
+
+    let diagnostics = package
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| renderer.render(diagnostic))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (diagnostics, errors.len())
+}
+
+#[test]
+fn valid_program_has_no_diagnostics() {
+    let source = r"
+extern fn abs(value: i32): i32;
+
+fn main(argc: i32): i32 {
+    let x: i32 = abs(argc);
+    return x + 1;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn unknown_names() {
+    let source = r"
+fn main(): i32 {
+    let x: Foo = 1;
+    print(y);
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn argument_count_mismatch() {
+    let source = r"
+fn add(a: i32, b: i32): i32 {
+    return a + b;
+}
+
+fn main(): i32 {
+    return add(1);
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn unsupported_features() {
+    let source = r"
+fn main(): char {
+    return 'é';
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn lowers_around_syntax_errors() {
+    let source = r"
+fn broken(: i32 {}
+
+fn main(): i32 {
+    let x = ;
+    let y: i32 = 1 + * 2;
+    return z;
+}
+";
+
+    let (rendered, syntax_errors) = lower_with_syntax_errors(source);
+
+    assert_eq!(syntax_errors, 3);
+    insta::assert_snapshot!(rendered);
+}
+
+#[test]
+fn mismatched_types() {
+    let source = r#"
+extern fn puts(string: *u8): i32;
+
+fn main(): i32 {
+    let flag: i32 = true;
+    puts("hello");
+    if 1 { return 0; }
+    while 2 {}
+    return 1.5;
+}
+"#;
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn invalid_references() {
+    let source = r#"
+fn main(): i32 {
+    let x: i32 = 1;
+    let y: *bool = x.&;
+    let z: *i32 = "hello".&;
+    return x.*;
+}
+"#;
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn type_errors_do_not_cascade() {
+    let source = r"
+fn main(): i32 {
+    let x = y;
+    let z: bool = x + 1;
+    return z;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn type_annotations_needed() {
+    let source = r"
+fn none<T>(): i32 {
+    return 0;
+}
+
+fn main(): i32 {
+    let value = none();
+    return 0;
+}
+
+fn empty(): i32 {
+    let values = [];
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn references_to_values_of_inferred_types() {
+    let source = r"
+fn main(): i32 {
+    let x = 1;
+    let p = x.&;
+    let q = 2.5.&;
+    let r = p.&;
+    let s = [x, 3].&;
+    return p.* + r.*.* + s.*[1];
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn loops() {
+    let source = r"
+fn main(n: i32): i32 {
+    let total = 0;
+    for let i = 0; i < n; i + 1; {
+        if i > 10 { break; }
+        continue;
+    }
+    while n > 0 {
+        for let j: i32 = 0; j < n; j + 1; { break; }
+        continue;
+    }
+    return total;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn loop_control_outside_of_loop() {
+    let source = r"
+fn main(): i32 {
+    break;
+    if true { continue; }
+    for let i = 0; let j = 1; i + 1; {}
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn assignments() {
+    let source = r"
+fn main(pointer: *i32, values: *i32): i32 {
+    let x = 1;
+    x = x + 1;
+    pointer.* = x;
+    values[x] = pointer.*;
+    values[0] = values[1];
+    return x;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_assignments() {
+    let source = r"
+fn five(): i32 {
+    return 5;
+}
+
+fn main(): i32 {
+    let x = 1;
+    1 = x;
+    five() = 2;
+    x = true;
+    let n: i32 = 0;
+    n[0] = 1;
+    let p: *i32 = x.&;
+    p[false] = 2;
+    y = 3;
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn operators() {
+    let source = r"
+fn main(a: i32, b: u32, x: f64): bool {
+    let c = a % 3 == 0 || a << 2 >= 8 && (a ^ 1) != 0;
+    let d = b / 2 < (b >> 1 | 4);
+    let e = x <= 1.0;
+    return c && d || e;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn logical_operators_expect_bool() {
+    let source = r"
+fn main(a: i32): bool {
+    return a && true;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn integer_literals_out_of_range() {
+    let source = r"
+type Byte = u8;
+
+fn main(): i32 {
+    let fits: i8 = -128;
+    let largest: u64 = 18446744073709551615;
+    let mask: u8 = 0xff;
+    let x: u8 = 300;
+    let y: u32 = -1;
+    let z: Byte = 0x100;
+    let huge = 340282366920938463463374607431768211456;
+    let big: f64 = 1000000;
+    return 2147483648;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn void_values() {
+    let source = r#"
+extern fn printf(format: *const u8, ...): i32;
+
+struct S {
+    v: void,
+}
+
+enum E {
+    A(void),
+}
+
+struct Box<T> {
+    value: T,
+}
+
+fn f() {}
+
+fn g(x: void) {}
+
+fn id<T>(x: T): T {
+    return x;
+}
+
+fn h() {
+    return f();
+}
+
+fn main(): i32 {
+    let v = f();
+    let values = [f(), f()];
+    let repeated = [f(); 2];
+    let p = f().&;
+    let typed: i32 = f();
+    printf("%d".&, f());
+    id(f());
+    let boxed = Box { value: f() };
+    let b: Box<void> = boxed;
+    let a: [void; 2] = values;
+    f();
+    return 0;
+}
+"#;
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn missing_returns() {
+    let source = r"
+enum Color { Red, Green }
+
+fn empty(): i32 {}
+
+fn without_else(flag: bool): i32 {
+    if flag {
+        return 1;
+    }
+}
+
+fn with_else(flag: bool): i32 {
+    if flag {
+        return 1;
+    } else {
+        return 2;
+    }
+}
+
+fn endless(): i32 {
+    while true {
+        while true {
+            break;
+        }
+    }
+}
+
+fn exited(): i32 {
+    while true {
+        if true {
+            break;
+        }
+    }
+}
+
+fn matched(color: Color): i32 {
+    match color {
+        Color::Red => return 1;
+        Color::Green => { return 2; }
+    }
+}
+
+fn partly_matched(color: Color): i32 {
+    match color {
+        Color::Red => return 1;
+        Color::Green => {}
+    }
+}
+
+fn nothing() {}
+
+fn main(): i32 {
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn invalid_operands() {
+    let source = r#"
+struct P {
+    x: i32,
+}
+
+fn main(f: f32): i32 {
+    let p = P { x: 1 } + P { x: 2 };
+    let s = "a" + "b";
+    let b = true + false;
+    let m = f & 2.0;
+    let c = P { x: 1 } == P { x: 2 };
+    return 0;
+}
+"#;
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn literals_and_casts() {
+    let source = r#"
+extern fn printf(format: *u8, ...): i32;
+
+fn main(): i32 {
+    let newline = '\n';
+    let quote: char = '\'';
+    let half = 1.0 / 2:f64;
+    let byte = newline:u8 + quote:u8;
+    let flag = !(half > 0.25);
+    let pointer: *u8 = "tab\t\"quoted\"\0".&;
+    let address = pointer:u64;
+    printf(address:*u8, -(byte:i32), ~byte, flag:i32);
+    return 1.5:i32;
+}
+"#;
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_literals_and_casts() {
+    let source = r#"
+fn main(): i32 {
+    let text = "unknown \q escape";
+    let accent = 'é';
+    let flag = 1:bool;
+    let pointer = 1.5:*u8;
+    let inverted = !1;
+    return "text":i32;
+}
+"#;
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn structs() {
+    let source = r"
+struct Line {
+    start: Point,
+    end: Point,
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn length(line: *Line): i32 {
+    return line.end.x - line.start.x + line.end.y - line.start.y;
+}
+
+fn main(): i32 {
+    let origin = Point { x: 0, y: 0 };
+    let line = Line { end: Point { y: 4, x: 3 }, start: origin };
+    line.start.x = 1;
+    let pointer = line.&;
+    pointer.end.y = 2;
+    return length(pointer);
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_structs() {
+    let source = r"
+struct Point {
+    x: i32,
+    x: i32,
+    y: i32,
+}
+
+struct Point {}
+
+fn main(): i32 {
+    let a = Point { x: 1, x: 2, z: 3 };
+    let b = Vector { x: 1 };
+    let c = Point { x: true, y: 2 };
+    return a.z + c.x.y;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn enums() {
+    let source = r"
+enum Color: u8 {
+    Red = 1,
+    Green,
+    Blue = 0x10,
+}
+
+enum Ordering: i8 { Less = -1, Equal, Greater }
+
+fn is_red(color: Color): bool {
+    return color == Color::Red;
+}
+
+fn main(): i32 {
+    let color = 2:Color;
+    let order = Ordering::Less;
+    if is_red(color) || order != Ordering::Greater {
+        return Color::Blue:i32;
+    }
+    return order:i32;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_enums() {
+    let source = r"
+enum Color: u8 {
+    Red = 255,
+    Green,
+    Blue = 255,
+    Red,
+}
+
+enum Flag: bool { Yes, No = x }
+
+fn main(): i32 {
+    let a: Color = 1;
+    let b = Color::Purple;
+    let c = Shade::Dark;
+    let d = Color::Red:bool;
+    return Color::Red;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn tagged_unions() {
+    let source = r"
+enum Shape: u8 {
+    Empty,
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+}
+
+enum Color { Red, Green }
+
+fn is_round(shape: Shape): bool {
+    return shape is Shape::Circle;
+}
+
+fn main(): i32 {
+    let empty = Shape::Empty;
+    let circle = Shape::Circle(1.5);
+    let rect = Shape::Rect { h: 2.0, w: 3.0 };
+    if is_round(circle) && !(rect is Shape::Empty) && Color::Red is Color::Red {
+        return 1;
+    }
+    return 0;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_tagged_unions() {
+    let source = r"
+enum Shape {
+    Circle(f32),
+    Rect { w: f32, h: f32, h: f32 },
+    Circle,
+}
+
+enum Color { Red }
+
+fn main(): i32 {
+    let a = Shape::Circle;
+    let b = Shape::Circle(1.0, 2.0);
+    let c = Shape::Rect(1.0);
+    let d = Shape::Rect { w: 1.0 };
+    let e = Shape::Circle { x: true };
+    let f = Color::Red(1);
+    let g = Shape::Square(1.0);
+    let h = Shape::Circle(true);
+    return (Shape::Circle(1.0) is Color::Red):i32;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn matches() {
+    let source = r"
+enum Shape: u8 {
+    Empty,
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+}
+
+enum Color { Red, Green, Blue }
+
+fn area(shape: Shape): f32 {
+    match shape {
+        Shape::Empty => return 0.0;
+        Shape::Circle(radius) => return radius * radius * 3.14;
+        Shape::Rect { w, h: height } => { return w * height; }
+    }
+    return 0.0;
+}
+
+fn classify(value: u8, color: Color, flag: bool): i32 {
+    match value {
+        0 => return 0;
+        1 | 2 | 3 => return 1;
+        4..10 => return 2;
+        10..=254 => return 3;
+        255 => return 4;
+    }
+    match color {
+        Color::Red | Color::Green => return 5;
+        _ => {}
+    }
+    match flag {
+        true => return 6;
+        false => {}
+    }
+    match 'a' {
+        'a'..='z' => return 7;
+        other => return other:i32;
+    }
+}
+
+fn main(): i32 {
+    let shape = Shape::Rect { w: 1.0, h: 2.0 };
+    match shape {
+        Shape::Rect { .. } => return 1;
+        _ => return 0;
+    }
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_matches() {
+    let source = r"
+enum Shape {
+    Empty,
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+}
+
+enum Color { Red, Green, Blue }
+
+fn main(shape: Shape, color: Color, value: i8): i32 {
+    match shape {
+        Shape::Empty => {}
+        Shape::Circle(_, _) => {}
+        Shape::Rect { w } => {}
+        Shape::Square => {}
+        Shape::Empty(x) => {}
+    }
+    match shape {
+        Shape::Circle(radius) => {}
+    }
+    match color {
+        Color::Red => {}
+        Color::Red | Color::Green => {}
+    }
+    match value {
+        -128..0 => {}
+        1..=100 => {}
+        5 => {}
+    }
+    match value {
+        10..5 => {}
+        _ => {}
+    }
+    match true {
+        true => {}
+        1 => {}
+        x | false => {}
+        _ => {}
+    }
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn generic_functions() {
+    let source = r"
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn max<T>(a: T, b: T): T {
+    if a > b {
+        return a;
+    }
+    return b;
+}
+
+fn first<T>(values: *T): T {
+    return values[0];
+}
+
+fn swap<A, B>(a: *A, b: *B, value: A): B {
+    a.* = value;
+    return b.*;
+}
+
+fn identity<T>(value: T): T {
+    return value;
+}
+
+fn main(values: *f64): i32 {
+    let a = max(1, 2);
+    let b: u8 = max(3, 4);
+    let c = first(values);
+    let point = Point { x: 1, y: 2 };
+    let d = identity(point).x;
+    let e = swap(a.&, c.&, 5);
+    return a + identity(b):i32 + d;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_generic_functions() {
+    let source = r"
+fn pair<T, T>(a: T): T {
+    return a;
+}
+
+fn max<T>(a: T, b: T): T {
+    return a;
+}
+
+fn first<T>(values: *T): T {
+    return values.*;
+}
+
+fn none<T>(): i32 {
+    return 0;
+}
+
+fn main(): i32 {
+    let a = max(1, true);
+    let b = first(1);
+    return 0;
+}
+
+fn other(): i32 {
+    return none();
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn infinitely_recursive_generic_function() {
+    let source = r"
+fn nest<T>(value: T): i32 {
+    return nest(value.&);
+}
+
+fn main(): i32 {
+    return nest(1);
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn generic_types() {
+    let source = r"
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+enum Option<T> {
+    None,
+    Some(T),
+}
+
+struct List<T> {
+    value: T,
+    next: *List<T>,
+}
+
+fn first<A, B>(pair: Pair<A, B>): A {
+    return pair.first;
+}
+
+fn unwrap_or<T>(option: Option<T>, default: T): T {
+    match option {
+        Option::Some(value) => return value;
+        Option::None => return default;
+    }
+    return default;
+}
+
+fn main(list: List<u8>): i32 {
+    let pair = Pair { first: 1, second: 2.0 };
+    let sum = pair.first + first(pair);
+    let nested: Pair<Option<i64>, bool> = Pair { first: Option::Some(3), second: true };
+    let none: Option<i32> = Option::None;
+    let head = list.next.value;
+    if nested.first is Option::Some && none is Option::None {
+        return unwrap_or(none, sum) + unwrap_or(Option::Some(4), 5) + head:i32;
+    }
+    return pair.second:i32;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_generic_types() {
+    let source = r"
+struct Pair<A, A> {
+    first: A,
+}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+enum Option<T> {
+    None,
+    Some(T),
+}
+
+fn main(): i32 {
+    let a: Wrapper = Wrapper { value: 1 };
+    let b: Wrapper<i32, i32> = Wrapper { value: 1 };
+    let c: i32<u8> = 0;
+    let d: Wrapper<bool> = Wrapper { value: 1 };
+    let e = Wrapper { value: true }.missing;
+    return 0;
+}
+
+fn other(): i32 {
+    let none = Option::None;
+    return 0;
+}
+
+struct Node<T> {
+    value: T,
+    next: *Node<*T>,
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn traits() {
+    let source = r"
+impl Shape for Circle {
+    fn area(self: *Self): f32 {
+        let circle: *Circle = self;
+        return 3.14 * circle.radius * circle.radius;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.radius = self.radius * by;
+    }
+}
+
+trait Shape {
+    fn area(self: *Self): f32;
+    fn scale(self: *Self, by: f32);
+}
+
+struct Circle {
+    radius: f32,
+}
+
+struct Square {
+    side: f32,
+}
+
+impl Shape for Square {
+    fn area(self: *Square): f32 {
+        return self.side * self.side;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.side = self.side * by;
+    }
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_traits() {
+    let source = r"
+trait Shape {
+    fn area(self: *Self): f32;
+    fn scale(self: *Self, by: f32);
+    fn area(self: *Self): f64;
+}
+
+trait Shape {}
+
+struct Circle {
+    radius: f32,
+}
+
+impl Shape for Circle {
+    fn area(self: *Self): i32 {
+        return 0;
+    }
+
+    fn perimeter(self: *Self): f32 {
+        return 0.0;
+    }
+}
+
+impl Shape for Circle {
+    fn area(self: *Self): f32 {
+        return true;
+    }
+}
+
+impl Drawable for Circle {}
+
+impl Shape for Missing {}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn trait_bounds() {
+    let source = r#"
+trait Shape {
+    fn area(self: *Self): f32;
+}
+
+trait Named {
+    fn name(self: *Self): *u8;
+}
+
+struct Circle {
+    radius: f32,
+}
+
+struct Square {
+    side: f32,
+}
+
+impl Shape for Circle {
+    fn area(self: *Self): f32 {
+        return 3.14 * self.radius * self.radius;
+    }
+}
+
+impl Shape for Square {
+    fn area(self: *Self): f32 {
+        return self.side * self.side;
+    }
+}
+
+impl Named for Square {
+    fn name(self: *Self): *u8 {
+        return "square".&;
+    }
+}
+
+fn area<T: Shape>(shape: *T): f32 {
+    return shape.area();
+}
+
+fn describe<T: Shape + Named>(shape: *T): f32 {
+    let name = shape.name();
+    return area(shape);
+}
+
+fn main(): i32 {
+    let circle = Circle { radius: 1.0 };
+    let square = Square { side: 2.0 };
+    let total = area(circle.&) + describe(square.&) + square.&.area();
+    return total:i32;
+}
+"#;
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_trait_bounds() {
+    let source = r#"
+trait Shape {
+    fn area(self: *Self): f32;
+}
+
+trait Solid {
+    fn area(self: *Self): f32;
+}
+
+trait Named {
+    fn name(self: *Self): *u8;
+}
+
+struct Circle {
+    radius: f32,
+}
+
+struct Wrapper<T: Shape> {
+    value: T,
+}
+
+impl Named for Circle {
+    fn name(self: *Self): *u8 {
+        return "circle".&;
+    }
+}
+
+fn perimeter<T: Shape + Missing>(shape: *T): f32 {
+    return 0.0;
+}
+
+fn name<T>(value: *T): *u8 {
+    return value.name();
+}
+
+fn main(): i32 {
+    let circle = Circle { radius: 1.0 };
+    let a = perimeter(circle.&);
+    let b = (circle.&).area();
+    let c = (circle.&).volume();
+    return 0;
+}
+"#;
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn inherent_impls() {
+    let source = r"
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+impl Point {
+    fn new(x: f32, y: f32): Point {
+        return Point { x: x, y: y };
+    }
+
+    fn length(self: *const Point): f32 {
+        return self.x * self.x + self.y * self.y;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.x = self.x * by;
+        self.y = self.y * by;
+    }
+}
+
+impl Point {
+    fn origin(): Self {
+        return Self::new(0.0, 0.0);
+    }
+}
+
+fn main(): i32 {
+    let point = Point::new(3.0, 4.0);
+    point.scale(2.0);
+    let a = point.length();
+    let b = (point.&).length();
+    let c = Point::origin().length();
+    let d = Point::length(point.&);
+    return 0;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_inherent_impls() {
+    let source = r"
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+impl Point {
+    fn length(self: *Point): f32 {
+        return 0.0;
+    }
+
+    fn length(self: *Point): f32 {
+        return 1.0;
+    }
+}
+
+impl Point {
+    fn length(self: *Point): f32 {
+        return 2.0;
+    }
+}
+
+impl Missing {
+    fn new(): i32 {
+        return 0;
+    }
+}
+
+fn main(): i32 {
+    let point = Point { x: 1.0, y: 2.0 };
+    let a = point.width();
+    let b = Point::width();
+    let c = Point::length(point);
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn trait_objects() {
+    let source = r"
+trait Shape {
+    fn area(self: *Self): f32;
+    fn scale(self: *Self, by: f32);
+}
+
+struct Circle {
+    radius: f32,
+}
+
+struct Square {
+    side: f32,
+}
+
+impl Shape for Circle {
+    fn area(self: *Self): f32 {
+        return 3.14 * self.radius * self.radius;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.radius = self.radius * by;
+    }
+}
+
+impl Shape for Square {
+    fn area(self: *Self): f32 {
+        return self.side * self.side;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.side = self.side * by;
+    }
+}
+
+struct Scene {
+    first: *dyn Shape,
+    second: *dyn Shape,
+}
+
+fn total(shape: *dyn Shape, other: *dyn Shape): f32 {
+    shape.scale(2.0);
+    return shape.area() + other.area();
+}
+
+fn widen<T: Shape>(shape: *T): *dyn Shape {
+    return shape;
+}
+
+fn main(): i32 {
+    let circle = Circle { radius: 1.0 };
+    let square = Square { side: 2.0 };
+    let shape: *dyn Shape = circle.&;
+    let scene = Scene { first: shape, second: square.& };
+    let a = total(circle.&, widen(square.&));
+    let b = scene.second.area();
+    let c = (circle.&):*dyn Shape;
+    return 0;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_trait_objects() {
+    let source = r"
+trait Shape {
+    fn area(self: *Self): f32;
+    fn same(self: *Self, other: *Self): bool;
+}
+
+struct Circle {
+    radius: f32,
+}
+
+struct Square {
+    side: f32,
+}
+
+impl Shape for Circle {
+    fn area(self: *Self): f32 {
+        return 0.0;
+    }
+
+    fn same(self: *Self, other: *Self): bool {
+        return true;
+    }
+}
+
+fn describe(shape: *dyn Shape, value: dyn Shape, other: *dyn Missing) {
+    let a = shape.area(1.0);
+    let b = shape.same(shape);
+    let c = shape.perimeter();
+}
+
+fn measure(shape: *dyn Shape): f32 {
+    return shape.area();
+}
+
+fn main(): i32 {
+    let square = Square { side: 1.0 };
+    let shape: *dyn Shape = square.&;
+    let area = measure(1.0.&);
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn modules() {
+    let source = r"
+mod shapes {
+    struct Circle {
+        radius: f32,
+    }
+
+    impl Circle {
+        fn new(radius: f32): Circle {
+            return Circle { radius: radius };
+        }
+    }
+
+    enum Kind {
+        Round,
+        Square(f32),
+    }
+
+    fn area(circle: *Circle): f32 {
+        return 3.14 * circle.radius * circle.radius;
+    }
+
+    mod units {
+        fn scale(value: f32): f32 {
+            return value * 2.0;
+        }
+
+        fn doubled(circle: Circle): f32 {
+            return scale(area(circle.&));
+        }
+    }
+}
+
+struct Circle {
+    diameter: i32,
+}
+
+fn area(circle: *shapes::Circle): f32 {
+    return shapes::area(circle);
+}
+
+fn main(): i32 {
+    let circle = shapes::Circle { radius: 1.0 };
+    let other: shapes::Circle = shapes::Circle::new(2.0);
+    let round = shapes::Kind::Round;
+    let square = shapes::Kind::Square(1.0);
+    let a = area(circle.&) + shapes::units::scale(other.radius);
+    let b = shapes::units::doubled(circle);
+    let c = Circle { diameter: 2 };
+    return c.diameter;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn module_items_are_named_by_their_path() {
+    let source = r"
+mod shapes {
+    mod circle {
+        fn area(): f32 {
+            return 0.0;
+        }
+    }
+}
+
+fn main(): i32 {
+    return 0;
+}
+";
+    let lexer = tantalum_lexer::Lexer::new("test.ta", source);
+    let mut parser = tantalum_parser::Parser::new(lexer);
+    let (ast, _) = parser.parse();
+
+    let mut context = ASTLoweringContext::new();
+    context.lower(&ast);
+    let package = context.finish();
+
+    let mut names = package
+        .prototypes()
+        .map(|(_, name, _)| name)
+        .collect::<Vec<_>>();
+    names.sort();
+
+    assert_eq!(names, ["__main", "__shapes__circle__area"]);
+}
+
+#[test]
+fn invalid_modules() {
+    let source = r"
+mod shapes {
+    struct Circle {
+        radius: f32,
+    }
+
+    struct Circle {
+        radius: f32,
+    }
+
+    fn area(circle: *Circle): f32 {
+        return circle.radius;
+    }
+}
+
+mod shapes {}
+
+fn main(): i32 {
+    let a = shapes::perimeter(1.0);
+    let b: shapes::Square = 1;
+    let c = area(1.0);
+    let d = Circle { radius: 1.0 };
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn imports() {
+    let source = r"
+mod shapes {
+    struct Circle {
+        radius: f32,
+    }
+
+    struct Square {
+        side: f32,
+    }
+
+    trait Shape {
+        fn area(self: *Self): f32;
+    }
+
+    fn unit(): Circle {
+        return Circle { radius: 1.0 };
+    }
+
+    mod units {
+        fn scale(value: f32): f32 {
+            return value * 2.0;
+        }
+    }
+}
+
+mod scene {
+    use shapes::{Circle, Shape, Square as Quad, unit};
+    use shapes::units;
+
+    impl Shape for Circle {
+        fn area(self: *Self): f32 {
+            return units::scale(self.radius);
+        }
+    }
+
+    fn total(circle: *Circle, quad: Quad): f32 {
+        return circle.area() + quad.side + unit().radius;
+    }
+}
+
+use scene::total;
+use shapes::units::scale as double;
+
+fn main(): i32 {
+    let circle = shapes::Circle { radius: 1.0 };
+    let quad = shapes::Square { side: 2.0 };
+    let a = double(total(circle.&, quad));
+    return 0;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_imports() {
+    let source = r"
+mod shapes {
+    struct Circle {
+        radius: f32,
+    }
+}
+
+use shapes::{Circle, Triangle};
+use shapes::Circle as Circle;
+use missing::Thing;
+use nothing;
+
+struct Square {
+    side: f32,
+}
+
+use shapes::Circle as Square;
+
+fn main(): i32 {
+    let triangle: Triangle = 1;
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn package_files_are_lowered_into_one_package() {
+    let files = [
+        (
+            "main.ta",
+            "mod shapes;\nuse shapes::area;\nfn main(): i32 { let a = area(2.0); return 0; }",
+        ),
+        (
+            "shapes/mod.ta",
+            "mod circle;\nfn area(radius: f32): f32 { return circle::pi() * radius * radius; }",
+        ),
+        ("shapes/circle.ta", "fn pi(): f32 { return 3.14; }"),
+    ];
+    let files =
+        tantalum_parser::package::SourceFiles::load_with(std::path::Path::new("main.ta"), |path| {
+            files
+                .iter()
+                .find(|(name, _)| std::path::Path::new(name) == path)
+                .map(|(_, source)| (*source).to_string())
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))
+        })
+        .expect("expected the root file to exist");
+
+    let (ast, diagnostics) = files.parse();
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    let mut context = ASTLoweringContext::new();
+    context.lower(&ast);
+    let package = context.finish();
+    assert!(
+        package.diagnostics().is_empty(),
+        "{:?}",
+        package.diagnostics()
+    );
+
+    let mut names = package
+        .prototypes()
+        .map(|(_, name, _)| name)
+        .collect::<Vec<_>>();
+    names.sort();
+
+    assert_eq!(names, ["__main", "__shapes__area", "__shapes__circle__pi"]);
+}
+
+#[test]
+fn module_files_declared_twice() {
+    let files = [
+        (
+            "main.ta",
+            "mod b;\nmod b;\nfn main(): i32 { return b::f(); }",
+        ),
+        (
+            "b.ta",
+            "fn f(): i32 { return 1; }\nfn f(): i32 { return 2; }",
+        ),
+    ];
+    let files =
+        tantalum_parser::package::SourceFiles::load_with(std::path::Path::new("main.ta"), |path| {
+            files
+                .iter()
+                .find(|(name, _)| std::path::Path::new(name) == path)
+                .map(|(_, source)| (*source).to_string())
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))
+        })
+        .expect("expected the root file to exist");
+
+    let (ast, diagnostics) = files.parse();
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    let mut context = ASTLoweringContext::new();
+    context.lower(&ast);
+    let package = context.finish();
+
+    let mut sources = SourceMap::new();
+    for file in files.files() {
+        sources.add(file.name(), file.source());
+    }
+    let renderer = Renderer::new(&sources);
+
+    insta::assert_snapshot!(package
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| renderer.render(diagnostic))
+        .collect::<Vec<_>>()
+        .join("\n"));
+}
+
+#[test]
+fn type_aliases() {
+    let source = r"
+type Meters = Distance;
+type Distance = f32;
+
+struct Path {
+    points: *Point,
+    length: Meters,
+}
+
+mod geometry {
+    type Coordinate = i32;
+    type Pair = super_point;
+    type super_point = Point;
+}
+
+struct Point {
+    x: geometry::Coordinate,
+    y: geometry::Coordinate,
+}
+
+fn length(path: *Path): Meters {
+    type Scale = f32;
+    let scale: Scale = 2.0;
+    return path.length * scale;
+}
+
+fn main(): i32 {
+    {
+        type Count = i64;
+        let count: Count = 3;
+    }
+
+    type Count = u8;
+    let origin: geometry::Pair = Point { x: 0, y: 0 };
+    let count: Count = 1;
+    return origin.x;
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_type_aliases() {
+    let source = r"
+type A = B;
+type B = *A;
+type Itself = [Itself];
+type Uses = A;
+
+struct Point {
+    x: i32,
+}
+
+type Point = i32;
+type Missing = Unknown;
+
+fn main(): i32 {
+    {
+        type Inner = i32;
+    }
+
+    let inner: Inner = 1;
+    let missing: Missing = 1;
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn type_errors_name_type_aliases() {
+    let source = r"
+type Int = i32;
+
+mod units {
+    type Meters = f32;
+}
+
+fn main(): i32 {
+    let y: Int = 2.0;
+    let z: Int = true;
+    let length: units::Meters = 1.0;
+    let flag: bool = length;
+    let x: i32 = false;
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn globals() {
+    let source = r#"
+const AREA: i32 = WIDTH * HEIGHT;
+const WIDTH: i32 = 4 + 2;
+const HEIGHT: i32 = -(1 << 3);
+const MASK: u8 = ~0x0f;
+const SCALE: f64 = 1.5 * 2;
+const WIDE: bool = WIDTH > 5 && !false;
+const BYTE: u8 = 300:u8;
+const NAME: str = "tantalum";
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+const ORIGIN: Point = Point { x: 0, y: WIDTH };
+const CORNERS: [Point; 2] = [ORIGIN, Point { x: WIDTH, y: HEIGHT }];
+
+static counter: i64 = AREA:i64;
+static table: [u8; 3] = [1, 2, MASK];
+static zeros: [i32; 4] = [0; 4];
+extern static errno: i32;
+
+mod config {
+    const LIMIT: u16 = super_limit;
+    const super_limit: u16 = 8;
+}
+
+fn main(): i32 {
+    let limit = config::LIMIT;
+    counter = counter + 1;
+    let WIDTH = 3;
+    WIDTH = errno;
+    return AREA;
+}
+"#;
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_globals() {
+    let source = r"
+fn compute(): i32 {
+    return 1;
+}
+
+static COUNTER: i32 = 0;
+
+const CALL: i32 = compute();
+const FROM_STATIC: i32 = COUNTER;
+const PING: i32 = PONG + 1;
+const PONG: i32 = PING;
+const ITSELF: i32 = ITSELF;
+const USES_CYCLE: i32 = PING;
+const OVERFLOW: u8 = 200 + 100;
+const DIVISION: i32 = 1 / (2 - 2);
+const SHIFT: i32 = 1 << 32;
+const COUNTER: i32 = 1;
+const WRONG: bool = 1;
+const CELLS: [i32; 2] = [1, compute()];
+
+fn main(): i32 {
+    OVERFLOW = 1;
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn arrays() {
+    let source = r"
+struct Grid {
+    cells: [[u8; 3]; 2],
+}
+
+fn sum(values: [i32; 4]): i32 {
+    let total = 0;
+    let i: u32 = 0;
+    while i < 4 {
+        total = total + values[i];
+        i = i + 1;
+    }
+    return total;
+}
+
+fn main(): i32 {
+    let values = [1, 2, 3, 4];
+    let zeros: [i64; 8] = [0; 8];
+    let grid = Grid { cells: [[0; 3], [1, 2, 3]] };
+    let flags = [true; 2];
+    values[0] = 10;
+    let cell: u8 = grid.cells[1][2];
+    return sum(values) + zeros[7]:i32 + values[grid.cells[0][0]];
+}
+";
+
+    assert_eq!(lower(source), "");
+}
+
+#[test]
+fn invalid_arrays() {
+    let source = r"
+fn main(): i32 {
+    let mixed = [1, true];
+    let short: [i32; 3] = [1, 2];
+    let values = [1, 2, 3];
+    let value = values[1.5];
+    let number = 4;
+    let digit = number[0];
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
+
+#[test]
+fn duplicate_functions() {
+    let source = r"
+extern fn puts(string: *const u8): i32;
+extern fn puts(string: *const u8): i32;
+
+fn helper(): i32 {
+    return 1;
+}
+
+fn helper(value: bool): bool {
+    return value;
+}
+
+fn main(): i32 {
+    return helper();
+}
+";
+
+    insta::assert_snapshot!(lower(source));
+}
@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_hlir::{
+    EnumVariant, HLIRPackage, LiteralValue, Path, PathSegment, PrimitiveType, Type, UnionVariant,
+    VariantKind,
+};
+use tantalum_span::Spanned;
+
+/// Declares every type and trait defined in the AST, so that they can be
+/// referred to before the item defining them.
+///
+/// The contents of structs are lowered along with the prototypes, which is
+/// also where structs defined more than once are reported. Enums without
+/// values only refer to integer types, so they are lowered entirely here,
+/// while the values carried by the variants of tagged unions are lowered along
+/// with the prototypes, as are the methods of traits.
+pub struct TypeDeclarationContext<'a, 'file_name> {
+    package: &'a mut HLIRPackage<'file_name>,
+
+    /// The paths of the modules declared so far, to find the ones defined
+    /// more than once
+    modules: HashSet<Path>,
+}
+
+impl<'a, 'file_name> TypeDeclarationContext<'a, 'file_name> {
+    pub fn new(package: &'a mut HLIRPackage<'file_name>) -> Self {
+        Self {
+            package,
+            modules: HashSet::new(),
+        }
+    }
+
+    pub fn lower(mut self, ast: &tantalum_ast::AST<'file_name, '_>) {
+        <Self as tantalum_ast::ASTVisitor>::visit_ast(&mut self, ast);
+    }
+
+    /// Get the integer type an enum is stored as, which is `i32` unless
+    /// another one is given.
+    fn lower_representation(
+        &mut self,
+        ty: Option<&Spanned<'file_name, tantalum_ast::Type<'file_name, '_>>>,
+    ) -> PrimitiveType {
+        let Some(ty) = ty else {
+            return PrimitiveType::I32;
+        };
+
+        let representation = match ty.data() {
+            tantalum_ast::Type::Named(named) => self
+                .package
+                .resolve_type_id(&crate::lower_path(&named.name.data().segments))
+                .and_then(|id| self.package.get_type(id)),
+            _ => None,
+        };
+
+        match representation.as_deref() {
+            Some(Type::Primitive(primitive)) if primitive.is_integer() => *primitive,
+            _ => {
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_ENUM_REPRESENTATION,
+                    "enums must be stored as an integer type",
+                    Label::new(ty.span(), "expected an integer type"),
+                ));
+
+                PrimitiveType::I32
+            }
+        }
+    }
+
+    /// Report the trait bounds of the type parameters of a generic type, which
+    /// are only supported on functions.
+    fn report_bounds(
+        &mut self,
+        generics: &[Spanned<'file_name, tantalum_ast::GenericParameter<'file_name, '_>>],
+    ) {
+        for bound in generics.iter().flat_map(|generic| &generic.data().bounds) {
+            crate::report_unsupported(self.package, "trait bounds on types", bound.span());
+        }
+    }
+
+    /// Get the value of an explicit discriminant, which must be an integer
+    /// literal, reporting it if it is not.
+    fn evaluate_discriminant(
+        &mut self,
+        expression: &Spanned<'file_name, tantalum_ast::Expression<'file_name, '_>>,
+    ) -> Option<i128> {
+        let value = match expression.data() {
+            tantalum_ast::Expression::Literal(tantalum_ast::Literal::Integer(integer)) => {
+                LiteralValue::parse_integer(integer.value.data(), integer.radix).ok()
+            }
+            tantalum_ast::Expression::UnaryOperation(unary)
+                if *(unary.operator.data()) == tantalum_ast::UnaryOperator::Negation =>
+            {
+                return self
+                    .evaluate_discriminant(&unary.operand)
+                    .map(|value| -value);
+            }
+            _ => None,
+        };
+
+        if value.is_none() {
+            self.package.report(Diagnostic::error(
+                codes::INVALID_DISCRIMINANT,
+                "discriminants must be integer literals",
+                Label::new(expression.span(), "expected an integer literal"),
+            ));
+        }
+
+        value
+    }
+}
+
+impl<'file_name> tantalum_ast::ASTVisitor<'file_name, '_>
+    for TypeDeclarationContext<'_, 'file_name>
+{
+    fn visit_struct(&mut self, structure: &tantalum_ast::Struct<'file_name, '_>) {
+        let path = self.package.item_path(structure.name.data());
+
+        if self.package.get_type_id(&path).is_none() {
+            let ty = self.package.add_struct(path);
+
+            self.report_bounds(&structure.generics);
+            let names = crate::lower_type_parameter_names(self.package, &structure.generics);
+            if !names.is_empty() {
+                self.package.set_type_parameters(ty, &names);
+            }
+        }
+    }
+
+    fn visit_enum(&mut self, enumeration: &tantalum_ast::Enum<'file_name, '_>) {
+        let name = *(enumeration.name.data());
+        let path = self.package.item_path(name);
+
+        if self.package.get_type_id(&path).is_some() {
+            crate::report_duplicate_definition(self.package, "type", name, enumeration.name.span());
+            return;
+        }
+
+        let representation = self.lower_representation(enumeration.ty.as_ref());
+        let range = representation
+            .integer_range()
+            .expect("expected the representation of an enum to be an integer");
+
+        let mut variants: Vec<(EnumVariant, VariantKind)> = Vec::new();
+        // Variants without a discriminant follow on from the previous one
+        let mut next = 0;
+
+        for variant in enumeration.variants.data() {
+            let span = variant.span();
+            let variant = variant.data();
+            let variant_name = *(variant.name.data());
+
+            let discriminant = variant
+                .discriminant
+                .as_ref()
+                .and_then(|discriminant| self.evaluate_discriminant(discriminant))
+                .unwrap_or(next);
+            next = discriminant.saturating_add(1);
+
+            if variants.iter().any(|(other, _)| other.name == variant_name) {
+                crate::report_duplicate_definition(
+                    self.package,
+                    "variant",
+                    variant_name,
+                    variant.name.span(),
+                );
+                continue;
+            }
+
+            if let Some((other, _)) = variants
+                .iter()
+                .find(|(other, _)| other.discriminant == discriminant)
+            {
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_DISCRIMINANT,
+                    format!("discriminant value `{discriminant}` is assigned more than once"),
+                    Label::new(span, format!("`{}` already has this value", other.name)),
+                ));
+            } else if !range.contains(&discriminant) {
+                self.package.report(Diagnostic::error(
+                    codes::INVALID_DISCRIMINANT,
+                    format!(
+                        "discriminant value `{discriminant}` does not fit in `{representation}`"
+                    ),
+                    Label::new(span, format!("out of range for `{representation}`")),
+                ));
+            }
+
+            let kind = match variant.fields {
+                tantalum_ast::VariantFields::Unit => VariantKind::Unit,
+                tantalum_ast::VariantFields::Tuple(_) => VariantKind::Tuple,
+                tantalum_ast::VariantFields::Struct(_) => VariantKind::Struct,
+            };
+
+            variants.push((
+                EnumVariant {
+                    name: variant_name.to_string(),
+                    discriminant,
+                },
+                kind,
+            ));
+        }
+
+        self.report_bounds(&enumeration.generics);
+        let type_parameter_names =
+            crate::lower_type_parameter_names(self.package, &enumeration.generics);
+
+        // Only tagged unions can be instantiated, so generic enums are always
+        // lowered as one
+        if type_parameter_names.is_empty()
+            && variants.iter().all(|(_, kind)| *kind == VariantKind::Unit)
+        {
+            let ty = self.package.add_enum(path, representation);
+            self.package.set_enum_variants(
+                ty,
+                variants.into_iter().map(|(variant, _)| variant).collect(),
+            );
+        } else {
+            // The values of the variants are set along with the prototypes
+            let ty = self.package.add_union(path, representation);
+            if !type_parameter_names.is_empty() {
+                self.package.set_type_parameters(ty, &type_parameter_names);
+            }
+            self.package.set_union_variants(
+                ty,
+                variants
+                    .into_iter()
+                    .map(|(variant, kind)| UnionVariant {
+                        name: variant.name,
+                        discriminant: variant.discriminant,
+                        kind,
+                        fields: Vec::new(),
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    fn visit_use(&mut self, use_: &tantalum_ast::Use<'file_name, '_>) {
+        for import in &use_.imports {
+            let import = import.data();
+            let name = import.alias.unwrap_or(import.name);
+
+            let mut path = crate::lower_path(&use_.prefix.segments);
+            path.push(PathSegment::from(*(import.name.data())));
+
+            if !self.package.add_import(name.data(), path) {
+                crate::report_duplicate_definition(
+                    self.package,
+                    "import",
+                    name.data(),
+                    name.span(),
+                );
+            }
+        }
+    }
+
+    fn visit_module(&mut self, module: &tantalum_ast::Module<'file_name, '_>) {
+        let name = *(module.name.data());
+
+        // The items of a module defined more than once are still declared,
+        // as if they were all in the first one
+        if !self.modules.insert(self.package.item_path(name)) {
+            crate::report_duplicate_definition(self.package, "module", name, module.name.span());
+        }
+
+        self.package.enter_module(name);
+        for item in module.items.iter().flat_map(Spanned::data) {
+            self.visit_item(item.data());
+        }
+        self.package.exit_module();
+    }
+
+    fn visit_trait(&mut self, trait_: &tantalum_ast::Trait<'file_name, '_>) {
+        let name = *(trait_.name.data());
+        let path = self.package.item_path(name);
+
+        if self.package.get_trait_id(&path).is_some() {
+            crate::report_duplicate_definition(self.package, "trait", name, trait_.name.span());
+            return;
+        }
+
+        self.package.create_trait(path);
+    }
+}
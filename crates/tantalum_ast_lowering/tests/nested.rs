@@ -56,13 +56,11 @@ fn basic_assertions() {
     let mut ast =
         tantalum_parser::Parser::new(tantalum_lexer::Lexer::new("basic_assertions", SOURCE));
 
-    let ast = match ast.parse() {
-        Ok(ast) => ast,
-        Err(err) => {
-            eprintln!("{err}");
-            panic!();
-        }
-    };
+    let (ast, errors) = ast.parse();
+    if let Some(err) = errors.first() {
+        eprintln!("{err}");
+        panic!();
+    }
 
     let mut context = ASTLoweringContext::new();
 
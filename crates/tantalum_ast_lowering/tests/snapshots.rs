@@ -7,7 +7,8 @@ macro_rules! snapshot_from_file {
 
             let mut parser = tantalum_parser::Parser::new(lexer);
 
-            let ast = parser.parse().expect("failed to parse source");
+            let (ast, errors) = parser.parse();
+            assert!(errors.is_empty(), "failed to parse source: {errors:?}");
 
             let mut context = tantalum_ast_lowering::ASTLoweringContext::new();
 
@@ -1,18 +1,29 @@
+use inkwell::basic_block::BasicBlock;
 use inkwell::builder::{Builder, BuilderError};
-use inkwell::context::Context;
-use inkwell::module::Module;
-use inkwell::targets::{
-    FileType, InitializationConfig, Target, TargetMachine, TargetMachineOptions,
+use inkwell::module::{Linkage, Module};
+use inkwell::passes::PassBuilderOptions;
+use inkwell::targets::{InitializationConfig, Target, TargetMachine, TargetMachineOptions};
+use inkwell::types::{AnyTypeEnum, BasicType, BasicTypeEnum, FunctionType, StructType};
+use inkwell::values::{
+    AnyValue, AnyValueEnum, BasicMetadataValueEnum, BasicValueEnum, FloatValue, FunctionValue,
+    GlobalValue, IntValue, PointerValue,
 };
-use inkwell::types::{AnyTypeEnum, BasicTypeEnum, StringRadix};
-use inkwell::values::{AnyValue, AnyValueEnum, BasicValueEnum, FunctionValue};
-use inkwell::AddressSpace;
+use inkwell::{AddressSpace, FloatPredicate, IntPredicate};
 use std::collections::HashMap;
+use std::rc::Rc;
 use tantalum_hlir::{
-    BinaryOperation, BinaryOperator, Expression, FunctionCall, FunctionId, HLIRPackage, Let,
-    Literal, LiteralValue, PrimitiveType, Return, Statement, Type, TypeId, VariableId,
+    ArrayLiteral, ArrayRepeat, Assign, BinaryOperation, BinaryOperator, Constant, DynamicCall,
+    EnumValue, Expression, FieldAccess, For, FunctionCall, FunctionId, FunctionPrototype,
+    GlobalKind, HLIRPackage, If, Index, InferenceId, Let, Literal, LiteralValue, Match, MatchArm,
+    Pattern, Place, PrimitiveType, Return, Statement, StructLiteral, TraitId, Type, TypeCast,
+    TypeId, UnaryOperation, UnaryOperator, UnionValue, UnionVariant, VariableId, VariantCheck,
+    While,
 };
 
+pub use inkwell::context::Context;
+pub use inkwell::targets::FileType;
+pub use inkwell::OptimizationLevel;
+
 #[derive(Debug)]
 pub struct LLVMCodegenContext<'ctx> {
     context: &'ctx Context,
@@ -22,7 +33,24 @@ pub struct LLVMCodegenContext<'ctx> {
     // package: HLIRPackage,
     types: HashMap<TypeId, AnyTypeEnum<'ctx>>,
     functions: HashMap<FunctionId, FunctionValue<'ctx>>,
-    values: HashMap<VariableId, AnyValueEnum<'ctx>>,
+    /// The table of methods of every implementation of a trait, which trait
+    /// objects point to
+    vtables: HashMap<(TraitId, TypeId), GlobalValue<'ctx>>,
+    /// The stack slot of every variable, including parameters so that they
+    /// can be assigned to, and the storage of every static
+    values: HashMap<VariableId, PointerValue<'ctx>>,
+    /// The loops enclosing the statement being built, innermost last
+    loops: Vec<Loop<'ctx>>,
+    /// The type arguments of the instantiation of a generic function being
+    /// built, which replace the type parameters in the types of its body
+    type_arguments: Vec<TypeId>,
+}
+
+/// The blocks that `break` and `continue` branch to inside of a loop
+#[derive(Debug, Clone, Copy)]
+struct Loop<'ctx> {
+    continue_block: BasicBlock<'ctx>,
+    break_block: BasicBlock<'ctx>,
 }
 
 impl<'ctx> LLVMCodegenContext<'ctx> {
@@ -36,7 +64,10 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
             // package,
             types: HashMap::new(),
             functions: HashMap::new(),
+            vtables: HashMap::new(),
             values: HashMap::new(),
+            loops: Vec::new(),
+            type_arguments: Vec::new(),
         }
     }
 
@@ -61,6 +92,72 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
     /// Returns an error if the target machine could not be initialized or
     /// if the target machine could not write the assembly to the output file.
     pub fn compile(&self, output: impl AsRef<std::path::Path>) -> Result<(), String> {
+        self.write_to_file(output, FileType::Assembly, OptimizationLevel::None)
+    }
+
+    /// Write the module to `output` as either assembly or an object file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target machine could not be initialized or
+    /// if the target machine could not write the output file.
+    pub fn write_to_file(
+        &self,
+        output: impl AsRef<std::path::Path>,
+        file_type: FileType,
+        level: OptimizationLevel,
+    ) -> Result<(), String> {
+        let target_machine = Self::target_machine(level)?;
+
+        target_machine
+            .write_to_file(&self.module, file_type, output.as_ref())
+            .map_err(|err| err.to_string())?;
+
+        Ok(())
+    }
+
+    /// Write the module to memory as either assembly or an object file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target machine could not be initialized or
+    /// if the target machine could not emit the module.
+    pub fn write_to_memory(
+        &self,
+        file_type: FileType,
+        level: OptimizationLevel,
+    ) -> Result<Vec<u8>, String> {
+        let target_machine = Self::target_machine(level)?;
+
+        let buffer = target_machine
+            .write_to_memory_buffer(&self.module, file_type)
+            .map_err(|err| err.to_string())?;
+
+        Ok(buffer.as_slice().to_vec())
+    }
+
+    /// Run the default LLVM optimization pipeline for `level` over the module.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target machine could not be initialized or if
+    /// the pass pipeline failed to run.
+    pub fn optimize(&self, level: OptimizationLevel) -> Result<(), String> {
+        let target_machine = Self::target_machine(level)?;
+
+        let passes = match level {
+            OptimizationLevel::None => "default<O0>",
+            OptimizationLevel::Less => "default<O1>",
+            OptimizationLevel::Default => "default<O2>",
+            OptimizationLevel::Aggressive => "default<O3>",
+        };
+
+        self.module
+            .run_passes(passes, &target_machine, PassBuilderOptions::create())
+            .map_err(|err| err.to_string())
+    }
+
+    fn target_machine(level: OptimizationLevel) -> Result<TargetMachine, String> {
         Target::initialize_native(&InitializationConfig::default())
             .map_err(|err| err.to_string())?;
 
@@ -68,17 +165,11 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
 
         let target = Target::from_triple(&triple).map_err(|err| err.to_string())?;
 
-        let options = TargetMachineOptions::default();
+        let options = TargetMachineOptions::default().set_level(level);
 
-        let target_machine = target
+        target
             .create_target_machine_from_options(&triple, options)
-            .ok_or_else(|| "failed to create target machine".to_string())?;
-
-        target_machine
-            .write_to_file(&self.module, FileType::Assembly, output.as_ref())
-            .map_err(|err| err.to_string())?;
-
-        Ok(())
+            .ok_or_else(|| "failed to create target machine".to_string())
     }
 
     /// # Errors
@@ -91,8 +182,12 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
     pub fn build(&mut self, package: &HLIRPackage) {
         self.build_types(package);
 
+        self.build_globals(package);
+
         self.build_prototypes(package);
 
+        self.build_vtables(package);
+
         self.build_functions(package);
     }
 
@@ -102,52 +197,230 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
     //     }
     // }
     fn build_types(&mut self, package: &HLIRPackage) {
+        // Structs are declared first so that any type can refer to them, and
+        // their fields are set once every type is known. Generic structs are
+        // only built through their instantiations, each a struct of its own
+        for (id, ty) in package.types() {
+            if let Type::Struct(_) = ty {
+                if package.contains_parameters(id) {
+                    continue;
+                }
+
+                let name = package.type_name(id);
+                self.types
+                    .insert(id, self.context.opaque_struct_type(&name).into());
+            }
+        }
+
+        // Tagged unions are a tag followed by an area big enough for the
+        // values of any variant, made of integers as wide as its alignment so
+        // that LLVM aligns it the same way
+        for (id, ty) in package.types() {
+            if let Type::Union(_) = ty {
+                if package.contains_parameters(id) {
+                    continue;
+                }
+
+                let definition = package.get_union(id).expect("expected union to exist");
+                let name = package.type_name(id);
+                let payload = package
+                    .payload_layout(id)
+                    .expect("expected union to have a payload");
+
+                let tag: BasicTypeEnum<'ctx> = self
+                    .primitive_type(definition.tag)
+                    .try_into()
+                    .expect("expected tag to be an integer type");
+                let unit = self.context.custom_width_int_type(
+                    u32::try_from(payload.align * 8).expect("alignment is too large"),
+                );
+                let area = unit.array_type(
+                    u32::try_from(payload.size.div_ceil(payload.align))
+                        .expect("payload is too large"),
+                );
+
+                let union_ty = self.context.opaque_struct_type(&name);
+                union_ty.set_body(&[tag, area.into()], false);
+                self.types.insert(id, union_ty.into());
+            }
+        }
+
         for (id, ty) in package.types() {
             let ty = match ty {
-                Type::Primitive(primitive) => match primitive {
-                    PrimitiveType::Void => self.context.void_type().into(),
-                    PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::Char => {
-                        self.context.i8_type().into()
-                    }
-                    PrimitiveType::I16 | PrimitiveType::U16 => self.context.i16_type().into(),
-                    PrimitiveType::I32 | PrimitiveType::U32 => self.context.i32_type().into(),
-                    PrimitiveType::I64 | PrimitiveType::U64 => self.context.i64_type().into(),
-                    PrimitiveType::F32 => self.context.f32_type().into(),
-                    PrimitiveType::F64 => self.context.f64_type().into(),
-                    PrimitiveType::Bool => self.context.bool_type().into(),
-                    PrimitiveType::Str => self.context.ptr_type(AddressSpace::default()).into(),
-                },
-                Type::Ptr(_) | Type::SizedArray(_, _) | Type::UnsizedArray(_) => {
+                Type::Primitive(primitive) => self.primitive_type(*primitive),
+                Type::Enum(_) => {
+                    let definition = package.get_enum(id).expect("expected enum to exist");
+                    self.primitive_type(definition.representation)
+                }
+                Type::Ptr(_) | Type::UnsizedArray(_) => {
                     self.context.ptr_type(AddressSpace::default()).into()
                 }
-                Type::Unresolved(_) => panic!("unsupported type {ty:?}"),
+                // A pointer to the value and a pointer to its table of methods
+                Type::TraitObject(_, _) => {
+                    let pointer = self.context.ptr_type(AddressSpace::default());
+                    self.context
+                        .struct_type(&[pointer.into(), pointer.into()], false)
+                        .into()
+                }
+                // Only the instantiations of generic functions are built, and
+                // their types have the parameters replaced. Arrays are built
+                // once the types of their elements are
+                Type::Struct(_)
+                | Type::Union(_)
+                | Type::Parameter(_, _)
+                | Type::SizedArray(_, _) => continue,
+                Type::Unresolved(_) | Type::Error => panic!("unsupported type {ty:?}"),
             };
 
             self.types.insert(id, ty);
         }
+
+        for (id, ty) in package.types() {
+            if let Type::SizedArray(_, _) = ty {
+                self.build_array_type(id, package);
+            }
+        }
+
+        for (id, ty) in package.types() {
+            if let Type::Struct(_) = ty {
+                if package.contains_parameters(id) {
+                    continue;
+                }
+
+                let definition = package.get_struct(id).expect("expected struct to exist");
+                let fields = definition
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        self.types[&field.ty]
+                            .try_into()
+                            .expect("expected field type to be a basic type")
+                    })
+                    .collect::<Vec<BasicTypeEnum<'ctx>>>();
+
+                self.types[&id].into_struct_type().set_body(&fields, false);
+            }
+        }
     }
 
-    fn build_prototypes(&mut self, package: &HLIRPackage) {
-        for (id, name, prototype) in package.prototypes() {
-            let return_ty = self.types[&prototype.return_type];
-            let param_tys = prototype
-                .parameters
-                .iter()
-                .map(|id| {
-                    self.types[id]
-                        .try_into()
-                        .expect("expected type to be a basic type")
-                })
-                .collect::<Vec<_>>();
+    /// Get the layout of the values carried by a variant of a tagged union,
+    /// as they are stored in the payload area of the union.
+    fn variant_type(&self, variant: &UnionVariant) -> StructType<'ctx> {
+        let fields = variant
+            .fields
+            .iter()
+            .map(|field| {
+                self.types[&field.ty]
+                    .try_into()
+                    .expect("expected field type to be a basic type")
+            })
+            .collect::<Vec<BasicTypeEnum<'ctx>>>();
+
+        self.context.struct_type(&fields, false)
+    }
+
+    fn primitive_type(&self, primitive: PrimitiveType) -> AnyTypeEnum<'ctx> {
+        match primitive {
+            PrimitiveType::Void => self.context.void_type().into(),
+            PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::Char => {
+                self.context.i8_type().into()
+            }
+            PrimitiveType::I16 | PrimitiveType::U16 => self.context.i16_type().into(),
+            PrimitiveType::I32 | PrimitiveType::U32 => self.context.i32_type().into(),
+            PrimitiveType::I64 | PrimitiveType::U64 => self.context.i64_type().into(),
+            PrimitiveType::F32 => self.context.f32_type().into(),
+            PrimitiveType::F64 => self.context.f64_type().into(),
+            PrimitiveType::Bool => self.context.bool_type().into(),
+            PrimitiveType::Str => self.context.ptr_type(AddressSpace::default()).into(),
+        }
+    }
+
+    /// Get the LLVM type of the functions with the signature `prototype`.
+    fn function_type(&self, prototype: &FunctionPrototype) -> FunctionType<'ctx> {
+        let return_ty = self.types[&prototype.return_type];
+        let param_tys = prototype
+            .parameters
+            .iter()
+            .map(|id| {
+                self.types[id]
+                    .try_into()
+                    .expect("expected type to be a basic type")
+            })
+            .collect::<Vec<_>>();
+
+        let is_variadic = prototype.is_variadic;
 
-            let function_type = match return_ty {
-                AnyTypeEnum::VoidType(ty) => ty.fn_type(&param_tys, false),
-                AnyTypeEnum::IntType(ty) => ty.fn_type(&param_tys, false),
-                AnyTypeEnum::FloatType(ty) => ty.fn_type(&param_tys, false),
-                AnyTypeEnum::PointerType(ty) => ty.fn_type(&param_tys, false),
-                _ => panic!("unsupported return type {return_ty:?}"),
+        match return_ty {
+            AnyTypeEnum::VoidType(ty) => ty.fn_type(&param_tys, is_variadic),
+            AnyTypeEnum::IntType(ty) => ty.fn_type(&param_tys, is_variadic),
+            AnyTypeEnum::FloatType(ty) => ty.fn_type(&param_tys, is_variadic),
+            AnyTypeEnum::PointerType(ty) => ty.fn_type(&param_tys, is_variadic),
+            AnyTypeEnum::StructType(ty) => ty.fn_type(&param_tys, is_variadic),
+            AnyTypeEnum::ArrayType(ty) => ty.fn_type(&param_tys, is_variadic),
+            _ => panic!("unsupported return type {return_ty:?}"),
+        }
+    }
+
+    /// Build the storage of every static, including the ones defined outside
+    /// of the package. Constants have none, their values are built wherever
+    /// they are used instead.
+    fn build_globals(&mut self, package: &HLIRPackage) {
+        for (id, global) in package.globals() {
+            let ty: BasicTypeEnum<'ctx> = self.types[&global.ty]
+                .try_into()
+                .expect("expected global to have a basic type");
+
+            let value = match global.kind {
+                GlobalKind::Constant => continue,
+                GlobalKind::Static => {
+                    let constant = global
+                        .value
+                        .as_ref()
+                        .expect("expected the value of a static to be computed");
+                    let initializer = self.build_constant(constant, package);
+
+                    // Only used by the package, unlike an external static
+                    let value = self.module.add_global(ty, None, &global.symbol);
+                    value.set_linkage(Linkage::Internal);
+                    value.set_initializer(&initializer);
+                    value
+                }
+                // Declared without a value, to be linked with its definition
+                GlobalKind::ExternalStatic => self.module.add_global(ty, None, &global.symbol),
             };
 
+            self.values.insert(id, value.as_pointer_value());
+        }
+    }
+
+    /// Build a sized array type, building the type of its elements first if
+    /// it is an array too. Arrays of type parameters are not built, like
+    /// their elements.
+    fn build_array_type(&mut self, id: TypeId, package: &HLIRPackage) -> Option<AnyTypeEnum<'ctx>> {
+        if let Some(ty) = self.types.get(&id) {
+            return Some(*ty);
+        }
+
+        let Type::SizedArray(element, length) = *package.get_type(id)? else {
+            return None;
+        };
+        let element: BasicTypeEnum<'ctx> = self
+            .build_array_type(element, package)?
+            .try_into()
+            .expect("expected element type to be a basic type");
+
+        let ty = element
+            .array_type(u32::try_from(length).expect("array is too long"))
+            .into();
+        self.types.insert(id, ty);
+
+        Some(ty)
+    }
+
+    fn build_prototypes(&mut self, package: &HLIRPackage) {
+        for (id, name, prototype) in package.prototypes() {
+            let function_type = self.function_type(prototype);
+
             let function = self.module.add_function(&name, function_type, None);
 
             if name == "__main" {
@@ -170,10 +443,35 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
         }
     }
 
+    /// Build the table of methods of every implementation of a trait, a
+    /// constant array of pointers to the functions defining the methods in
+    /// the order the trait declares them.
+    fn build_vtables(&mut self, package: &HLIRPackage) {
+        let pointer = self.context.ptr_type(AddressSpace::default());
+
+        for (trait_id, ty, name, functions) in package.vtables() {
+            let methods = functions
+                .iter()
+                .map(|function| {
+                    self.functions[function]
+                        .as_global_value()
+                        .as_pointer_value()
+                })
+                .collect::<Vec<_>>();
+            let table = pointer.const_array(&methods);
+
+            let vtable = self.module.add_global(table.get_type(), None, &name);
+            vtable.set_initializer(&table);
+            vtable.set_constant(true);
+            self.vtables.insert((trait_id, ty), vtable);
+        }
+    }
+
     fn build_functions(&mut self, package: &HLIRPackage) {
         for (id, body) in package.impls() {
             let function = self.functions[&id];
             let entry = self.context.append_basic_block(function, "entry");
+            self.type_arguments.clone_from(&body.type_arguments);
 
             self.builder.position_at_end(entry);
 
@@ -181,48 +479,251 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
                 let variable = body.parameters[i];
                 let name = package.get_variable_name(&variable).to_string();
                 arg.set_name(&name);
-                self.values.insert(variable, arg.into());
+
+                let alloca = self
+                    .build_entry_alloca(function, arg.get_type(), &name)
+                    .expect("failed to build alloca");
+                self.builder
+                    .build_store(alloca, arg)
+                    .expect("failed to build store");
+                self.values.insert(variable, alloca);
             }
 
-            self.build_statement(/* function, */ &body.body, package)
+            self.build_statement(function, &body.body, package)
                 .expect("failed to build statement");
+
+            // Only the end of a void function may be reached without a return.
+            // The end of any other function is only left open after a loop
+            // that never ends, which lowering checks
+            if !self.is_terminated() {
+                if function.get_type().get_return_type().is_none() {
+                    self.builder
+                        .build_return(None)
+                        .expect("failed to build return");
+                } else {
+                    self.builder
+                        .build_unreachable()
+                        .expect("failed to build unreachable");
+                }
+            }
         }
     }
 
     fn build_statement(
         &mut self,
-        /* func: FunctionValue<'ctx>, */
+        function: FunctionValue<'ctx>,
         statement: &Statement,
         package: &HLIRPackage,
-    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
-        Ok(match statement {
+    ) -> Result<(), BuilderError> {
+        match statement {
             Statement::Block(block) => {
-                let mut last = None;
                 for statement in &block.statements {
-                    last = Some(self.build_statement(/* func, */ statement, package)?);
+                    // Statements after a terminator are unreachable and cannot
+                    // be added to the block
+                    if self.is_terminated() {
+                        break;
+                    }
+
+                    self.build_statement(function, statement, package)?;
                 }
-                last.unwrap()
             }
             Statement::Let(Let { variable, value }) => {
                 let value = self.build_expression(value, package)?;
                 let value: BasicValueEnum<'ctx> = value.try_into().expect("expected value");
                 let name = package.get_variable_name(variable).to_string();
-                let alloca = self.builder.build_alloca(value.get_type(), &name)?;
+                let alloca = self.build_entry_alloca(function, value.get_type(), &name)?;
                 self.builder.build_store(alloca, value)?;
-                self.values.insert(*variable, alloca.into());
-                alloca.into()
+                self.values.insert(*variable, alloca);
+            }
+            Statement::If(statement) => self.build_if(function, statement, package)?,
+            Statement::While(statement) => self.build_while(function, statement, package)?,
+            Statement::For(statement) => self.build_for(function, statement, package)?,
+            Statement::Break => {
+                let target = self.loops.last().expect("break outside of a loop");
+                self.builder
+                    .build_unconditional_branch(target.break_block)?;
+            }
+            Statement::Continue => {
+                let target = self.loops.last().expect("continue outside of a loop");
+                self.builder
+                    .build_unconditional_branch(target.continue_block)?;
+            }
+            Statement::Return(Return { value: None }) => {
+                self.builder.build_return(None)?;
             }
-            Statement::Return(Return { value: None }) => self.builder.build_return(None)?.into(),
             Statement::Return(Return { value: Some(value) }) => {
                 let value: BasicValueEnum<'ctx> = self
                     .build_expression(value, package)?
                     .try_into()
                     .expect("expected value");
-                self.builder.build_return(Some(&value))?.into()
+                self.builder.build_return(Some(&value))?;
+            }
+            Statement::Match(match_statement) => {
+                self.build_match(function, match_statement, package)?;
             }
-            Statement::Expression(expr) => self.build_expression(expr, package)?,
-            _ => todo!(),
-        })
+            Statement::Expression(expr) => {
+                self.build_expression(expr, package)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build_if(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        statement: &If,
+        package: &HLIRPackage,
+    ) -> Result<(), BuilderError> {
+        let If {
+            condition,
+            then_branch,
+            else_branch,
+        } = statement;
+
+        let condition = self.build_expression(condition, package)?.into_int_value();
+
+        let then_block = self.context.append_basic_block(function, "then");
+        let else_block = else_branch
+            .as_ref()
+            .map(|_| self.context.append_basic_block(function, "else"));
+        let merge_block = self.context.append_basic_block(function, "merge");
+
+        self.builder.build_conditional_branch(
+            condition,
+            then_block,
+            else_block.unwrap_or(merge_block),
+        )?;
+
+        self.builder.position_at_end(then_block);
+        self.build_statement(function, then_branch, package)?;
+        self.build_branch_if_unterminated(merge_block)?;
+
+        if let (Some(else_block), Some(else_branch)) = (else_block, else_branch) {
+            self.builder.position_at_end(else_block);
+            self.build_statement(function, else_branch, package)?;
+            self.build_branch_if_unterminated(merge_block)?;
+        }
+
+        self.builder.position_at_end(merge_block);
+
+        Ok(())
+    }
+
+    fn build_while(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        statement: &While,
+        package: &HLIRPackage,
+    ) -> Result<(), BuilderError> {
+        let While { condition, body } = statement;
+
+        let condition_block = self.context.append_basic_block(function, "while.cond");
+        let body_block = self.context.append_basic_block(function, "while.body");
+        let exit_block = self.context.append_basic_block(function, "while.exit");
+
+        self.builder.build_unconditional_branch(condition_block)?;
+
+        self.builder.position_at_end(condition_block);
+        let condition = self.build_expression(condition, package)?.into_int_value();
+        self.builder
+            .build_conditional_branch(condition, body_block, exit_block)?;
+
+        self.builder.position_at_end(body_block);
+        self.loops.push(Loop {
+            continue_block: condition_block,
+            break_block: exit_block,
+        });
+        self.build_statement(function, body, package)?;
+        self.loops.pop();
+        self.build_branch_if_unterminated(condition_block)?;
+
+        self.builder.position_at_end(exit_block);
+
+        Ok(())
+    }
+
+    fn build_for(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        statement: &For,
+        package: &HLIRPackage,
+    ) -> Result<(), BuilderError> {
+        let For {
+            condition,
+            update,
+            body,
+        } = statement;
+
+        let condition_block = self.context.append_basic_block(function, "for.cond");
+        let body_block = self.context.append_basic_block(function, "for.body");
+        let update_block = self.context.append_basic_block(function, "for.update");
+        let exit_block = self.context.append_basic_block(function, "for.exit");
+
+        self.builder.build_unconditional_branch(condition_block)?;
+
+        self.builder.position_at_end(condition_block);
+        let condition = self.build_expression(condition, package)?.into_int_value();
+        self.builder
+            .build_conditional_branch(condition, body_block, exit_block)?;
+
+        self.builder.position_at_end(body_block);
+        self.loops.push(Loop {
+            continue_block: update_block,
+            break_block: exit_block,
+        });
+        self.build_statement(function, body, package)?;
+        self.loops.pop();
+        self.build_branch_if_unterminated(update_block)?;
+
+        self.builder.position_at_end(update_block);
+        self.build_statement(function, update, package)?;
+        self.build_branch_if_unterminated(condition_block)?;
+
+        self.builder.position_at_end(exit_block);
+
+        Ok(())
+    }
+
+    /// Whether the block being built already ends in a terminator, such as a
+    /// return or a branch.
+    fn is_terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_terminator)
+            .is_some()
+    }
+
+    /// Branch to `block` unless the block being built has already branched or
+    /// returned elsewhere.
+    fn build_branch_if_unterminated(&self, block: BasicBlock<'ctx>) -> Result<(), BuilderError> {
+        if !self.is_terminated() {
+            self.builder.build_unconditional_branch(block)?;
+        }
+
+        Ok(())
+    }
+
+    /// Allocate a variable at the start of the entry block of `function`, so
+    /// that variables declared inside of loops are not reallocated on every
+    /// iteration.
+    fn build_entry_alloca(
+        &self,
+        function: FunctionValue<'ctx>,
+        ty: BasicTypeEnum<'ctx>,
+        name: &str,
+    ) -> Result<PointerValue<'ctx>, BuilderError> {
+        let builder = self.context.create_builder();
+        let entry = function
+            .get_first_basic_block()
+            .expect("expected function to have an entry block");
+
+        match entry.get_first_instruction() {
+            Some(instruction) => builder.position_before(&instruction),
+            None => builder.position_at_end(entry),
+        }
+
+        builder.build_alloca(ty, name)
     }
 
     fn build_expression(
@@ -232,31 +733,30 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
     ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
         match expression {
             Expression::Variable(variable) => {
-                let value = self.values[variable];
-                let expected_ty = package
-                    .get_resolved_type(expression.ty(package))
-                    .expect("unresolved type");
-
-                let expected_ty = self.types[&expected_ty];
-
-                Ok(match (value, expected_ty) {
-                    (AnyValueEnum::PointerValue(ptr), AnyTypeEnum::PointerType(_)) => {
-                        ptr.as_any_value_enum()
-                    }
-                    (AnyValueEnum::PointerValue(ptr), ty) => {
-                        let ty: BasicTypeEnum = ty.try_into().expect("expected basic type");
-                        self.builder.build_load(ty, ptr, "")?.into()
-                    }
-                    (value, _) => value,
-                })
+                self.build_variable(*variable, expression.ty(package), package)
             }
-            Expression::Literal(literal) => self.build_literal(literal, package),
-            Expression::FunctionCall(FunctionCall {
-                function,
-                arguments,
-                result: _,
-            }) => {
-                let function = self.functions[function];
+            Expression::Index(index) => {
+                let element = self.build_index(index, package)?;
+                let ty = self.basic_type(index.result, package);
+                Ok(self.builder.build_load(ty, element, "")?.into())
+            }
+            Expression::Assign(Assign { place, value, .. }) => {
+                let value: BasicValueEnum<'ctx> = self
+                    .build_expression(value, package)?
+                    .try_into()
+                    .expect("expected value");
+                let pointer = self.build_place(place, package)?;
+                Ok(self
+                    .builder
+                    .build_store(pointer, value)?
+                    .as_any_value_enum())
+            }
+            Expression::Literal(literal) => Ok(self.build_literal(literal, package).into()),
+            Expression::FunctionCall(call @ FunctionCall { arguments, .. }) => {
+                let function = package
+                    .get_called_function(call, &self.type_arguments)
+                    .expect("expected generic function to be instantiated");
+                let function = self.functions[&function];
                 let arguments = arguments
                     .iter()
                     .map(|arg| self.build_expression(arg, package))
@@ -271,80 +771,1371 @@ impl<'ctx> LLVMCodegenContext<'ctx> {
 
                 Ok(result)
             }
-            Expression::BinaryOperation(BinaryOperation {
-                left,
-                operator,
-                right,
-                result,
-            }) => {
-                let left = self.build_expression(left, package)?;
-                let right = self.build_expression(right, package)?;
-
-                let left: BasicValueEnum<'ctx> = left.try_into().expect("expected value");
-                let right: BasicValueEnum<'ctx> = right.try_into().expect("expected value");
-
-                let result_ty = package.get_resolved_type(*result).expect("unresolved type");
-                let result_ty = self.types[&result_ty];
-
-                macro_rules! create_operation {
-                    ($($op:ident => { $int:ident, $float:ident }),*) => {
-                        match operator {
-                            $(
-                                BinaryOperator::$op => match result_ty {
-                                    AnyTypeEnum::IntType(_) => self.builder.$int(left.into_int_value(), right.into_int_value(), "")?.as_any_value_enum(),
-                                    AnyTypeEnum::FloatType(_) => self.builder.$float(left.into_float_value(), right.into_float_value(), "")?.as_any_value_enum(),
-                                    _ => panic!("unsupported type {result_ty:?}"),
-                                },
-                            )*
-                            _ => todo!(),
-                        }
-                    };
-                }
+            Expression::DynamicCall(call) => self.build_dynamic_call(call, package),
+            Expression::UnaryOperation(operation) => self.build_unary_operation(operation, package),
+            Expression::BinaryOperation(operation) => {
+                self.build_binary_operation(operation, package)
+            }
+            Expression::TypeCast(cast) => self.build_type_cast(cast, package),
+            Expression::StructLiteral(literal) => self.build_struct_literal(literal, package),
+            Expression::ArrayLiteral(literal) => self.build_array_literal(literal, package),
+            Expression::ArrayRepeat(repeat) => self.build_array_repeat(repeat, package),
+            Expression::FieldAccess(access) => self.build_field_access(access, package),
+            Expression::EnumValue(EnumValue { variant, ty }) => {
+                let definition = self
+                    .instance_type(*ty, package)
+                    .and_then(|ty| package.get_enum(ty))
+                    .expect("expected enum to exist");
+                let discriminant = definition.variants[*variant].discriminant;
 
-                let result = create_operation!(
-                    Addition => { build_int_add, build_float_add },
-                    Subtraction => { build_int_sub, build_float_sub },
-                    Multiplication => { build_int_mul, build_float_mul },
-                    Division => { build_int_unsigned_div, build_float_div },
-                    Remainder => { build_int_signed_rem, build_float_rem }
-                );
+                // The discriminant fits in the representation of the enum,
+                // and `const_int` truncates it to that width
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let discriminant = discriminant as u64;
 
-                Ok(result)
+                Ok(self
+                    .basic_type(*ty, package)
+                    .into_int_type()
+                    .const_int(discriminant, false)
+                    .into())
+            }
+            Expression::UnionValue(value) => self.build_union_value(value, package),
+            Expression::VariantCheck(check) => self.build_variant_check(check, package),
+            Expression::Error(_) => {
+                unreachable!("packages with errors are not passed to codegen")
             }
-            _ => todo!(),
         }
     }
 
-    fn build_literal(
-        &self,
-        literal: &Literal,
+    /// Load the value of a variable, or build the value of a constant, which
+    /// is not stored anywhere.
+    fn build_variable(
+        &mut self,
+        variable: VariableId,
+        ty: InferenceId,
         package: &HLIRPackage,
     ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
-        match &literal.value {
-            LiteralValue::Integer { value, radix } => {
-                let literal_ty = package
-                    .get_resolved_type(literal.ty)
-                    .expect("unresolved type");
-                let ty = self.types[&literal_ty];
+        if let Some(value) = package
+            .get_global(variable)
+            .filter(|global| global.kind == GlobalKind::Constant)
+            .and_then(|global| global.value.as_ref())
+        {
+            return Ok(self.build_constant(value, package).into());
+        }
+
+        let ty = self.basic_type(ty, package);
+        Ok(self
+            .builder
+            .build_load(ty, self.values[&variable], "")?
+            .into())
+    }
+
+    fn build_struct_literal(
+        &mut self,
+        literal: &StructLiteral,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let StructLiteral { fields, ty } = literal;
+        let struct_ty = self.basic_type(*ty, package).into_struct_type();
+        let mut value = struct_ty.get_undef();
 
-                assert!(ty.is_int_type());
+        for (index, field) in fields.iter().enumerate() {
+            let field: BasicValueEnum<'ctx> = self
+                .build_expression(field, package)?
+                .try_into()
+                .expect("expected value");
+            let index = u32::try_from(index).expect("too many fields in struct");
 
-                let ty = ty.into_int_type();
-                Ok(ty
-                    .const_int_from_string(
-                        value,
-                        match radix {
-                            2 => StringRadix::Binary,
-                            8 => StringRadix::Octal,
-                            10 => StringRadix::Decimal,
-                            16 => StringRadix::Hexadecimal,
-                            _ => panic!("unsupported radix {radix}"),
-                        },
-                    )
-                    .expect("failed to parse integer")
-                    .into())
+            value = self
+                .builder
+                .build_insert_value(value, field, index, "")?
+                .into_struct_value();
+        }
+
+        Ok(value.into())
+    }
+
+    fn build_array_literal(
+        &mut self,
+        literal: &ArrayLiteral,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let ArrayLiteral { elements, result } = literal;
+        let array_ty = self.basic_type(*result, package).into_array_type();
+        let mut value = array_ty.get_undef();
+
+        for (index, element) in elements.iter().enumerate() {
+            let element: BasicValueEnum<'ctx> = self
+                .build_expression(element, package)?
+                .try_into()
+                .expect("expected value");
+            let index = u32::try_from(index).expect("too many elements in array");
+
+            value = self
+                .builder
+                .build_insert_value(value, element, index, "")?
+                .into_array_value();
+        }
+
+        Ok(value.into())
+    }
+
+    fn build_field_access(
+        &mut self,
+        access: &FieldAccess,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let ty = self.basic_type(access.result, package);
+
+        // Load only the field if the struct is in memory, instead of
+        // loading the whole struct
+        if let Some(field) = self.build_field_address(access, package)? {
+            Ok(self.builder.build_load(ty, field, "")?.into())
+        } else {
+            let base = self
+                .build_expression(&access.base, package)?
+                .into_struct_value();
+            let index = u32::try_from(access.field).expect("too many fields in struct");
+
+            Ok(self.builder.build_extract_value(base, index, "")?.into())
+        }
+    }
+
+    /// Build a match by storing the scrutinee in memory and comparing it with
+    /// the pattern of each arm in turn, or by switching on it when every
+    /// pattern compares it with a few constants.
+    fn build_match(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        match_statement: &Match,
+        package: &HLIRPackage,
+    ) -> Result<(), BuilderError> {
+        let scrutinee = &match_statement.scrutinee;
+        let ty = self
+            .instance_type(scrutinee.ty(package), package)
+            .expect("unresolved type");
+        let value: BasicValueEnum<'ctx> = self
+            .build_expression(scrutinee, package)?
+            .try_into()
+            .expect("expected value");
+        let pointer = self.build_entry_alloca(function, value.get_type(), "scrutinee")?;
+        self.builder.build_store(pointer, value)?;
+
+        let arm_blocks = match_statement
+            .arms
+            .iter()
+            .map(|_| self.context.append_basic_block(function, "match.arm"))
+            .collect::<Vec<_>>();
+        let merge_block = self.context.append_basic_block(function, "match.end");
+
+        if let Some(cases) = Self::switch_cases(&match_statement.arms, ty, package) {
+            let discriminant = self.build_discriminant(pointer, ty, package)?;
+            let integer_ty = discriminant.get_type();
+            let current = self
+                .builder
+                .get_insert_block()
+                .expect("expected builder to be inside of a function");
+
+            // The checker guarantees that the arms are exhaustive, so the
+            // default is only reached for the values of an irrefutable arm
+            let default = if let Some(arm) = match_statement
+                .arms
+                .iter()
+                .position(|arm| arm.pattern.is_irrefutable())
+            {
+                arm_blocks[arm]
+            } else {
+                let block = self.context.append_basic_block(function, "match.default");
+                self.builder.position_at_end(block);
+                self.builder.build_unreachable()?;
+                block
+            };
+
+            // Values are truncated to the width of the integer
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let cases = cases
+                .into_iter()
+                .map(|(value, arm)| (integer_ty.const_int(value as u64, false), arm_blocks[arm]))
+                .collect::<Vec<_>>();
+
+            self.builder.position_at_end(current);
+            self.builder.build_switch(discriminant, default, &cases)?;
+        } else {
+            for (arm, arm_block) in match_statement.arms.iter().zip(&arm_blocks) {
+                let test = self.build_pattern_test(&arm.pattern, pointer, ty, package)?;
+                let next_block = self.context.append_basic_block(function, "match.next");
+
+                self.builder
+                    .build_conditional_branch(test, *arm_block, next_block)?;
+                self.builder.position_at_end(next_block);
             }
-            _ => todo!(),
+
+            // The checker guarantees that one of the arms matches
+            self.builder.build_unreachable()?;
+        }
+
+        for (arm, arm_block) in match_statement.arms.iter().zip(arm_blocks) {
+            self.builder.position_at_end(arm_block);
+            self.build_pattern_bindings(&arm.pattern, pointer, ty, package)?;
+            self.build_statement(function, &arm.body, package)?;
+            self.build_branch_if_unterminated(merge_block)?;
         }
+
+        self.builder.position_at_end(merge_block);
+
+        Ok(())
+    }
+
+    /// Get the values to switch on and the arm each of them leads to, if the
+    /// scrutinee is an integer, an enum or a tagged union and every pattern
+    /// matches it against at most a few hundred constants without looking
+    /// into the values carried by variants. Values that an earlier arm
+    /// already matches are left out.
+    fn switch_cases(
+        arms: &[MatchArm],
+        ty: TypeId,
+        package: &HLIRPackage,
+    ) -> Option<Vec<(i128, usize)>> {
+        const MAX_CASES: usize = 256;
+
+        let range = match package.get_type(ty)?.as_ref() {
+            Type::Primitive(primitive) => pattern_range(*primitive)?,
+            Type::Enum(_) => pattern_range(package.get_enum(ty)?.representation)?,
+            Type::Union(_) => pattern_range(package.get_union(ty)?.tag)?,
+            _ => return None,
+        };
+
+        let mut cases: Vec<(i128, usize)> = Vec::new();
+
+        for (arm, MatchArm { pattern, .. }) in arms.iter().enumerate() {
+            if pattern.is_irrefutable() {
+                // The arms after it can never be reached
+                break;
+            }
+
+            let mut values = Vec::new();
+            Self::pattern_constants(pattern, ty, package, &mut values)?;
+
+            for value in values {
+                if range.contains(&value) && !cases.iter().any(|(case, _)| *case == value) {
+                    cases.push((value, arm));
+                }
+            }
+
+            if cases.len() > MAX_CASES {
+                return None;
+            }
+        }
+
+        Some(cases)
+    }
+
+    /// Collect the constants a pattern compares the scrutinee, or the tag of
+    /// a tagged union, with, if that is all it does.
+    fn pattern_constants(
+        pattern: &Pattern,
+        ty: TypeId,
+        package: &HLIRPackage,
+        values: &mut Vec<i128>,
+    ) -> Option<()> {
+        const MAX_RANGE: i128 = 256;
+
+        match pattern {
+            Pattern::Range { start, end } if end - start < MAX_RANGE => {
+                values.extend(*start..=*end);
+            }
+            Pattern::Variant { variant, fields } if fields.iter().all(Pattern::is_irrefutable) => {
+                values.push(Self::discriminant(*variant, ty, package));
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    Self::pattern_constants(alternative, ty, package, values)?;
+                }
+            }
+            _ => return None,
+        }
+
+        Some(())
+    }
+
+    /// Get the discriminant of the variant at position `variant` of the enum
+    /// or tagged union `ty`.
+    fn discriminant(variant: usize, ty: TypeId, package: &HLIRPackage) -> i128 {
+        match package.get_union(ty) {
+            Some(definition) => definition.variants[variant].discriminant,
+            None => {
+                package
+                    .get_enum(ty)
+                    .expect("expected enum to exist")
+                    .variants[variant]
+                    .discriminant
+            }
+        }
+    }
+
+    /// Load the integer that identifies the value at `pointer`, which is the
+    /// tag of a tagged union or the value itself otherwise.
+    fn build_discriminant(
+        &self,
+        pointer: PointerValue<'ctx>,
+        ty: TypeId,
+        package: &HLIRPackage,
+    ) -> Result<IntValue<'ctx>, BuilderError> {
+        if package.get_union(ty).is_some() {
+            let union_ty = self.types[&ty].into_struct_type();
+            let tag_ty = union_ty
+                .get_field_type_at_index(0)
+                .expect("expected union to have a tag");
+            let tag = self.builder.build_struct_gep(union_ty, pointer, 0, "")?;
+
+            Ok(self.builder.build_load(tag_ty, tag, "")?.into_int_value())
+        } else {
+            let integer_ty = self.types[&ty].into_int_type();
+
+            Ok(self
+                .builder
+                .build_load(integer_ty, pointer, "")?
+                .into_int_value())
+        }
+    }
+
+    /// Build whether the value of type `ty` at `pointer` matches `pattern`.
+    ///
+    /// The values carried by a variant are only compared if the tag matches,
+    /// using `select` rather than `and` since the payload of another variant
+    /// may not be initialized.
+    fn build_pattern_test(
+        &mut self,
+        pattern: &Pattern,
+        pointer: PointerValue<'ctx>,
+        ty: TypeId,
+        package: &HLIRPackage,
+    ) -> Result<IntValue<'ctx>, BuilderError> {
+        let bool_ty = self.context.bool_type();
+
+        match pattern {
+            Pattern::Wildcard | Pattern::Binding(_) | Pattern::Error => {
+                Ok(bool_ty.const_all_ones())
+            }
+            Pattern::Range { start, end } => {
+                let value = self.build_discriminant(pointer, ty, package)?;
+                let integer_ty = value.get_type();
+                let primitive = match package.get_type(ty).as_deref() {
+                    Some(Type::Primitive(primitive)) => *primitive,
+                    _ => {
+                        package
+                            .get_enum(ty)
+                            .expect("expected enum to exist")
+                            .representation
+                    }
+                };
+                let signed = primitive.is_signed();
+                let range =
+                    pattern_range(primitive).expect("expected range pattern to match an integer");
+
+                // Bounds outside of the type are left out, or make the range
+                // empty
+                if *start > *range.end() || *end < *range.start() {
+                    return Ok(bool_ty.const_zero());
+                }
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let constant = |value: i128| integer_ty.const_int(value as u64, false);
+
+                if start == end {
+                    return self.builder.build_int_compare(
+                        IntPredicate::EQ,
+                        value,
+                        constant(*start),
+                        "",
+                    );
+                }
+
+                let (lower, upper) = if signed {
+                    (IntPredicate::SGE, IntPredicate::SLE)
+                } else {
+                    (IntPredicate::UGE, IntPredicate::ULE)
+                };
+                let mut test = bool_ty.const_all_ones();
+
+                if *start > *range.start() {
+                    test = self
+                        .builder
+                        .build_int_compare(lower, value, constant(*start), "")?;
+                }
+                if *end < *range.end() {
+                    let below = self
+                        .builder
+                        .build_int_compare(upper, value, constant(*end), "")?;
+                    test = self.builder.build_and(test, below, "")?;
+                }
+
+                Ok(test)
+            }
+            Pattern::Variant { variant, fields } => {
+                let tag = self.build_discriminant(pointer, ty, package)?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let discriminant = tag
+                    .get_type()
+                    .const_int(Self::discriminant(*variant, ty, package) as u64, false);
+                let mut test =
+                    self.builder
+                        .build_int_compare(IntPredicate::EQ, tag, discriminant, "")?;
+
+                for (index, field) in fields.iter().enumerate() {
+                    if field.is_irrefutable() {
+                        continue;
+                    }
+
+                    let (address, field_ty) =
+                        self.build_variant_field_address(pointer, ty, *variant, index, package)?;
+                    let matches = self.build_pattern_test(field, address, field_ty, package)?;
+                    test = self
+                        .builder
+                        .build_select(test, matches, bool_ty.const_zero(), "")?
+                        .into_int_value();
+                }
+
+                Ok(test)
+            }
+            Pattern::Or(alternatives) => {
+                let mut test = bool_ty.const_zero();
+
+                for alternative in alternatives {
+                    let matches = self.build_pattern_test(alternative, pointer, ty, package)?;
+                    test = self
+                        .builder
+                        .build_select(test, bool_ty.const_all_ones(), matches, "")?
+                        .into_int_value();
+                }
+
+                Ok(test)
+            }
+        }
+    }
+
+    /// Make the variables bound by `pattern`, which the value of type `ty` at
+    /// `pointer` matches, refer to the parts of the value they are bound to.
+    ///
+    /// The scrutinee is a copy, so the variables do not need copies of their
+    /// own.
+    fn build_pattern_bindings(
+        &mut self,
+        pattern: &Pattern,
+        pointer: PointerValue<'ctx>,
+        ty: TypeId,
+        package: &HLIRPackage,
+    ) -> Result<(), BuilderError> {
+        match pattern {
+            Pattern::Binding(variable) => {
+                self.values.insert(*variable, pointer);
+            }
+            Pattern::Variant { variant, fields } => {
+                for (index, field) in fields.iter().enumerate() {
+                    let (address, field_ty) =
+                        self.build_variant_field_address(pointer, ty, *variant, index, package)?;
+                    self.build_pattern_bindings(field, address, field_ty, package)?;
+                }
+            }
+            // Bindings are not allowed inside of or-patterns
+            Pattern::Wildcard | Pattern::Range { .. } | Pattern::Or(_) | Pattern::Error => {}
+        }
+
+        Ok(())
+    }
+
+    /// Get the address and the type of a value carried by a variant of the
+    /// tagged union at `pointer`.
+    fn build_variant_field_address(
+        &self,
+        pointer: PointerValue<'ctx>,
+        ty: TypeId,
+        variant: usize,
+        field: usize,
+        package: &HLIRPackage,
+    ) -> Result<(PointerValue<'ctx>, TypeId), BuilderError> {
+        let variant = &package
+            .get_union(ty)
+            .expect("expected union to exist")
+            .variants[variant];
+        let union_ty = self.types[&ty].into_struct_type();
+        let variant_ty = self.variant_type(variant);
+
+        let payload = self.builder.build_struct_gep(union_ty, pointer, 1, "")?;
+        let index = u32::try_from(field).expect("too many fields in variant");
+        let address = self
+            .builder
+            .build_struct_gep(variant_ty, payload, index, "")?;
+
+        Ok((address, variant.fields[field].ty))
+    }
+
+    /// Build a value of a tagged union by writing the tag and the values of
+    /// the variant to a temporary, then loading the whole union back.
+    fn build_union_value(
+        &mut self,
+        value: &UnionValue,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let union_ty = self.basic_type(value.ty, package).into_struct_type();
+        let variant = self
+            .instance_type(value.ty, package)
+            .and_then(|ty| package.get_union(ty))
+            .map(|definition| &definition.variants[value.variant])
+            .expect("expected union to exist");
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_parent)
+            .expect("expected builder to be inside of a function");
+        let temporary = self.build_entry_alloca(function, union_ty.into(), "")?;
+
+        // The discriminant fits in the tag type, and `const_int` truncates it
+        // to that width
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let discriminant = variant.discriminant as u64;
+        let tag_ty = union_ty
+            .get_field_type_at_index(0)
+            .expect("expected union to have a tag")
+            .into_int_type();
+        let tag = self.builder.build_struct_gep(union_ty, temporary, 0, "")?;
+        self.builder
+            .build_store(tag, tag_ty.const_int(discriminant, false))?;
+
+        let variant_ty = self.variant_type(variant);
+        let payload = self.builder.build_struct_gep(union_ty, temporary, 1, "")?;
+
+        for (index, field) in value.fields.iter().enumerate() {
+            let field: BasicValueEnum<'ctx> = self
+                .build_expression(field, package)?
+                .try_into()
+                .expect("expected value");
+            let index = u32::try_from(index).expect("too many fields in variant");
+            let address = self
+                .builder
+                .build_struct_gep(variant_ty, payload, index, "")?;
+
+            self.builder.build_store(address, field)?;
+        }
+
+        Ok(self.builder.build_load(union_ty, temporary, "")?.into())
+    }
+
+    /// Compare the tag of a tagged union, or the value of an enum, with the
+    /// discriminant of a variant.
+    fn build_variant_check(
+        &mut self,
+        check: &VariantCheck,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let ty = self
+            .instance_type(check.value.ty(package), package)
+            .expect("unresolved type");
+        let value = self.build_expression(&check.value, package)?;
+
+        let (tag, discriminant) = if let Some(definition) = package.get_union(ty) {
+            let tag = self
+                .builder
+                .build_extract_value(value.into_struct_value(), 0, "")?
+                .into_int_value();
+
+            (tag, definition.variants[check.variant].discriminant)
+        } else {
+            let definition = package.get_enum(ty).expect("expected enum to exist");
+
+            (
+                value.into_int_value(),
+                definition.variants[check.variant].discriminant,
+            )
+        };
+
+        // See `build_union_value`
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let discriminant = tag.get_type().const_int(discriminant as u64, false);
+
+        Ok(self
+            .builder
+            .build_int_compare(IntPredicate::EQ, tag, discriminant, "")?
+            .into())
+    }
+
+    fn build_unary_operation(
+        &mut self,
+        operation: &UnaryOperation,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let UnaryOperation {
+            operator,
+            operand,
+            result,
+        } = operation;
+
+        if *operator == UnaryOperator::Ref {
+            return Ok(self.build_reference(operand, package)?.into());
+        }
+
+        let value: BasicValueEnum<'ctx> = self
+            .build_expression(operand, package)?
+            .try_into()
+            .expect("expected value");
+
+        let value = match (operator, value) {
+            (UnaryOperator::Negation, BasicValueEnum::IntValue(value)) => {
+                self.builder.build_int_neg(value, "")?.into()
+            }
+            (UnaryOperator::Negation, BasicValueEnum::FloatValue(value)) => {
+                self.builder.build_float_neg(value, "")?.into()
+            }
+            (
+                UnaryOperator::BitwiseNot | UnaryOperator::LogicalNot,
+                BasicValueEnum::IntValue(value),
+            ) => self.builder.build_not(value, "")?.into(),
+            (UnaryOperator::Deref, BasicValueEnum::PointerValue(pointer)) => {
+                let ty = self.basic_type(*result, package);
+                self.builder.build_load(ty, pointer, "")?.into()
+            }
+            (operator, value) => panic!("unsupported operand {value:?} for {operator:?}"),
+        };
+
+        Ok(value)
+    }
+
+    /// Get the address of `operand`. Values that are not already in memory are
+    /// stored in a temporary first.
+    fn build_reference(
+        &mut self,
+        operand: &Expression,
+        package: &HLIRPackage,
+    ) -> Result<PointerValue<'ctx>, BuilderError> {
+        // A string is already a pointer to its first byte, wherever it is
+        // stored
+        if let Type::Primitive(PrimitiveType::Str) =
+            *self.resolved_type(operand.ty(package), package)
+        {
+            return Ok(self
+                .build_expression(operand, package)?
+                .into_pointer_value());
+        }
+
+        match operand {
+            // Constants have no storage, so their values are copied into a
+            // temporary like any other value
+            Expression::Variable(variable) if self.values.contains_key(variable) => {
+                return Ok(self.values[variable])
+            }
+            Expression::Index(index) => return self.build_index(index, package),
+            Expression::FieldAccess(access) => {
+                if let Some(field) = self.build_field_address(access, package)? {
+                    return Ok(field);
+                }
+            }
+            Expression::UnaryOperation(UnaryOperation {
+                operator: UnaryOperator::Deref,
+                operand,
+                ..
+            }) => {
+                return Ok(self
+                    .build_expression(operand, package)?
+                    .into_pointer_value())
+            }
+            _ => {}
+        }
+
+        let value: BasicValueEnum<'ctx> = self
+            .build_expression(operand, package)?
+            .try_into()
+            .expect("expected value");
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_parent)
+            .expect("expected builder to be inside of a function");
+        let temporary = self.build_entry_alloca(function, value.get_type(), "")?;
+        self.builder.build_store(temporary, value)?;
+
+        Ok(temporary)
+    }
+
+    /// Convert a value between two types that the type checker has allowed a
+    /// cast between.
+    fn build_type_cast(
+        &mut self,
+        cast: &TypeCast,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let from = self.resolved_type(cast.expression.ty(package), package);
+        let to = self.resolved_type(cast.target_type, package);
+        let target = self.basic_type(cast.target_type, package);
+
+        let value: BasicValueEnum<'ctx> = self
+            .build_expression(&cast.expression, package)?
+            .try_into()
+            .expect("expected value");
+
+        let value = match (from.as_ref(), to.as_ref(), value) {
+            _ if from == to => value,
+            (Type::Primitive(from), Type::Primitive(to), BasicValueEnum::IntValue(value))
+                if to.is_float() =>
+            {
+                if from.is_signed() {
+                    self.builder
+                        .build_signed_int_to_float(value, target.into_float_type(), "")?
+                        .into()
+                } else {
+                    self.builder
+                        .build_unsigned_int_to_float(value, target.into_float_type(), "")?
+                        .into()
+                }
+            }
+            // Bools and characters are unsigned
+            (Type::Primitive(from), Type::Primitive(_), BasicValueEnum::IntValue(value)) => self
+                .builder
+                .build_int_cast_sign_flag(value, target.into_int_type(), from.is_signed(), "")?
+                .into(),
+            (Type::Primitive(_), Type::Primitive(to), BasicValueEnum::FloatValue(value))
+                if to.is_float() =>
+            {
+                self.builder
+                    .build_float_cast(value, target.into_float_type(), "")?
+                    .into()
+            }
+            (Type::Primitive(_), Type::Primitive(to), BasicValueEnum::FloatValue(value)) => {
+                if to.is_signed() {
+                    self.builder
+                        .build_float_to_signed_int(value, target.into_int_type(), "")?
+                        .into()
+                } else {
+                    self.builder
+                        .build_float_to_unsigned_int(value, target.into_int_type(), "")?
+                        .into()
+                }
+            }
+            // Pointers are opaque, so they all have the same type
+            (Type::Ptr(_), Type::Ptr(_), value) => value,
+            // A trait object is the pointer along with the table of methods of
+            // the type it points to
+            (
+                Type::Ptr(pointee),
+                Type::TraitObject(trait_id, _),
+                BasicValueEnum::PointerValue(value),
+            ) => {
+                let vtable = self.vtables[&(*trait_id, *pointee)].as_pointer_value();
+                let object = target.into_struct_type().get_undef();
+                let object = self.builder.build_insert_value(object, value, 0, "")?;
+                self.builder
+                    .build_insert_value(object, vtable, 1, "")?
+                    .into_struct_value()
+                    .into()
+            }
+            (Type::Ptr(_), Type::Primitive(_), BasicValueEnum::PointerValue(value)) => self
+                .builder
+                .build_ptr_to_int(value, target.into_int_type(), "")?
+                .into(),
+            (Type::Primitive(_), Type::Ptr(_), BasicValueEnum::IntValue(value)) => self
+                .builder
+                .build_int_to_ptr(value, target.into_pointer_type(), "")?
+                .into(),
+            _ => panic!("unsupported cast from {from:?} to {to:?}"),
+        };
+
+        Ok(value.into())
+    }
+
+    /// Build a call of a method through a trait object, calling the function
+    /// at the position of the method in the table of methods of the object
+    /// with the pointer to the value as the first argument.
+    fn build_dynamic_call(
+        &mut self,
+        call: &DynamicCall,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let object = self
+            .build_expression(&call.object, package)?
+            .into_struct_value();
+        let value = self.builder.build_extract_value(object, 0, "")?;
+        let vtable = self
+            .builder
+            .build_extract_value(object, 1, "")?
+            .into_pointer_value();
+
+        let pointer = self.context.ptr_type(AddressSpace::default());
+        let index = self.context.i64_type().const_int(call.index as u64, false);
+        // SAFETY: the table has an entry for every method of the trait
+        let entry = unsafe {
+            self.builder
+                .build_in_bounds_gep(pointer, vtable, &[index], "")?
+        };
+        let method = self
+            .builder
+            .build_load(pointer, entry, "")?
+            .into_pointer_value();
+
+        let prototype = package
+            .get_prototype(call.function)
+            .expect("expected trait method to have a prototype");
+        let function_type = self.function_type(&prototype);
+
+        let mut arguments: Vec<BasicMetadataValueEnum<'ctx>> = vec![value.into()];
+        for argument in &call.arguments {
+            let argument: BasicValueEnum<'ctx> = self
+                .build_expression(argument, package)?
+                .try_into()
+                .expect("expected value");
+            arguments.push(argument.into());
+        }
+
+        Ok(self
+            .builder
+            .build_indirect_call(function_type, method, &arguments, "")?
+            .as_any_value_enum())
+    }
+
+    /// Build a binary operation, choosing the instruction from the type of the
+    /// operands.
+    fn build_binary_operation(
+        &mut self,
+        operation: &BinaryOperation,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let BinaryOperation {
+            left,
+            operator,
+            right,
+            ..
+        } = operation;
+
+        if matches!(
+            operator,
+            BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr
+        ) {
+            return self.build_logical_operation(operation, package);
+        }
+
+        let operand_ty = self.resolved_type(left.ty(package), package);
+
+        let left: BasicValueEnum<'ctx> = self
+            .build_expression(left, package)?
+            .try_into()
+            .expect("expected value");
+        let right: BasicValueEnum<'ctx> = self
+            .build_expression(right, package)?
+            .try_into()
+            .expect("expected value");
+
+        match operand_ty.as_ref() {
+            Type::Primitive(primitive) if primitive.is_float() => self.build_float_operation(
+                operator,
+                left.into_float_value(),
+                right.into_float_value(),
+            ),
+            Type::Primitive(primitive) => self.build_int_operation(
+                operator,
+                left.into_int_value(),
+                right.into_int_value(),
+                primitive.is_signed(),
+            ),
+            Type::Ptr(_) if is_comparison(operator) => Ok(self
+                .builder
+                .build_int_compare(
+                    int_predicate(operator, false),
+                    left.into_pointer_value(),
+                    right.into_pointer_value(),
+                    "",
+                )?
+                .into()),
+            _ => panic!("unsupported operand type {operand_ty:?} for {operator:?}"),
+        }
+    }
+
+    fn build_int_operation(
+        &self,
+        operator: &BinaryOperator,
+        left: IntValue<'ctx>,
+        right: IntValue<'ctx>,
+        signed: bool,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let value = match operator {
+            BinaryOperator::Addition => self.builder.build_int_add(left, right, "")?,
+            BinaryOperator::Subtraction => self.builder.build_int_sub(left, right, "")?,
+            BinaryOperator::Multiplication => self.builder.build_int_mul(left, right, "")?,
+            BinaryOperator::Division if signed => {
+                self.builder.build_int_signed_div(left, right, "")?
+            }
+            BinaryOperator::Division => self.builder.build_int_unsigned_div(left, right, "")?,
+            BinaryOperator::Remainder if signed => {
+                self.builder.build_int_signed_rem(left, right, "")?
+            }
+            BinaryOperator::Remainder => self.builder.build_int_unsigned_rem(left, right, "")?,
+            BinaryOperator::BitwiseAnd => self.builder.build_and(left, right, "")?,
+            BinaryOperator::BitwiseOr => self.builder.build_or(left, right, "")?,
+            BinaryOperator::BitwiseXor => self.builder.build_xor(left, right, "")?,
+            BinaryOperator::BitwiseShiftLeft => self.builder.build_left_shift(left, right, "")?,
+            // Signed integers use an arithmetic shift to preserve their sign
+            BinaryOperator::BitwiseShiftRight => {
+                self.builder.build_right_shift(left, right, signed, "")?
+            }
+            BinaryOperator::Equals
+            | BinaryOperator::NotEquals
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => {
+                self.builder
+                    .build_int_compare(int_predicate(operator, signed), left, right, "")?
+            }
+            BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => {
+                unreachable!("logical operators are built separately")
+            }
+        };
+
+        Ok(value.into())
+    }
+
+    fn build_float_operation(
+        &self,
+        operator: &BinaryOperator,
+        left: FloatValue<'ctx>,
+        right: FloatValue<'ctx>,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let value = match operator {
+            BinaryOperator::Addition => self
+                .builder
+                .build_float_add(left, right, "")?
+                .as_any_value_enum(),
+            BinaryOperator::Subtraction => self
+                .builder
+                .build_float_sub(left, right, "")?
+                .as_any_value_enum(),
+            BinaryOperator::Multiplication => self
+                .builder
+                .build_float_mul(left, right, "")?
+                .as_any_value_enum(),
+            BinaryOperator::Division => self
+                .builder
+                .build_float_div(left, right, "")?
+                .as_any_value_enum(),
+            BinaryOperator::Remainder => self
+                .builder
+                .build_float_rem(left, right, "")?
+                .as_any_value_enum(),
+            operator if is_comparison(operator) => self
+                .builder
+                .build_float_compare(float_predicate(operator), left, right, "")?
+                .as_any_value_enum(),
+            _ => panic!("unsupported operator {operator:?} for floats"),
+        };
+
+        Ok(value)
+    }
+
+    /// Build `&&` or `||`, only evaluating the right operand if the left one
+    /// does not already decide the result.
+    fn build_logical_operation(
+        &mut self,
+        operation: &BinaryOperation,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let is_and = operation.operator == BinaryOperator::LogicalAnd;
+        let (right_name, end_name) = if is_and {
+            ("and.rhs", "and.end")
+        } else {
+            ("or.rhs", "or.end")
+        };
+
+        let left = self
+            .build_expression(&operation.left, package)?
+            .into_int_value();
+        let left_block = self
+            .builder
+            .get_insert_block()
+            .expect("expected builder to be positioned");
+        let function = left_block
+            .get_parent()
+            .expect("expected block to be in a function");
+
+        let right_block = self.context.append_basic_block(function, right_name);
+        let end_block = self.context.append_basic_block(function, end_name);
+
+        if is_and {
+            self.builder
+                .build_conditional_branch(left, right_block, end_block)?;
+        } else {
+            self.builder
+                .build_conditional_branch(left, end_block, right_block)?;
+        }
+
+        self.builder.position_at_end(right_block);
+        let right = self
+            .build_expression(&operation.right, package)?
+            .into_int_value();
+        let right_block = self
+            .builder
+            .get_insert_block()
+            .expect("expected builder to be positioned");
+        self.builder.build_unconditional_branch(end_block)?;
+
+        self.builder.position_at_end(end_block);
+        let bool_type = self.context.bool_type();
+        // Skipping the right operand means `&&` is false and `||` is true
+        let short_circuit = bool_type.const_int(u64::from(!is_and), false);
+        let phi = self.builder.build_phi(bool_type, "")?;
+        phi.add_incoming(&[(&short_circuit, left_block), (&right, right_block)]);
+
+        Ok(phi.as_any_value_enum())
+    }
+
+    /// Get the address of a place in memory.
+    fn build_place(
+        &mut self,
+        place: &Place,
+        package: &HLIRPackage,
+    ) -> Result<PointerValue<'ctx>, BuilderError> {
+        match place {
+            Place::Variable(variable) => Ok(self.values[variable]),
+            Place::Deref { pointer, .. } => Ok(self
+                .build_expression(pointer, package)?
+                .into_pointer_value()),
+            Place::Index(index) => self.build_index(index, package),
+            Place::Field { base, field, .. } => {
+                let struct_ty = self
+                    .basic_type(base.ty(package), package)
+                    .into_struct_type();
+                let base = self.build_place(base, package)?;
+                let index = u32::try_from(*field).expect("too many fields in struct");
+
+                self.builder.build_struct_gep(struct_ty, base, index, "")
+            }
+        }
+    }
+
+    /// Get the address of a field, if the struct it belongs to is in memory.
+    fn build_field_address(
+        &mut self,
+        access: &FieldAccess,
+        package: &HLIRPackage,
+    ) -> Result<Option<PointerValue<'ctx>>, BuilderError> {
+        match Place::try_from(Expression::FieldAccess(access.clone())) {
+            Ok(place) => self.build_place(&place, package).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Get the address of an element of a pointer or an array.
+    fn build_index(
+        &mut self,
+        index: &Index,
+        package: &HLIRPackage,
+    ) -> Result<PointerValue<'ctx>, BuilderError> {
+        let base_ty = index.base.ty(package);
+
+        // A sized array is a value, so its elements are found from where it
+        // is stored rather than from a pointer
+        if let Type::SizedArray(_, _) = *self.resolved_type(base_ty, package) {
+            let array_ty = self.basic_type(base_ty, package);
+            let base = self.build_reference(&index.base, package)?;
+            let position = self.build_index_position(&index.index, package)?;
+            let zero = position.get_type().const_zero();
+
+            // SAFETY: indexing out of bounds is undefined behaviour in the
+            // source language, as it is in LLVM
+            return unsafe {
+                self.builder
+                    .build_in_bounds_gep(array_ty, base, &[zero, position], "")
+            };
+        }
+
+        let base = self
+            .build_expression(&index.base, package)?
+            .into_pointer_value();
+        let position = self.build_index_position(&index.index, package)?;
+        let element_ty = self.basic_type(index.result, package);
+
+        // SAFETY: indexing out of bounds is undefined behaviour in the source
+        // language, as it is in LLVM
+        unsafe { self.builder.build_gep(element_ty, base, &[position], "") }
+    }
+
+    /// Build an index as a 64 bit integer, since LLVM treats the indices of a
+    /// GEP as signed and would read a large unsigned index as negative.
+    fn build_index_position(
+        &mut self,
+        position: &Expression,
+        package: &HLIRPackage,
+    ) -> Result<IntValue<'ctx>, BuilderError> {
+        let signed = match *self.resolved_type(position.ty(package), package) {
+            Type::Primitive(primitive) => primitive.is_signed(),
+            _ => false,
+        };
+        let value = self.build_expression(position, package)?.into_int_value();
+
+        self.builder
+            .build_int_cast_sign_flag(value, self.context.i64_type(), signed, "")
+    }
+
+    /// Build an array with every element set to the same value, by storing
+    /// the value into each element of a temporary array in a loop.
+    fn build_array_repeat(
+        &mut self,
+        repeat: &ArrayRepeat,
+        package: &HLIRPackage,
+    ) -> Result<AnyValueEnum<'ctx>, BuilderError> {
+        let array_ty = self.basic_type(repeat.result, package).into_array_type();
+        let value: BasicValueEnum<'ctx> = self
+            .build_expression(&repeat.value, package)?
+            .try_into()
+            .expect("expected value");
+
+        // The value is still built for its side effects
+        if repeat.length == 0 {
+            return Ok(array_ty.get_undef().into());
+        }
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_parent)
+            .expect("expected builder to be inside of a function");
+        let array = self.build_entry_alloca(function, array_ty.into(), "")?;
+
+        let counter_ty = self.context.i64_type();
+        let length = counter_ty.const_int(repeat.length as u64, false);
+
+        let entry_block = self
+            .builder
+            .get_insert_block()
+            .expect("expected builder to be inside of a block");
+        let body_block = self.context.append_basic_block(function, "repeat.body");
+        let exit_block = self.context.append_basic_block(function, "repeat.exit");
+
+        self.builder.build_unconditional_branch(body_block)?;
+
+        self.builder.position_at_end(body_block);
+        let counter = self.builder.build_phi(counter_ty, "")?;
+        let position = counter.as_basic_value().into_int_value();
+
+        // SAFETY: the counter stays within the length of the array
+        let element = unsafe {
+            self.builder.build_in_bounds_gep(
+                array_ty,
+                array,
+                &[counter_ty.const_zero(), position],
+                "",
+            )?
+        };
+        self.builder.build_store(element, value)?;
+
+        let next = self
+            .builder
+            .build_int_add(position, counter_ty.const_int(1, false), "")?;
+        counter.add_incoming(&[(&counter_ty.const_zero(), entry_block), (&next, body_block)]);
+
+        let remaining = self
+            .builder
+            .build_int_compare(IntPredicate::ULT, next, length, "")?;
+        self.builder
+            .build_conditional_branch(remaining, body_block, exit_block)?;
+
+        self.builder.position_at_end(exit_block);
+        Ok(self.builder.build_load(array_ty, array, "")?.into())
+    }
+
+    /// Get the type of a value whose type has been inferred, as it is in the
+    /// function being built.
+    fn instance_type(&self, ty: InferenceId, package: &HLIRPackage) -> Option<TypeId> {
+        package.get_instance_type(ty, &self.type_arguments)
+    }
+
+    /// Get the type of a value whose type has been inferred.
+    ///
+    /// Enums are given as the integer type they are stored as, since values of
+    /// an enum are built the same way as integers.
+    fn resolved_type(&self, ty: InferenceId, package: &HLIRPackage) -> Rc<Type> {
+        let ty = self.instance_type(ty, package).expect("unresolved type");
+
+        match package.get_enum(ty) {
+            Some(definition) => Rc::new(Type::Primitive(definition.representation)),
+            None => package.get_type(ty).expect("unresolved type"),
+        }
+    }
+
+    /// Get the LLVM type of a value whose type has been inferred.
+    fn basic_type(&self, ty: InferenceId, package: &HLIRPackage) -> BasicTypeEnum<'ctx> {
+        let ty = self.instance_type(ty, package).expect("unresolved type");
+
+        self.types[&ty]
+            .try_into()
+            .expect("expected type to be a basic type")
+    }
+
+    /// Build the constant value of a literal, which needs no instructions.
+    fn build_literal(&self, literal: &Literal, package: &HLIRPackage) -> BasicValueEnum<'ctx> {
+        let ty = self.basic_type(literal.ty, package);
+
+        match &literal.value {
+            LiteralValue::Integer { value, radix } => {
+                // Type checking rejects the literals that do not fit their type
+                let value = LiteralValue::parse_integer(value, *radix)
+                    .expect("expected integer literal to be in range");
+
+                // An integer literal may also be used as a float
+                if ty.is_float_type() {
+                    #[allow(clippy::cast_precision_loss)]
+                    let value = value as f64;
+
+                    ty.into_float_type().const_float(value).into()
+                } else {
+                    // Only the lowest bits are kept, which also gives the
+                    // two's complement of a negative value
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let value = value as u64;
+
+                    ty.into_int_type().const_int(value, false).into()
+                }
+            }
+            LiteralValue::Float { value } => ty
+                .into_float_type()
+                .const_float(value.parse().expect("failed to parse float"))
+                .into(),
+            LiteralValue::Boolean { value } => ty
+                .into_int_type()
+                .const_int(u64::from(*value), false)
+                .into(),
+            LiteralValue::Character { value } => {
+                let byte = value.bytes().next().expect("empty character literal");
+
+                ty.into_int_type().const_int(u64::from(byte), false).into()
+            }
+            LiteralValue::String { value } => {
+                let value = self.context.const_string(value.as_bytes(), true);
+
+                let global = self.module.add_global(value.get_type(), None, "str");
+                global.set_linkage(Linkage::Private);
+                global.set_constant(true);
+                global.set_unnamed_addr(true);
+                global.set_initializer(&value);
+
+                global.as_pointer_value().into()
+            }
+        }
+    }
+
+    /// Build the value of a global computed at compile time.
+    fn build_constant(&self, constant: &Constant, package: &HLIRPackage) -> BasicValueEnum<'ctx> {
+        match constant {
+            Constant::Literal(literal) => self.build_literal(literal, package),
+            Constant::Array { elements, ty } => {
+                let element_ty = self
+                    .basic_type(*ty, package)
+                    .into_array_type()
+                    .get_element_type();
+                let elements = elements
+                    .iter()
+                    .map(|element| self.build_constant(element, package))
+                    .collect::<Vec<_>>();
+
+                match element_ty {
+                    BasicTypeEnum::IntType(ty) => ty.const_array(
+                        &elements
+                            .iter()
+                            .map(|element| element.into_int_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::FloatType(ty) => ty.const_array(
+                        &elements
+                            .iter()
+                            .map(|element| element.into_float_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::PointerType(ty) => ty.const_array(
+                        &elements
+                            .iter()
+                            .map(|element| element.into_pointer_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::StructType(ty) => ty.const_array(
+                        &elements
+                            .iter()
+                            .map(|element| element.into_struct_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::ArrayType(ty) => ty.const_array(
+                        &elements
+                            .iter()
+                            .map(|element| element.into_array_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::VectorType(_) => {
+                        panic!("unsupported element type {element_ty:?}")
+                    }
+                }
+                .into()
+            }
+            Constant::Struct { fields, ty } => {
+                let fields = fields
+                    .iter()
+                    .map(|field| self.build_constant(field, package))
+                    .collect::<Vec<_>>();
+
+                self.basic_type(*ty, package)
+                    .into_struct_type()
+                    .const_named_struct(&fields)
+                    .into()
+            }
+        }
+    }
+}
+
+/// Get the values that range patterns can match for a type, which are those
+/// of integers, characters and booleans.
+fn pattern_range(primitive: PrimitiveType) -> Option<core::ops::RangeInclusive<i128>> {
+    match primitive {
+        PrimitiveType::Bool => Some(0..=1),
+        // Characters are single bytes
+        PrimitiveType::Char => Some(0..=255),
+        _ => primitive.integer_range(),
+    }
+}
+
+fn is_comparison(operator: &BinaryOperator) -> bool {
+    matches!(
+        operator,
+        BinaryOperator::Equals
+            | BinaryOperator::NotEquals
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual
+    )
+}
+
+fn int_predicate(operator: &BinaryOperator, signed: bool) -> IntPredicate {
+    match (operator, signed) {
+        (BinaryOperator::Equals, _) => IntPredicate::EQ,
+        (BinaryOperator::NotEquals, _) => IntPredicate::NE,
+        (BinaryOperator::LessThan, true) => IntPredicate::SLT,
+        (BinaryOperator::LessThan, false) => IntPredicate::ULT,
+        (BinaryOperator::LessThanOrEqual, true) => IntPredicate::SLE,
+        (BinaryOperator::LessThanOrEqual, false) => IntPredicate::ULE,
+        (BinaryOperator::GreaterThan, true) => IntPredicate::SGT,
+        (BinaryOperator::GreaterThan, false) => IntPredicate::UGT,
+        (BinaryOperator::GreaterThanOrEqual, true) => IntPredicate::SGE,
+        (BinaryOperator::GreaterThanOrEqual, false) => IntPredicate::UGE,
+        _ => panic!("{operator:?} is not a comparison"),
+    }
+}
+
+/// Comparisons with NaN are false, except for `!=`
+fn float_predicate(operator: &BinaryOperator) -> FloatPredicate {
+    match operator {
+        BinaryOperator::Equals => FloatPredicate::OEQ,
+        BinaryOperator::NotEquals => FloatPredicate::UNE,
+        BinaryOperator::LessThan => FloatPredicate::OLT,
+        BinaryOperator::LessThanOrEqual => FloatPredicate::OLE,
+        BinaryOperator::GreaterThan => FloatPredicate::OGT,
+        BinaryOperator::GreaterThanOrEqual => FloatPredicate::OGE,
+        _ => panic!("{operator:?} is not a comparison"),
     }
 }
@@ -0,0 +1,537 @@
+use inkwell::context::Context;
+use tantalum_ast_lowering::ASTLoweringContext;
+use tantalum_codegen_llvm::LLVMCodegenContext;
+use tantalum_diagnostics::{Renderer, SourceMap};
+use tantalum_lexer::Lexer;
+use tantalum_parser::Parser;
+
+/// Build `source` into a module, check that LLVM accepts it, and get its IR
+fn build(source: &str) -> String {
+    let lexer = Lexer::new("test.ta", source);
+    let mut parser = Parser::new(lexer);
+    let (ast, errors) = parser.parse();
+    assert!(errors.is_empty(), "failed to parse source: {errors:?}");
+
+    let mut lowering_context = ASTLoweringContext::new();
+    lowering_context.lower(&ast);
+    let package = lowering_context.finish();
+
+    let mut sources = SourceMap::new();
+    sources.add("test.ta", source);
+    let renderer = Renderer::new(&sources);
+    let diagnostics = package
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| renderer.render(diagnostic))
+        .collect::<Vec<_>>();
+    assert!(diagnostics.is_empty(), "{}", diagnostics.join("\n"));
+
+    let context = Context::create();
+    let mut codegen_context = LLVMCodegenContext::new(&context);
+    codegen_context.build(&package);
+
+    let ir = codegen_context.emit_ll();
+    if let Err(error) = codegen_context.verify() {
+        panic!("{error}\n{ir}");
+    }
+
+    ir
+}
+
+#[test]
+fn basic() {
+    let source = r"
+fn add(a: i32, b: i32): i32 {
+    return a + b;
+}
+
+fn main(): i32 {
+    let c = 30;
+    return add(c, 2);
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn conditionals_example() {
+    insta::assert_snapshot!(build(include_str!("../../../examples/conditionals.ta")));
+}
+
+#[test]
+fn loops_example() {
+    insta::assert_snapshot!(build(include_str!("../../../examples/loops.ta")));
+}
+
+#[test]
+fn control_flow() {
+    let source = r#"
+extern fn printf(format: *const u8, ...): i32;
+
+fn classify(n: i32): i32 {
+    if n < 0 {
+        return -1;
+    } else if n == 0 {
+        return 0;
+    }
+
+    let steps = 0;
+    while true {
+        if n == 1 {
+            break;
+        }
+        steps = steps + 1;
+        if n % 2 == 0 {
+            n = n / 2;
+            continue;
+        }
+        n = 3 * n + 1;
+    }
+
+    printf("%d\n".&, steps);
+    return steps;
+}
+"#;
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn operators() {
+    let source = r"
+fn signed(a: i32, b: i32): i32 {
+    return a / b + a % b + (a >> 2) + (a << 1);
+}
+
+fn unsigned(a: u32, b: u32): u32 {
+    return a / b + a % b + (a >> 2) + (a ^ b & 7 | 1);
+}
+
+fn float(x: f64, y: f64): bool {
+    return x / y - x % y < 1.0;
+}
+
+fn short_circuit(a: i32, b: u32): bool {
+    return a > 0 && b < 10 || a == -1;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn casts() {
+    let source = r"
+fn widen(a: i8, b: u8, c: f32, d: bool): f64 {
+    return a:f64 + b:f64 + c:f64 + d:i32:f64 + a:i64:f64;
+}
+
+fn narrow(x: f64, y: i64): u8 {
+    return x:i32:u8 + y:u8 + x:f32:u8;
+}
+
+fn pointers(p: *i32): *u8 {
+    let address = p:u64;
+    return (address + 4):*u8;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn integer_literals() {
+    let source = r"
+fn main(): i32 {
+    let smallest: i8 = -128;
+    let largest: u64 = 18446744073709551615;
+    let mask: u16 = 0xffff;
+    let bits: i32 = -0b101;
+    let scale: f64 = 3;
+    return bits;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn string_literals() {
+    let source = r#"
+extern fn puts(s: *const u8): i32;
+
+fn main(): i32 {
+    let greeting = "hello";
+    puts(greeting.&);
+    return puts("escaped \"world\"\n".&);
+}
+"#;
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn arrays() {
+    let source = r"
+struct Grid {
+    cells: [[u8; 3]; 2],
+}
+
+fn pair(): [i32; 2] {
+    return [1, 2];
+}
+
+fn first(values: *i32, position: u8): i32 {
+    return values[position];
+}
+
+fn main(): i32 {
+    let grid = Grid { cells: [[0; 3]; 2] };
+    let row: u32 = 1;
+    grid.cells[row][2] = 7;
+    let values = pair();
+    let offset: i8 = -1;
+    return values[offset + 2] + first(values[0].&, 200) + grid.cells[1][2]:i32;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn statics() {
+    let source = r"
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+const LIMIT: i32 = 2 * 5;
+const U: [i32; 2] = [4, LIMIT];
+const ORIGIN: Point = Point { x: 0, y: -1 };
+
+static counter: i32 = LIMIT;
+static T: [i32; 3] = [1, 2, 3];
+static zeros: [u8; 4] = [0; 4];
+static corner: Point = Point { x: LIMIT, y: 3 };
+extern static errno: i32;
+
+fn main(): i32 {
+    counter = counter + errno;
+    let u = U;
+    let origin = ORIGIN;
+    return T[1] + u[0] + corner.x + origin.y + zeros[2]:i32;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn structs() {
+    let source = r"
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Line {
+    start: Point,
+    end: Point,
+}
+
+fn length(line: *Line): i32 {
+    return line.*.end.x - line.*.start.x + line.*.end.y - line.*.start.y;
+}
+
+fn main(): i32 {
+    let line = Line {
+        start: Point { x: 1, y: 2 },
+        end: Point { x: 4, y: 6 },
+    };
+    line.end.x = 5;
+    let start = line.start;
+    return length(line.&) + start.y + Point { x: 3, y: 0 }.x;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn tagged_unions() {
+    let source = r"
+enum Shape: u8 {
+    Empty,
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+}
+
+fn is_round(shape: Shape): bool {
+    return shape is Shape::Circle;
+}
+
+fn main(): i32 {
+    let circle = Shape::Circle(1.5);
+    let rect = Shape::Rect { h: 2.0, w: 3.0 };
+    if is_round(circle) && !(rect is Shape::Empty) {
+        return 1;
+    }
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn tagged_unions_with_mixed_alignment() {
+    let source = r"
+enum Value {
+    Bytes(u8, u8, u8),
+    Short(u16),
+}
+
+fn main(): i32 {
+    let bytes = Value::Bytes(1, 2, 3);
+    let short = Value::Short(4);
+    if bytes is Value::Bytes && short is Value::Short {
+        return 1;
+    }
+    return 0;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn matches() {
+    let source = r"
+enum Shape: u8 {
+    Empty,
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+}
+
+enum Color { Red, Green, Blue }
+
+fn area(shape: Shape): f32 {
+    match shape {
+        Shape::Empty => return 0.0;
+        Shape::Circle(radius) => return radius * radius * 3.14;
+        Shape::Rect { w, h } => return w * h;
+    }
+    return 0.0;
+}
+
+fn dense(value: u8, color: Color): i32 {
+    match value {
+        0 => return 0;
+        1 | 2 => return 1;
+        3..6 => return 2;
+        other => return other:i32;
+    }
+    match color {
+        Color::Red => return 3;
+        Color::Green | Color::Blue => return 4;
+    }
+    return 5;
+}
+
+fn sparse(value: i64): i32 {
+    match value {
+        0..=100000 => return 1;
+        _ => return 0;
+    }
+    return 2;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn trait_objects() {
+    let source = r"
+trait Shape {
+    fn area(self: *Self): f32;
+    fn scale(self: *Self, by: f32);
+}
+
+struct Circle {
+    radius: f32,
+}
+
+impl Shape for Circle {
+    fn area(self: *Self): f32 {
+        return 3.14 * self.radius * self.radius;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.radius = self.radius * by;
+    }
+}
+
+impl Shape for i32 {
+    fn area(self: *Self): f32 {
+        return self.*:f32;
+    }
+
+    fn scale(self: *Self, by: f32) {
+        self.* = (self.*:f32 * by):i32;
+    }
+}
+
+fn total(shape: *dyn Shape, other: *dyn Shape): f32 {
+    shape.scale(2.0);
+    return shape.area() + other.area();
+}
+
+fn main(): i32 {
+    let circle = Circle { radius: 1.0 };
+    return total(circle.&, 4.&):i32;
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn generic_pointer_and_array_arguments() {
+    let source = r"
+struct ptr<T> {
+    value: T,
+}
+
+fn id<T>(value: T): T {
+    return value;
+}
+
+fn main(): i32 {
+    let x = 1;
+    let p = id(x.&);
+    let wrapped = id(ptr { value: x });
+    let values = id([1, 2]);
+    return p.* + wrapped.value + values[0];
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn generics() {
+    let source = r"
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+enum Either<A, B> {
+    Left(A, A, A),
+    Right(B),
+}
+
+fn first<A, B>(pair: Pair<A, B>): A {
+    return pair.first;
+}
+
+fn left_or<A, B>(either: Either<A, B>, default: A): A {
+    match either {
+        Either::Left(value, _, _) => return value;
+        Either::Right(_) => return default;
+    }
+}
+
+fn main(): i32 {
+    let pair = Pair { first: 1, second: 2.0 };
+    let other = Pair { first: 3:u8, second: true };
+    let bytes: Either<u8, u16> = Either::Left(1, 2, 3);
+    let wide: Either<i32, f64> = Either::Right(4.0);
+    let sum = first(pair) + first(other):i32;
+    return sum + left_or(bytes, 0):i32 + left_or(wide, 5);
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn inherent_impls() {
+    let source = r"
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn new(x: i32, y: i32): Self {
+        return Point { x: x, y: y };
+    }
+
+    fn length(self: *const Self): i32 {
+        return self.x * self.x + self.y * self.y;
+    }
+
+    fn scale(self: *Self, by: i32) {
+        self.x = self.x * by;
+        self.y = self.y * by;
+    }
+}
+
+fn main(): i32 {
+    let point = Point::new(1, 2);
+    point.scale(3);
+    return point.length();
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
+
+#[test]
+fn modules() {
+    let source = r"
+mod shapes {
+    struct Circle {
+        radius: f32,
+    }
+
+    impl Circle {
+        fn new(radius: f32): Circle {
+            return Circle { radius: radius };
+        }
+    }
+
+    fn area(circle: *Circle): f32 {
+        return 3.14 * circle.radius * circle.radius;
+    }
+
+    mod units {
+        static SCALE: f32 = 2.0;
+
+        fn scale(value: f32): f32 {
+            return value * SCALE;
+        }
+    }
+}
+
+struct Circle {
+    diameter: i32,
+}
+
+fn area(circle: *Circle): i32 {
+    return circle.diameter;
+}
+
+fn main(): i32 {
+    let circle = shapes::Circle::new(1.0);
+    let other = Circle { diameter: 2 };
+    return shapes::units::scale(shapes::area(circle.&)):i32 + area(other.&);
+}
+";
+
+    insta::assert_snapshot!(build(source));
+}
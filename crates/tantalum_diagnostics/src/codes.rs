@@ -0,0 +1,154 @@
+//! The stable codes of every diagnostic the compiler can report
+//!
+//! Codes are grouped by the stage of the compiler that reports them:
+//!
+//! - `E00xx` for the lexer
+//! - `E01xx` for the parser
+//! - `E02xx` for lowering the AST into HLIR
+//! - `E03xx` for the HLIR and type checking
+//!
+//! A code is never reused for a different kind of diagnostic once assigned.
+
+use crate::Code;
+
+////////////////////////////////////////////////////////////////////////////////
+// Lexer
+////////////////////////////////////////////////////////////////////////////////
+
+/// A character that does not start any token
+pub const UNKNOWN_CHARACTER: Code = Code::new("E0001");
+/// A `/*` block comment without a matching `*/`
+pub const UNTERMINATED_BLOCK_COMMENT: Code = Code::new("E0002");
+/// A string literal without a closing `"`
+pub const UNTERMINATED_STRING_LITERAL: Code = Code::new("E0003");
+/// A character literal without a closing `'`
+pub const UNTERMINATED_CHARACTER_LITERAL: Code = Code::new("E0004");
+
+////////////////////////////////////////////////////////////////////////////////
+// Parser
+////////////////////////////////////////////////////////////////////////////////
+
+/// The source ended in the middle of an item, statement, or expression
+pub const UNEXPECTED_END_OF_FILE: Code = Code::new("E0101");
+/// A token that cannot appear at this position
+pub const UNEXPECTED_TOKEN: Code = Code::new("E0102");
+/// A `mod name;` whose file cannot be found
+pub const MODULE_NOT_FOUND: Code = Code::new("E0103");
+/// A `mod name;` whose file is already being loaded, as the file declares
+/// the module itself, directly or through other modules
+pub const MODULE_CYCLE: Code = Code::new("E0104");
+/// The length of an array that is too large to be represented
+pub const INVALID_ARRAY_LENGTH: Code = Code::new("E0105");
+
+////////////////////////////////////////////////////////////////////////////////
+// Lowering
+////////////////////////////////////////////////////////////////////////////////
+
+/// A type name that is not defined
+pub const UNKNOWN_TYPE: Code = Code::new("E0201");
+/// A function name that is not defined
+pub const UNKNOWN_FUNCTION: Code = Code::new("E0202");
+/// A call of an expression that is not a function name
+pub const NOT_CALLABLE: Code = Code::new("E0203");
+/// A language feature that the compiler does not support yet
+pub const UNSUPPORTED: Code = Code::new("E0204");
+/// A `break` or `continue` outside of a loop
+pub const BREAK_OUTSIDE_OF_LOOP: Code = Code::new("E0205");
+/// A loop condition that is not an expression
+pub const INVALID_LOOP_CONDITION: Code = Code::new("E0206");
+/// An assignment to an expression that is not a place in memory
+pub const INVALID_ASSIGNMENT_TARGET: Code = Code::new("E0207");
+/// A `\` in a string or character literal that does not start a known escape
+pub const UNKNOWN_ESCAPE_SEQUENCE: Code = Code::new("E0208");
+/// A name that is defined more than once in the same scope
+pub const DUPLICATE_DEFINITION: Code = Code::new("E0209");
+/// A struct literal that does not initialize every field of the struct
+pub const MISSING_FIELDS: Code = Code::new("E0210");
+/// An enum stored as a type that is not an integer
+pub const INVALID_ENUM_REPRESENTATION: Code = Code::new("E0211");
+/// An enum discriminant that is not an integer literal, does not fit in the
+/// type of the enum, or is the same as another variant
+pub const INVALID_DISCRIMINANT: Code = Code::new("E0212");
+/// A path to a variant that the enum does not have
+pub const UNKNOWN_VARIANT: Code = Code::new("E0213");
+/// A variant constructed with a syntax that does not match the values it
+/// carries, e.g. a tuple variant without parentheses
+pub const MISMATCHED_VARIANT_KIND: Code = Code::new("E0214");
+/// A pattern that cannot be matched against, e.g. a float literal or an empty
+/// range
+pub const INVALID_PATTERN: Code = Code::new("E0215");
+/// A trait name that is not defined
+pub const UNKNOWN_TRAIT: Code = Code::new("E0216");
+/// An `impl` block that does not define every method of its trait
+pub const MISSING_TRAIT_METHODS: Code = Code::new("E0217");
+/// A method in an `impl` block that its trait does not declare
+pub const UNKNOWN_TRAIT_METHOD: Code = Code::new("E0218");
+/// A `use` of an item that does not exist
+pub const UNRESOLVED_IMPORT: Code = Code::new("E0219");
+/// A type alias that refers back to itself, e.g. `type A = B; type B = A;`
+pub const CYCLIC_TYPE_ALIAS: Code = Code::new("E0220");
+
+////////////////////////////////////////////////////////////////////////////////
+// HLIR
+////////////////////////////////////////////////////////////////////////////////
+
+/// A variable name that is not defined in the current scope
+pub const UNKNOWN_VARIABLE: Code = Code::new("E0301");
+/// A function call with the wrong number of arguments
+pub const ARGUMENT_COUNT_MISMATCH: Code = Code::new("E0302");
+/// A value whose type is not the type expected at its position
+pub const MISMATCHED_TYPES: Code = Code::new("E0303");
+/// A numeric literal used as a type it cannot be converted to
+pub const INVALID_LITERAL: Code = Code::new("E0304");
+/// A value whose type cannot be inferred from its uses
+pub const TYPE_ANNOTATIONS_NEEDED: Code = Code::new("E0305");
+/// A reference whose type does not point to the type of its operand
+pub const INVALID_REFERENCE: Code = Code::new("E0306");
+/// A dereference of a value that is not a pointer
+pub const INVALID_DEREFERENCE: Code = Code::new("E0307");
+/// An index into a value that is not a pointer or an array
+pub const INVALID_INDEX: Code = Code::new("E0308");
+/// A value that is not an integer where an integer is required
+pub const EXPECTED_INTEGER: Code = Code::new("E0309");
+/// A cast between two types that cannot be converted into each other
+pub const INVALID_CAST: Code = Code::new("E0310");
+/// An access of a field that the type does not have
+pub const UNKNOWN_FIELD: Code = Code::new("E0311");
+/// A `match` whose arms do not cover every value of the scrutinee
+pub const NON_EXHAUSTIVE_PATTERNS: Code = Code::new("E0312");
+/// A `match` arm that only matches values already matched by the arms above it
+pub const UNREACHABLE_PATTERN: Code = Code::new("E0313");
+/// A generic function that instantiates itself with ever larger types
+pub const RECURSION_LIMIT: Code = Code::new("E0314");
+/// A method in an `impl` block whose parameters or return type differ from
+/// its declaration in the trait
+pub const MISMATCHED_METHOD_SIGNATURE: Code = Code::new("E0315");
+/// A type used where it must implement a trait that it does not implement
+pub const TRAIT_NOT_IMPLEMENTED: Code = Code::new("E0316");
+/// A method call of a method that neither the type of the receiver nor any
+/// trait declares
+pub const UNKNOWN_METHOD: Code = Code::new("E0317");
+/// A method call of a method declared by more than one trait
+pub const AMBIGUOUS_METHOD: Code = Code::new("E0318");
+/// A method call through a trait object of a method that takes or returns
+/// `Self` other than through its `self: *Self` parameter
+pub const UNDISPATCHABLE_METHOD: Code = Code::new("E0319");
+/// A constant or static whose value cannot be computed at compile time
+pub const NON_CONSTANT_VALUE: Code = Code::new("E0320");
+/// An operation that overflows or divides by zero while computing the value
+/// of a constant or static
+pub const CONSTANT_EVALUATION_FAILED: Code = Code::new("E0321");
+/// A constant whose value depends on itself
+pub const CYCLIC_CONSTANT: Code = Code::new("E0322");
+/// A value that is not a number where a number is required, e.g. an operand of
+/// an arithmetic operator
+pub const EXPECTED_NUMBER: Code = Code::new("E0323");
+/// A comparison of values whose type cannot be compared, e.g. structs
+pub const NOT_COMPARABLE: Code = Code::new("E0324");
+/// An integer literal whose value does not fit in its type
+pub const LITERAL_OUT_OF_RANGE: Code = Code::new("E0325");
+/// Something of type `void` used as a value, or `void` used as the type of a
+/// field, a parameter or an element of an array
+pub const VOID_VALUE: Code = Code::new("E0326");
+/// A function returning a value whose end can be reached without a `return`
+pub const MISSING_RETURN: Code = Code::new("E0327");
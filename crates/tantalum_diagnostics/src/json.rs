@@ -0,0 +1,41 @@
+//! Emits diagnostics as JSON for consumption by editors and other tools.
+//!
+//! Each diagnostic is emitted as a single line containing one JSON object
+//! with the fields of the [`Diagnostic`] and a `rendered` field holding the
+//! output of the [`Renderer`].
+
+use crate::{Diagnostic, Renderer, SourceMap};
+
+#[derive(serde::Serialize)]
+struct JsonDiagnostic<'a, 'file_name> {
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic<'file_name>,
+    rendered: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct JsonEmitter<'a, 'file_name, 'source> {
+    sources: &'a SourceMap<'file_name, 'source>,
+}
+
+impl<'a, 'file_name, 'source> JsonEmitter<'a, 'file_name, 'source> {
+    #[must_use]
+    pub fn new(sources: &'a SourceMap<'file_name, 'source>) -> Self {
+        Self { sources }
+    }
+
+    /// Serialize `diagnostic` as a single line of JSON
+    ///
+    /// # Panics
+    ///
+    /// Panics if the diagnostic cannot be serialized, which cannot happen for
+    /// the types used by [`Diagnostic`].
+    #[must_use]
+    pub fn emit(&self, diagnostic: &Diagnostic<'_>) -> String {
+        serde_json::to_string(&JsonDiagnostic {
+            diagnostic,
+            rendered: Renderer::new(self.sources).render(diagnostic),
+        })
+        .expect("expected diagnostics to always be serializable")
+    }
+}
@@ -0,0 +1,246 @@
+//! # Tantalum Diagnostics
+//!
+//! Provides the diagnostics reported by every stage of the compiler, along with
+//! a renderer for displaying them alongside the source code and a JSON emitter
+//! for consumption by other tools.
+
+use std::collections::HashMap;
+
+use tantalum_span::Span;
+
+pub mod codes;
+#[cfg(feature = "json")]
+pub mod json;
+mod render;
+
+#[cfg(test)]
+mod tests;
+
+pub use render::Renderer;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// The source code is invalid and cannot be compiled
+    Error,
+    /// The source code is valid but likely contains a mistake
+    Warning,
+    /// Additional information that is not a problem on its own
+    Note,
+}
+
+impl core::fmt::Display for Severity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// A stable identifier for a kind of diagnostic (e.g. `E0102`)
+///
+/// All known codes are listed in [`codes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Code(&'static str);
+
+impl Code {
+    #[must_use]
+    pub const fn new(code: &'static str) -> Self {
+        Self(code)
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl core::fmt::Display for Code {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A message attached to a span of the source code
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Label<'file_name> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    span: Span<'file_name>,
+    message: String,
+}
+
+impl<'file_name> Label<'file_name> {
+    #[must_use]
+    pub fn new(span: Span<'file_name>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn span(&self) -> Span<'file_name> {
+        self.span
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// A problem found in the source code by one of the stages of the compiler
+///
+/// # Example
+/// ```
+/// use tantalum_diagnostics::{codes, Diagnostic, Label};
+/// use tantalum_span::{Location, Span};
+///
+/// let span = Span::new(
+///     Location::new_at("main.ta", 4, 1, 5),
+///     Location::new_at("main.ta", 5, 1, 6),
+/// );
+///
+/// let diagnostic = Diagnostic::error(
+///     codes::UNKNOWN_VARIABLE,
+///     "cannot find variable `x` in this scope",
+///     Label::new(span, "not found in this scope"),
+/// )
+/// .with_help("declare the variable with `let x = ...;` before using it");
+///
+/// assert!(diagnostic.is_error());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic<'file_name> {
+    severity: Severity,
+    code: Code,
+    message: String,
+    /// The location the diagnostic is reported at
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    primary: Label<'file_name>,
+    /// Other locations that are related to the diagnostic
+    secondary: Vec<Label<'file_name>>,
+    notes: Vec<String>,
+    help: Option<String>,
+}
+
+impl<'file_name> Diagnostic<'file_name> {
+    #[must_use]
+    pub fn new(
+        severity: Severity,
+        code: Code,
+        message: impl Into<String>,
+        primary: Label<'file_name>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            primary,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    #[must_use]
+    pub fn error(code: Code, message: impl Into<String>, primary: Label<'file_name>) -> Self {
+        Self::new(Severity::Error, code, message, primary)
+    }
+
+    #[must_use]
+    pub fn warning(code: Code, message: impl Into<String>, primary: Label<'file_name>) -> Self {
+        Self::new(Severity::Warning, code, message, primary)
+    }
+
+    /// Attach a secondary label pointing at a related location
+    #[must_use]
+    pub fn with_label(mut self, label: Label<'file_name>) -> Self {
+        self.secondary.push(label);
+        self
+    }
+
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    #[must_use]
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[must_use]
+    pub fn primary(&self) -> &Label<'file_name> {
+        &self.primary
+    }
+
+    #[must_use]
+    pub fn secondary(&self) -> &[Label<'file_name>] {
+        &self.secondary
+    }
+
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    #[must_use]
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+}
+
+/// The source code of every file that diagnostics may point into
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap<'file_name, 'source> {
+    files: HashMap<&'file_name str, &'source str>,
+}
+
+impl<'file_name, 'source> SourceMap<'file_name, 'source> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            files: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, file_name: &'file_name str, source: &'source str) {
+        self.files.insert(file_name, source);
+    }
+
+    #[must_use]
+    pub fn get(&self, file_name: &str) -> Option<&'source str> {
+        self.files.get(file_name).copied()
+    }
+}
@@ -0,0 +1,385 @@
+use core::cmp::Reverse;
+use core::fmt::Write as _;
+use std::collections::BTreeSet;
+
+use crate::{Diagnostic, Label, SourceMap};
+
+/// Labels spanning more lines than this only show their first and last two
+/// lines
+const MAX_MULTILINE_LINES: usize = 4;
+
+/// The width a tab is expanded to when displaying source code
+const TAB_WIDTH: usize = 4;
+
+/// Renders diagnostics in a human readable format, with the labelled lines of
+/// the source code displayed beneath the message.
+///
+/// ```text
+/// error[E0301]: cannot find variable `x` in this scope
+///  --> main.ta:2:12
+///   |
+/// 2 |     return x;
+///   |            ^ not found in this scope
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer<'a, 'file_name, 'source> {
+    sources: &'a SourceMap<'file_name, 'source>,
+}
+
+/// A label along with how it should be drawn
+#[derive(Debug, Clone, Copy)]
+struct Annotation<'a, 'file_name> {
+    label: &'a Label<'file_name>,
+    is_primary: bool,
+}
+
+impl Annotation<'_, '_> {
+    fn marker(&self) -> char {
+        if self.is_primary {
+            '^'
+        } else {
+            '-'
+        }
+    }
+
+    fn start_line(&self) -> usize {
+        self.label.span().start().line()
+    }
+
+    fn end_line(&self) -> usize {
+        self.label.span().end().line()
+    }
+
+    fn is_multiline(&self) -> bool {
+        self.start_line() != self.end_line()
+    }
+
+    /// Whether the label starts at the first non-whitespace character of its
+    /// first line, allowing it to be drawn entirely in the gutter
+    fn starts_line(&self, text: &str) -> bool {
+        text.chars()
+            .take(self.label.span().start().column().saturating_sub(1))
+            .all(char::is_whitespace)
+    }
+
+    fn is_active(&self, line: usize) -> bool {
+        self.start_line() <= line && line <= self.end_line()
+    }
+}
+
+impl<'a, 'file_name, 'source> Renderer<'a, 'file_name, 'source> {
+    #[must_use]
+    pub fn new(sources: &'a SourceMap<'file_name, 'source>) -> Self {
+        Self { sources }
+    }
+
+    #[must_use]
+    pub fn render(&self, diagnostic: &Diagnostic<'_>) -> String {
+        let mut output = String::new();
+
+        let _ = writeln!(
+            output,
+            "{}[{}]: {}",
+            diagnostic.severity(),
+            diagnostic.code(),
+            diagnostic.message()
+        );
+
+        let annotations = core::iter::once(Annotation {
+            label: diagnostic.primary(),
+            is_primary: true,
+        })
+        .chain(diagnostic.secondary().iter().map(|label| Annotation {
+            label,
+            is_primary: false,
+        }))
+        .collect::<Vec<_>>();
+
+        let width = annotations
+            .iter()
+            .map(|annotation| annotation.end_line().to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        let mut files = Vec::new();
+        for annotation in &annotations {
+            let file_name = annotation.label.span().file_name();
+            if !files.contains(&file_name) {
+                files.push(file_name);
+            }
+        }
+
+        for (index, file_name) in files.into_iter().enumerate() {
+            let annotations = annotations
+                .iter()
+                .filter(|annotation| annotation.label.span().file_name() == file_name)
+                .copied()
+                .collect::<Vec<_>>();
+
+            let arrow = if index == 0 { "-->" } else { ":::" };
+            let _ = writeln!(
+                output,
+                "{:width$}{arrow} {}",
+                "",
+                annotations[0].label.span().start()
+            );
+            Self::row(&mut output, width, None, "");
+
+            self.render_snippet(&mut output, file_name, &annotations, width);
+        }
+
+        if !diagnostic.notes().is_empty() || diagnostic.help().is_some() {
+            Self::row(&mut output, width, None, "");
+        }
+
+        for note in diagnostic.notes() {
+            let _ = writeln!(output, "{:width$} = note: {note}", "");
+        }
+
+        if let Some(help) = diagnostic.help() {
+            let _ = writeln!(output, "{:width$} = help: {help}", "");
+        }
+
+        output
+    }
+
+    fn render_snippet(
+        self,
+        output: &mut String,
+        file_name: &str,
+        annotations: &[Annotation<'_, '_>],
+        width: usize,
+    ) {
+        let source = self.sources.get(file_name).unwrap_or_default();
+        let lines = source.lines().collect::<Vec<_>>();
+        let text_of = |line: usize| {
+            lines
+                .get(line.saturating_sub(1))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let mut multiline = annotations
+            .iter()
+            .filter(|annotation| annotation.is_multiline())
+            .copied()
+            .collect::<Vec<_>>();
+        multiline.sort_by_key(|annotation| {
+            (
+                annotation.label.span().start().position(),
+                Reverse(annotation.label.span().end().position()),
+            )
+        });
+
+        let mut single = annotations
+            .iter()
+            .filter(|annotation| !annotation.is_multiline())
+            .copied()
+            .collect::<Vec<_>>();
+        single.sort_by_key(|annotation| annotation.label.span().start().position());
+
+        let mut shown = BTreeSet::new();
+        for annotation in annotations {
+            let (start, end) = (annotation.start_line(), annotation.end_line());
+
+            if end - start < MAX_MULTILINE_LINES {
+                shown.extend(start..=end);
+            } else {
+                shown.extend([start, start + 1, end - 1, end]);
+            }
+        }
+
+        let separator = separator(&multiline);
+
+        let mut previous: Option<usize> = None;
+
+        for line in shown {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                let gutter = multiline
+                    .iter()
+                    .map(|annotation| {
+                        if annotation.start_line() < line && line <= annotation.end_line() {
+                            '|'
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect::<String>();
+
+                Self::row(output, width, None, &format!("{gutter}{separator}..."));
+            }
+            previous = Some(line);
+
+            let text = text_of(line);
+
+            let gutter = multiline
+                .iter()
+                .map(|annotation| {
+                    if annotation.start_line() == line && annotation.starts_line(text) {
+                        '/'
+                    } else if annotation.start_line() < line && line <= annotation.end_line() {
+                        '|'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect::<String>();
+
+            Self::row(
+                output,
+                width,
+                Some(line),
+                &format!("{gutter}{separator}{}", expand_tabs(text)),
+            );
+
+            Self::render_multiline_starts(output, width, line, text, &multiline);
+            Self::render_single(output, width, line, text, &multiline, &single);
+            Self::render_multiline_ends(output, width, line, text, &multiline);
+        }
+    }
+
+    /// Draw the multi-line labels starting part way through `line`
+    fn render_multiline_starts(
+        output: &mut String,
+        width: usize,
+        line: usize,
+        text: &str,
+        multiline: &[Annotation<'_, '_>],
+    ) {
+        for (index, annotation) in multiline.iter().enumerate() {
+            if annotation.start_line() != line || annotation.starts_line(text) {
+                continue;
+            }
+
+            let mut content = multiline
+                .iter()
+                .enumerate()
+                .map(|(other_index, other)| match other_index.cmp(&index) {
+                    core::cmp::Ordering::Less if other.is_active(line) => '|',
+                    core::cmp::Ordering::Less | core::cmp::Ordering::Equal => ' ',
+                    core::cmp::Ordering::Greater => '_',
+                })
+                .collect::<String>();
+
+            let offset = visual_offset(text, annotation.label.span().start().column());
+            content.push_str(&"_".repeat(offset + 1));
+            content.push(annotation.marker());
+
+            Self::row(output, width, None, &content);
+        }
+    }
+
+    /// Draw the labels contained within `line`
+    fn render_single(
+        output: &mut String,
+        width: usize,
+        line: usize,
+        text: &str,
+        multiline: &[Annotation<'_, '_>],
+        single: &[Annotation<'_, '_>],
+    ) {
+        let separator = separator(multiline);
+
+        for annotation in single
+            .iter()
+            .filter(|annotation| annotation.start_line() == line)
+        {
+            let gutter = multiline
+                .iter()
+                .map(|other| if other.is_active(line) { '|' } else { ' ' })
+                .collect::<String>();
+
+            let start = visual_offset(text, annotation.label.span().start().column());
+            let end = visual_offset(text, annotation.label.span().end().column());
+
+            let mut content = format!(
+                "{gutter}{separator}{:start$}{}",
+                "",
+                annotation
+                    .marker()
+                    .to_string()
+                    .repeat(end.saturating_sub(start).max(1))
+            );
+
+            if !annotation.label.message().is_empty() {
+                content.push(' ');
+                content.push_str(annotation.label.message());
+            }
+
+            Self::row(output, width, None, &content);
+        }
+    }
+
+    /// Draw the multi-line labels ending on `line`
+    fn render_multiline_ends(
+        output: &mut String,
+        width: usize,
+        line: usize,
+        text: &str,
+        multiline: &[Annotation<'_, '_>],
+    ) {
+        for (index, annotation) in multiline.iter().enumerate() {
+            if annotation.end_line() != line {
+                continue;
+            }
+
+            let mut content = multiline
+                .iter()
+                .enumerate()
+                .map(|(other_index, other)| match other_index.cmp(&index) {
+                    core::cmp::Ordering::Less
+                        if other.start_line() <= line && line < other.end_line() =>
+                    {
+                        '|'
+                    }
+                    core::cmp::Ordering::Less => ' ',
+                    core::cmp::Ordering::Equal => '|',
+                    core::cmp::Ordering::Greater => '_',
+                })
+                .collect::<String>();
+
+            let end_column = annotation.label.span().end().column();
+            let offset = visual_offset(text, end_column.saturating_sub(1).max(1));
+            content.push_str(&"_".repeat(offset + 1));
+            content.push(annotation.marker());
+
+            if !annotation.label.message().is_empty() {
+                content.push(' ');
+                content.push_str(annotation.label.message());
+            }
+
+            Self::row(output, width, None, &content);
+        }
+    }
+
+    /// Write a single row of the snippet, with an optional line number
+    fn row(output: &mut String, width: usize, line: Option<usize>, content: &str) {
+        let row = match line {
+            Some(line) => format!("{line:>width$} | {content}"),
+            None => format!("{:width$} | {content}", ""),
+        };
+
+        let _ = writeln!(output, "{}", row.trim_end());
+    }
+}
+
+/// The displayed width of the text before the 1-based `column`
+fn visual_offset(text: &str, column: usize) -> usize {
+    text.chars()
+        .take(column.saturating_sub(1))
+        .map(|character| if character == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Separates the multi-line gutter from the source code
+fn separator(multiline: &[Annotation<'_, '_>]) -> &'static str {
+    if multiline.is_empty() {
+        ""
+    } else {
+        " "
+    }
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
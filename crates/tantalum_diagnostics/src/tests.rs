@@ -0,0 +1,162 @@
+use tantalum_span::{Location, Span};
+
+use crate::{codes, Diagnostic, Label, Renderer, SourceMap};
+
+/// Build a span over the byte `range` of `source`
+fn span<'file_name>(
+    file_name: &'file_name str,
+    source: &str,
+    range: core::ops::Range<usize>,
+) -> Span<'file_name> {
+    let mut start = Location::new(file_name);
+    for character in source[..range.start].chars() {
+        start.advance(character);
+    }
+
+    let mut end = start;
+    for character in source[range].chars() {
+        end.advance(character);
+    }
+
+    Span::new(start, end)
+}
+
+fn render(sources: &[(&str, &str)], diagnostic: &Diagnostic<'_>) -> String {
+    let mut map = SourceMap::new();
+    for (file_name, source) in sources {
+        map.add(file_name, source);
+    }
+
+    Renderer::new(&map).render(diagnostic)
+}
+
+#[test]
+fn single_line_label() {
+    let source = "fn main(): i32 {\n    return x;\n}\n";
+    let diagnostic = Diagnostic::error(
+        codes::UNKNOWN_VARIABLE,
+        "cannot find variable `x` in this scope",
+        Label::new(span("main.ta", source, 28..29), "not found in this scope"),
+    )
+    .with_help("declare the variable with `let` before using it");
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[test]
+fn secondary_labels() {
+    let source = "fn add(a: i32, b: i32): i32 {\n    return a + b;\n}\n\nfn main(): i32 {\n    return add(1);\n}\n";
+    let diagnostic = Diagnostic::error(
+        codes::ARGUMENT_COUNT_MISMATCH,
+        "this function takes 2 arguments but 1 argument was supplied",
+        Label::new(span("main.ta", source, 79..82), "expected 2 arguments"),
+    )
+    .with_label(Label::new(span("main.ta", source, 83..84), "1 argument"))
+    .with_label(Label::new(
+        span("main.ta", source, 3..6),
+        "function defined here",
+    ))
+    .with_note("variadic functions accept any number of extra arguments");
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[test]
+fn multiline_label_starting_mid_line() {
+    let source = "fn main(): i32 {\n    let a = 1;\n    let b = 2;\n}\n";
+    let diagnostic = Diagnostic::error(
+        codes::UNSUPPORTED,
+        "missing return",
+        Label::new(
+            span("main.ta", source, 15..47),
+            "this block does not return a value",
+        ),
+    );
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[test]
+fn multiline_label_starting_line() {
+    let source = "fn main(): i32 {\n    return 0;\n}\n";
+    let diagnostic = Diagnostic::warning(
+        codes::UNSUPPORTED,
+        "unused function",
+        Label::new(span("main.ta", source, 0..32), "never called"),
+    )
+    .with_label(Label::new(span("main.ta", source, 3..7), "declared here"));
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[test]
+fn long_multiline_label_is_elided() {
+    let source = "/* one\ntwo\nthree\nfour\nfive\nsix\nseven */\n";
+    let diagnostic = Diagnostic::error(
+        codes::UNTERMINATED_BLOCK_COMMENT,
+        "a long comment",
+        Label::new(
+            span("main.ta", source, 0..source.len() - 1),
+            "spans seven lines",
+        ),
+    );
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[test]
+fn labels_in_multiple_files() {
+    let main = "mod util;\n\nfn main(): i32 {\n    return util::f(1);\n}\n";
+    let util = "fn f(): i32 {\n    return 1;\n}\n";
+    let diagnostic = Diagnostic::error(
+        codes::ARGUMENT_COUNT_MISMATCH,
+        "this function takes 0 arguments but 1 argument was supplied",
+        Label::new(span("main.ta", main, 40..47), "expected 0 arguments"),
+    )
+    .with_label(Label::new(
+        span("util.ta", util, 3..4),
+        "function defined here",
+    ));
+
+    insta::assert_snapshot!(render(&[("main.ta", main), ("util.ta", util)], &diagnostic));
+}
+
+#[test]
+fn empty_span_at_end_of_file() {
+    let source = "fn main(): i32 {\n    return 0;";
+    let diagnostic = Diagnostic::error(
+        codes::UNEXPECTED_END_OF_FILE,
+        "unexpected end of file",
+        Label::new(span("main.ta", source, 30..30), "expected `}`"),
+    );
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[test]
+fn tabs_are_expanded() {
+    let source = "fn main(): i32 {\n\treturn x;\n}\n";
+    let diagnostic = Diagnostic::error(
+        codes::UNKNOWN_VARIABLE,
+        "cannot find variable `x` in this scope",
+        Label::new(span("main.ta", source, 25..26), "not found in this scope"),
+    );
+
+    insta::assert_snapshot!(render(&[("main.ta", source)], &diagnostic));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    let source = "fn main(): i32 {\n    return x;\n}\n";
+    let diagnostic = Diagnostic::error(
+        codes::UNKNOWN_VARIABLE,
+        "cannot find variable `x` in this scope",
+        Label::new(span("main.ta", source, 28..29), "not found in this scope"),
+    );
+
+    let mut sources = SourceMap::new();
+    sources.add("main.ta", source);
+
+    insta::assert_snapshot!(crate::json::JsonEmitter::new(&sources).emit(&diagnostic));
+}
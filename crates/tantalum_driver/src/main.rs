@@ -0,0 +1,137 @@
+//! # Tantalum Driver
+//!
+//! The `tantalum` command line compiler. Runs a package, a root source file
+//! along with the files of the modules it declares, through the lexer, parser,
+//! AST lowering, and LLVM code generation and writes out the requested
+//! artifact.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+mod pipeline;
+
+use pipeline::{Emit, ErrorFormat, Output};
+
+#[derive(Debug, Parser)]
+#[command(name = "tantalum", version, about = "The Tantalum compiler")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// How errors and warnings are reported
+    #[arg(long, value_enum, global = true, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compile a source file, writing an object file by default
+    Build(BuildArgs),
+    /// Check a source file for errors without generating any code
+    Check(CheckArgs),
+    /// Emit an intermediate representation of a source file
+    Emit(EmitArgs),
+}
+
+#[derive(Debug, Args)]
+struct CheckArgs {
+    /// The root source file of the package to compile
+    input: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct BuildArgs {
+    /// The root source file of the package to compile
+    input: PathBuf,
+    /// The kind of output to produce
+    #[arg(long, value_enum, default_value_t = Emit::Obj)]
+    emit: Emit,
+    /// The file to write the output to, derived from the input when not given
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// The optimization level to compile with
+    #[arg(short = 'O', long = "opt-level", value_enum, default_value_t = OptLevel::O0)]
+    opt_level: OptLevel,
+}
+
+#[derive(Debug, Args)]
+struct EmitArgs {
+    /// The root source file of the package to compile
+    input: PathBuf,
+    /// The kind of output to produce
+    #[arg(long, value_enum)]
+    emit: Emit,
+    /// The file to write the output to, standard output when not given
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// The optimization level to compile with
+    #[arg(short = 'O', long = "opt-level", value_enum, default_value_t = OptLevel::O0)]
+    opt_level: OptLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OptLevel {
+    #[value(name = "0")]
+    O0,
+    #[value(name = "1")]
+    O1,
+    #[value(name = "2")]
+    O2,
+    #[value(name = "3")]
+    O3,
+}
+
+impl From<OptLevel> for tantalum_codegen_llvm::OptimizationLevel {
+    fn from(level: OptLevel) -> Self {
+        match level {
+            OptLevel::O0 => Self::None,
+            OptLevel::O1 => Self::Less,
+            OptLevel::O2 => Self::Default,
+            OptLevel::O3 => Self::Aggressive,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // Every stage of the compiler is run by the pipeline such that a panic is
+    // reported as an internal compiler error, so it is not printed as well
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let result = match cli.command {
+        Command::Check(args) => pipeline::check(&args.input, cli.error_format),
+        Command::Build(args) => {
+            let output = args.output.unwrap_or_else(|| {
+                args.input
+                    .with_extension(args.emit.default_extension())
+                    .file_name()
+                    .map_or_else(|| PathBuf::from("out"), PathBuf::from)
+            });
+
+            pipeline::emit(
+                &args.input,
+                args.emit,
+                &Output::File(output),
+                args.opt_level.into(),
+                cli.error_format,
+            )
+        }
+        Command::Emit(args) => pipeline::emit(
+            &args.input,
+            args.emit,
+            &args.output.map_or(Output::Stdout, Output::File),
+            args.opt_level.into(),
+            cli.error_format,
+        ),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
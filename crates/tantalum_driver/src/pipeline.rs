@@ -0,0 +1,332 @@
+use core::fmt::Write as _;
+use std::any::Any;
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use tantalum_ast::AST;
+use tantalum_ast_lowering::ASTLoweringContext;
+use tantalum_codegen_llvm::{Context, FileType, LLVMCodegenContext, OptimizationLevel};
+use tantalum_diagnostics::json::JsonEmitter;
+use tantalum_diagnostics::{Diagnostic, Renderer, SourceMap};
+use tantalum_hlir::HLIRPackage;
+use tantalum_lexer::Lexer;
+use tantalum_parser::package::SourceFiles;
+
+/// The kinds of output the driver is able to produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Emit {
+    /// The tokens produced by the lexer
+    Tokens,
+    /// The abstract syntax tree produced by the parser
+    Ast,
+    /// The HLIR package produced by lowering the AST
+    Hlir,
+    /// Textual LLVM IR
+    LlvmIr,
+    /// LLVM bitcode
+    Bc,
+    /// Target assembly
+    Asm,
+    /// A target object file
+    Obj,
+}
+
+impl Emit {
+    #[must_use]
+    pub fn default_extension(self) -> &'static str {
+        match self {
+            Emit::Tokens => "tokens",
+            Emit::Ast => "ast",
+            Emit::Hlir => "hlir",
+            Emit::LlvmIr => "ll",
+            Emit::Bc => "bc",
+            Emit::Asm => "s",
+            Emit::Obj => "o",
+        }
+    }
+}
+
+/// How diagnostics are written to standard error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Human readable messages alongside the source code
+    Human,
+    /// One JSON object per line
+    Json,
+}
+
+/// Where the output of the driver is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    Stdout,
+    File(PathBuf),
+}
+
+impl Output {
+    fn write(&self, bytes: &[u8]) -> Result<(), Error> {
+        match self {
+            Output::Stdout => std::io::stdout()
+                .write_all(bytes)
+                .map_err(|error| Error::Io {
+                    path: PathBuf::from("<stdout>"),
+                    error,
+                }),
+            Output::File(path) => std::fs::write(path, bytes).map_err(|error| Error::Io {
+                path: path.clone(),
+                error,
+            }),
+        }
+    }
+}
+
+/// A failure in one of the stages of the compiler.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the input or writing the output failed
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// Errors were reported in the source file, contains the number of errors
+    Aborted(usize),
+    /// A stage of the compiler panicked
+    Internal {
+        stage: &'static str,
+        message: String,
+    },
+    /// LLVM rejected the generated module or failed to emit it
+    Codegen(String),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Io { path, error } => {
+                write!(f, "error: could not access `{}`: {error}", path.display())
+            }
+            Error::Aborted(1) => write!(f, "error: aborting due to 1 previous error"),
+            Error::Aborted(count) => {
+                write!(f, "error: aborting due to {count} previous errors")
+            }
+            Error::Internal { stage, message } => {
+                write!(
+                    f,
+                    "error: internal compiler error during {stage}: {message}"
+                )
+            }
+            Error::Codegen(message) => write!(f, "error: code generation failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Writes diagnostics to standard error as they are reported, keeping count of
+/// the errors among them.
+struct Emitter<'file_name, 'source> {
+    sources: SourceMap<'file_name, 'source>,
+    format: ErrorFormat,
+    errors: usize,
+}
+
+impl<'a> Emitter<'a, 'a> {
+    fn new(files: &'a SourceFiles, format: ErrorFormat) -> Self {
+        let mut sources = SourceMap::new();
+        for file in files.files() {
+            sources.add(file.name(), file.source());
+        }
+
+        Self {
+            sources,
+            format,
+            errors: 0,
+        }
+    }
+}
+
+impl Emitter<'_, '_> {
+    fn emit(&mut self, diagnostic: &Diagnostic<'_>) {
+        if diagnostic.is_error() {
+            self.errors += 1;
+        }
+
+        match self.format {
+            ErrorFormat::Human => eprintln!("{}", Renderer::new(&self.sources).render(diagnostic)),
+            ErrorFormat::Json => eprintln!("{}", JsonEmitter::new(&self.sources).emit(diagnostic)),
+        }
+    }
+
+    /// Stop compiling if any errors have been reported so far.
+    fn abort_if_errors(&self) -> Result<(), Error> {
+        if self.errors == 0 {
+            Ok(())
+        } else {
+            Err(Error::Aborted(self.errors))
+        }
+    }
+}
+
+/// Run the front end of the compiler over the package whose root file is
+/// `input`, reporting every error found.
+///
+/// # Errors
+///
+/// Returns an error if the root file cannot be read, or if the package cannot
+/// be parsed or lowered.
+pub fn check(input: &Path, format: ErrorFormat) -> Result<(), Error> {
+    let files = load(input)?;
+    let mut emitter = Emitter::new(&files, format);
+
+    let ast = parse(&files, &mut emitter)?;
+    let _package = lower(&ast, &mut emitter)?;
+
+    Ok(())
+}
+
+/// Run the compiler over the package whose root file is `input` up to the
+/// stage needed to produce `emit`.
+///
+/// # Errors
+///
+/// Returns an error if any stage of the compiler fails or if the output
+/// cannot be written.
+pub fn emit(
+    input: &Path,
+    emit: Emit,
+    output: &Output,
+    level: OptimizationLevel,
+    format: ErrorFormat,
+) -> Result<(), Error> {
+    let files = load(input)?;
+    let mut emitter = Emitter::new(&files, format);
+
+    // Only the tokens of the root file are emitted
+    if emit == Emit::Tokens {
+        let root = &files.files()[0];
+        let tokens =
+            Lexer::new(root.name(), root.source()).fold(String::new(), |mut tokens, token| {
+                let _ = writeln!(
+                    tokens,
+                    "{}:{} {:?} {:?}",
+                    token.line(),
+                    token.column(),
+                    token.data().kind(),
+                    token.data().lexeme()
+                );
+                tokens
+            });
+
+        return output.write(tokens.as_bytes());
+    }
+
+    let ast = parse(&files, &mut emitter)?;
+
+    if emit == Emit::Ast {
+        emitter.abort_if_errors()?;
+        return output.write(format!("{ast:#?}\n").as_bytes());
+    }
+
+    let package = lower(&ast, &mut emitter)?;
+
+    if emit == Emit::Hlir {
+        return output.write(format!("{package:#?}\n").as_bytes());
+    }
+
+    let context = Context::create();
+    let codegen = generate(&context, &package, level)?;
+
+    let bytes = match emit {
+        Emit::LlvmIr => codegen.emit_ll().into_bytes(),
+        Emit::Bc => codegen.emit_bc(),
+        Emit::Asm => codegen
+            .write_to_memory(FileType::Assembly, level)
+            .map_err(Error::Codegen)?,
+        Emit::Obj => codegen
+            .write_to_memory(FileType::Object, level)
+            .map_err(Error::Codegen)?,
+        Emit::Tokens | Emit::Ast | Emit::Hlir => unreachable!("handled by earlier stages"),
+    };
+
+    output.write(&bytes)
+}
+
+/// Read the root file at `input` along with the files of every module of the
+/// package.
+fn load(input: &Path) -> Result<SourceFiles, Error> {
+    SourceFiles::load(input).map_err(|error| Error::Io {
+        path: input.to_path_buf(),
+        error,
+    })
+}
+
+/// Parse every file of the package, reporting every syntax error and missing
+/// module file found. The errors do not stop compilation here, so lowering can
+/// still report errors in the parts of the package that did parse.
+fn parse<'a>(files: &'a SourceFiles, emitter: &mut Emitter<'_, '_>) -> Result<AST<'a, 'a>, Error> {
+    let (ast, diagnostics) = guard("parsing", || files.parse())?;
+
+    for diagnostic in &diagnostics {
+        emitter.emit(diagnostic);
+    }
+
+    Ok(ast)
+}
+
+fn lower<'file_name>(
+    ast: &AST<'file_name, '_>,
+    emitter: &mut Emitter<'_, '_>,
+) -> Result<HLIRPackage<'file_name>, Error> {
+    let package = guard("lowering", || {
+        let mut context = ASTLoweringContext::new();
+        context.lower(ast);
+        context.finish()
+    })?;
+
+    for diagnostic in package.diagnostics() {
+        emitter.emit(diagnostic);
+    }
+    emitter.abort_if_errors()?;
+
+    Ok(package)
+}
+
+fn generate<'ctx>(
+    context: &'ctx Context,
+    package: &HLIRPackage<'_>,
+    level: OptimizationLevel,
+) -> Result<LLVMCodegenContext<'ctx>, Error> {
+    let codegen = guard("code generation", || {
+        let mut codegen = LLVMCodegenContext::new(context);
+        codegen.build(package);
+        codegen
+    })?;
+
+    codegen.verify().map_err(Error::Codegen)?;
+    codegen.optimize(level).map_err(Error::Codegen)?;
+
+    Ok(codegen)
+}
+
+/// Run a stage of the compiler, converting any panic into an internal error
+/// instead of aborting the driver.
+///
+/// The panic is only reported through the returned error, `main` installs a
+/// panic hook that prints nothing.
+fn guard<T>(stage: &'static str, f: impl FnOnce() -> T) -> Result<T, Error> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| Error::Internal {
+        stage,
+        message: panic_message(payload.as_ref()),
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
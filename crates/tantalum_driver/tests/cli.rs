@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const VALID: &str = r#"
+extern fn printf(format: *const u8, ...): i32;
+
+fn main(): i32 {
+    printf("%d\n".&, 42);
+    return 0;
+}
+"#;
+
+const TYPE_ERROR: &str = r"
+fn main(): i32 {
+    let x: i32 = true;
+    return x;
+}
+";
+
+/// A directory of its own for each test, removed once the test is done
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("tantalum-cli-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).expect("failed to create test directory");
+        Self(path)
+    }
+
+    /// Write `source` to `name` in the directory and get its path
+    fn file(&self, name: &str, source: &str) -> PathBuf {
+        let path = self.0.join(name);
+        std::fs::write(&path, source).expect("failed to write source file");
+        path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run the compiler in `dir` with `args`
+fn tantalum(dir: &TestDir, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_tantalum"))
+        .args(args)
+        .current_dir(&dir.0)
+        .output()
+        .expect("failed to run the compiler")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn check_valid_file() {
+    let dir = TestDir::new("check_valid_file");
+    dir.file("main.ta", VALID);
+
+    let output = tantalum(&dir, &["check", "main.ta"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn check_type_error() {
+    let dir = TestDir::new("check_type_error");
+    dir.file("main.ta", TYPE_ERROR);
+
+    let output = tantalum(&dir, &["check", "main.ta"]);
+    let stderr = stderr(&output);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("error[E"), "{stderr}");
+    assert!(stderr.contains("main.ta:3:"), "{stderr}");
+    assert!(
+        stderr.ends_with("error: aborting due to 1 previous error\n"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn check_missing_file() {
+    let dir = TestDir::new("check_missing_file");
+
+    let output = tantalum(&dir, &["check", "missing.ta"]);
+    let stderr = stderr(&output);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr.starts_with("error: could not access `missing.ta`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn json_error_format() {
+    let dir = TestDir::new("json_error_format");
+    dir.file("main.ta", TYPE_ERROR);
+
+    let output = tantalum(&dir, &["check", "main.ta", "--error-format", "json"]);
+    let stderr = stderr(&output);
+    let (aborted, diagnostics) = stderr
+        .trim_end()
+        .lines()
+        .collect::<Vec<_>>()
+        .split_last()
+        .map(|(last, rest)| (*last, rest.to_vec()))
+        .expect("expected errors to be reported");
+
+    assert!(!output.status.success());
+    assert_eq!(aborted, "error: aborting due to 1 previous error");
+    assert_eq!(diagnostics.len(), 1, "{stderr}");
+
+    let diagnostic: serde_json::Value =
+        serde_json::from_str(diagnostics[0]).expect("expected a diagnostic as JSON");
+    assert_eq!(diagnostic["severity"], "error");
+    assert!(diagnostic["code"]
+        .as_str()
+        .is_some_and(|code| code.starts_with('E')));
+    assert_eq!(diagnostic["primary"]["span"]["start"]["line"], 3);
+    assert!(diagnostic["rendered"].is_string());
+}
+
+#[test]
+fn emit_to_stdout() {
+    let dir = TestDir::new("emit_to_stdout");
+    dir.file("main.ta", VALID);
+
+    for (emit, expected) in [
+        ("tokens", "Identifier"),
+        ("ast", "main"),
+        ("hlir", "main"),
+        ("llvm-ir", "define i32 @main()"),
+    ] {
+        let output = tantalum(&dir, &["emit", "main.ta", "--emit", emit]);
+
+        assert!(output.status.success(), "{emit}: {}", stderr(&output));
+        assert!(
+            stdout(&output).contains(expected),
+            "{emit}: {}",
+            stdout(&output)
+        );
+    }
+}
+
+#[test]
+fn emit_with_type_error() {
+    let dir = TestDir::new("emit_with_type_error");
+    dir.file("main.ta", TYPE_ERROR);
+
+    let output = tantalum(&dir, &["emit", "main.ta", "--emit", "llvm-ir"]);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(stderr(&output).contains("error[E"));
+}
+
+#[test]
+fn emit_to_output_file() {
+    let dir = TestDir::new("emit_to_output_file");
+    dir.file("main.ta", VALID);
+
+    let output = tantalum(
+        &dir,
+        &["emit", "main.ta", "--emit", "llvm-ir", "-o", "out.ll"],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(output.stdout.is_empty());
+    let ir = std::fs::read_to_string(dir.0.join("out.ll")).expect("expected output file");
+    assert!(ir.contains("define i32 @main()"), "{ir}");
+}
+
+#[test]
+fn build_default_output() {
+    let dir = TestDir::new("build_default_output");
+    dir.file("main.ta", VALID);
+
+    let output = tantalum(&dir, &["build", "main.ta"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let object = std::fs::read(dir.0.join("main.o")).expect("expected object file");
+    assert!(!object.is_empty());
+}
+
+#[test]
+fn build_with_emit_and_output() {
+    let dir = TestDir::new("build_with_emit_and_output");
+    dir.file("main.ta", VALID);
+
+    let output = tantalum(
+        &dir,
+        &["build", "main.ta", "--emit", "asm", "-o", "main.asm"],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.0.join("main.asm").exists());
+    assert!(!dir.0.join("main.o").exists());
+}
@@ -1,6 +1,7 @@
 use crate::functions::FunctionId;
 use crate::inference::InferenceId;
 use crate::literals::Literal;
+use crate::traits::TraitId;
 use crate::variables::VariableId;
 use crate::HLIRPackage;
 
@@ -14,9 +15,22 @@ pub enum Expression {
     Variable(VariableId),
     Literal(Literal),
     FunctionCall(FunctionCall),
+    DynamicCall(DynamicCall),
     UnaryOperation(UnaryOperation),
     BinaryOperation(BinaryOperation),
     TypeCast(TypeCast),
+    Index(Index),
+    Assign(Assign),
+    StructLiteral(StructLiteral),
+    ArrayLiteral(ArrayLiteral),
+    ArrayRepeat(ArrayRepeat),
+    FieldAccess(FieldAccess),
+    EnumValue(EnumValue),
+    UnionValue(UnionValue),
+    VariantCheck(VariantCheck),
+    /// An expression that could not be lowered because of an error that has
+    /// already been reported.
+    Error(InferenceId),
 }
 
 impl Expression {
@@ -34,9 +48,20 @@ impl Expression {
                 .expect("expected variable to exist"),
             Expression::Literal(literal) => literal.ty(),
             Expression::FunctionCall(function_call) => function_call.result,
+            Expression::DynamicCall(dynamic_call) => dynamic_call.result,
             Expression::UnaryOperation(unary_operation) => unary_operation.result,
             Expression::BinaryOperation(binary_operation) => binary_operation.result,
             Expression::TypeCast(type_cast) => type_cast.target_type,
+            Expression::Index(index) => index.result,
+            Expression::Assign(assign) => assign.result,
+            Expression::StructLiteral(struct_literal) => struct_literal.ty,
+            Expression::ArrayLiteral(array_literal) => array_literal.result,
+            Expression::ArrayRepeat(array_repeat) => array_repeat.result,
+            Expression::FieldAccess(field_access) => field_access.result,
+            Expression::EnumValue(enum_value) => enum_value.ty,
+            Expression::UnionValue(union_value) => union_value.ty,
+            Expression::VariantCheck(variant_check) => variant_check.result,
+            Expression::Error(ty) => *ty,
         }
     }
 }
@@ -47,9 +72,20 @@ impl core::fmt::Debug for Expression {
             Expression::Variable(variable_id) => variable_id.fmt(f),
             Expression::Literal(literal) => literal.fmt(f),
             Expression::FunctionCall(function_call) => function_call.fmt(f),
+            Expression::DynamicCall(dynamic_call) => dynamic_call.fmt(f),
             Expression::UnaryOperation(unary_operation) => unary_operation.fmt(f),
             Expression::BinaryOperation(binary_operation) => binary_operation.fmt(f),
             Expression::TypeCast(type_cast) => type_cast.fmt(f),
+            Expression::Index(index) => index.fmt(f),
+            Expression::Assign(assign) => assign.fmt(f),
+            Expression::StructLiteral(struct_literal) => struct_literal.fmt(f),
+            Expression::ArrayLiteral(array_literal) => array_literal.fmt(f),
+            Expression::ArrayRepeat(array_repeat) => array_repeat.fmt(f),
+            Expression::FieldAccess(field_access) => field_access.fmt(f),
+            Expression::EnumValue(enum_value) => enum_value.fmt(f),
+            Expression::UnionValue(union_value) => union_value.fmt(f),
+            Expression::VariantCheck(variant_check) => variant_check.fmt(f),
+            Expression::Error(ty) => write!(f, "Error({ty:?})"),
         }
     }
 }
@@ -58,6 +94,24 @@ impl core::fmt::Debug for Expression {
 pub struct FunctionCall {
     pub function: FunctionId,
     pub arguments: Vec<Expression>,
+    /// The types inferred for the type parameters of a generic function
+    pub type_arguments: Vec<InferenceId>,
+    pub result: InferenceId,
+}
+
+/// A call of a method of a trait through a trait object, which calls the
+/// function found in the table of methods the object points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicCall {
+    pub trait_id: TraitId,
+    /// The generic function declaring the method in the trait
+    pub function: FunctionId,
+    /// The position of the method in the table of methods
+    pub index: usize,
+    /// The trait object, whose pointer to the value is passed as the first
+    /// argument of the method
+    pub object: Box<Expression>,
+    pub arguments: Vec<Expression>,
     pub result: InferenceId,
 }
 
@@ -127,3 +181,205 @@ pub struct TypeCast {
     pub expression: Box<Expression>,
     pub target_type: InferenceId,
 }
+
+/// An element of a pointer or an array
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    pub base: Box<Expression>,
+    pub index: Box<Expression>,
+    pub result: InferenceId,
+}
+
+impl From<Index> for Expression {
+    fn from(index: Index) -> Self {
+        Self::Index(index)
+    }
+}
+
+/// A location in memory that can be assigned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Place {
+    Variable(VariableId),
+    /// The value a pointer points to
+    Deref {
+        pointer: Box<Expression>,
+        ty: InferenceId,
+    },
+    /// An element of a pointer or an array
+    Index(Index),
+    /// A field of a struct stored in another place
+    Field {
+        base: Box<Place>,
+        field: usize,
+        ty: InferenceId,
+    },
+}
+
+impl Place {
+    /// Get the type of the value stored in the place
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variable does not exist
+    #[must_use]
+    pub fn ty(&self, package: &HLIRPackage) -> InferenceId {
+        match self {
+            Place::Variable(variable_id) => package
+                .variables
+                .get_type(*variable_id)
+                .expect("expected variable to exist"),
+            Place::Deref { ty, .. } | Place::Field { ty, .. } => *ty,
+            Place::Index(index) => index.result,
+        }
+    }
+}
+
+impl TryFrom<Expression> for Place {
+    type Error = Expression;
+
+    /// Get the place an expression refers to, giving back the expression if it
+    /// does not refer to one.
+    fn try_from(expression: Expression) -> Result<Self, Self::Error> {
+        match expression {
+            Expression::Variable(variable) => Ok(Place::Variable(variable)),
+            Expression::UnaryOperation(UnaryOperation {
+                operator: UnaryOperator::Deref,
+                operand,
+                result,
+            }) => Ok(Place::Deref {
+                pointer: operand,
+                ty: result,
+            }),
+            Expression::Index(index) => Ok(Place::Index(index)),
+            Expression::FieldAccess(FieldAccess {
+                base,
+                field,
+                result,
+            }) => match Place::try_from(*base) {
+                Ok(base) => Ok(Place::Field {
+                    base: Box::new(base),
+                    field,
+                    ty: result,
+                }),
+                Err(base) => Err(Expression::FieldAccess(FieldAccess {
+                    base: Box::new(base),
+                    field,
+                    result,
+                })),
+            },
+            expression => Err(expression),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assign {
+    pub place: Place,
+    pub value: Box<Expression>,
+    /// The type of the assignment itself, which is always `void`
+    pub result: InferenceId,
+}
+
+impl From<Assign> for Expression {
+    fn from(assign: Assign) -> Self {
+        Self::Assign(assign)
+    }
+}
+
+/// A value of a struct, with the values of its fields in the order they were
+/// declared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructLiteral {
+    pub fields: Vec<Expression>,
+    pub ty: InferenceId,
+}
+
+impl From<StructLiteral> for Expression {
+    fn from(struct_literal: StructLiteral) -> Self {
+        Self::StructLiteral(struct_literal)
+    }
+}
+
+/// An array of the values of its elements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayLiteral {
+    pub elements: Vec<Expression>,
+    pub result: InferenceId,
+}
+
+impl From<ArrayLiteral> for Expression {
+    fn from(array_literal: ArrayLiteral) -> Self {
+        Self::ArrayLiteral(array_literal)
+    }
+}
+
+/// An array of `length` copies of a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayRepeat {
+    pub value: Box<Expression>,
+    pub length: usize,
+    pub result: InferenceId,
+}
+
+impl From<ArrayRepeat> for Expression {
+    fn from(array_repeat: ArrayRepeat) -> Self {
+        Self::ArrayRepeat(array_repeat)
+    }
+}
+
+/// A field of a struct, `field` is its position in the struct
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldAccess {
+    pub base: Box<Expression>,
+    pub field: usize,
+    pub result: InferenceId,
+}
+
+impl From<FieldAccess> for Expression {
+    fn from(field_access: FieldAccess) -> Self {
+        Self::FieldAccess(field_access)
+    }
+}
+
+/// A variant of an enum, `variant` is its position in the enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumValue {
+    pub variant: usize,
+    pub ty: InferenceId,
+}
+
+impl From<EnumValue> for Expression {
+    fn from(enum_value: EnumValue) -> Self {
+        Self::EnumValue(enum_value)
+    }
+}
+
+/// A variant of a tagged union, `variant` is its position in the union and
+/// `fields` are the values it carries in the order they were declared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionValue {
+    pub variant: usize,
+    pub fields: Vec<Expression>,
+    pub ty: InferenceId,
+}
+
+impl From<UnionValue> for Expression {
+    fn from(union_value: UnionValue) -> Self {
+        Self::UnionValue(union_value)
+    }
+}
+
+/// Whether `value`, an enum or a tagged union, holds the variant at position
+/// `variant`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantCheck {
+    pub value: Box<Expression>,
+    pub variant: usize,
+    pub result: InferenceId,
+}
+
+impl From<VariantCheck> for Expression {
+    fn from(variant_check: VariantCheck) -> Self {
+        Self::VariantCheck(variant_check)
+    }
+}
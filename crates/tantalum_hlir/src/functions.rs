@@ -1,5 +1,6 @@
 use crate::items::{Function, FunctionPrototype};
 use crate::path::Path;
+use crate::types::TypeId;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -19,6 +20,8 @@ pub struct Functions {
     known: HashMap<Path, FunctionId>,
     function_prototypes: HashMap<FunctionId, Rc<FunctionPrototype>>,
     function_impls: HashMap<FunctionId, Function>,
+    /// The instantiations of generic functions by their type arguments
+    instances: HashMap<(FunctionId, Vec<TypeId>), FunctionId>,
 }
 
 impl Functions {
@@ -29,19 +32,74 @@ impl Functions {
             known: HashMap::new(),
             function_prototypes: HashMap::new(),
             function_impls: HashMap::new(),
+            instances: HashMap::new(),
         }
     }
 
+    /// Get the prototypes of every function that is not generic, which
+    /// includes the instantiations of generic functions, in the order they
+    /// were declared.
     pub fn prototypes(&self) -> impl Iterator<Item = (FunctionId, &FunctionPrototype)> {
-        self.function_prototypes
+        let mut prototypes: Vec<_> = self
+            .function_prototypes
             .iter()
+            .filter(|(_, prototype)| !prototype.is_generic())
             .map(|(id, prototype)| (*id, prototype.as_ref()))
+            .collect();
+        prototypes.sort_by_key(|(id, _)| *id);
+
+        prototypes.into_iter()
     }
 
+    /// Get the implementations of every function that is not generic, which
+    /// includes the instantiations of generic functions, in the order they
+    /// were declared.
     pub fn impls(&self) -> impl Iterator<Item = (FunctionId, &Function)> {
-        self.function_impls
+        let mut impls: Vec<_> = self
+            .function_impls
             .iter()
+            .filter(|(_, function)| !function.prototype.is_generic())
             .map(|(id, function)| (*id, function))
+            .collect();
+        impls.sort_by_key(|(id, _)| *id);
+
+        impls.into_iter()
+    }
+
+    #[must_use]
+    pub fn get_impl(&self, id: FunctionId) -> Option<&Function> {
+        self.function_impls.get(&id)
+    }
+
+    /// Get the instantiation of the generic function `id` with `arguments`.
+    #[must_use]
+    pub fn get_instance(&self, id: FunctionId, arguments: &[TypeId]) -> Option<FunctionId> {
+        self.instances.get(&(id, arguments.to_vec())).copied()
+    }
+
+    /// Use `instance` as the instantiation of the generic function `id` with
+    /// `arguments`, which is already implemented.
+    pub fn insert_instance(
+        &mut self,
+        id: FunctionId,
+        arguments: Vec<TypeId>,
+        instance: FunctionId,
+    ) {
+        self.instances.insert((id, arguments), instance);
+    }
+
+    /// Create the instantiation of the generic function `id` with `arguments`,
+    /// whose implementation is inserted separately.
+    pub fn create_instance(
+        &mut self,
+        id: FunctionId,
+        arguments: Vec<TypeId>,
+        path: Path,
+        prototype: FunctionPrototype,
+    ) -> FunctionId {
+        let instance = self.create_function(path, prototype);
+        self.instances.insert((id, arguments), instance);
+        instance
     }
 
     pub fn next_id(&mut self) -> FunctionId {
@@ -111,6 +169,11 @@ impl core::fmt::Debug for Functions {
                 function_impls.sort_by_key(|(id, _)| **id);
                 f.debug_map().entries(function_impls).finish()
             })
+            .field_with("instances", |f| {
+                let mut instances: Vec<_> = self.instances.iter().collect();
+                instances.sort_by_key(|(_, id)| **id);
+                f.debug_map().entries(instances).finish()
+            })
             .finish()
     }
 }
@@ -0,0 +1,125 @@
+use crate::expressions::{Expression, FunctionCall, Place};
+use crate::inference::InferenceId;
+use crate::statements::Statement;
+use crate::types::TypeId;
+
+/// The number of nested instantiations after which a generic function is
+/// assumed to instantiate itself forever
+pub(crate) const RECURSION_LIMIT: usize = 64;
+
+/// A call of a generic function, or a use of a value of a generic type, whose
+/// values are checked against the generic definition once the type arguments
+/// are inferred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GenericCall {
+    pub type_arguments: Vec<InferenceId>,
+    /// The arguments and result of the call, or the value and its fields,
+    /// along with their types in the definition, which contain type parameters
+    pub values: Vec<(TypeId, InferenceId)>,
+}
+
+/// Find every function call in `statement`.
+pub(crate) fn collect_calls<'a>(statement: &'a Statement, calls: &mut Vec<&'a FunctionCall>) {
+    match statement {
+        Statement::Block(block) => {
+            for statement in &block.statements {
+                collect_calls(statement, calls);
+            }
+        }
+        Statement::Let(let_) => collect_expression_calls(&let_.value, calls),
+        Statement::If(if_) => {
+            collect_expression_calls(&if_.condition, calls);
+            collect_calls(&if_.then_branch, calls);
+            if let Some(else_branch) = &if_.else_branch {
+                collect_calls(else_branch, calls);
+            }
+        }
+        Statement::While(while_) => {
+            collect_expression_calls(&while_.condition, calls);
+            collect_calls(&while_.body, calls);
+        }
+        Statement::For(for_) => {
+            collect_expression_calls(&for_.condition, calls);
+            collect_calls(&for_.update, calls);
+            collect_calls(&for_.body, calls);
+        }
+        Statement::Match(match_) => {
+            collect_expression_calls(&match_.scrutinee, calls);
+            for arm in &match_.arms {
+                collect_calls(&arm.body, calls);
+            }
+        }
+        Statement::Return(return_) => {
+            if let Some(value) = &return_.value {
+                collect_expression_calls(value, calls);
+            }
+        }
+        Statement::Expression(expression) => collect_expression_calls(expression, calls),
+        Statement::Break | Statement::Continue => {}
+    }
+}
+
+fn collect_expression_calls<'a>(expression: &'a Expression, calls: &mut Vec<&'a FunctionCall>) {
+    match expression {
+        Expression::FunctionCall(call) => {
+            calls.push(call);
+            for argument in &call.arguments {
+                collect_expression_calls(argument, calls);
+            }
+        }
+        Expression::DynamicCall(call) => {
+            collect_expression_calls(&call.object, calls);
+            for argument in &call.arguments {
+                collect_expression_calls(argument, calls);
+            }
+        }
+        Expression::UnaryOperation(unary) => collect_expression_calls(&unary.operand, calls),
+        Expression::BinaryOperation(binary) => {
+            collect_expression_calls(&binary.left, calls);
+            collect_expression_calls(&binary.right, calls);
+        }
+        Expression::TypeCast(cast) => collect_expression_calls(&cast.expression, calls),
+        Expression::Index(index) => {
+            collect_expression_calls(&index.base, calls);
+            collect_expression_calls(&index.index, calls);
+        }
+        Expression::Assign(assign) => {
+            collect_place_calls(&assign.place, calls);
+            collect_expression_calls(&assign.value, calls);
+        }
+        Expression::StructLiteral(literal) => {
+            for field in &literal.fields {
+                collect_expression_calls(field, calls);
+            }
+        }
+        Expression::ArrayLiteral(literal) => {
+            for element in &literal.elements {
+                collect_expression_calls(element, calls);
+            }
+        }
+        Expression::ArrayRepeat(repeat) => collect_expression_calls(&repeat.value, calls),
+        Expression::UnionValue(value) => {
+            for field in &value.fields {
+                collect_expression_calls(field, calls);
+            }
+        }
+        Expression::FieldAccess(access) => collect_expression_calls(&access.base, calls),
+        Expression::VariantCheck(check) => collect_expression_calls(&check.value, calls),
+        Expression::Variable(_)
+        | Expression::Literal(_)
+        | Expression::EnumValue(_)
+        | Expression::Error(_) => {}
+    }
+}
+
+fn collect_place_calls<'a>(place: &'a Place, calls: &mut Vec<&'a FunctionCall>) {
+    match place {
+        Place::Variable(_) => {}
+        Place::Deref { pointer, .. } => collect_expression_calls(pointer, calls),
+        Place::Index(index) => {
+            collect_expression_calls(&index.base, calls);
+            collect_expression_calls(&index.index, calls);
+        }
+        Place::Field { base, .. } => collect_place_calls(base, calls),
+    }
+}
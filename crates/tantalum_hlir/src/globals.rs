@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use crate::expressions::BinaryOperator;
+use crate::inference::InferenceId;
+use crate::literals::{Literal, LiteralValue};
+use crate::path::Path;
+use crate::types::{PrimitiveType, TypeId};
+use crate::variables::VariableId;
+
+/// How a variable declared at the level of a module is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GlobalKind {
+    /// A `const`, whose value is copied wherever it is used.
+    Constant,
+    /// A `static`, stored once for the whole program.
+    Static,
+    /// An `extern static`, defined outside of the package.
+    ExternalStatic,
+}
+
+impl core::fmt::Display for GlobalKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GlobalKind::Constant => write!(f, "constant"),
+            GlobalKind::Static | GlobalKind::ExternalStatic => write!(f, "static"),
+        }
+    }
+}
+
+/// A constant or static in the HLIR.
+///
+/// Globals are variables, so they are used through their [`VariableId`] like
+/// any other variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Global {
+    pub kind: GlobalKind,
+    /// The path of the global, e.g. `config::LIMIT`.
+    pub path: Path,
+    /// The name of the symbol the global is stored at, which is the name it
+    /// is declared with for an external static.
+    pub symbol: String,
+    pub ty: TypeId,
+    /// The value computed at compile time, which is `None` for an external
+    /// static or a global whose value could not be computed.
+    pub value: Option<Constant>,
+}
+
+impl Global {
+    /// The name the global is declared with, to name it in diagnostics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path of the global is empty.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self
+            .path
+            .segments()
+            .last()
+            .expect("expected a global to have a name")
+            .name
+    }
+}
+
+/// The value of a global, computed at compile time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constant {
+    Literal(Literal),
+    /// An array of type `ty` with the values of its elements.
+    Array {
+        elements: Vec<Constant>,
+        ty: InferenceId,
+    },
+    /// A struct of type `ty` with the values of its fields, in the order they
+    /// are declared in.
+    Struct {
+        fields: Vec<Constant>,
+        ty: InferenceId,
+    },
+}
+
+/// A value computed at compile time, while evaluating the value of a global.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConstantValue {
+    Integer(i128),
+    Float(f64),
+    Boolean(bool),
+    Character(String),
+    String(String),
+    /// The values of the elements of an array, with their types.
+    Array(Vec<(ConstantValue, InferenceId)>),
+    /// The values of the fields of a struct, with their types.
+    Struct(Vec<(ConstantValue, InferenceId)>),
+}
+
+impl ConstantValue {
+    /// Get the constant of type `ty` with this value.
+    pub fn into_constant(self, ty: InferenceId) -> Constant {
+        let into_constants = |values: Vec<(ConstantValue, InferenceId)>| {
+            values
+                .into_iter()
+                .map(|(value, ty)| value.into_constant(ty))
+                .collect()
+        };
+
+        let value = match self {
+            ConstantValue::Integer(value) => LiteralValue::Integer {
+                value: value.to_string(),
+                radix: 10,
+            },
+            ConstantValue::Float(value) => LiteralValue::Float {
+                value: format!("{value:?}"),
+            },
+            ConstantValue::Boolean(value) => LiteralValue::Boolean { value },
+            ConstantValue::Character(value) => LiteralValue::Character { value },
+            ConstantValue::String(value) => LiteralValue::String { value },
+            ConstantValue::Array(elements) => {
+                return Constant::Array {
+                    elements: into_constants(elements),
+                    ty,
+                }
+            }
+            ConstantValue::Struct(fields) => {
+                return Constant::Struct {
+                    fields: into_constants(fields),
+                    ty,
+                }
+            }
+        };
+
+        Constant::Literal(Literal::new(value, ty))
+    }
+}
+
+/// The reasons the value of a global cannot be computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum EvaluationError {
+    /// The value is not made only of literals, constants, operators and
+    /// array and struct literals.
+    NotConstant,
+    /// An operation on integers gave a value that does not fit in its type.
+    Overflow(PrimitiveType),
+    DivisionByZero,
+    /// The constants, each of which refers to the next one, the last one
+    /// referring to the first.
+    Cycle(Vec<VariableId>),
+    /// The value uses another global whose problem has already been reported.
+    Reported,
+}
+
+/// The state of the evaluation of the values of the globals.
+#[derive(Debug, Default)]
+pub(crate) struct Evaluation {
+    /// The values computed so far, which are `None` for the globals whose
+    /// value could not be computed
+    pub values: HashMap<VariableId, Option<ConstantValue>>,
+    /// The globals being evaluated, each of which uses the next one
+    pub stack: Vec<VariableId>,
+}
+
+/// Get the result of comparing two values with `operator`, given how they are
+/// ordered, or `None` if `operator` is not a comparison.
+pub(crate) fn compare(
+    operator: &BinaryOperator,
+    ordering: Option<core::cmp::Ordering>,
+) -> Option<bool> {
+    use core::cmp::Ordering;
+
+    Some(match operator {
+        BinaryOperator::Equals => ordering == Some(Ordering::Equal),
+        BinaryOperator::NotEquals => ordering != Some(Ordering::Equal),
+        BinaryOperator::LessThan => ordering == Some(Ordering::Less),
+        BinaryOperator::LessThanOrEqual => {
+            matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+        }
+        BinaryOperator::GreaterThan => ordering == Some(Ordering::Greater),
+        BinaryOperator::GreaterThanOrEqual => {
+            matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+        }
+        _ => return None,
+    })
+}
+
+/// Get `value` as an integer of type `primitive` the way a cast does, keeping
+/// only as many of its lowest bits as the type has.
+pub(crate) fn wrap_integer(value: i128, primitive: PrimitiveType) -> i128 {
+    let Some(range) = primitive.integer_range() else {
+        return value;
+    };
+
+    let modulus = range.end() - range.start() + 1;
+    let value = value.rem_euclid(modulus);
+
+    if value > *range.end() {
+        value - modulus
+    } else {
+        value
+    }
+}
@@ -1,6 +1,12 @@
 use crate::types::{PrimitiveType, Type, Types};
-use crate::{traits::TraitId, types::TypeId, Path, PathSegment};
-use std::collections::HashMap;
+use crate::{
+    traits::{TraitId, Traits},
+    types::TypeId,
+    Path, PathSegment,
+};
+use std::collections::{HashMap, HashSet};
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_span::Span;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -28,10 +34,28 @@ pub enum TypeConstraint {
     /// The type implements the given trait.
     Implements(TraitId),
 
+    /// The type is a pointer to the given type.
     DerefTo(InferenceId),
+    /// The type can be indexed, giving elements of the given type.
+    IndexTo(InferenceId),
+    /// The type is a signed or unsigned integer.
+    Integer,
+    /// The type is an integer or a floating point number.
+    Numeric,
+    /// Values of the type can be compared with each other.
+    Comparable,
+    /// The type is a reference to a value of the given type.
     RefTo(InferenceId),
+    /// A value of the type is referenced by a value of the given type.
+    ReferencedBy(InferenceId),
+    /// A value of the type is cast to the given type.
+    CastTo(TypeId),
+    /// The type is the type of a value, so it is not `void`.
+    Value,
 
-    FromIntegerLiteral,
+    /// The type of an integer literal with the given value, which is `None`
+    /// if the value does not even fit in an `i128`.
+    FromIntegerLiteral(Option<i128>),
     FromFloatLiteral,
 }
 
@@ -39,32 +63,374 @@ impl TypeConstraint {
     #[must_use]
     pub fn to_display(&self, types: &Types) -> String {
         match self {
-            TypeConstraint::Type(ty) => types.to_display(*ty).to_string(),
+            TypeConstraint::Type(ty) => types.to_display(*ty),
             TypeConstraint::ConvertedFrom(id) => format!("ConvertedFrom({id})"),
             TypeConstraint::ConvertibleTo(id) => format!("ConvertibleTo({id})"),
             TypeConstraint::Implements(trait_id) => format!("Implements({trait_id:?})"),
             TypeConstraint::DerefTo(id) => format!("DerefTo({id})"),
+            TypeConstraint::IndexTo(id) => format!("IndexTo({id})"),
+            TypeConstraint::Integer => "Integer".to_string(),
+            TypeConstraint::Value => "Value".to_string(),
+            TypeConstraint::Numeric => "Numeric".to_string(),
+            TypeConstraint::Comparable => "Comparable".to_string(),
             TypeConstraint::RefTo(id) => format!("RefTo({id})"),
-            TypeConstraint::FromIntegerLiteral => "FromIntegerLiteral".to_string(),
+            TypeConstraint::ReferencedBy(id) => format!("ReferencedBy({id})"),
+            TypeConstraint::CastTo(ty) => format!("CastTo({})", types.to_display(*ty)),
+            TypeConstraint::FromIntegerLiteral(_) => "FromIntegerLiteral".to_string(),
             TypeConstraint::FromFloatLiteral => "FromFloatLiteral".to_string(),
         }
     }
 }
 
+/// An inference variable along with the span of the source code it was
+/// created for, if known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Origin<'file_name> {
+    pub id: InferenceId,
+    pub span: Option<Span<'file_name>>,
+}
+
+/// The kind of literal a [`TypeError::InvalidLiteral`] was found for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiteralKind {
+    Integer,
+    Float,
+}
+
+impl core::fmt::Display for LiteralKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LiteralKind::Integer => write!(f, "integer"),
+            LiteralKind::Float => write!(f, "float"),
+        }
+    }
+}
+
+/// A problem found while inferring the types of a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeError<'file_name> {
+    /// Two types that must be equal are not.
+    Mismatch {
+        expected: TypeId,
+        found: TypeId,
+        /// The value whose type was found
+        origin: Origin<'file_name>,
+        /// The value the expected type came from, if it came from one
+        expected_origin: Option<Origin<'file_name>>,
+    },
+    /// A literal is used as a type that cannot be created from it.
+    InvalidLiteral {
+        kind: LiteralKind,
+        ty: TypeId,
+        origin: Origin<'file_name>,
+        /// The value the type came from, if it came from one
+        expected_origin: Option<Origin<'file_name>>,
+    },
+    /// An integer literal is used as an integer type that cannot hold its
+    /// value.
+    LiteralOutOfRange {
+        ty: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// Nothing constrains the type enough to resolve it.
+    Unresolved { origin: Origin<'file_name> },
+    /// Something that has no value, such as a call of a function that returns
+    /// nothing, is used as a value.
+    VoidValue { origin: Origin<'file_name> },
+    /// A reference to a value of type `referent` cannot have type `reference`.
+    InvalidReference {
+        reference: TypeId,
+        referent: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// A value of a type that is not a pointer is dereferenced.
+    InvalidDereference {
+        ty: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// A value of a type that is not a pointer or an array is indexed.
+    InvalidIndex {
+        ty: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// A value of a type that is not an integer is used as one.
+    ExpectedInteger {
+        ty: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// A value of a type that is not a number is used as one.
+    ExpectedNumber {
+        ty: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// Values of a type that cannot be compared are compared.
+    NotComparable {
+        ty: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// A value of type `from` is cast to `to`, which it cannot be converted to.
+    InvalidCast {
+        from: TypeId,
+        to: TypeId,
+        origin: Origin<'file_name>,
+    },
+    /// A type that does not implement a trait is used where it must.
+    NotImplemented {
+        ty: TypeId,
+        trait_id: TraitId,
+        origin: Origin<'file_name>,
+    },
+}
+
+impl<'file_name> TypeError<'file_name> {
+    /// The inference variable the error was found at.
+    #[must_use]
+    pub fn origin(&self) -> Origin<'file_name> {
+        match self {
+            TypeError::Mismatch { origin, .. }
+            | TypeError::InvalidLiteral { origin, .. }
+            | TypeError::Unresolved { origin }
+            | TypeError::InvalidReference { origin, .. }
+            | TypeError::InvalidDereference { origin, .. }
+            | TypeError::InvalidIndex { origin, .. }
+            | TypeError::ExpectedInteger { origin, .. }
+            | TypeError::ExpectedNumber { origin, .. }
+            | TypeError::NotComparable { origin, .. }
+            | TypeError::LiteralOutOfRange { origin, .. }
+            | TypeError::VoidValue { origin }
+            | TypeError::InvalidCast { origin, .. }
+            | TypeError::NotImplemented { origin, .. } => *origin,
+        }
+    }
+
+    /// Build the diagnostic reporting this error.
+    ///
+    /// `fallback` is used as the location of the error if its origin has no
+    /// span. The types of values are shown by the names `environment` has
+    /// recorded for them, if any.
+    #[must_use]
+    pub fn to_diagnostic(
+        &self,
+        environment: &TypeInferenceEnvironment<'file_name>,
+        types: &Types,
+        traits: &Traits,
+        fallback: Span<'file_name>,
+    ) -> Diagnostic<'file_name> {
+        let span = self.origin().span.unwrap_or(fallback);
+
+        match *self {
+            TypeError::Mismatch {
+                expected,
+                found,
+                origin,
+                expected_origin,
+            } => Self::mismatch_diagnostic(
+                span,
+                &environment.display_type(expected, expected_origin, types),
+                &environment.display_type(found, Some(origin), types),
+                expected_origin,
+            ),
+            TypeError::InvalidLiteral {
+                kind,
+                ty,
+                expected_origin,
+                ..
+            } => Self::literal_diagnostic(
+                span,
+                kind,
+                &environment.display_type(ty, expected_origin, types),
+            ),
+            TypeError::LiteralOutOfRange { ty, origin } => Self::range_diagnostic(
+                span,
+                ty,
+                &environment.display_type(ty, Some(origin), types),
+                types,
+            ),
+            TypeError::Unresolved { .. } => Diagnostic::error(
+                codes::TYPE_ANNOTATIONS_NEEDED,
+                "type annotations needed",
+                Label::new(span, "cannot infer the type of this"),
+            ),
+            TypeError::VoidValue { .. } => Diagnostic::error(
+                codes::VOID_VALUE,
+                "expected a value, found `void`",
+                Label::new(span, "has no value"),
+            ),
+            TypeError::InvalidReference {
+                reference,
+                referent,
+                ..
+            } => Diagnostic::error(
+                codes::INVALID_REFERENCE,
+                format!(
+                    "cannot reference a value of type `{}` as `{}`",
+                    types.to_display(referent),
+                    types.to_display(reference)
+                ),
+                Label::new(span, "invalid reference"),
+            ),
+            TypeError::InvalidDereference { ty, .. } => Diagnostic::error(
+                codes::INVALID_DEREFERENCE,
+                format!("type `{}` cannot be dereferenced", types.to_display(ty)),
+                Label::new(span, "not a pointer"),
+            ),
+            TypeError::InvalidIndex { ty, .. } => Diagnostic::error(
+                codes::INVALID_INDEX,
+                format!(
+                    "cannot index into a value of type `{}`",
+                    types.to_display(ty)
+                ),
+                Label::new(span, "not a pointer or an array"),
+            ),
+            TypeError::ExpectedInteger { ty, .. } => Diagnostic::error(
+                codes::EXPECTED_INTEGER,
+                format!("expected an integer, found `{}`", types.to_display(ty)),
+                Label::new(span, "expected an integer"),
+            ),
+            TypeError::ExpectedNumber { ty, .. } => Diagnostic::error(
+                codes::EXPECTED_NUMBER,
+                format!("expected a number, found `{}`", types.to_display(ty)),
+                Label::new(span, "expected an integer or a float"),
+            ),
+            TypeError::NotComparable { ty, .. } => Diagnostic::error(
+                codes::NOT_COMPARABLE,
+                format!("cannot compare values of type `{}`", types.to_display(ty)),
+                Label::new(span, "cannot be compared"),
+            ),
+            TypeError::InvalidCast { from, to, .. } => Diagnostic::error(
+                codes::INVALID_CAST,
+                format!(
+                    "cannot cast a value of type `{}` to `{}`",
+                    types.to_display(from),
+                    types.to_display(to)
+                ),
+                Label::new(span, "invalid cast"),
+            ),
+            TypeError::NotImplemented { ty, trait_id, .. } => Self::not_implemented_diagnostic(
+                span,
+                &types.to_display(ty),
+                &traits.to_display(trait_id),
+            ),
+        }
+    }
+
+    /// Build the diagnostic reporting a [`TypeError::InvalidLiteral`] found at
+    /// `span`.
+    fn literal_diagnostic(
+        span: Span<'file_name>,
+        kind: LiteralKind,
+        ty: &str,
+    ) -> Diagnostic<'file_name> {
+        Diagnostic::error(
+            codes::INVALID_LITERAL,
+            format!(
+                "cannot use {} {kind} literal as a value of type `{ty}`",
+                if matches!(kind, LiteralKind::Integer) {
+                    "an"
+                } else {
+                    "a"
+                },
+            ),
+            Label::new(span, format!("expected `{ty}`")),
+        )
+    }
+
+    /// Build the diagnostic reporting a [`TypeError::NotImplemented`] found at
+    /// `span`.
+    fn not_implemented_diagnostic(
+        span: Span<'file_name>,
+        ty: &str,
+        trait_name: &str,
+    ) -> Diagnostic<'file_name> {
+        Diagnostic::error(
+            codes::TRAIT_NOT_IMPLEMENTED,
+            format!("the trait `{trait_name}` is not implemented for `{ty}`"),
+            Label::new(span, format!("`{trait_name}` is not implemented")),
+        )
+    }
+
+    /// Build the diagnostic reporting a [`TypeError::LiteralOutOfRange`] found
+    /// at `span`.
+    fn range_diagnostic(
+        span: Span<'file_name>,
+        ty: TypeId,
+        name: &str,
+        types: &Types,
+    ) -> Diagnostic<'file_name> {
+        let diagnostic = Diagnostic::error(
+            codes::LITERAL_OUT_OF_RANGE,
+            format!("integer literal out of range for `{name}`"),
+            Label::new(span, format!("does not fit in `{name}`")),
+        );
+
+        match types.get_by_id(ty).as_deref() {
+            Some(Type::Primitive(primitive)) => match primitive.integer_range() {
+                Some(range) => diagnostic.with_note(format!(
+                    "`{name}` holds values from {} to {}",
+                    range.start(),
+                    range.end()
+                )),
+                None => diagnostic,
+            },
+            _ => diagnostic,
+        }
+    }
+
+    /// Build the diagnostic reporting a [`TypeError::Mismatch`] found at
+    /// `span`.
+    fn mismatch_diagnostic(
+        span: Span<'file_name>,
+        expected: &str,
+        found: &str,
+        expected_origin: Option<Origin<'file_name>>,
+    ) -> Diagnostic<'file_name> {
+        let diagnostic = Diagnostic::error(
+            codes::MISMATCHED_TYPES,
+            "mismatched types",
+            Label::new(span, format!("expected `{expected}`, found `{found}`")),
+        );
+
+        match expected_origin.and_then(|origin| origin.span) {
+            Some(expected_span) if expected_span != span => {
+                diagnostic.with_label(Label::new(expected_span, "expected due to this"))
+            }
+            _ => diagnostic,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
-pub struct TypeInferenceEnvironment {
+pub struct TypeInferenceEnvironment<'file_name> {
     next_id: InferenceId,
     resolved: HashMap<InferenceId, TypeId>,
     constraints: HashMap<InferenceId, Vec<TypeConstraint>>,
+    /// Where each inference variable was most recently used in the source code
+    origins: HashMap<InferenceId, Span<'file_name>>,
+    /// Where each inference variable was first used in the source code, which
+    /// is where a variable is declared
+    declarations: HashMap<InferenceId, Span<'file_name>>,
+    /// The name the type of each inference variable was written with, where
+    /// it was written with a name other than its own, e.g. a type alias
+    names: HashMap<InferenceId, String>,
+    /// The types known to implement each trait
+    implementations: HashSet<(TypeId, TraitId)>,
+    /// The type parameters of the function being inferred, along with the
+    /// traits they are bounded by
+    bounds: Vec<(TypeId, TraitId)>,
 }
 
-impl TypeInferenceEnvironment {
+impl<'file_name> TypeInferenceEnvironment<'file_name> {
     #[must_use]
     pub fn new() -> Self {
         Self {
             next_id: InferenceId(0),
             resolved: HashMap::new(),
             constraints: HashMap::new(),
+            origins: HashMap::new(),
+            declarations: HashMap::new(),
+            names: HashMap::new(),
+            implementations: HashSet::new(),
+            bounds: Vec::new(),
         }
     }
 
@@ -94,6 +460,64 @@ impl TypeInferenceEnvironment {
         id
     }
 
+    /// Record where `id` is used in the source code.
+    ///
+    /// Errors are reported at the most recent span recorded for an inference
+    /// variable, so each use of a variable is reported where it is used.
+    pub fn set_origin(&mut self, id: InferenceId, span: Span<'file_name>) {
+        self.origins.insert(id, span);
+        self.declarations.entry(id).or_insert(span);
+    }
+
+    /// Where `id` was most recently used.
+    #[must_use]
+    pub fn origin(&self, id: InferenceId) -> Origin<'file_name> {
+        Origin {
+            id,
+            span: self.origins.get(&id).copied(),
+        }
+    }
+
+    /// Where `id` was first used, which is where the type of a variable is
+    /// declared.
+    #[must_use]
+    pub fn declaration(&self, id: InferenceId) -> Origin<'file_name> {
+        Origin {
+            id,
+            span: self.declarations.get(&id).copied(),
+        }
+    }
+
+    /// Record the name the type of `id` was written with, e.g. a type alias.
+    pub fn set_name(&mut self, id: InferenceId, name: String) {
+        self.names.insert(id, name);
+    }
+
+    /// Display `ty`, the type of the value at `origin` if known, by the name
+    /// it was written with there, if any.
+    #[must_use]
+    pub fn display_type(&self, ty: TypeId, origin: Option<Origin>, types: &Types) -> String {
+        origin
+            .filter(|origin| self.resolve(origin.id) == Some(ty))
+            .and_then(|origin| self.names.get(&origin.id))
+            .map_or_else(|| types.to_display(ty), Clone::clone)
+    }
+
+    /// Record that `ty` implements the trait `trait_id`.
+    pub fn add_implementation(&mut self, ty: TypeId, trait_id: TraitId) {
+        self.implementations.insert((ty, trait_id));
+    }
+
+    /// Set the traits the type parameters of the function being inferred are
+    /// bounded by, which they implement within it.
+    pub fn set_bounds(&mut self, bounds: Vec<(TypeId, TraitId)>) {
+        self.bounds = bounds;
+    }
+
+    fn implements(&self, ty: TypeId, trait_id: TraitId) -> bool {
+        self.implementations.contains(&(ty, trait_id)) || self.bounds.contains(&(ty, trait_id))
+    }
+
     /// # Panics
     ///
     /// Panics if the `id` is not found in the constraints.
@@ -105,231 +529,555 @@ impl TypeInferenceEnvironment {
         entry.push(constraint);
     }
 
-    /// # Panics
+    /// Constrain the type of `id`, checking the constraint immediately if the
+    /// type is already resolved.
     ///
-    /// Panics if both `a` and `b` are resolved to different types.
-    pub fn unify(&mut self, a: InferenceId, b: InferenceId, types: &Types) {
-        match (self.resolve(a), self.resolve(b)) {
-            (Some(a), Some(b)) => {
-                assert_eq!(
-                    a,
-                    b,
-                    "Cannot unify types {} and {}",
-                    types.to_display(a),
-                    types.to_display(b)
-                );
-            }
+    /// # Errors
+    ///
+    /// Returns an error if `id` is resolved to a type that breaks `constraint`.
+    pub fn constrain(
+        &mut self,
+        id: InferenceId,
+        constraint: TypeConstraint,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        if let Some(ty) = self.resolve(id) {
+            self.constraints.insert(id, vec![constraint]);
+            self.check_constraints(id, ty, types)
+        } else {
+            self.add_constraint(id, constraint);
+            Ok(())
+        }
+    }
+
+    /// Unify the types of `expected` and `found`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both are resolved to different types, or if
+    /// resolving one of them breaks its constraints.
+    pub fn unify(
+        &mut self,
+        expected: InferenceId,
+        found: InferenceId,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        match (self.resolve(expected), self.resolve(found)) {
+            (Some(a), Some(b)) if types.is_error(a) || types.is_error(b) => Ok(()),
+            (Some(a), Some(b)) if a == b => Ok(()),
+            (Some(a), Some(b)) => Err(Box::new(TypeError::Mismatch {
+                expected: a,
+                found: b,
+                origin: self.origin(found),
+                expected_origin: Some(self.declaration(expected)),
+            })),
             (Some(type_id), None) => {
-                self.resolved.insert(b, type_id);
-                self.check_constraints(b, type_id, types);
+                self.resolved.insert(found, type_id);
+                self.check_constraints(found, type_id, types)
+                    .map_err(|mut error| {
+                        // The type came from `expected`, so point at it too
+                        if let TypeError::Mismatch {
+                            origin,
+                            expected_origin: expected_origin @ None,
+                            ..
+                        }
+                        | TypeError::InvalidLiteral {
+                            origin,
+                            expected_origin: expected_origin @ None,
+                            ..
+                        } = error.as_mut()
+                        {
+                            if origin.id == found {
+                                *expected_origin = Some(self.declaration(expected));
+                            }
+                        }
+                        error
+                    })
             }
             (None, Some(type_id)) => {
-                self.resolved.insert(a, type_id);
-                self.check_constraints(a, type_id, types);
+                self.resolved.insert(expected, type_id);
+                self.check_constraints(expected, type_id, types)
             }
             (None, None) => {
-                self.add_constraint(a, TypeConstraint::ConvertedFrom(b));
-                self.add_constraint(b, TypeConstraint::ConvertibleTo(a));
+                self.add_constraint(expected, TypeConstraint::ConvertedFrom(found));
+                self.add_constraint(found, TypeConstraint::ConvertibleTo(expected));
+                Ok(())
             }
         }
     }
 
-    /// # Panics
+    /// Unify the type of `a` with the type `ty`.
     ///
-    /// Panics if the type of `a` is not equal to `ty`.
-    pub fn unify_with(&mut self, a: InferenceId, ty: TypeId, types: &Types) {
+    /// # Errors
+    ///
+    /// Returns an error if `a` is resolved to a different type, or if
+    /// resolving it breaks its constraints.
+    pub fn unify_with(
+        &mut self,
+        a: InferenceId,
+        ty: TypeId,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
         if let Some(resolved) = self.resolve(a) {
-            dbg!(&self, &types);
-            assert_eq!(
-                resolved,
-                ty,
-                "Cannot unify type {} with type {}",
-                types.to_display(resolved),
-                types.to_display(ty)
-            );
+            if types.is_error(resolved) || types.is_error(ty) || resolved == ty {
+                return Ok(());
+            }
+
+            Err(Box::new(TypeError::Mismatch {
+                expected: ty,
+                found: resolved,
+                origin: self.origin(a),
+                expected_origin: None,
+            }))
         } else {
             self.resolved.insert(a, ty);
-            self.check_constraints(a, ty, types);
+            self.check_constraints(a, ty, types)
         }
     }
 
+    /// Resolve every remaining inference variable, using the default types of
+    /// literals where nothing else constrains them.
+    ///
+    /// Returns every error found, along with a [`TypeError::Unresolved`] for the
+    /// first inference variable that could not be resolved.
+    ///
     /// # Panics
     ///
-    /// Panics if it cannot unify the constraints into a concrete type id.
-    pub fn unify_final(&mut self, types: &Types) {
-        let constraints = self.constraints.clone();
-
-        for (id, constraints) in constraints {
-            for constraint in constraints {
-                match constraint {
-                    TypeConstraint::Type(ty) => {
-                        self.resolved.insert(id, ty);
-                    }
-                    TypeConstraint::ConvertedFrom(other) | TypeConstraint::ConvertibleTo(other) => {
-                        self.unify(id, other, types);
-                    }
-                    TypeConstraint::FromIntegerLiteral => {
-                        self.unify_with(
-                            id,
-                            types
-                                .get(&Path::new(vec![PathSegment::new("i32".to_string())]))
-                                .unwrap(),
-                            types,
-                        );
-                    }
-                    TypeConstraint::FromFloatLiteral => {
-                        self.unify_with(
-                            id,
-                            types
-                                .get(&Path::new(vec![PathSegment::new("f32".to_string())]))
-                                .unwrap(),
-                            types,
-                        );
-                    }
-                    TypeConstraint::RefTo(other) => {
-                        self.unify(other, id, types);
-                    }
-                    _ => {
-                        dbg!(&self);
-                        dbg!(&constraint);
-                        panic!(
-                            "Cannot resolve type inference id {} with constraint {}\n  --> {}",
-                            self.to_display(id, types),
-                            constraint.to_display(types),
-                            self.constraints
-                                .get(&id)
-                                .iter()
-                                .map(|c| c
-                                    .iter()
-                                    .map(|c| c.to_display(types))
-                                    .collect::<Vec<_>>()
-                                    .join(", "))
-                                .collect::<Vec<_>>()
-                                .join("\n")
-                        );
-                    }
+    /// Panics if the `i32` or `f32` types are not found in `types`.
+    pub fn unify_final(&mut self, types: &Types) -> Vec<TypeError<'file_name>> {
+        let mut errors = self.apply_defaults(types);
+
+        // Unresolved inference variables are usually constrained by each
+        // other, so only the first is reported
+        if let Some(id) = self.constraints.keys().min() {
+            errors.push(TypeError::Unresolved {
+                origin: self.declaration(*id),
+            });
+        }
+
+        // Every inference variable is either resolved or reported now, so none
+        // are checked again when the next function is finished
+        self.constraints.clear();
+
+        errors
+    }
+
+    /// Resolve the inference variables that have a default type, which are
+    /// those of literals and those constrained to a type of their own.
+    ///
+    /// Returns every error found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `i32` or `f32` types are not found in `types`.
+    pub fn apply_defaults(&mut self, types: &Types) -> Vec<TypeError<'file_name>> {
+        let mut errors = Vec::new();
+
+        let mut ids = self.constraints.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+
+        for id in ids {
+            // Resolving an earlier inference variable may have resolved this
+            // one too, in which case its constraints have been checked
+            let Some(constraints) = self.constraints.get(&id) else {
+                continue;
+            };
+
+            let default = constraints.iter().find_map(|constraint| match constraint {
+                TypeConstraint::Type(ty) => Some(*ty),
+                TypeConstraint::FromIntegerLiteral(_) | TypeConstraint::Integer => Some(
+                    types
+                        .get(&Path::new(vec![PathSegment::new("i32".to_string())]))
+                        .expect("expected i32 type to exist"),
+                ),
+                TypeConstraint::FromFloatLiteral => Some(
+                    types
+                        .get(&Path::new(vec![PathSegment::new("f32".to_string())]))
+                        .expect("expected f32 type to exist"),
+                ),
+                TypeConstraint::ConvertedFrom(_)
+                | TypeConstraint::ConvertibleTo(_)
+                | TypeConstraint::Implements(_)
+                | TypeConstraint::DerefTo(_)
+                | TypeConstraint::IndexTo(_)
+                | TypeConstraint::RefTo(_)
+                | TypeConstraint::ReferencedBy(_)
+                | TypeConstraint::CastTo(_)
+                | TypeConstraint::Numeric
+                | TypeConstraint::Comparable
+                | TypeConstraint::Value => None,
+            });
+
+            if let Some(ty) = default {
+                if let Err(error) = self.unify_with(id, ty, types) {
+                    errors.push(*error);
                 }
             }
         }
+
+        errors
     }
 
+    /// Check that resolving `id` to `ty` is allowed by its constraints.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first constraint of `id` that `ty` breaks.
+    ///
     /// # Panics
     ///
-    /// Panics if the type of `id` does not match the expected type or if
-    /// the other constraints cannot be resolved.
-    #[allow(clippy::too_many_lines)]
-    fn check_constraints(&mut self, id: InferenceId, ty: TypeId, types: &Types) {
-        let constraints = self.constraints.remove(&id).unwrap();
+    /// Panics if `ty` is not found in `types`.
+    fn check_constraints(
+        &mut self,
+        id: InferenceId,
+        ty: TypeId,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        let constraints = self.constraints.remove(&id).unwrap_or_default();
+        let mut result = Ok(());
+
+        // The constraints after a broken one are still checked, as they may
+        // carry `ty` over to other inference variables
         for constraint in constraints {
-            match constraint {
-                TypeConstraint::Type(expected) => {
-                    assert_eq!(
+            let checked = self.check_constraint(id, ty, constraint, types);
+            if result.is_ok() {
+                result = checked;
+            }
+        }
+
+        result
+    }
+
+    /// Check that resolving `id` to `ty` is allowed by `constraint`.
+    fn check_constraint(
+        &mut self,
+        id: InferenceId,
+        ty: TypeId,
+        constraint: TypeConstraint,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        match constraint {
+            // The constraint is the type the value has on its own, so it
+            // is the type found where `ty` was expected
+            TypeConstraint::Type(own) => {
+                if !types.is_error(own) && !types.is_error(ty) && own != ty {
+                    return Err(Box::new(TypeError::Mismatch {
+                        expected: ty,
+                        found: own,
+                        origin: self.origin(id),
+                        expected_origin: None,
+                    }));
+                }
+            }
+            TypeConstraint::ConvertedFrom(other) | TypeConstraint::ConvertibleTo(other) => {
+                self.unify(id, other, types)?;
+            }
+            TypeConstraint::Implements(trait_id) => {
+                if !types.is_error(ty) && !self.implements(ty, trait_id) {
+                    return Err(Box::new(TypeError::NotImplemented {
                         ty,
-                        expected,
-                        "Type {} does not match expected type {}",
-                        types.to_display(ty),
-                        types.to_display(expected)
-                    );
+                        trait_id,
+                        origin: self.origin(id),
+                    }));
                 }
-                TypeConstraint::ConvertedFrom(other) | TypeConstraint::ConvertibleTo(other) => {
-                    self.unify(id, other, types);
+            }
+            TypeConstraint::DerefTo(other) => {
+                match types
+                    .get_by_id(ty)
+                    .expect("Type not found in types")
+                    .as_ref()
+                {
+                    Type::Error => {}
+                    Type::Ptr(pointee) => self.unify_with(other, *pointee, types)?,
+                    _ => {
+                        return Err(Box::new(TypeError::InvalidDereference {
+                            ty,
+                            origin: self.origin(id),
+                        }));
+                    }
                 }
-                TypeConstraint::Implements(_trait_id) => {
-                    todo!()
+            }
+            TypeConstraint::IndexTo(element) => {
+                match types
+                    .get_by_id(ty)
+                    .expect("Type not found in types")
+                    .as_ref()
+                {
+                    Type::Error => {}
+                    Type::Ptr(inner) | Type::SizedArray(inner, _) | Type::UnsizedArray(inner) => {
+                        self.unify_with(element, *inner, types)?;
+                    }
+                    _ => {
+                        return Err(Box::new(TypeError::InvalidIndex {
+                            ty,
+                            origin: self.origin(id),
+                        }));
+                    }
                 }
-                TypeConstraint::DerefTo(_other) => {
-                    todo!()
+            }
+            TypeConstraint::RefTo(referent) => match self.resolve(referent) {
+                Some(referent_ty) => self.check_reference(id, ty, referent_ty, types)?,
+                None => self.add_constraint(referent, TypeConstraint::ReferencedBy(id)),
+            },
+            TypeConstraint::ReferencedBy(reference) => {
+                let reference_ty = self
+                    .resolve(reference)
+                    .expect("references are resolved before being constrained");
+                self.check_reference(reference, reference_ty, ty, types)?;
+            }
+            TypeConstraint::CastTo(target) => self.check_cast(id, ty, target, types)?,
+            TypeConstraint::Value => {
+                if let Some(Type::Primitive(PrimitiveType::Void)) = types.get_by_id(ty).as_deref() {
+                    return Err(Box::new(TypeError::VoidValue {
+                        origin: self.origin(id),
+                    }));
                 }
-                TypeConstraint::RefTo(other) => match self.resolve(other) {
-                    Some(other) => match self.resolve(id) {
-                        Some(ty) => {
-                            match (
-                                types
-                                    .get_by_id(ty)
-                                    .expect("Type not found in types")
-                                    .as_ref(),
-                                types
-                                    .get_by_id(other)
-                                    .expect("Type not found in types")
-                                    .as_ref(),
-                            ) {
-                                // Handle referencing a str type
-                                (Type::Primitive(PrimitiveType::Str), Type::Ptr(id))
-                                    if types.get_by_id(*id).is_some_and(|ty| {
-                                        matches!(ty.as_ref(), Type::Primitive(PrimitiveType::U8))
-                                    }) => {}
-                                _ => {
-                                    panic!(
-                                        "Cannot reference type {} to type {}",
-                                        types.to_display(ty),
-                                        types.to_display(other)
-                                    )
-                                }
-                            }
-                        }
-                        None => {
-                            self.add_constraint(id, TypeConstraint::Type(other));
-                        }
-                    },
-                    None => {
-                        self.add_constraint(other, TypeConstraint::RefTo(id));
-                    }
+            }
+            TypeConstraint::Integer
+            | TypeConstraint::Numeric
+            | TypeConstraint::Comparable
+            | TypeConstraint::FromIntegerLiteral(_)
+            | TypeConstraint::FromFloatLiteral => {
+                self.check_numeric(id, ty, constraint, types)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that `ty` is a numeric type allowed by `constraint`, which is
+    /// either the constraint of an operand or the constraint of a literal.
+    ///
+    /// A type parameter is allowed as the operand of an arithmetic operator or
+    /// a comparison, as nothing bounds it to the types that support them.
+    fn check_numeric(
+        &self,
+        id: InferenceId,
+        ty: TypeId,
+        constraint: TypeConstraint,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        if types.is_error(ty) {
+            return Ok(());
+        }
+
+        let resolved = types.get_by_id(ty).expect("Type not found in types");
+        let primitive = match resolved.as_ref() {
+            Type::Primitive(primitive) => Some(*primitive),
+            _ => None,
+        };
+        let is_parameter = matches!(resolved.as_ref(), Type::Parameter(..));
+
+        let (valid, error) = match constraint {
+            TypeConstraint::Integer => (
+                primitive.is_some_and(PrimitiveType::is_integer),
+                TypeError::ExpectedInteger {
+                    ty,
+                    origin: self.origin(id),
                 },
-                TypeConstraint::FromIntegerLiteral => match types.get_by_id(ty) {
-                    None => panic!("Type {ty:?} not found in types"),
-                    Some(ty) => match *ty {
-                        Type::Primitive(primitive) => match primitive {
-                            PrimitiveType::I8
-                            | PrimitiveType::I16
-                            | PrimitiveType::I32
-                            | PrimitiveType::I64
-                            | PrimitiveType::U8
-                            | PrimitiveType::U16
-                            | PrimitiveType::U32
-                            | PrimitiveType::U64
-                            | PrimitiveType::F32
-                            | PrimitiveType::F64 => {}
-                            PrimitiveType::Bool
-                            | PrimitiveType::Char
-                            | PrimitiveType::Str
-                            | PrimitiveType::Void => {
-                                panic!("Type {ty:?} cannot be converted to from an integer literal")
-                            }
-                        },
-                        _ => panic!("Type {ty:?} is not a primitive type"),
+            ),
+            TypeConstraint::Numeric => (
+                is_parameter
+                    || primitive
+                        .is_some_and(|primitive| primitive.is_integer() || primitive.is_float()),
+                TypeError::ExpectedNumber {
+                    ty,
+                    origin: self.origin(id),
+                },
+            ),
+            TypeConstraint::Comparable => (
+                is_parameter
+                    || matches!(resolved.as_ref(), Type::Ptr(_) | Type::Enum(_))
+                    || primitive.is_some_and(|primitive| {
+                        !matches!(primitive, PrimitiveType::Void | PrimitiveType::Str)
+                    }),
+                TypeError::NotComparable {
+                    ty,
+                    origin: self.origin(id),
+                },
+            ),
+            TypeConstraint::FromIntegerLiteral(value) => {
+                if !primitive
+                    .is_some_and(|primitive| primitive.is_integer() || primitive.is_float())
+                {
+                    return Err(Box::new(TypeError::InvalidLiteral {
+                        kind: LiteralKind::Integer,
+                        ty,
+                        origin: self.origin(id),
+                        expected_origin: None,
+                    }));
+                }
+
+                // A float holds any integer, if not always exactly
+                let range = primitive.and_then(PrimitiveType::integer_range);
+                (
+                    value.is_some_and(|value| {
+                        range.as_ref().is_none_or(|range| range.contains(&value))
+                    }),
+                    TypeError::LiteralOutOfRange {
+                        ty,
+                        origin: self.origin(id),
                     },
+                )
+            }
+            TypeConstraint::FromFloatLiteral => (
+                primitive.is_some_and(PrimitiveType::is_float),
+                TypeError::InvalidLiteral {
+                    kind: LiteralKind::Float,
+                    ty,
+                    origin: self.origin(id),
+                    expected_origin: None,
                 },
-                TypeConstraint::FromFloatLiteral => {
-                    // TODO: Check if the type is a float type
-                    match types.get_by_id(ty) {
-                        None => panic!("Type {ty:?} not found in types"),
-                        Some(resolved_type) => match *resolved_type {
-                            Type::Primitive(primitive) => match primitive {
-                                PrimitiveType::F32 | PrimitiveType::F64 => {}
-                                PrimitiveType::I8
-                                | PrimitiveType::I16
-                                | PrimitiveType::I32
-                                | PrimitiveType::I64
-                                | PrimitiveType::U8
-                                | PrimitiveType::U16
-                                | PrimitiveType::U32
-                                | PrimitiveType::U64
-                                | PrimitiveType::Bool
-                                | PrimitiveType::Char
-                                | PrimitiveType::Str
-                                | PrimitiveType::Void => {
-                                    panic!(
-                                        "Type {} cannot be converted to from an float literal",
-                                        types.to_display(ty)
-                                    )
-                                }
-                            },
-                            _ => panic!("Type {resolved_type:?} is not a primitive type"),
-                        },
+            ),
+            _ => unreachable!("not a numeric constraint"),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Box::new(error))
+        }
+    }
+
+    /// Check that a value of type `from` may be cast to `to`.
+    fn check_cast(
+        &self,
+        id: InferenceId,
+        from: TypeId,
+        to: TypeId,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        // A pointer becomes a trait object if the type it points to implements
+        // the trait
+        if let (Type::Ptr(pointee), Type::TraitObject(trait_id, _)) = (
+            types
+                .get_by_id(from)
+                .expect("Type not found in types")
+                .as_ref(),
+            types
+                .get_by_id(to)
+                .expect("Type not found in types")
+                .as_ref(),
+        ) {
+            if types.is_error(*pointee) || self.implements(*pointee, *trait_id) {
+                return Ok(());
+            }
+
+            return Err(Box::new(TypeError::NotImplemented {
+                ty: *pointee,
+                trait_id: *trait_id,
+                origin: self.origin(id),
+            }));
+        }
+
+        let valid = from == to
+            || types.is_error(from)
+            || types.is_error(to)
+            || match (
+                types
+                    .get_by_id(from)
+                    .expect("Type not found in types")
+                    .as_ref(),
+                types
+                    .get_by_id(to)
+                    .expect("Type not found in types")
+                    .as_ref(),
+            ) {
+                (Type::Primitive(from), Type::Primitive(to)) => {
+                    let numeric =
+                        |primitive: PrimitiveType| primitive.is_integer() || primitive.is_float();
+
+                    match (*from, *to) {
+                        // Characters are bytes, so they only convert to and from `u8`
+                        (PrimitiveType::Char, PrimitiveType::U8)
+                        | (PrimitiveType::U8, PrimitiveType::Char) => true,
+                        (PrimitiveType::Bool | PrimitiveType::Char, to) => to.is_integer(),
+                        (from, to) => numeric(from) && numeric(to),
+                    }
+                }
+                (Type::Ptr(_), Type::Ptr(_)) => true,
+                // Enums are stored as integers, so they convert to and from any
+                // of them like pointers do
+                (Type::Ptr(_) | Type::Enum(_), Type::Primitive(primitive))
+                | (Type::Primitive(primitive), Type::Ptr(_) | Type::Enum(_)) => {
+                    primitive.is_integer()
+                }
+                _ => false,
+            };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Box::new(TypeError::InvalidCast {
+                from,
+                to,
+                origin: self.origin(id),
+            }))
+        }
+    }
+
+    /// Check that `reference`, resolved to `reference_ty`, may reference a
+    /// value of type `referent_ty`.
+    fn check_reference(
+        &self,
+        reference: InferenceId,
+        reference_ty: TypeId,
+        referent_ty: TypeId,
+        types: &Types,
+    ) -> Result<(), Box<TypeError<'file_name>>> {
+        let valid = types.is_error(reference_ty)
+            || types.is_error(referent_ty)
+            || match (
+                types
+                    .get_by_id(reference_ty)
+                    .expect("Type not found in types")
+                    .as_ref(),
+                types
+                    .get_by_id(referent_ty)
+                    .expect("Type not found in types")
+                    .as_ref(),
+            ) {
+                (Type::Ptr(pointee), _) if *pointee == referent_ty => true,
+                // A reference to a string is a pointer to its first byte
+                (Type::Ptr(pointee), Type::Primitive(PrimitiveType::Str)) => types
+                    .get_by_id(*pointee)
+                    .is_some_and(|ty| matches!(ty.as_ref(), Type::Primitive(PrimitiveType::U8))),
+                _ => false,
+            };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Box::new(TypeError::InvalidReference {
+                reference: reference_ty,
+                referent: referent_ty,
+                origin: self.origin(reference),
+            }))
+        }
+    }
+
+    /// Every inference variable that `id` has been unified with while neither
+    /// was resolved, directly or through others, including `id` itself.
+    #[must_use]
+    pub fn unified_with(&self, id: InferenceId) -> Vec<InferenceId> {
+        let mut found = vec![id];
+        let mut index = 0;
+
+        while let Some(&current) = found.get(index) {
+            index += 1;
+
+            for constraint in self.constraints.get(&current).into_iter().flatten() {
+                if let TypeConstraint::ConvertedFrom(other) | TypeConstraint::ConvertibleTo(other) =
+                    constraint
+                {
+                    if !found.contains(other) {
+                        found.push(*other);
                     }
                 }
             }
         }
+
+        found
     }
 
     #[must_use]
@@ -343,13 +1091,13 @@ impl TypeInferenceEnvironment {
     }
 }
 
-impl Default for TypeInferenceEnvironment {
+impl Default for TypeInferenceEnvironment<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl core::fmt::Debug for TypeInferenceEnvironment {
+impl core::fmt::Debug for TypeInferenceEnvironment<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("TypeInferenceEnvironment")
             .field("next_id", &self.next_id)
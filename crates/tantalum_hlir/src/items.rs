@@ -1,4 +1,5 @@
 use crate::statements::Statement;
+use crate::traits::TraitId;
 use crate::types::{TypeId, TypeScopeId};
 use crate::variables::{VariableId, VariableScopeBlockId};
 use std::rc::Rc;
@@ -9,6 +10,16 @@ use std::rc::Rc;
 /// function in the compiled code itself).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FunctionPrototype {
+    /// The type parameters of a generic function, each a [`Type::Parameter`].
+    ///
+    /// Generic functions are never generated themselves, only their
+    /// instantiations, whose prototypes have no type parameters.
+    ///
+    /// [`Type::Parameter`]: crate::types::Type::Parameter
+    pub type_parameters: Vec<TypeId>,
+    /// The traits each type parameter must implement, in the same order as
+    /// `type_parameters`.
+    pub bounds: Vec<Vec<TraitId>>,
     /// The type of the parameters to the function.
     ///
     /// These can be resolved from [`TypeId`]'s to [`Type`]'s using the [`Types`] struct.
@@ -28,6 +39,14 @@ pub struct FunctionPrototype {
     pub return_type: TypeId,
 }
 
+impl FunctionPrototype {
+    /// Whether the function has type parameters.
+    #[must_use]
+    pub fn is_generic(&self) -> bool {
+        !self.type_parameters.is_empty()
+    }
+}
+
 /// A function in the HLIR.
 ///
 /// References a [`FunctionPrototype`] and contains the parameters and body of the function.
@@ -43,6 +62,12 @@ pub struct Function {
     pub parameters: Vec<VariableId>,
     /// The body of the function. Can be either a single statement or a block of statements.
     pub body: Statement,
+    /// The types that replace the type parameters of the generic function
+    /// this is an instantiation of, empty for other functions.
+    ///
+    /// The types of the body are those of the generic function, so they must
+    /// be substituted with these.
+    pub type_arguments: Vec<TypeId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,6 +114,7 @@ impl FunctionBuilder {
             prototype: self.prototype,
             parameters: self.parameters,
             body: self.body?,
+            type_arguments: Vec::new(),
         })
     }
 }
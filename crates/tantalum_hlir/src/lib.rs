@@ -1,25 +1,39 @@
 #![feature(debug_closure_helpers)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::rc::Rc;
 
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_span::Span;
+
 pub use expressions::*;
 pub use functions::*;
+pub use globals::*;
 pub use inference::*;
 pub use items::*;
 pub use literals::*;
 pub use path::*;
+pub use patterns::*;
 pub use statements::*;
 pub use traits::*;
 pub use types::*;
 pub use variables::*;
 
+use generics::GenericCall;
+
+/// The number of imports followed to find an item, after which the imports
+/// are assumed to refer to each other in a cycle
+const IMPORT_LIMIT: usize = 16;
+
 mod expressions;
 mod functions;
+mod generics;
+mod globals;
 mod inference;
 mod items;
 mod literals;
 mod path;
+mod patterns;
 mod statements;
 mod traits;
 mod types;
@@ -27,7 +41,7 @@ mod variables;
 
 /// A HLIR program package
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct HLIRPackage {
+pub struct HLIRPackage<'file_name> {
     /// All the types found while parsing the package
     types: Types,
     /// All the traits and their implementations found while parsing the package
@@ -36,15 +50,53 @@ pub struct HLIRPackage {
     functions: Functions,
     /// All the variables found while parsing the package
     variables: Variables,
-
-    type_inference_environment: TypeInferenceEnvironment,
+    /// The constants and statics declared in the package
+    globals: HashMap<VariableId, Global>,
+    /// The values the globals are initialized with, along with where they are
+    /// written, until they are computed once every function has been built
+    initializers: HashMap<VariableId, (Expression, Span<'file_name>)>,
+    /// The module items are currently declared in, which is empty at the root
+    /// of the package
+    module: Path,
+    /// The paths of every module entered so far
+    modules: HashSet<Path>,
+    /// The items imported with `use` by the path they are imported at, along
+    /// with the module they are imported into and their path as written there
+    imports: HashMap<Path, (Path, Path)>,
+
+    type_inference_environment: TypeInferenceEnvironment<'file_name>,
+    /// The type errors found in the function being built
+    type_errors: Vec<TypeError<'file_name>>,
+    /// The matches in the function being built, which are checked once the
+    /// type of their scrutinee is known
+    match_checks: Vec<MatchCheck<'file_name>>,
+    /// The calls of generic functions in the function being built, which are
+    /// checked once their type arguments are known
+    generic_calls: Vec<GenericCall>,
+    /// The values of generic types in the function being built, along with
+    /// the generic type and the inference variables of its type arguments, so
+    /// that their fields can be used before the arguments are known
+    generic_values: HashMap<InferenceId, (TypeId, Vec<InferenceId>)>,
+    /// The arrays built in the function being built, along with the type of
+    /// their elements and their length, whose types are only known once the
+    /// type of their elements is
+    array_values: Vec<(InferenceId, InferenceId, usize)>,
+    /// The references taken in the function being built whose types are not
+    /// known yet, along with the type of the value they refer to
+    reference_values: Vec<(InferenceId, InferenceId)>,
 
     current_function: Option<FunctionId>,
+    /// Where the function being built is declared, used as the location of
+    /// type errors that have no better one
+    current_function_span: Option<Span<'file_name>>,
     building_functions: HashMap<FunctionId, FunctionBuilder>,
     current_blocks: Vec<(VariableScopeBlockId, TypeScopeId)>,
+
+    /// The problems found while building the package
+    diagnostics: Vec<Diagnostic<'file_name>>,
 }
 
-impl HLIRPackage {
+impl<'file_name> HLIRPackage<'file_name> {
     #[must_use]
     pub fn new() -> Self {
         let mut package = Self {
@@ -52,12 +104,26 @@ impl HLIRPackage {
             traits: Traits::new(),
             functions: Functions::new(),
             variables: Variables::new(),
+            globals: HashMap::new(),
+            initializers: HashMap::new(),
+            module: Path::new(Vec::new()),
+            modules: HashSet::new(),
+            imports: HashMap::new(),
 
             type_inference_environment: TypeInferenceEnvironment::new(),
+            type_errors: Vec::new(),
+            match_checks: Vec::new(),
+            generic_calls: Vec::new(),
+            generic_values: HashMap::new(),
+            array_values: Vec::new(),
+            reference_values: Vec::new(),
 
             current_function: None,
+            current_function_span: None,
             building_functions: HashMap::new(),
             current_blocks: Vec::new(),
+
+            diagnostics: Vec::new(),
         };
 
         package.add_builtin_types();
@@ -128,66 +194,48 @@ impl HLIRPackage {
         }
     }
 
-    // pub fn add_builtin_traits(&mut self) {
-    //     let trait_id = self
-    //         .traits
-    //         .get_or_insert(Path::new(vec![PathSegment::from("Ref")]));
-    //
-    //     let t = self.traits.get_trait_mut(id).expect("trait not found");
-    //
-    //     t.add_type_parameter("T".to_string());
-    //
-    //     let id = t.add_method(
-    //         "ref".to_string(),
-    //         vec![t.get_type_parameter("Self").unwrap()],
-    //         t.get_type_parameter("T").unwrap(),
-    //     );
-    //
-    //     let trait_impl = TraitImpl {
-    //         trait_id,
-    //         type_id: self
-    //             .get_type_id(&Path::new(vec![PathSegment::from("str")]))
-    //             .unwrap(),
-    //         type_constraints: {
-    //             let mut map = HashMap::new();
-    //             map.insert(
-    //                 t.get_type_parameter("T").unwrap(),
-    //                 vec![TypeConstraint::Type(self.types.get_or_insert(Type::Ptr(
-    //                     self.types.get_or_insert(Type::Primitive(PrimitiveType::U8)),
-    //                 )))],
-    //             );
-    //             map
-    //         },
-    //         method_impls: {
-    //             let mut map = HashMap::new();
-    //             // map.insert(
-    //             //     id,
-    //             //     Function {
-    //             //         variable_scope: (),
-    //             //         type_scope: (),
-    //             //         prototype: Rc::new(FunctionPrototype {}),
-    //             //         parameters: vec![],
-    //             //         body: (),
-    //             //     },
-    //             // );
-    //             map
-    //         },
-    //     };
-    // }
+    /// Report a problem found while building the package.
+    pub fn report(&mut self, diagnostic: Diagnostic<'file_name>) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic<'file_name>] {
+        &self.diagnostics
+    }
+
+    /// Whether any errors have been reported, in which case the package must
+    /// not be used for code generation.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(Diagnostic::is_error)
+    }
 
     pub fn types(&self) -> impl Iterator<Item = (TypeId, &Type)> {
         self.types.iter()
     }
 
+    /// Get the prototype of every function along with the name of its
+    /// symbol. External functions are defined outside of the package, so
+    /// their symbols have the names they are declared with.
+    ///
     /// # Panics
     ///
     /// Panics if no path is found for the function ID.
     pub fn prototypes(&self) -> impl Iterator<Item = (FunctionId, String, &FunctionPrototype)> {
         self.functions.prototypes().map(|(id, prototype)| {
-            let name = self
+            let path = self
                 .get_function_path(&id)
-                .expect("expected function to exist")
-                .to_mangled_string();
+                .expect("expected function to exist");
+            let name = match self.functions.get_impl(id) {
+                Some(_) => path.to_mangled_string(),
+                None => path
+                    .segments()
+                    .last()
+                    .expect("expected a function to have a name")
+                    .name
+                    .clone(),
+            };
             (id, name, prototype)
         })
     }
@@ -219,6 +267,103 @@ impl HLIRPackage {
 
     // TODO: Implement a building interface for types
 
+    /// Start declaring items in the module `name`, nested in the current
+    /// module, until [`HLIRPackage::exit_module`] is called.
+    pub fn enter_module(&mut self, name: &str) {
+        self.module.push(PathSegment::from(name));
+        self.modules.insert(self.module.clone());
+    }
+
+    pub fn exit_module(&mut self) {
+        self.module.pop();
+    }
+
+    /// Make `module` the current module, getting the one it replaces, e.g. to
+    /// lower an item of another module as soon as it is needed.
+    pub fn switch_module(&mut self, module: Path) -> Path {
+        core::mem::replace(&mut self.module, module)
+    }
+
+    /// Get the path of the item called `name` declared in the current module,
+    /// e.g. `shapes::Circle` for `Circle` in `mod shapes`.
+    #[must_use]
+    pub fn item_path(&self, name: &str) -> Path {
+        self.module.join(&Path::from(name))
+    }
+
+    /// Whether a path written in the current module refers to a module.
+    #[must_use]
+    pub fn is_module(&self, path: &Path) -> bool {
+        self.resolve(path, |path| self.modules.contains(path).then_some(()))
+            .is_some()
+    }
+
+    /// Get the full path of the item a path written in the current module
+    /// refers to, among the paths `exists` holds for, e.g. to find items that
+    /// have not been added to the package yet.
+    #[must_use]
+    pub fn resolve_path(&self, path: &Path, exists: impl Fn(&Path) -> bool) -> Option<Path> {
+        self.resolve(path, |path| exists(path).then(|| path.clone()))
+    }
+
+    /// Import the item at `path`, as written in the current module, into the
+    /// current module as `name`, unless an item is already imported as
+    /// `name`.
+    ///
+    /// Returns whether the item was imported.
+    pub fn add_import(&mut self, name: &str, path: Path) -> bool {
+        let import = self.item_path(name);
+        if self.imports.contains_key(&import) {
+            return false;
+        }
+
+        self.imports.insert(import, (self.module.clone(), path));
+        true
+    }
+
+    /// Find an item with `get` by its `path` relative to the current module,
+    /// or else relative to each of the modules enclosing it, innermost first,
+    /// up to the root of the package.
+    fn resolve<T>(&self, path: &Path, get: impl Fn(&Path) -> Option<T>) -> Option<T> {
+        self.resolve_in(&self.module, path, &get, IMPORT_LIMIT)
+    }
+
+    /// Find an item with `get` by its `path` relative to `module`, following
+    /// at most `limit` imports.
+    fn resolve_in<T>(
+        &self,
+        module: &Path,
+        path: &Path,
+        get: &impl Fn(&Path) -> Option<T>,
+        limit: usize,
+    ) -> Option<T> {
+        let segments = module.segments();
+
+        (0..=segments.len()).rev().find_map(|depth| {
+            let path = Path::new(segments[..depth].to_vec()).join(path);
+            get(&path).or_else(|| self.resolve_import(&path, get, limit))
+        })
+    }
+
+    /// Find an item with `get` by its full `path` starting with an import,
+    /// e.g. `shapes::Quad` for `use shapes::{Square as Quad};` in `shapes`.
+    fn resolve_import<T>(
+        &self,
+        path: &Path,
+        get: &impl Fn(&Path) -> Option<T>,
+        limit: usize,
+    ) -> Option<T> {
+        let limit = limit.checked_sub(1)?;
+        let segments = path.segments();
+
+        (1..=segments.len()).find_map(|length| {
+            let (module, imported) = self.imports.get(&Path::new(segments[..length].to_vec()))?;
+            let path = imported.join(&Path::new(segments[length..].to_vec()));
+
+            self.resolve_in(module, &path, get, limit)
+        })
+    }
+
     /// Used to define completely new types.
     ///
     /// With
@@ -226,24 +371,33 @@ impl HLIRPackage {
         self.types.create_type(path, ty)
     }
 
-    /// Used to alias types (e.g. `type MyInt = i32;`)
-    ///
-    /// # Panics
-    ///
-    /// Panics if the current type is not found.
-    pub fn add_type_alias(&mut self, new: Path, current: &Path) {
-        let current_id = self.types.get(current).expect("Type not found");
-        self.types.create_type_with_id(new, current_id);
+    /// Used to alias types (e.g. `type MyInt = i32;`), in the current type
+    /// scope, so that an alias declared in a block ends with it.
+    pub fn add_type_alias(&mut self, new: Path, ty: TypeId) {
+        self.types.create_type_with_id(new, ty);
     }
 
     /// Get the ID of a type by its path.
     ///
-    /// This performs a lookup in the current scope.
+    /// This performs a lookup in the current scope, but not relative to the
+    /// current module, see [`HLIRPackage::resolve_type_id`].
     #[must_use]
     pub fn get_type_id(&self, path: &Path) -> Option<TypeId> {
         self.types.get(path)
     }
 
+    /// Get the ID of the type a path written in the current module refers
+    /// to, e.g. `Circle` or `shapes::Circle`.
+    #[must_use]
+    pub fn resolve_type_id(&self, path: &Path) -> Option<TypeId> {
+        self.resolve(path, |path| self.types.get(path))
+    }
+
+    #[must_use]
+    pub fn get_type(&self, id: TypeId) -> Option<Rc<Type>> {
+        self.types.get_by_id(id)
+    }
+
     #[must_use]
     pub fn get_resolved_type(&self, id: InferenceId) -> Option<TypeId> {
         self.type_inference_environment.resolve(id)
@@ -255,6 +409,17 @@ impl HLIRPackage {
         self.types.get_or_insert(Type::Ptr(ty))
     }
 
+    /// Build the type of pointers to values of any type implementing the
+    /// trait `id`, written `*dyn Trait`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait does not exist.
+    pub fn build_type_trait_object(&mut self, id: TraitId) -> TypeId {
+        let name = self.traits.to_display(id);
+        self.types.get_or_insert(Type::TraitObject(id, name))
+    }
+
     pub fn build_type_array(&mut self, ty: TypeId, size: usize) -> TypeId {
         self.types.get_or_insert(Type::SizedArray(ty, size))
     }
@@ -263,6 +428,190 @@ impl HLIRPackage {
         self.types.get_or_insert(Type::UnsizedArray(ty))
     }
 
+    /// The type used in place of a type that could not be resolved, after
+    /// the problem has been reported.
+    pub fn build_type_error(&mut self) -> TypeId {
+        self.types.get_or_insert(Type::Error)
+    }
+
+    /// Declare a struct at `path` without any fields.
+    ///
+    /// The fields are set with [`Self::set_struct_fields`] once every type is
+    /// declared, so that they can refer to structs declared later on.
+    pub fn add_struct(&mut self, path: Path) -> TypeId {
+        self.types.create_struct(path)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `ty` is not a struct.
+    pub fn set_struct_fields(&mut self, ty: TypeId, fields: Vec<StructField>) {
+        let Some(Type::Struct(id)) = self.types.get_by_id(ty).as_deref().cloned() else {
+            panic!("expected {ty:?} to be a struct");
+        };
+
+        self.types.set_struct_fields(id, fields);
+    }
+
+    /// Make the struct or tagged union `ty` generic over type parameters named
+    /// `names`, which are then found by name with
+    /// [`HLIRPackage::push_type_parameters_of`] to lower its fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ty` is not a struct or a tagged union.
+    pub fn set_type_parameters(&mut self, ty: TypeId, names: &[&str]) {
+        let type_parameters = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                self.types
+                    .get_or_insert(Type::Parameter(index, (*name).to_string()))
+            })
+            .collect();
+
+        match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Struct(id)) => self.types.set_struct_type_parameters(*id, type_parameters),
+            Some(Type::Union(id)) => self.types.set_union_type_parameters(*id, type_parameters),
+            _ => panic!("expected {ty:?} to be a struct or a tagged union"),
+        }
+    }
+
+    /// The type parameters of the generic struct or tagged union `ty`, empty
+    /// if it is not generic.
+    #[must_use]
+    pub fn get_type_parameters(&self, ty: TypeId) -> &[TypeId] {
+        self.types.type_parameters(ty)
+    }
+
+    /// Get the instantiation of the generic struct or tagged union `ty` with
+    /// `arguments`, see [`Types::instantiate`], written at `span`.
+    pub fn build_type_instance(
+        &mut self,
+        ty: TypeId,
+        arguments: Vec<TypeId>,
+        span: Span<'file_name>,
+    ) -> TypeId {
+        let instance = self.types.instantiate(ty, arguments);
+        self.report_type_recursion_limit(span);
+        instance
+    }
+
+    /// Report a generic type that contains itself with other type arguments,
+    /// which was found instantiating types at `span`, if there is one.
+    pub fn report_type_recursion_limit(&mut self, span: Span<'file_name>) {
+        let Some(generic) = self.types.take_overflowed() else {
+            return;
+        };
+
+        self.report(Diagnostic::error(
+            codes::RECURSION_LIMIT,
+            format!(
+                "reached the recursion limit while instantiating `{}`",
+                self.types.to_display(generic)
+            ),
+            Label::new(span, "instantiated recursively here"),
+        ));
+    }
+
+    /// Whether `ty` is, or is built from, a type whose errors have already
+    /// been reported.
+    #[must_use]
+    pub fn is_error_type(&self, ty: TypeId) -> bool {
+        self.types.is_error(ty)
+    }
+
+    /// Whether `ty` is, or is built from, a type parameter, in which case it
+    /// is only used through its instantiations.
+    #[must_use]
+    pub fn contains_parameters(&self, ty: TypeId) -> bool {
+        self.types.contains_parameters(ty)
+    }
+
+    /// The name of `ty` as it is written in the source code, e.g.
+    /// `Pair<i32, f32>`.
+    #[must_use]
+    pub fn type_name(&self, ty: TypeId) -> String {
+        self.types.to_display(ty)
+    }
+
+    /// Get the definition of the struct `ty`, if it is one.
+    #[must_use]
+    pub fn get_struct(&self, ty: TypeId) -> Option<&Struct> {
+        match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Struct(id)) => self.types.get_struct(*id),
+            _ => None,
+        }
+    }
+
+    /// Declare an enum at `path` stored as `representation`, which must be an
+    /// integer type.
+    pub fn add_enum(&mut self, path: Path, representation: PrimitiveType) -> TypeId {
+        self.types.create_enum(path, representation)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `ty` is not an enum.
+    pub fn set_enum_variants(&mut self, ty: TypeId, variants: Vec<EnumVariant>) {
+        let Some(Type::Enum(id)) = self.types.get_by_id(ty).as_deref().cloned() else {
+            panic!("expected {ty:?} to be an enum");
+        };
+
+        self.types.set_enum_variants(id, variants);
+    }
+
+    /// Get the definition of the enum `ty`, if it is one.
+    #[must_use]
+    pub fn get_enum(&self, ty: TypeId) -> Option<&Enum> {
+        match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Enum(id)) => self.types.get_enum(*id),
+            _ => None,
+        }
+    }
+
+    /// Declare an enum at `path` whose variants carry values, with a tag of
+    /// type `tag`, which must be an integer type.
+    pub fn add_union(&mut self, path: Path, tag: PrimitiveType) -> TypeId {
+        self.types.create_union(path, tag)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `ty` is not a tagged union.
+    pub fn set_union_variants(&mut self, ty: TypeId, variants: Vec<UnionVariant>) {
+        let Some(Type::Union(id)) = self.types.get_by_id(ty).as_deref().cloned() else {
+            panic!("expected {ty:?} to be a tagged union");
+        };
+
+        self.types.set_union_variants(id, variants);
+    }
+
+    /// Get the definition of the tagged union `ty`, if it is one.
+    #[must_use]
+    pub fn get_union(&self, ty: TypeId) -> Option<&Union> {
+        match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Union(id)) => self.types.get_union(*id),
+            _ => None,
+        }
+    }
+
+    /// The size and alignment of values of `ty`, see [`Types::layout`].
+    #[must_use]
+    pub fn layout(&self, ty: TypeId) -> Layout {
+        self.types.layout(ty)
+    }
+
+    /// The size and alignment of the values carried by the variants of the
+    /// tagged union `ty`, if it is one.
+    #[must_use]
+    pub fn payload_layout(&self, ty: TypeId) -> Option<Layout> {
+        match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Union(id)) => Some(self.types.payload_layout(*id)),
+            _ => None,
+        }
+    }
+
     // TODO: Implement a building interface for variable types
 
     pub fn create_type_inference_variable(&mut self) -> InferenceId {
@@ -273,18 +622,319 @@ impl HLIRPackage {
         self.type_inference_environment.create_resolved(ty)
     }
 
-    // TODO: Implement a building interface for traits
-    //          - Create a trait
-    //          - Add trait generic type
-    //          - Add trait method
-    //              - Add method generic type
-    //              - Add method parameter (name is irrelevant since it can be
-    //                overridden by the implementor)
-    //              - Add method return type
-    //          - Add trait implementation
-    //              - Add implementation generic type
-    //              - Add implementation generic type restrictions
-    //              - Add implementation method (ideally treated a normal function)
+    /// Record the span of the source code that `id` is the type of, which
+    /// type errors involving it are reported at.
+    ///
+    /// Called for every use of a variable, so errors point at the use they
+    /// were found in rather than the declaration.
+    pub fn set_type_inference_origin(&mut self, id: InferenceId, span: Span<'file_name>) {
+        self.type_inference_environment.set_origin(id, span);
+    }
+
+    /// Record the name the type of `id` was written with where it is not the
+    /// name of the type, e.g. a type alias, which type errors show instead.
+    pub fn set_type_inference_name(&mut self, id: InferenceId, name: String) {
+        self.type_inference_environment.set_name(id, name);
+    }
+
+    fn unify(&mut self, expected: InferenceId, found: InferenceId) {
+        if let Err(error) = self
+            .type_inference_environment
+            .unify(expected, found, &self.types)
+        {
+            self.type_errors.push(*error);
+        }
+    }
+
+    fn constrain(&mut self, id: InferenceId, constraint: TypeConstraint) {
+        if let Err(error) = self
+            .type_inference_environment
+            .constrain(id, constraint, &self.types)
+        {
+            self.type_errors.push(*error);
+        }
+    }
+
+    /// Require `value` to be a value, which something of type `void` is not.
+    ///
+    /// Values that are unified with a type that is known not to be `void`
+    /// need no such requirement, as `void` would not match it.
+    fn constrain_value(&mut self, value: &Expression) {
+        let ty = value.ty(self);
+        self.constrain(ty, TypeConstraint::Value);
+    }
+
+    /// Whether `ty` is `void`, which has no values.
+    #[must_use]
+    pub fn is_void_type(&self, ty: TypeId) -> bool {
+        matches!(
+            self.types.get_by_id(ty).as_deref(),
+            Some(Type::Primitive(PrimitiveType::Void))
+        )
+    }
+
+    fn unify_with(&mut self, id: InferenceId, ty: TypeId) {
+        if let Err(error) = self
+            .type_inference_environment
+            .unify_with(id, ty, &self.types)
+        {
+            self.type_errors.push(*error);
+        }
+    }
+
+    /// Get the ID of a trait by its path.
+    #[must_use]
+    pub fn get_trait_id(&self, path: &Path) -> Option<TraitId> {
+        self.traits.get(path)
+    }
+
+    /// Get the ID of the trait a path written in the current module refers
+    /// to.
+    #[must_use]
+    pub fn resolve_trait_id(&self, path: &Path) -> Option<TraitId> {
+        self.resolve(path, |path| self.traits.get(path))
+    }
+
+    #[must_use]
+    pub fn get_trait(&self, id: TraitId) -> Option<&Trait> {
+        self.traits.get_trait(id)
+    }
+
+    /// Declare a trait without any methods.
+    pub fn create_trait(&mut self, path: Path) -> TraitId {
+        self.traits.get_or_insert(path)
+    }
+
+    /// Add a method to a trait, `prototype` being generic over the type
+    /// implementing the trait (see [`HLIRPackage::push_type_parameters`]),
+    /// which is bounded by the trait.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait does not exist.
+    pub fn add_trait_method(
+        &mut self,
+        id: TraitId,
+        name: &str,
+        mut prototype: FunctionPrototype,
+    ) -> TraitMethodId {
+        let trait_ = self.traits.get_trait(id).expect("trait not found");
+        let mut path = trait_.name.clone();
+        path.push(PathSegment::from(name));
+
+        prototype.bounds = vec![vec![id]];
+        let function = self.functions.create_function(path, prototype);
+        self.traits.add_method(id, name.to_string(), function)
+    }
+
+    /// Start an implementation of the trait `id` for `ty`, whose methods are
+    /// added with [`HLIRPackage::create_trait_impl_method`].
+    pub fn create_trait_impl(&mut self, id: TraitId, ty: TypeId) {
+        self.type_inference_environment.add_implementation(ty, id);
+        self.traits.insert_trait_impl(TraitImpl {
+            trait_id: id,
+            type_id: ty,
+            method_impls: HashMap::new(),
+        });
+    }
+
+    #[must_use]
+    pub fn get_trait_impl(&self, id: TraitId, ty: TypeId) -> Option<&TraitImpl> {
+        self.traits.get_trait_impl(id, ty)
+    }
+
+    /// Create the function defining `method` in the implementation of the
+    /// trait `id` for `ty`, reporting it if `prototype` does not match the
+    /// method in the trait.
+    ///
+    /// The function is named after the trait and the type, e.g.
+    /// `Shape<Circle>::area`, and is implemented like any other function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait, the method, or the implementation do not exist.
+    pub fn create_trait_impl_method(
+        &mut self,
+        id: TraitId,
+        ty: TypeId,
+        method: TraitMethodId,
+        prototype: FunctionPrototype,
+        span: Span<'file_name>,
+    ) -> FunctionId {
+        let trait_ = self.traits.get_trait(id).expect("trait not found");
+        let definition = trait_
+            .method_definitions
+            .get(&method)
+            .expect("trait method not found");
+        let name = definition.name.clone();
+
+        let trait_name = self.traits.to_display(id);
+        let mut path = self.trait_impl_path(id, ty);
+        path.push(PathSegment::from(name.as_str()));
+
+        let expected = self
+            .functions
+            .get_prototype(definition.function)
+            .expect("expected trait method to have a prototype");
+        let expected = FunctionPrototype {
+            type_parameters: Vec::new(),
+            bounds: Vec::new(),
+            parameters: expected
+                .parameters
+                .iter()
+                .map(|parameter| self.types.substitute(*parameter, &[ty]))
+                .collect(),
+            is_variadic: expected.is_variadic,
+            return_type: self.types.substitute(expected.return_type, &[ty]),
+        };
+
+        if prototype != expected {
+            self.report(Diagnostic::error(
+                codes::MISMATCHED_METHOD_SIGNATURE,
+                format!("method `{name}` does not match its declaration in trait `{trait_name}`"),
+                Label::new(
+                    span,
+                    format!(
+                        "expected `{}`, found `{}`",
+                        self.prototype_display(&expected),
+                        self.prototype_display(&prototype)
+                    ),
+                ),
+            ));
+        }
+
+        let function = self.functions.create_function(path, prototype);
+        self.traits
+            .get_trait_impl_mut(id, ty)
+            .expect("trait implementation not found")
+            .method_impls
+            .insert(method, function);
+        function
+    }
+
+    /// The path of the implementation of the trait `id` for `ty`, which the
+    /// items of the implementation are named after, e.g. `Shape<Circle>`.
+    fn trait_impl_path(&self, id: TraitId, ty: TypeId) -> Path {
+        let mut path = self
+            .traits
+            .get_trait(id)
+            .expect("trait not found")
+            .name
+            .clone();
+        let trait_name = path.pop().expect("expected path to have a name").name;
+        path.push(PathSegment::with_arguments(
+            trait_name,
+            vec![self.types.to_path(ty)],
+        ));
+        path
+    }
+
+    /// Get the table of methods of every implementation of a trait, which the
+    /// trait objects pointing to values of the type use to call the methods.
+    ///
+    /// Each is given with the trait, the type, the name of the table, and the
+    /// functions defining the methods in the order the trait declares them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a trait with implementations does not exist.
+    #[must_use]
+    pub fn vtables(&self) -> Vec<(TraitId, TypeId, String, Vec<FunctionId>)> {
+        let mut vtables = Vec::new();
+
+        for id in self.traits.ids() {
+            let methods = self
+                .traits
+                .get_trait(id)
+                .expect("trait not found")
+                .method_ids();
+            let mut impls = self
+                .traits
+                .get_trait_impls(id)
+                .into_iter()
+                .flat_map(HashMap::values)
+                .collect::<Vec<_>>();
+            impls.sort_unstable_by_key(|impl_| impl_.type_id);
+
+            for impl_ in impls {
+                // Implementations missing methods have been reported
+                let Some(functions) = methods
+                    .iter()
+                    .map(|method| impl_.method_impls.get(method).copied())
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+
+                let mut path = self.trait_impl_path(id, impl_.type_id);
+                path.push(PathSegment::from("vtable"));
+                vtables.push((id, impl_.type_id, path.to_mangled_string(), functions));
+            }
+        }
+
+        vtables
+    }
+
+    /// Get the function defining the method `name` in the implementation of
+    /// the trait `id` for `ty`.
+    #[must_use]
+    pub fn get_trait_impl_method(&self, id: TraitId, ty: TypeId, name: &str) -> Option<FunctionId> {
+        let method = self.traits.get_trait(id)?.get_method(name)?;
+        self.traits
+            .get_trait_impl(id, ty)?
+            .method_impls
+            .get(&method)
+            .copied()
+    }
+
+    /// The path of the method `name` defined by an `impl` block of `ty`
+    /// itself, e.g. `Point::length`.
+    fn method_path(&self, ty: TypeId, name: &str) -> Path {
+        let mut path = self.types.to_path(ty);
+        path.push(PathSegment::from(name));
+        path
+    }
+
+    /// Define the method `name` in an `impl` block of `ty` itself.
+    pub fn create_method(
+        &mut self,
+        ty: TypeId,
+        name: &str,
+        prototype: FunctionPrototype,
+    ) -> FunctionId {
+        let path = self.method_path(ty, name);
+        self.functions.create_function(path, prototype)
+    }
+
+    /// Get the function defining the method `name` in an `impl` block of `ty`
+    /// itself.
+    #[must_use]
+    pub fn get_method(&self, ty: TypeId, name: &str) -> Option<FunctionId> {
+        self.functions.get(&self.method_path(ty, name))
+    }
+
+    /// The signature of a function as it is written in the source code, e.g.
+    /// `fn(*Circle, f32): f32`.
+    fn prototype_display(&self, prototype: &FunctionPrototype) -> String {
+        let mut parameters = prototype
+            .parameters
+            .iter()
+            .map(|ty| self.types.to_display(*ty))
+            .collect::<Vec<_>>();
+        if prototype.is_variadic {
+            parameters.push("...".to_string());
+        }
+
+        let mut display = format!("fn({})", parameters.join(", "));
+        if !matches!(
+            self.types.get_by_id(prototype.return_type).as_deref(),
+            Some(Type::Primitive(PrimitiveType::Void))
+        ) {
+            display.push_str(": ");
+            display.push_str(&self.types.to_display(prototype.return_type));
+        }
+        display
+    }
 
     // TODO: Implement a building interface for function prototypes
 
@@ -293,6 +943,13 @@ impl HLIRPackage {
         self.functions.get(path)
     }
 
+    /// Get the ID of the function a path written in the current module refers
+    /// to, e.g. `area` or `shapes::area`.
+    #[must_use]
+    pub fn resolve_function_id(&self, path: &Path) -> Option<FunctionId> {
+        self.resolve(path, |path| self.functions.get(path))
+    }
+
     #[must_use]
     pub fn get_function_path(&self, id: &FunctionId) -> Option<&Path> {
         self.functions.get_path(id)
@@ -305,35 +962,93 @@ impl HLIRPackage {
 
     pub fn build_function_prototype(
         &mut self,
+        type_parameters: Vec<TypeId>,
+        bounds: Vec<Vec<TraitId>>,
         parameters: Vec<TypeId>,
         is_variadic: bool,
         return_type: TypeId,
     ) -> FunctionPrototype {
         FunctionPrototype {
+            type_parameters,
+            bounds,
             parameters,
             is_variadic,
             return_type,
         }
     }
 
+    /// Start a scope in which the type parameters of a generic function can
+    /// be found by their names, getting the type of each parameter.
+    ///
+    /// The scope is ended with [`HLIRPackage::pop_type_parameters`].
+    pub fn push_type_parameters(&mut self, names: &[&str]) -> Vec<TypeId> {
+        self.types.push_scope();
+
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let ty = self
+                    .types
+                    .get_or_insert(Type::Parameter(index, (*name).to_string()));
+                self.types.create_type_with_id(Path::from(*name), ty);
+                ty
+            })
+            .collect()
+    }
+
+    /// Start a scope in which the type parameters of the generic struct or
+    /// tagged union `ty` can be found by their names, to lower its fields.
+    ///
+    /// The scope is ended with [`HLIRPackage::pop_type_parameters`].
+    pub fn push_type_parameters_of(&mut self, ty: TypeId) {
+        self.types.push_scope();
+
+        let type_parameters = self.types.type_parameters(ty).to_vec();
+        self.add_type_parameters_to_scope(&type_parameters);
+    }
+
+    /// Start a scope in which `Self` is the type `ty`, to lower the methods
+    /// of an implementation of a trait for it.
+    ///
+    /// The scope is ended with [`HLIRPackage::pop_type_parameters`].
+    pub fn push_self_type(&mut self, ty: TypeId) {
+        self.types.push_scope();
+        self.types.create_type_with_id(Path::from("Self"), ty);
+    }
+
+    pub fn pop_type_parameters(&mut self) {
+        self.types.pop_scope();
+    }
+
+    fn add_type_parameters_to_scope(&mut self, type_parameters: &[TypeId]) {
+        for ty in type_parameters {
+            if let Some(Type::Parameter(_, name)) = self.types.get_by_id(*ty).as_deref() {
+                self.types
+                    .create_type_with_id(Path::from(name.as_str()), *ty);
+            }
+        }
+    }
+
     // TODO: Implement a building interface for functions
 
     pub fn create_function(&mut self, path: Path, prototype: FunctionPrototype) -> FunctionId {
         self.functions.create_function(path, prototype)
     }
 
-    /// Start building a function.
+    /// Start building a function declared at `span`.
     ///
     /// # Panics
     ///
     /// This function will panic if a function with the same ID is already being built.
-    pub fn start_function_impl(&mut self, id: FunctionId) {
+    pub fn start_function_impl(&mut self, id: FunctionId, span: Span<'file_name>) {
         assert!(
             !self.building_functions.contains_key(&id),
             "function already being built"
         );
 
         self.current_function = Some(id);
+        self.current_function_span = Some(span);
 
         let prototype = self
             .functions
@@ -343,6 +1058,18 @@ impl HLIRPackage {
         let variable_scope = self.variables.push_scope();
 
         let type_scope = self.types.push_scope();
+        self.add_type_parameters_to_scope(&prototype.type_parameters);
+
+        // The type parameters implement the traits they are bounded by
+        // throughout the body
+        self.type_inference_environment.set_bounds(
+            prototype
+                .type_parameters
+                .iter()
+                .zip(&prototype.bounds)
+                .flat_map(|(ty, bounds)| bounds.iter().map(|bound| (*ty, *bound)))
+                .collect(),
+        );
 
         self.building_functions.insert(
             id,
@@ -378,7 +1105,7 @@ impl HLIRPackage {
         builder.set_body(body);
     }
 
-    /// Finish building a function.
+    /// Finish building a function, reporting every type error found in it.
     ///
     /// # Panics
     ///
@@ -390,17 +1117,80 @@ impl HLIRPackage {
             .remove(&id)
             .expect("function not being built");
         self.current_function.take();
+        let span = self
+            .current_function_span
+            .take()
+            .expect("function not being built");
 
-        self.type_inference_environment.unify_final(&self.types);
+        self.finish_type_inference(span);
 
         let function = builder.build().expect("function body not set");
 
+        let return_type = function.prototype.return_type;
+        if !self.is_void_type(return_type) && !function.body.diverges() {
+            self.report(Diagnostic::error(
+                codes::MISSING_RETURN,
+                "not all paths of this function return a value",
+                Label::new(
+                    span,
+                    format!(
+                        "may reach its end without returning `{}`",
+                        self.types.to_display(return_type)
+                    ),
+                ),
+            ));
+        }
+
         self.variables.pop_scope();
         self.types.pop_scope();
 
         self.functions.insert(id, function);
     }
 
+    /// Finish inferring the types of the function or global being built,
+    /// reporting every type error found in it at `span` unless the error has a
+    /// better location.
+    fn finish_type_inference(&mut self, span: Span<'file_name>) {
+        // Type arguments are inferred from the values whose types are known
+        // before the default types of literals are used in their place
+        let calls = core::mem::take(&mut self.generic_calls);
+        self.bind_resolved_calls(&calls);
+        self.bind_value_types();
+
+        // The types of generic calls are only known once the type arguments
+        // are, which may need the default types of literals
+        let errors = self.type_inference_environment.apply_defaults(&self.types);
+        self.type_errors.extend(errors);
+        self.bind_resolved_calls(&calls);
+        self.bind_value_types();
+        self.array_values.clear();
+        self.reference_values.clear();
+
+        for call in calls {
+            self.check_generic_call(&call);
+        }
+        self.generic_values.clear();
+        self.report_type_recursion_limit(span);
+
+        let errors = self.type_inference_environment.unify_final(&self.types);
+        self.type_errors.extend(errors);
+        self.type_inference_environment.set_bounds(Vec::new());
+
+        for error in core::mem::take(&mut self.type_errors) {
+            let diagnostic = error.to_diagnostic(
+                &self.type_inference_environment,
+                &self.types,
+                &self.traits,
+                span,
+            );
+            self.report(diagnostic);
+        }
+
+        for check in core::mem::take(&mut self.match_checks) {
+            self.check_match(&check);
+        }
+    }
+
     // TODO: Implement a building interface for variables
 
     /// Add a variable to the HLIR package and allow it to be accessed by its
@@ -412,7 +1202,9 @@ impl HLIRPackage {
     /// Get the ID of a variable by its name.
     #[must_use]
     pub fn get_variable_id(&self, name: &str) -> Option<VariableId> {
-        self.variables.get(name)
+        self.variables
+            .get(name)
+            .or_else(|| self.resolve_global_id(&Path::from(name)))
     }
 
     /// # Panics
@@ -423,18 +1215,510 @@ impl HLIRPackage {
         self.variables.get_name(id).expect("variable not found")
     }
 
-    // TODO: Implement a building interface for statements
+    /// Declare a constant or static of type `ty` at `path`, unless a value is
+    /// already declared there.
+    pub fn add_global(&mut self, path: Path, kind: GlobalKind, ty: TypeId) -> Option<VariableId> {
+        let inference = self.create_type_inference_resolved(ty);
+        let id = self.variables.create_global(path.clone(), inference)?;
+
+        // External statics are defined by other programs, which know them by
+        // the name they are declared with
+        let symbol = match kind {
+            GlobalKind::ExternalStatic => path
+                .segments()
+                .last()
+                .map_or_else(String::new, |segment| segment.name.clone()),
+            GlobalKind::Constant | GlobalKind::Static => path.to_mangled_string(),
+        };
 
-    pub fn build_block_start(&mut self) {
-        let variable_scope = self.variables.push_scope();
-        let type_scope = self.types.push_scope();
+        self.globals.insert(
+            id,
+            Global {
+                kind,
+                path,
+                symbol,
+                ty,
+                value: None,
+            },
+        );
 
-        self.current_blocks.push((variable_scope, type_scope));
+        Some(id)
     }
 
-    /// Finish building a block and get the statement.
-    ///
-    /// # Panics
+    /// Get the constant or static declared at the full `path`.
+    #[must_use]
+    pub fn get_global_id(&self, path: &Path) -> Option<VariableId> {
+        self.variables.get_global(path)
+    }
+
+    /// Find the constant or static a path written in the current module refers
+    /// to.
+    #[must_use]
+    pub fn resolve_global_id(&self, path: &Path) -> Option<VariableId> {
+        self.resolve(path, |path| self.variables.get_global(path))
+    }
+
+    /// Get the constant or static a variable refers to, if it is one.
+    #[must_use]
+    pub fn get_global(&self, id: VariableId) -> Option<&Global> {
+        self.globals.get(&id)
+    }
+
+    /// Get every constant and static, in the order they were declared.
+    pub fn globals(&self) -> impl Iterator<Item = (VariableId, &Global)> {
+        let mut globals: Vec<_> = self
+            .globals
+            .iter()
+            .map(|(id, global)| (*id, global))
+            .collect();
+        globals.sort_by_key(|(id, _)| *id);
+
+        globals.into_iter()
+    }
+
+    /// Start building the value of a constant or static, written at `span`,
+    /// until [`HLIRPackage::finish_global_initializer`] is called.
+    pub fn start_global_initializer(&mut self, span: Span<'file_name>) {
+        self.current_function_span = Some(span);
+    }
+
+    /// Finish building the value of the constant or static `id`, reporting
+    /// every type error found in it.
+    ///
+    /// The value itself is only computed by [`HLIRPackage::evaluate_globals`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a global or if no value is being built.
+    pub fn finish_global_initializer(&mut self, id: VariableId, value: Expression) {
+        let span = self
+            .current_function_span
+            .take()
+            .expect("global initializer not being built");
+        let global_ty = self.globals.get(&id).expect("expected a global").ty;
+        let ty = self.variables.get_type(id).expect("expected a global");
+
+        let value = self.coerce(value, global_ty);
+        let value_ty = value.ty(self);
+        self.unify(ty, value_ty);
+
+        let reported = self.diagnostics.len();
+        self.finish_type_inference(span);
+
+        // The value is not computed if it has type errors, which have just
+        // been reported
+        let value = if self.diagnostics.len() == reported {
+            value
+        } else {
+            self.build_expression_error()
+        };
+
+        self.initializers.insert(id, (value, span));
+    }
+
+    /// Compute the values of every constant and static, reporting the ones that
+    /// cannot be computed at compile time.
+    pub fn evaluate_globals(&mut self) {
+        let mut ids: Vec<VariableId> = self.initializers.keys().copied().collect();
+        ids.sort();
+
+        let mut evaluation = Evaluation::default();
+        for id in ids {
+            // The problems have already been reported
+            let _ = self.evaluate_global(id, &mut evaluation);
+        }
+    }
+
+    /// Compute the value of the global `id`, reporting the problems found in
+    /// its own initializer.
+    fn evaluate_global(
+        &mut self,
+        id: VariableId,
+        evaluation: &mut Evaluation,
+    ) -> Result<ConstantValue, EvaluationError> {
+        if let Some(value) = evaluation.values.get(&id) {
+            return value.clone().ok_or(EvaluationError::Reported);
+        }
+
+        if let Some(position) = evaluation.stack.iter().position(|other| *other == id) {
+            return Err(EvaluationError::Cycle(
+                evaluation.stack[position..].to_vec(),
+            ));
+        }
+
+        // External statics have no value
+        let Some((initializer, span)) = self.initializers.remove(&id) else {
+            return Err(EvaluationError::NotConstant);
+        };
+
+        evaluation.stack.push(id);
+        let result = self.evaluate(&initializer, evaluation);
+        evaluation.stack.pop();
+
+        let value = match result {
+            Ok(value) => value,
+            // The global that closes the cycle reports it
+            Err(EvaluationError::Cycle(cycle)) if cycle[0] != id => {
+                evaluation.values.insert(id, None);
+                return Err(EvaluationError::Cycle(cycle));
+            }
+            Err(error) => {
+                self.report_evaluation_error(id, &error, span);
+                evaluation.values.insert(id, None);
+                return Err(EvaluationError::Reported);
+            }
+        };
+
+        let ty = self.variables.get_type(id).expect("expected a global");
+        let global = self.globals.get_mut(&id).expect("expected a global");
+        global.value = Some(value.clone().into_constant(ty));
+
+        evaluation.values.insert(id, Some(value.clone()));
+
+        Ok(value)
+    }
+
+    fn report_evaluation_error(
+        &mut self,
+        id: VariableId,
+        error: &EvaluationError,
+        span: Span<'file_name>,
+    ) {
+        let global = self.globals.get(&id).expect("expected a global");
+        let (kind, name) = (global.kind, global.name());
+
+        let diagnostic = match error {
+            EvaluationError::NotConstant => Diagnostic::error(
+                codes::NON_CONSTANT_VALUE,
+                format!("the value of {kind} `{name}` cannot be computed at compile time"),
+                Label::new(span, "not a constant expression"),
+            )
+            .with_note(
+                "constants and statics can only be initialized with literals, constants, operators and array and struct literals",
+            ),
+            EvaluationError::Overflow(ty) => Diagnostic::error(
+                codes::CONSTANT_EVALUATION_FAILED,
+                format!("the value of {kind} `{name}` overflows `{ty}`"),
+                Label::new(span, format!("does not fit in `{ty}`")),
+            ),
+            EvaluationError::DivisionByZero => Diagnostic::error(
+                codes::CONSTANT_EVALUATION_FAILED,
+                format!("the value of {kind} `{name}` divides by zero"),
+                Label::new(span, "division by zero"),
+            ),
+            EvaluationError::Cycle(cycle) => {
+                let name_of = |id: &VariableId| {
+                    format!(
+                        "`{}`",
+                        self.globals.get(id).expect("expected a global").name()
+                    )
+                };
+                let references = cycle
+                    .iter()
+                    .skip(1)
+                    .chain([&cycle[0]])
+                    .map(name_of)
+                    .collect::<Vec<_>>()
+                    .join(", which refers to ");
+
+                Diagnostic::error(
+                    codes::CYCLIC_CONSTANT,
+                    format!("{kind} `{name}` refers to itself"),
+                    Label::new(span, "defined in terms of itself"),
+                )
+                .with_note(format!("`{name}` refers to {references}"))
+            }
+            EvaluationError::Reported => return,
+        };
+
+        self.report(diagnostic);
+    }
+
+    fn evaluate(
+        &mut self,
+        expression: &Expression,
+        evaluation: &mut Evaluation,
+    ) -> Result<ConstantValue, EvaluationError> {
+        match expression {
+            Expression::Literal(literal) => self.evaluate_literal(literal),
+            // The values of statics may change, so only constants can be used
+            Expression::Variable(variable) => match self.globals.get(variable) {
+                Some(global) if global.kind == GlobalKind::Constant => {
+                    self.evaluate_global(*variable, evaluation)
+                }
+                _ => Err(EvaluationError::NotConstant),
+            },
+            Expression::UnaryOperation(unary) => {
+                let operand = self.evaluate(&unary.operand, evaluation)?;
+                self.evaluate_unary(unary.operator, operand, unary.result)
+            }
+            Expression::BinaryOperation(binary) => {
+                let left = self.evaluate(&binary.left, evaluation)?;
+                let right = self.evaluate(&binary.right, evaluation)?;
+                self.evaluate_binary(&binary.operator, left, right, binary.result)
+            }
+            Expression::TypeCast(cast) => {
+                let value = self.evaluate(&cast.expression, evaluation)?;
+                self.evaluate_cast(value, cast.target_type)
+            }
+            Expression::ArrayLiteral(array) => self
+                .evaluate_all(&array.elements, evaluation)
+                .map(ConstantValue::Array),
+            Expression::ArrayRepeat(repeat) => {
+                let value = self.evaluate(&repeat.value, evaluation)?;
+                let element = (value, repeat.value.ty(self));
+                Ok(ConstantValue::Array(vec![element; repeat.length]))
+            }
+            Expression::StructLiteral(literal) => self
+                .evaluate_all(&literal.fields, evaluation)
+                .map(ConstantValue::Struct),
+            Expression::Error(_) => Err(EvaluationError::Reported),
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+    /// Evaluate each of `expressions`, keeping the type of each value.
+    fn evaluate_all(
+        &mut self,
+        expressions: &[Expression],
+        evaluation: &mut Evaluation,
+    ) -> Result<Vec<(ConstantValue, InferenceId)>, EvaluationError> {
+        expressions
+            .iter()
+            .map(|expression| {
+                let value = self.evaluate(expression, evaluation)?;
+                Ok((value, expression.ty(self)))
+            })
+            .collect()
+    }
+
+    /// Get the primitive type a value of type `ty` has, if it has one.
+    fn constant_type(&self, ty: InferenceId) -> Result<PrimitiveType, EvaluationError> {
+        let primitive = self
+            .get_resolved_type(ty)
+            .and_then(|ty| self.types.get_by_id(ty))
+            .and_then(|ty| match *ty {
+                Type::Primitive(primitive) => Some(primitive),
+                _ => None,
+            });
+
+        primitive.ok_or(EvaluationError::NotConstant)
+    }
+
+    /// Get `value` as an integer of type `ty`, if it is in the range of `ty`.
+    fn constant_integer(
+        &self,
+        value: Option<i128>,
+        ty: InferenceId,
+    ) -> Result<ConstantValue, EvaluationError> {
+        let primitive = self.constant_type(ty)?;
+        let range = primitive
+            .integer_range()
+            .ok_or(EvaluationError::NotConstant)?;
+
+        match value {
+            Some(value) if range.contains(&value) => Ok(ConstantValue::Integer(value)),
+            _ => Err(EvaluationError::Overflow(primitive)),
+        }
+    }
+
+    fn evaluate_literal(&self, literal: &Literal) -> Result<ConstantValue, EvaluationError> {
+        match &literal.value {
+            LiteralValue::Integer { value, radix } => {
+                let value = LiteralValue::parse_integer(value, *radix).ok();
+
+                // An integer literal may also be used as a float
+                match self.constant_type(literal.ty)? {
+                    primitive if primitive.is_float() => {
+                        #[allow(clippy::cast_precision_loss)]
+                        let value = value.map(|value| value as f64);
+                        value
+                            .map(ConstantValue::Float)
+                            .ok_or(EvaluationError::Overflow(primitive))
+                    }
+                    _ => self.constant_integer(value, literal.ty),
+                }
+            }
+            LiteralValue::Float { value } => value
+                .parse()
+                .map(ConstantValue::Float)
+                .map_err(|_| EvaluationError::NotConstant),
+            LiteralValue::Boolean { value } => Ok(ConstantValue::Boolean(*value)),
+            LiteralValue::Character { value } => Ok(ConstantValue::Character(value.clone())),
+            LiteralValue::String { value } => Ok(ConstantValue::String(value.clone())),
+        }
+    }
+
+    fn evaluate_unary(
+        &self,
+        operator: UnaryOperator,
+        operand: ConstantValue,
+        result: InferenceId,
+    ) -> Result<ConstantValue, EvaluationError> {
+        match (operator, operand) {
+            (UnaryOperator::Negation, ConstantValue::Integer(value)) => {
+                self.constant_integer(value.checked_neg(), result)
+            }
+            (UnaryOperator::Negation, ConstantValue::Float(value)) => {
+                Ok(ConstantValue::Float(-value))
+            }
+            (UnaryOperator::BitwiseNot, ConstantValue::Integer(value)) => {
+                let primitive = self.constant_type(result)?;
+                self.constant_integer(Some(wrap_integer(!value, primitive)), result)
+            }
+            (UnaryOperator::LogicalNot, ConstantValue::Boolean(value)) => {
+                Ok(ConstantValue::Boolean(!value))
+            }
+            _ => Err(EvaluationError::NotConstant),
+        }
+    }
+
+    fn evaluate_binary(
+        &self,
+        operator: &BinaryOperator,
+        left: ConstantValue,
+        right: ConstantValue,
+        result: InferenceId,
+    ) -> Result<ConstantValue, EvaluationError> {
+        let value = match (left, right) {
+            (ConstantValue::Integer(left), ConstantValue::Integer(right)) => {
+                return self.evaluate_integer_operation(operator, left, right, result);
+            }
+            (ConstantValue::Float(left), ConstantValue::Float(right)) => match operator {
+                BinaryOperator::Addition => ConstantValue::Float(left + right),
+                BinaryOperator::Subtraction => ConstantValue::Float(left - right),
+                BinaryOperator::Multiplication => ConstantValue::Float(left * right),
+                BinaryOperator::Division => ConstantValue::Float(left / right),
+                BinaryOperator::Remainder => ConstantValue::Float(left % right),
+                _ => compare(operator, left.partial_cmp(&right))
+                    .map(ConstantValue::Boolean)
+                    .ok_or(EvaluationError::NotConstant)?,
+            },
+            (ConstantValue::Boolean(left), ConstantValue::Boolean(right)) => match operator {
+                BinaryOperator::LogicalAnd => ConstantValue::Boolean(left && right),
+                BinaryOperator::LogicalOr => ConstantValue::Boolean(left || right),
+                _ => compare(operator, Some(left.cmp(&right)))
+                    .map(ConstantValue::Boolean)
+                    .ok_or(EvaluationError::NotConstant)?,
+            },
+            (ConstantValue::Character(left), ConstantValue::Character(right)) => {
+                compare(operator, Some(left.cmp(&right)))
+                    .map(ConstantValue::Boolean)
+                    .ok_or(EvaluationError::NotConstant)?
+            }
+            _ => return Err(EvaluationError::NotConstant),
+        };
+
+        Ok(value)
+    }
+
+    fn evaluate_integer_operation(
+        &self,
+        operator: &BinaryOperator,
+        left: i128,
+        right: i128,
+        result: InferenceId,
+    ) -> Result<ConstantValue, EvaluationError> {
+        if let Some(comparison) = compare(operator, Some(left.cmp(&right))) {
+            return Ok(ConstantValue::Boolean(comparison));
+        }
+
+        let value = match operator {
+            BinaryOperator::Addition => left.checked_add(right),
+            BinaryOperator::Subtraction => left.checked_sub(right),
+            BinaryOperator::Multiplication => left.checked_mul(right),
+            BinaryOperator::Division | BinaryOperator::Remainder if right == 0 => {
+                return Err(EvaluationError::DivisionByZero);
+            }
+            BinaryOperator::Division => left.checked_div(right),
+            BinaryOperator::Remainder => left.checked_rem(right),
+            BinaryOperator::BitwiseAnd => Some(left & right),
+            BinaryOperator::BitwiseOr => Some(left | right),
+            BinaryOperator::BitwiseXor => Some(left ^ right),
+            BinaryOperator::BitwiseShiftLeft | BinaryOperator::BitwiseShiftRight => {
+                let primitive = self.constant_type(result)?;
+                let range = primitive
+                    .integer_range()
+                    .ok_or(EvaluationError::NotConstant)?;
+                let bits = (range.end() - range.start()).count_ones();
+
+                // Shifting by at least as many bits as the type has overflows
+                match u32::try_from(right) {
+                    Ok(amount) if amount < bits => {
+                        if *operator == BinaryOperator::BitwiseShiftLeft {
+                            Some(wrap_integer(left.wrapping_shl(amount), primitive))
+                        } else {
+                            Some(left >> amount)
+                        }
+                    }
+                    _ => return Err(EvaluationError::Overflow(primitive)),
+                }
+            }
+            _ => return Err(EvaluationError::NotConstant),
+        };
+
+        self.constant_integer(value, result)
+    }
+
+    /// Convert a value to the type `ty` the way a cast does at run time.
+    fn evaluate_cast(
+        &self,
+        value: ConstantValue,
+        ty: InferenceId,
+    ) -> Result<ConstantValue, EvaluationError> {
+        let primitive = self.constant_type(ty)?;
+
+        let value = match (value, primitive.integer_range()) {
+            (ConstantValue::Integer(value), Some(_)) => {
+                ConstantValue::Integer(wrap_integer(value, primitive))
+            }
+            (ConstantValue::Boolean(value), Some(_)) => ConstantValue::Integer(i128::from(value)),
+            (ConstantValue::Character(value), Some(_)) => {
+                let byte = value.bytes().next().unwrap_or_default();
+                ConstantValue::Integer(wrap_integer(i128::from(byte), primitive))
+            }
+            // Floats are rounded towards zero, saturating at the bounds of
+            // the type
+            (ConstantValue::Float(value), Some(range)) => {
+                #[allow(clippy::cast_possible_truncation)]
+                let value = value as i128;
+                ConstantValue::Integer(value.clamp(*range.start(), *range.end()))
+            }
+            #[allow(clippy::cast_precision_loss)]
+            (ConstantValue::Integer(value), None) if primitive == PrimitiveType::F32 => {
+                ConstantValue::Float(f64::from(value as f32))
+            }
+            #[allow(clippy::cast_precision_loss)]
+            (ConstantValue::Integer(value), None) if primitive == PrimitiveType::F64 => {
+                ConstantValue::Float(value as f64)
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            (ConstantValue::Float(value), None) if primitive == PrimitiveType::F32 => {
+                ConstantValue::Float(f64::from(value as f32))
+            }
+            (value @ ConstantValue::Float(_), None) if primitive == PrimitiveType::F64 => value,
+            (value @ ConstantValue::Boolean(_), None) if primitive == PrimitiveType::Bool => value,
+            (value @ ConstantValue::Character(_), None) if primitive == PrimitiveType::Char => {
+                value
+            }
+            _ => return Err(EvaluationError::NotConstant),
+        };
+
+        Ok(value)
+    }
+
+    // TODO: Implement a building interface for statements
+
+    pub fn build_block_start(&mut self) {
+        let variable_scope = self.variables.push_scope();
+        let type_scope = self.types.push_scope();
+
+        self.current_blocks.push((variable_scope, type_scope));
+    }
+
+    /// Finish building a block and get the statement.
+    ///
+    /// # Panics
     ///
     /// This function will panic if a block is not being built.
     pub fn build_block_end(&mut self, statements: Vec<Statement>) -> Statement {
@@ -456,10 +1740,18 @@ impl HLIRPackage {
         ty: InferenceId,
         value: Expression,
     ) -> Statement {
+        let value = match self.get_resolved_type(ty) {
+            Some(resolved) => self.coerce(value, resolved),
+            None => value,
+        };
         let expression_ty = value.ty(self);
 
-        self.type_inference_environment
-            .unify(ty, expression_ty, &self.types);
+        // A type other than `void` given to the variable only matches values
+        match self.get_resolved_type(ty) {
+            Some(resolved) if !self.is_void_type(resolved) => {}
+            _ => self.constrain_value(&value),
+        }
+        self.unify(ty, expression_ty);
 
         Let {
             variable: self.create_variable(name, ty),
@@ -481,12 +1773,11 @@ impl HLIRPackage {
     ) -> Statement {
         let ty = condition.ty(self);
 
-        self.type_inference_environment.unify_with(
+        self.unify_with(
             ty,
             self.types
                 .get(&Path::new(vec![PathSegment::from("bool".to_string())]))
                 .expect("expected bool type to exist in package"),
-            &self.types,
         );
 
         If {
@@ -497,98 +1788,1116 @@ impl HLIRPackage {
         .into()
     }
 
+    /// Build a while loop.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is no bool type in the package.
     pub fn build_statement_while(&mut self, condition: Expression, block: Statement) -> Statement {
+        let ty = condition.ty(self);
+
+        self.unify_with(
+            ty,
+            self.types
+                .get(&Path::new(vec![PathSegment::from("bool".to_string())]))
+                .expect("expected bool type to exist in package"),
+        );
+
         While {
             condition,
             body: Box::new(block),
         }
-        .into()
-    }
+        .into()
+    }
+
+    /// Build a for loop, the initializer is built as a separate statement
+    /// before the loop.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there is no bool type in the package.
+    pub fn build_statement_for(
+        &mut self,
+        condition: Expression,
+        update: Statement,
+        body: Statement,
+    ) -> Statement {
+        let ty = condition.ty(self);
+
+        self.unify_with(
+            ty,
+            self.types
+                .get(&Path::new(vec![PathSegment::from("bool".to_string())]))
+                .expect("expected bool type to exist in package"),
+        );
+
+        For {
+            condition,
+            update: Box::new(update),
+            body: Box::new(body),
+        }
+        .into()
+    }
+
+    pub fn build_statement_break(&mut self) -> Statement {
+        Statement::Break
+    }
+
+    pub fn build_statement_continue(&mut self) -> Statement {
+        Statement::Continue
+    }
+
+    pub fn build_statement_return_void(&mut self) -> Statement {
+        Return::void().into()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the current function is not found.
+    pub fn build_statement_return(&mut self, value: Expression) -> Statement {
+        let function_return_ty = self
+            .building_functions
+            .get(
+                &self
+                    .current_function
+                    .expect("returns cannot be outside of a function"),
+            )
+            .expect("function not found")
+            .prototype
+            .return_type;
+
+        let value = self.coerce(value, function_return_ty);
+        let ty = value.ty(self);
+
+        // A function returning `void` returns without a value
+        if self.is_void_type(function_return_ty) {
+            self.constrain_value(&value);
+        }
+        self.unify_with(ty, function_return_ty);
+
+        Return { value: Some(value) }.into()
+    }
+
+    /// Build a match of `scrutinee`, found at `span`, against `arms`, each
+    /// paired with the span of its pattern.
+    ///
+    /// Whether the arms cover every value and whether each of them can be
+    /// reached is checked once the function is finished and the type of the
+    /// scrutinee is known.
+    pub fn build_statement_match(
+        &mut self,
+        scrutinee: Expression,
+        arms: Vec<(MatchArm, Span<'file_name>)>,
+        span: Span<'file_name>,
+    ) -> Statement {
+        self.constrain_value(&scrutinee);
+        self.match_checks.push(MatchCheck {
+            scrutinee: scrutinee.ty(self),
+            span,
+            arms: arms
+                .iter()
+                .map(|(arm, span)| (arm.pattern.clone(), *span))
+                .collect(),
+        });
+
+        Match {
+            scrutinee,
+            arms: arms.into_iter().map(|(arm, _)| arm).collect(),
+        }
+        .into()
+    }
+
+    /// Report the values of a match that no arm matches and the arms that can
+    /// never be reached.
+    fn check_match(&mut self, check: &MatchCheck<'file_name>) {
+        let Some(ty) = self.get_resolved_type(check.scrutinee) else {
+            return;
+        };
+
+        // The problems with the patterns have already been reported
+        if self.types.is_error(ty) || check.arms.iter().any(|(pattern, _)| pattern.has_error()) {
+            return;
+        }
+
+        let checker = PatternChecker::new(&self.types);
+        let patterns = check
+            .arms
+            .iter()
+            .map(|(pattern, _)| pattern)
+            .collect::<Vec<_>>();
+
+        let unreachable = check
+            .arms
+            .iter()
+            .enumerate()
+            .filter(|(index, (pattern, _))| !checker.is_reachable(&patterns[..*index], pattern, ty))
+            .map(|(_, (_, span))| *span)
+            .collect::<Vec<_>>();
+        let missing = checker.missing(&patterns, ty);
+
+        for span in unreachable {
+            self.report(Diagnostic::warning(
+                codes::UNREACHABLE_PATTERN,
+                "unreachable pattern",
+                Label::new(span, "already matched by the arms above"),
+            ));
+        }
+
+        if !missing.is_empty() {
+            // Only the first few are listed, there can be a lot of them
+            let mut listed = missing
+                .iter()
+                .take(3)
+                .map(|pattern| format!("`{pattern}`"))
+                .collect::<Vec<_>>();
+            let patterns = match missing.len() {
+                1 => format!("pattern {}", listed[0]),
+                2 | 3 => {
+                    let last = listed.pop().expect("expected a missing pattern");
+                    format!("patterns {} and {last}", listed.join(", "))
+                }
+                count => format!("patterns {} and {} more", listed.join(", "), count - 3),
+            };
+
+            self.report(Diagnostic::error(
+                codes::NON_EXHAUSTIVE_PATTERNS,
+                format!("non-exhaustive patterns: {patterns} not covered"),
+                Label::new(check.span, format!("{patterns} not covered")),
+            ));
+        }
+    }
+
+    pub fn build_statement_expression(&mut self, expression: Expression) -> Statement {
+        Statement::Expression(expression)
+    }
+
+    // TODO: Implement a building interface for expressions
+
+    /// Builds an expression that references a variable.
+    ///
+    /// Reports an error and builds an error expression if the variable is not
+    /// found in the current scope.
+    pub fn build_expression_variable(&mut self, name: &str, span: Span<'file_name>) -> Expression {
+        if let Some(variable) = self.get_variable_id(name) {
+            return Expression::Variable(variable);
+        }
+
+        self.report(Diagnostic::error(
+            codes::UNKNOWN_VARIABLE,
+            format!("cannot find variable `{name}` in this scope"),
+            Label::new(span, "not found in this scope"),
+        ));
+
+        self.build_expression_error()
+    }
+
+    /// Builds an expression in place of one that could not be lowered, after
+    /// the problem has been reported.
+    ///
+    /// The expression has the error type, which unifies with every other type
+    /// so that it does not cause further errors.
+    pub fn build_expression_error(&mut self) -> Expression {
+        let ty = self.build_type_error();
+        Expression::Error(self.create_type_inference_resolved(ty))
+    }
+
+    pub fn build_expression_literal(&mut self, literal: Literal) -> Expression {
+        Expression::Literal(literal)
+    }
+
+    /// Builds a call of `function`, reporting an error if the number of
+    /// arguments does not match its prototype.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function is not found.
+    pub fn build_function_call(
+        &mut self,
+        function: FunctionId,
+        arguments: Vec<Expression>,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let function_prototype = self.get_prototype(function).expect("function not found");
+
+        // The type arguments of a generic function are inferred from the
+        // arguments and the use of the result
+        let type_arguments = function_prototype
+            .type_parameters
+            .iter()
+            .map(|_| {
+                let id = self.create_type_inference_variable();
+                self.set_type_inference_origin(id, span);
+                id
+            })
+            .collect::<Vec<_>>();
+        for (type_argument, bounds) in type_arguments.iter().zip(&function_prototype.bounds) {
+            for bound in bounds {
+                self.constrain(*type_argument, TypeConstraint::Implements(*bound));
+            }
+        }
+        let mut generic_values = Vec::new();
+
+        let return_ty = function_prototype.return_type;
+        let result = if self.types.contains_parameters(return_ty) {
+            let result = self.create_type_inference_variable();
+            self.bind_type_arguments(return_ty, result, &type_arguments);
+            generic_values.push((return_ty, result));
+            result
+        } else {
+            self.create_type_inference_resolved(return_ty)
+        };
+
+        let arguments = arguments
+            .into_iter()
+            .enumerate()
+            .map(
+                |(index, argument)| match function_prototype.parameters.get(index) {
+                    Some(parameter_ty) => self.coerce(argument, *parameter_ty),
+                    None => argument,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let expected = function_prototype.parameters.len();
+        if arguments.len() < expected
+            || (arguments.len() > expected && !function_prototype.is_variadic)
+        {
+            let plural = |count: usize| if count == 1 { "" } else { "s" };
+            let at_least = if function_prototype.is_variadic {
+                "at least "
+            } else {
+                ""
+            };
+
+            self.report(Diagnostic::error(
+                codes::ARGUMENT_COUNT_MISMATCH,
+                format!(
+                    "this function takes {at_least}{expected} argument{} but {} argument{} {} supplied",
+                    plural(expected),
+                    arguments.len(),
+                    plural(arguments.len()),
+                    if arguments.len() == 1 { "was" } else { "were" },
+                ),
+                Label::new(
+                    span,
+                    format!("expected {at_least}{expected} argument{}", plural(expected)),
+                ),
+            ));
+        }
+
+        // The extra arguments of a variadic function have no type to match
+        for argument in arguments.iter().skip(expected) {
+            self.constrain_value(argument);
+        }
+
+        for (argument, parameter_ty) in arguments.iter().zip(&function_prototype.parameters) {
+            let argument_ty = argument.ty(self);
+
+            if self.types.contains_parameters(*parameter_ty) {
+                self.constrain_value(argument);
+                self.bind_type_arguments(*parameter_ty, argument_ty, &type_arguments);
+                generic_values.push((*parameter_ty, argument_ty));
+            } else {
+                self.unify_with(argument_ty, *parameter_ty);
+            }
+        }
+
+        if function_prototype.is_generic() {
+            self.generic_calls.push(GenericCall {
+                type_arguments: type_arguments.clone(),
+                values: generic_values,
+            });
+        }
+
+        Expression::FunctionCall(FunctionCall {
+            function,
+            arguments,
+            type_arguments,
+            result,
+        })
+    }
+
+    /// Build a call of the method `name` of `receiver`, which is passed as the
+    /// first argument of the method.
+    ///
+    /// A method defined for the type of the receiver itself, or for the type
+    /// it points to, is called if there is one. Otherwise the method is found
+    /// in the trait declaring it, and is called for the type implementing the
+    /// trait, so the implementation called is only known once the function is
+    /// instantiated.
+    ///
+    /// A reference to the receiver is taken if the method expects a pointer
+    /// and the receiver is not one. Methods of trait objects are called
+    /// through their table of methods.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a trait declaring the method does not exist.
+    pub fn build_method_call(
+        &mut self,
+        receiver: Expression,
+        name: &str,
+        mut arguments: Vec<Expression>,
+        name_span: Span<'file_name>,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let receiver_ty = self.get_resolved_type(receiver.ty(self));
+
+        if let Some(Type::TraitObject(trait_id, _)) = receiver_ty
+            .and_then(|ty| self.types.get_by_id(ty))
+            .as_deref()
+        {
+            return self.build_dynamic_call(*trait_id, receiver, name, arguments, name_span, span);
+        }
+
+        let inherent = receiver_ty.and_then(|ty| {
+            self.get_method(ty, name)
+                .or_else(|| match self.types.get_by_id(ty).as_deref() {
+                    Some(Type::Ptr(pointee)) => self.get_method(*pointee, name),
+                    _ => None,
+                })
+        });
+
+        let function = if let Some(function) = inherent {
+            function
+        } else {
+            let methods = self.traits.with_method(name);
+            let (trait_id, method) = match methods.as_slice() {
+                [method] => *method,
+                [] => {
+                    self.report(Diagnostic::error(
+                        codes::UNKNOWN_METHOD,
+                        format!("no method named `{name}` found"),
+                        Label::new(name_span, "method not found in any trait"),
+                    ));
+                    return self.build_expression_error();
+                }
+                _ => {
+                    let names = methods
+                        .iter()
+                        .map(|(trait_id, _)| format!("`{}`", self.traits.to_display(*trait_id)))
+                        .collect::<Vec<_>>();
+
+                    self.report(Diagnostic::error(
+                        codes::AMBIGUOUS_METHOD,
+                        format!("multiple traits have a method named `{name}`"),
+                        Label::new(name_span, format!("declared by {}", names.join(" and "))),
+                    ));
+                    return self.build_expression_error();
+                }
+            };
+
+            self.traits
+                .get_trait(trait_id)
+                .expect("expected trait to exist")
+                .method_definitions[&method]
+                .function
+        };
+
+        let receiver = match receiver_ty {
+            Some(ty) if self.takes_pointer(function) && !self.is_pointer_type(ty) => {
+                self.build_expression_unary(UnaryOperator::Ref, receiver)
+            }
+            _ => receiver,
+        };
+
+        arguments.insert(0, receiver);
+        self.build_function_call(function, arguments, span)
+    }
+
+    /// Build a call of the method `name` of the trait `id` through `object`, a
+    /// trait object of the trait.
+    ///
+    /// Only methods taking `self: *Self`, and using `Self` nowhere else, can
+    /// be called this way, since the type behind the object is not known.
+    fn build_dynamic_call(
+        &mut self,
+        id: TraitId,
+        object: Expression,
+        name: &str,
+        arguments: Vec<Expression>,
+        name_span: Span<'file_name>,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let trait_name = self.traits.to_display(id);
+        let trait_ = self.traits.get_trait(id).expect("trait not found");
+        let Some(method) = trait_.get_method(name) else {
+            self.report(Diagnostic::error(
+                codes::UNKNOWN_METHOD,
+                format!("no method named `{name}` found for `*dyn {trait_name}`"),
+                Label::new(name_span, format!("not a method of `{trait_name}`")),
+            ));
+            return self.build_expression_error();
+        };
+        let index = trait_
+            .method_ids()
+            .iter()
+            .position(|other| *other == method)
+            .expect("expected method to be in its trait");
+        let function = trait_.method_definitions[&method].function;
+
+        let prototype = self
+            .functions
+            .get_prototype(function)
+            .expect("expected trait method to have a prototype");
+        let Some((_, parameters)) =
+            prototype
+                .parameters
+                .split_first()
+                .filter(|(receiver, parameters)| {
+                    let takes_self = matches!(
+                        self.types.get_by_id(**receiver).as_deref(),
+                        Some(Type::Ptr(pointee)) if matches!(
+                            self.types.get_by_id(*pointee).as_deref(),
+                            Some(Type::Parameter(_, _))
+                        )
+                    );
+
+                    takes_self
+                        && !parameters
+                            .iter()
+                            .chain([&prototype.return_type])
+                            .any(|ty| self.types.contains_parameters(*ty))
+                })
+        else {
+            self.report(Diagnostic::error(
+                codes::UNDISPATCHABLE_METHOD,
+                format!(
+                    "method `{name}` of trait `{trait_name}` cannot be called on a trait object"
+                ),
+                Label::new(
+                    name_span,
+                    "only methods taking `self: *Self` and using `Self` nowhere else can be called",
+                ),
+            ));
+            return self.build_expression_error();
+        };
+
+        let expected = parameters.len();
+        if arguments.len() != expected {
+            let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+            self.report(Diagnostic::error(
+                codes::ARGUMENT_COUNT_MISMATCH,
+                format!(
+                    "this method takes {expected} argument{} but {} argument{} {} supplied",
+                    plural(expected),
+                    arguments.len(),
+                    plural(arguments.len()),
+                    if arguments.len() == 1 { "was" } else { "were" },
+                ),
+                Label::new(
+                    span,
+                    format!("expected {expected} argument{}", plural(expected)),
+                ),
+            ));
+        }
+
+        for (argument, parameter_ty) in arguments.iter().zip(parameters) {
+            let argument_ty = argument.ty(self);
+            self.unify_with(argument_ty, *parameter_ty);
+        }
+
+        let result = self.create_type_inference_resolved(prototype.return_type);
+
+        Expression::DynamicCall(DynamicCall {
+            trait_id: id,
+            function,
+            index,
+            object: Box::new(object),
+            arguments,
+            result,
+        })
+    }
+
+    /// Convert `value` to a trait object if `ty` is one and the value is a
+    /// pointer to a value of another type, which must implement the trait.
+    /// Any other value is kept as it is.
+    ///
+    /// A reference is a pointer even before the type of its operand is
+    /// inferred, so it is converted as well.
+    fn coerce(&mut self, value: Expression, ty: TypeId) -> Expression {
+        if !matches!(
+            self.types.get_by_id(ty).as_deref(),
+            Some(Type::TraitObject(_, _))
+        ) {
+            return value;
+        }
+
+        match self.get_resolved_type(value.ty(self)) {
+            Some(found) if self.is_pointer_type(found) => {
+                self.build_expression_type_cast(ty, value)
+            }
+            None if matches!(
+                value,
+                Expression::UnaryOperation(UnaryOperation {
+                    operator: UnaryOperator::Ref,
+                    ..
+                })
+            ) =>
+            {
+                self.build_expression_type_cast(ty, value)
+            }
+            _ => value,
+        }
+    }
+
+    /// Whether the first parameter of `function` is a pointer.
+    fn takes_pointer(&self, function: FunctionId) -> bool {
+        self.functions
+            .get_prototype(function)
+            .and_then(|prototype| prototype.parameters.first().copied())
+            .is_some_and(|parameter| self.is_pointer_type(parameter))
+    }
+
+    fn is_pointer_type(&self, ty: TypeId) -> bool {
+        matches!(self.types.get_by_id(ty).as_deref(), Some(Type::Ptr(_)))
+    }
+
+    /// Infer the type arguments of a generic call from a value whose type is
+    /// `found` where one of type `ty`, containing type parameters, is expected.
+    fn bind_type_arguments(
+        &mut self,
+        ty: TypeId,
+        found: InferenceId,
+        type_arguments: &[InferenceId],
+    ) {
+        if !self.types.contains_parameters(ty) {
+            self.unify_with(found, ty);
+            return;
+        }
+
+        match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Parameter(index, _)) => self.unify(type_arguments[*index], found),
+            Some(Type::Ptr(inner)) => {
+                let pointee = self.create_type_inference_variable();
+                self.constrain(found, TypeConstraint::DerefTo(pointee));
+                self.bind_inner_type_arguments(*inner, found, pointee, type_arguments);
+            }
+            Some(Type::SizedArray(inner, _) | Type::UnsizedArray(inner)) => {
+                let element = self.create_type_inference_variable();
+                self.constrain(found, TypeConstraint::IndexTo(element));
+                self.bind_inner_type_arguments(*inner, found, element, type_arguments);
+            }
+            Some(Type::Struct(_) | Type::Union(_)) => {
+                if let Some(found) = self.get_resolved_type(found) {
+                    self.bind_resolved_type_arguments(ty, found, type_arguments);
+                } else if let Some((generic, arguments)) = self.generic_value(found) {
+                    // The value is of a generic type whose own type arguments
+                    // are being inferred, so they are inferred together
+                    let Some((expected, expected_arguments)) = self
+                        .types
+                        .generic_arguments(ty)
+                        .map(|(generic, arguments)| (generic, arguments.to_vec()))
+                    else {
+                        return;
+                    };
+
+                    if expected == generic {
+                        for (ty, argument) in expected_arguments.into_iter().zip(arguments) {
+                            self.bind_type_arguments(ty, argument, type_arguments);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Infer the type arguments of a generic call from a value of the known
+    /// type `found` where one of type `ty` is expected.
+    ///
+    /// Type arguments that are already known are left as they are, the values
+    /// that do not match them are reported once the call is checked.
+    fn bind_resolved_type_arguments(
+        &mut self,
+        ty: TypeId,
+        found: TypeId,
+        type_arguments: &[InferenceId],
+    ) {
+        let Some(expected) = self.types.get_by_id(ty) else {
+            return;
+        };
+        let Some(found_ty) = self.types.get_by_id(found) else {
+            return;
+        };
+
+        match (expected.as_ref(), found_ty.as_ref()) {
+            (Type::Parameter(index, _), _) => {
+                if !self
+                    .type_inference_environment
+                    .is_resolved(type_arguments[*index])
+                {
+                    self.unify_with(type_arguments[*index], found);
+                }
+            }
+            (Type::Ptr(inner), Type::Ptr(found))
+            | (
+                Type::SizedArray(inner, _) | Type::UnsizedArray(inner),
+                Type::SizedArray(found, _) | Type::UnsizedArray(found),
+            ) => self.bind_resolved_type_arguments(*inner, *found, type_arguments),
+            (Type::Struct(_) | Type::Union(_), Type::Struct(_) | Type::Union(_)) => {
+                let (Some((expected, expected_arguments)), Some((generic, arguments))) = (
+                    self.types
+                        .generic_arguments(ty)
+                        .map(|(generic, arguments)| (generic, arguments.to_vec())),
+                    self.types
+                        .generic_arguments(found)
+                        .map(|(generic, arguments)| (generic, arguments.to_vec())),
+                ) else {
+                    return;
+                };
+
+                if expected == generic {
+                    for (ty, found) in expected_arguments.into_iter().zip(arguments) {
+                        self.bind_resolved_type_arguments(ty, found, type_arguments);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Infer the type arguments of a generic call from its values whose types
+    /// are known.
+    fn bind_resolved_values(&mut self, call: &GenericCall) {
+        for (ty, value) in &call.values {
+            if let Some(found) = self.get_resolved_type(*value) {
+                self.bind_resolved_type_arguments(*ty, found, &call.type_arguments);
+            }
+        }
+    }
+
+    /// Infer the type arguments of `calls` from their values whose types are
+    /// known, until no more can be inferred, since the type arguments of one
+    /// call can be what makes the type of a value of another known.
+    fn bind_resolved_calls(&mut self, calls: &[GenericCall]) {
+        let unresolved = |package: &Self| {
+            calls
+                .iter()
+                .flat_map(|call| &call.type_arguments)
+                .filter(|id| !package.type_inference_environment.is_resolved(**id))
+                .count()
+        };
+
+        loop {
+            let before = unresolved(self);
+            for call in calls {
+                self.bind_resolved_values(call);
+            }
+
+            if unresolved(self) == before {
+                break;
+            }
+        }
+    }
+
+    /// Give the arrays and references whose contents have a known type their
+    /// types, for as long as any is found, since arrays can hold references
+    /// and references can refer to arrays.
+    fn bind_value_types(&mut self) {
+        loop {
+            let pending = self.array_values.len() + self.reference_values.len();
+
+            self.bind_array_types();
+            self.bind_reference_types();
+
+            if self.array_values.len() + self.reference_values.len() == pending {
+                break;
+            }
+        }
+    }
+
+    /// Give the references to values of a known type, whose own type nothing
+    /// else has decided, the type of a pointer to them.
+    fn bind_reference_types(&mut self) {
+        for (reference, referent) in core::mem::take(&mut self.reference_values) {
+            if self.type_inference_environment.is_resolved(reference) {
+                continue;
+            }
+
+            match self.get_resolved_type(referent) {
+                Some(referent) => {
+                    let pointer = self.build_type_pointer(referent);
+                    self.unify_with(reference, pointer);
+                }
+                None => self.reference_values.push((reference, referent)),
+            }
+        }
+    }
+
+    /// Give the arrays whose elements have a known type the type of an array
+    /// of them, in the order they were built so that the elements of an array
+    /// that are arrays themselves are known first.
+    ///
+    /// An array whose type is already known from where it is used is checked
+    /// to have as many elements as that type.
+    fn bind_array_types(&mut self) {
+        for (array, element, length) in core::mem::take(&mut self.array_values) {
+            match self.get_resolved_type(element) {
+                Some(element) => {
+                    let ty = self.build_type_array(element, length);
+
+                    // The type the array is used as is the one expected
+                    match self.get_resolved_type(array) {
+                        Some(expected) if expected != ty && !self.types.is_error(expected) => {
+                            self.type_errors.push(TypeError::Mismatch {
+                                expected,
+                                found: ty,
+                                origin: self.type_inference_environment.origin(array),
+                                expected_origin: None,
+                            });
+                        }
+                        Some(_) => {}
+                        None => self.unify_with(array, ty),
+                    }
+                }
+                None => self.array_values.push((array, element, length)),
+            }
+        }
+    }
+
+    /// Find the generic type of a value whose type is not known yet, along
+    /// with the inference variables of its type arguments.
+    fn generic_value(&self, value: InferenceId) -> Option<(TypeId, Vec<InferenceId>)> {
+        self.type_inference_environment
+            .unified_with(value)
+            .into_iter()
+            .find_map(|id| self.generic_values.get(&id).cloned())
+    }
+
+    /// Bind `value` to be of the generic struct or tagged union `ty`, getting
+    /// the inference variables of its type arguments.
+    ///
+    /// The type arguments are taken from the type of the value if it is known,
+    /// or from the other values of the same type it is unified with.
+    fn bind_generic_value(
+        &mut self,
+        ty: TypeId,
+        value: InferenceId,
+        span: Option<Span<'file_name>>,
+    ) -> Vec<InferenceId> {
+        let type_arguments = self
+            .types
+            .type_parameters(ty)
+            .to_vec()
+            .into_iter()
+            .map(|_| {
+                let id = self.create_type_inference_variable();
+                if let Some(span) = span {
+                    self.set_type_inference_origin(id, span);
+                }
+                id
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(found) = self.get_resolved_type(value) {
+            self.bind_resolved_type_arguments(ty, found, &type_arguments);
+        } else if let Some((generic, arguments)) = self.generic_value(value) {
+            if generic == ty {
+                for (argument, other) in type_arguments.iter().zip(arguments) {
+                    self.unify(*argument, other);
+                }
+            }
+        }
 
-    pub fn build_statement_return_void(&mut self) -> Statement {
-        Return::void().into()
+        self.generic_values
+            .insert(value, (ty, type_arguments.clone()));
+
+        type_arguments
     }
 
-    /// # Panics
+    /// Bind `value` to be of the struct or tagged union `ty`, getting the
+    /// types that the fields of types `fields` have in it.
     ///
-    /// Panics if the current function is not found.
-    pub fn build_statement_return(&mut self, value: Expression) -> Statement {
-        let function_return_ty = self
-            .building_functions
-            .get(
-                &self
-                    .current_function
-                    .expect("returns cannot be outside of a function"),
-            )
-            .expect("function not found")
-            .prototype
-            .return_type;
+    /// The type arguments of a generic type are inferred from the uses of the
+    /// value and of its fields, with type errors reported at `span`.
+    fn bind_nominal_value(
+        &mut self,
+        ty: TypeId,
+        value: InferenceId,
+        fields: &[TypeId],
+        span: Option<Span<'file_name>>,
+    ) -> Vec<InferenceId> {
+        if self.types.type_parameters(ty).is_empty() {
+            self.unify_with(value, ty);
+
+            return fields
+                .iter()
+                .map(|field| self.create_type_inference_resolved(*field))
+                .collect();
+        }
 
-        let ty = value.ty(self);
+        let type_arguments = self.bind_generic_value(ty, value, span);
 
-        self.type_inference_environment
-            .unify_with(ty, function_return_ty, &self.types);
+        let mut values = vec![(ty, value)];
+        for field in fields {
+            let id = self.create_type_inference_variable();
+            self.bind_type_arguments(*field, id, &type_arguments);
+            values.push((*field, id));
+        }
+        let field_types = values[1..].iter().map(|(_, id)| *id).collect();
 
-        Return { value: Some(value) }.into()
-    }
+        self.generic_calls.push(GenericCall {
+            type_arguments,
+            values,
+        });
 
-    pub fn build_statement_expression(&mut self, expression: Expression) -> Statement {
-        Statement::Expression(expression)
+        field_types
     }
 
-    // TODO: Implement a building interface for expressions
+    /// Infer the type arguments from `inner`, the type that `found` points to
+    /// or holds.
+    fn bind_inner_type_arguments(
+        &mut self,
+        ty: TypeId,
+        found: InferenceId,
+        inner: InferenceId,
+        type_arguments: &[InferenceId],
+    ) {
+        // A value that breaks the constraint has already been reported, so
+        // nothing more is inferred from it
+        if self.type_inference_environment.is_resolved(found)
+            && !self.type_inference_environment.is_resolved(inner)
+        {
+            let error = self.build_type_error();
+            self.unify_with(inner, error);
+        }
 
-    /// Builds an expression that references a variable.
-    ///
-    /// # Panics
-    ///
-    /// This function will panic if the variable is not found.
-    pub fn build_expression_variable(&mut self, name: &str) -> Expression {
-        Expression::Variable(self.get_variable_id(name).expect("variable not found"))
+        self.bind_type_arguments(ty, inner, type_arguments);
     }
 
-    pub fn build_expression_literal(&mut self, literal: Literal) -> Expression {
-        Expression::Literal(literal)
+    /// Check the values of a generic call against the prototype of the
+    /// function, now that the type arguments are known.
+    ///
+    /// Type arguments that could not be inferred are reported on their own,
+    /// unless an error already found is the reason for it.
+    fn check_generic_call(&mut self, call: &GenericCall) {
+        self.bind_resolved_values(call);
+
+        if !self.type_errors.is_empty() {
+            let error = self.build_type_error();
+            for id in &call.type_arguments {
+                if !self.type_inference_environment.is_resolved(*id) {
+                    self.unify_with(*id, error);
+                }
+            }
+        }
+
+        let Some(type_arguments) = call
+            .type_arguments
+            .iter()
+            .map(|id| self.get_resolved_type(*id))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+
+        for (ty, value) in &call.values {
+            let ty = self.types.substitute(*ty, &type_arguments);
+            self.unify_with(*value, ty);
+        }
     }
 
+    /// Create an instantiation of every generic function called by a function
+    /// that is not generic, directly or through other instantiations.
+    ///
+    /// Must only be called once every function has been built without errors.
+    ///
     /// # Panics
     ///
-    /// Panics if the function is not found or if the arguments do not match the
-    /// function prototype.
-    pub fn build_function_call(
-        &mut self,
-        function: FunctionId,
-        arguments: Vec<Expression>,
-    ) -> Expression {
-        let function_prototype = self.get_prototype(function).expect("function not found");
-        let return_ty = function_prototype.return_type;
-        let result = self.create_type_inference_resolved(return_ty);
+    /// Panics if a called function does not exist or has no implementation,
+    /// or if a method of a trait is called for a type not implementing it.
+    pub fn monomorphize(&mut self) {
+        let mut functions = self
+            .functions
+            .impls()
+            .map(|(id, _)| (id, 0))
+            .collect::<Vec<_>>();
+        functions.sort_unstable();
+
+        // Each function is paired with the number of instantiations it is
+        // nested in
+        let mut queue = VecDeque::from(functions);
+
+        while let Some((id, depth)) = queue.pop_front() {
+            let function = self
+                .functions
+                .get_impl(id)
+                .expect("expected function to exist");
+            let arguments = function.type_arguments.clone();
+
+            let mut calls = Vec::new();
+            generics::collect_calls(&function.body, &mut calls);
+            let calls = calls
+                .into_iter()
+                .filter(|call| !call.type_arguments.is_empty())
+                .map(|call| (call.function, call.type_arguments.clone()))
+                .collect::<Vec<_>>();
+
+            for (generic, type_arguments) in calls {
+                let instance_arguments = type_arguments
+                    .iter()
+                    .map(|id| {
+                        let ty = self
+                            .get_resolved_type(*id)
+                            .expect("expected type argument to be resolved");
+                        self.types.substitute(ty, &arguments)
+                    })
+                    .collect::<Vec<_>>();
+
+                if self
+                    .functions
+                    .get_instance(generic, &instance_arguments)
+                    .is_some()
+                {
+                    continue;
+                }
+
+                // A method of a trait is implemented by the function in the
+                // implementation of the trait for the type
+                if let Some((trait_id, method)) = self.traits.get_method_of(generic) {
+                    let implementation = self
+                        .traits
+                        .get_trait_impl(trait_id, instance_arguments[0])
+                        .and_then(|implementation| implementation.method_impls.get(&method))
+                        .copied()
+                        .expect("expected the trait to be implemented for the type");
+                    self.functions
+                        .insert_instance(generic, instance_arguments, implementation);
+                    continue;
+                }
+
+                if depth == generics::RECURSION_LIMIT {
+                    self.report_recursion_limit(generic, &instance_arguments, type_arguments[0]);
+                    return;
+                }
+
+                let instance = self.instantiate(generic, instance_arguments);
+                queue.push_back((instance, depth + 1));
+            }
+        }
+    }
 
-        dbg!(
-            &function_prototype,
-            &arguments,
-            &function_prototype.parameters.len()
-        );
-        for (i, argument) in arguments.iter().enumerate() {
-            let argument_ty = argument.ty(self);
+    fn report_recursion_limit(
+        &mut self,
+        generic: FunctionId,
+        arguments: &[TypeId],
+        type_argument: InferenceId,
+    ) {
+        let name = self
+            .get_function_path(&generic)
+            .expect("expected function to exist")
+            .segments()
+            .iter()
+            .map(|segment| segment.name.as_str())
+            .collect::<Vec<_>>()
+            .join("::");
+        let arguments = arguments
+            .iter()
+            .map(|ty| self.types.to_display(*ty))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let span = self
+            .type_inference_environment
+            .declaration(type_argument)
+            .span
+            .expect("expected type argument to have been created for a call");
+
+        self.report(Diagnostic::error(
+            codes::RECURSION_LIMIT,
+            format!("reached the recursion limit while instantiating `{name}<{arguments}>`"),
+            Label::new(span, "instantiated recursively here"),
+        ));
+    }
+
+    /// Create the instantiation of `generic` with `arguments`.
+    fn instantiate(&mut self, generic: FunctionId, arguments: Vec<TypeId>) -> FunctionId {
+        let prototype = self
+            .functions
+            .get_prototype(generic)
+            .expect("expected function to exist");
+        let prototype = FunctionPrototype {
+            type_parameters: Vec::new(),
+            bounds: Vec::new(),
+            parameters: prototype
+                .parameters
+                .iter()
+                .map(|ty| self.types.substitute(*ty, &arguments))
+                .collect(),
+            is_variadic: prototype.is_variadic,
+            return_type: self.types.substitute(prototype.return_type, &arguments),
+        };
 
-            if i < function_prototype.parameters.len() {
-                let parameter_ty = function_prototype.parameters[i];
-                self.type_inference_environment
-                    .unify_with(argument_ty, parameter_ty, &self.types);
-            } else if function_prototype.is_variadic {
-                //
-            } else {
-                dbg!(i, &function_prototype.parameters.len(), &argument);
-                panic!("too many arguments for function");
-            }
+        let mut path = self
+            .functions
+            .get_path(&generic)
+            .expect("expected function to exist")
+            .clone();
+        let name = path.pop().expect("expected path to have a name").name;
+        path.push(PathSegment::with_arguments(
+            name,
+            arguments.iter().map(|ty| self.types.to_path(*ty)).collect(),
+        ));
+
+        // Code generation cannot create types, so every type of the body must
+        // already exist with the arguments in place of the parameters
+        let parameterized = self
+            .types
+            .iter()
+            .filter(|(id, _)| self.types.contains_parameters(*id))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        for ty in parameterized {
+            self.types.substitute(ty, &arguments);
         }
 
-        Expression::FunctionCall(FunctionCall {
-            function,
-            arguments,
-            result,
-        })
+        let instance = self
+            .functions
+            .create_instance(generic, arguments.clone(), path, prototype);
+
+        let mut function = self
+            .functions
+            .get_impl(generic)
+            .expect("expected generic function to be implemented")
+            .clone();
+        function.prototype = self
+            .functions
+            .get_prototype(instance)
+            .expect("expected instance to exist");
+        function.type_arguments = arguments;
+        self.functions.insert(instance, function);
+
+        instance
+    }
+
+    /// Get the type of `id` in a function instantiated with `type_arguments`.
+    #[must_use]
+    pub fn get_instance_type(&self, id: InferenceId, type_arguments: &[TypeId]) -> Option<TypeId> {
+        let ty = self.get_resolved_type(id)?;
+        self.types.get_substituted(ty, type_arguments)
+    }
+
+    /// Get the function that `call` calls from a function instantiated with
+    /// `type_arguments`, which is an instantiation if the called function is
+    /// generic.
+    #[must_use]
+    pub fn get_called_function(
+        &self,
+        call: &FunctionCall,
+        type_arguments: &[TypeId],
+    ) -> Option<FunctionId> {
+        if call.type_arguments.is_empty() {
+            Some(call.function)
+        } else {
+            let arguments = call
+                .type_arguments
+                .iter()
+                .map(|id| self.get_instance_type(*id, type_arguments))
+                .collect::<Option<Vec<_>>>()?;
+
+            self.functions.get_instance(call.function, &arguments)
+        }
     }
 
     pub fn build_unary_operator_negation(&mut self) -> UnaryOperator {
@@ -611,6 +2920,9 @@ impl HLIRPackage {
         UnaryOperator::Ref
     }
 
+    /// # Panics
+    ///
+    /// This function will panic if the `bool` type is not found in the package.
     pub fn build_expression_unary(
         &mut self,
         operator: UnaryOperator,
@@ -619,24 +2931,42 @@ impl HLIRPackage {
         let operand_ty = operand.ty(self);
 
         let result_ty = match operator {
-            UnaryOperator::Negation | UnaryOperator::BitwiseNot | UnaryOperator::LogicalNot => {
+            UnaryOperator::Negation | UnaryOperator::BitwiseNot => operand_ty,
+            UnaryOperator::LogicalNot => {
+                self.unify_with(
+                    operand_ty,
+                    self.types
+                        .get(&Path::new(vec![PathSegment::from("bool".to_string())]))
+                        .expect("expected bool type to exist in package"),
+                );
+
                 operand_ty
             }
             UnaryOperator::Deref => {
                 let ty = self.create_type_inference_variable();
 
-                self.type_inference_environment
-                    .add_constraint(operand_ty, TypeConstraint::DerefTo(ty));
+                self.constrain(operand_ty, TypeConstraint::DerefTo(ty));
 
                 ty
             }
             UnaryOperator::Ref => {
-                let ty = self.create_type_inference_variable();
-
-                self.type_inference_environment
-                    .add_constraint(ty, TypeConstraint::RefTo(operand_ty));
-
-                ty
+                self.constrain_value(&operand);
+
+                match self.get_resolved_type(operand_ty) {
+                    Some(operand) if self.types.is_error(operand) => operand_ty,
+                    Some(operand) => {
+                        let pointer = self.build_type_pointer(operand);
+                        self.create_type_inference_resolved(pointer)
+                    }
+                    None => {
+                        let ty = self.create_type_inference_variable();
+
+                        self.constrain(ty, TypeConstraint::RefTo(operand_ty));
+                        self.reference_values.push((ty, operand_ty));
+
+                        ty
+                    }
+                }
             }
         };
 
@@ -755,8 +3085,44 @@ impl HLIRPackage {
             ),
         };
 
-        self.type_inference_environment
-            .unify(left_ty, right_ty, &self.types);
+        if matches!(
+            operator,
+            BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr
+        ) {
+            let bool_ty = self
+                .types
+                .get(&Path::new(vec![PathSegment::from("bool".to_string())]))
+                .expect("expected bool type to exist in package");
+
+            self.unify_with(left_ty, bool_ty);
+            self.unify_with(right_ty, bool_ty);
+        } else {
+            self.unify(left_ty, right_ty);
+
+            // Both operands have the same type, so only the left one is checked
+            let constraint = match operator {
+                BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseOr
+                | BinaryOperator::BitwiseXor
+                | BinaryOperator::BitwiseShiftLeft
+                | BinaryOperator::BitwiseShiftRight => TypeConstraint::Integer,
+                BinaryOperator::Equals
+                | BinaryOperator::NotEquals
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessThanOrEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanOrEqual => TypeConstraint::Comparable,
+                BinaryOperator::Addition
+                | BinaryOperator::Subtraction
+                | BinaryOperator::Multiplication
+                | BinaryOperator::Division
+                | BinaryOperator::Remainder => TypeConstraint::Numeric,
+                BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => {
+                    unreachable!("logical operators are constrained to bool above")
+                }
+            };
+            self.constrain(left_ty, constraint);
+        }
 
         Expression::BinaryOperation(BinaryOperation {
             operator,
@@ -766,12 +3132,370 @@ impl HLIRPackage {
         })
     }
 
+    /// Build an index into a pointer or an array.
+    pub fn build_expression_index(&mut self, base: Expression, index: Expression) -> Expression {
+        let base_ty = base.ty(self);
+        let index_ty = index.ty(self);
+        let result = self.create_type_inference_variable();
+
+        self.constrain(base_ty, TypeConstraint::IndexTo(result));
+        self.constrain(index_ty, TypeConstraint::Integer);
+
+        Index {
+            base: Box::new(base),
+            index: Box::new(index),
+            result,
+        }
+        .into()
+    }
+
+    /// Build an assignment of `value` to `place`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the `void` type is not found in the package.
+    pub fn build_expression_assign(&mut self, place: Place, value: Expression) -> Expression {
+        let place_ty = place.ty(self);
+        let value = match self.get_resolved_type(place_ty) {
+            Some(resolved) => self.coerce(value, resolved),
+            None => value,
+        };
+        let value_ty = value.ty(self);
+
+        self.unify(place_ty, value_ty);
+
+        let result = self.create_type_inference_resolved(
+            self.types
+                .get(&Path::new(vec![PathSegment::from("void".to_string())]))
+                .expect("expected void type to exist in package"),
+        );
+
+        Assign {
+            place,
+            value: Box::new(value),
+            result,
+        }
+        .into()
+    }
+
+    /// Build a value of the struct `ty` from the values of its fields, in the
+    /// order they were declared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ty` is not a struct.
+    pub fn build_expression_struct(
+        &mut self,
+        ty: TypeId,
+        fields: Vec<Expression>,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let field_types = self
+            .get_struct(ty)
+            .expect("expected a struct type")
+            .fields
+            .iter()
+            .map(|field| field.ty)
+            .collect::<Vec<_>>();
+
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| match field_types.get(index) {
+                Some(field_ty) => self.coerce(field, *field_ty),
+                None => field,
+            })
+            .collect::<Vec<_>>();
+
+        // The fields of a generic struct may be given any type
+        for (field, field_ty) in fields.iter().zip(&field_types) {
+            if self.types.contains_parameters(*field_ty) {
+                self.constrain_value(field);
+            }
+        }
+
+        let result = self.create_type_inference_variable();
+        let field_types = self.bind_nominal_value(ty, result, &field_types, Some(span));
+
+        for (field, field_ty) in fields.iter().zip(field_types) {
+            let value_ty = field.ty(self);
+            self.unify(field_ty, value_ty);
+        }
+
+        StructLiteral { fields, ty: result }.into()
+    }
+
+    /// Build an array of the values of `elements`, which must all have the
+    /// same type. If the array is empty and the type of its elements cannot
+    /// be inferred, it is reported at `span`.
+    pub fn build_expression_array(
+        &mut self,
+        elements: Vec<Expression>,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let element = self.create_type_inference_variable();
+        if elements.is_empty() {
+            self.set_type_inference_origin(element, span);
+        }
+        for value in &elements {
+            let value_ty = value.ty(self);
+            self.constrain_value(value);
+            self.unify(element, value_ty);
+        }
+
+        let result = self.build_array_type_of(element, elements.len());
+
+        ArrayLiteral { elements, result }.into()
+    }
+
+    /// Build an array of `length` copies of `value`.
+    pub fn build_expression_array_repeat(
+        &mut self,
+        value: Expression,
+        length: usize,
+    ) -> Expression {
+        let element = value.ty(self);
+        self.constrain_value(&value);
+        let result = self.build_array_type_of(element, length);
+
+        ArrayRepeat {
+            value: Box::new(value),
+            length,
+            result,
+        }
+        .into()
+    }
+
+    /// Get the type of an array of `length` values of the type of `element`,
+    /// which is only known once the type of `element` is.
+    fn build_array_type_of(&mut self, element: InferenceId, length: usize) -> InferenceId {
+        if let Some(element) = self.get_resolved_type(element) {
+            let ty = self.build_type_array(element, length);
+            return self.create_type_inference_resolved(ty);
+        }
+
+        let result = self.create_type_inference_variable();
+        self.constrain(result, TypeConstraint::IndexTo(element));
+        self.array_values.push((result, element, length));
+
+        result
+    }
+
+    /// Build an access of the field `name` of `base`, looking through a
+    /// pointer to a struct.
+    ///
+    /// Reports an error and builds an error expression if the type of `base`
+    /// is not known yet or does not have the field.
+    pub fn build_expression_field(
+        &mut self,
+        base: Expression,
+        name: &str,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let base_ty = base.ty(self);
+
+        let Some(mut ty) = self.get_resolved_type(base_ty) else {
+            return self.build_expression_generic_field(base, name, span);
+        };
+
+        let mut base = base;
+
+        if let Some(Type::Ptr(pointee)) = self.types.get_by_id(ty).as_deref().cloned() {
+            base = UnaryOperation {
+                operator: UnaryOperator::Deref,
+                operand: Box::new(base),
+                result: self.create_type_inference_resolved(pointee),
+            }
+            .into();
+            ty = pointee;
+        }
+
+        if self.types.is_error(ty) {
+            return self.build_expression_error();
+        }
+
+        let field = self
+            .get_struct(ty)
+            .and_then(|definition| definition.field(name))
+            .map(|(index, field)| (index, field.ty));
+
+        let Some((field, field_ty)) = field else {
+            self.report_unknown_field(ty, name, span);
+            return self.build_expression_error();
+        };
+
+        FieldAccess {
+            base: Box::new(base),
+            field,
+            result: self.create_type_inference_resolved(field_ty),
+        }
+        .into()
+    }
+
+    /// Build an access of the field `name` of `base`, whose type is not known
+    /// yet, which is only possible for a value of a generic struct whose type
+    /// arguments are still being inferred.
+    fn build_expression_generic_field(
+        &mut self,
+        base: Expression,
+        name: &str,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let base_ty = base.ty(self);
+
+        let Some((ty, type_arguments)) = self.generic_value(base_ty) else {
+            self.type_errors.push(TypeError::Unresolved {
+                origin: self.type_inference_environment.origin(base_ty),
+            });
+            return self.build_expression_error();
+        };
+
+        let field = self
+            .get_struct(ty)
+            .and_then(|definition| definition.field(name))
+            .map(|(index, field)| (index, field.ty));
+
+        let Some((field, field_ty)) = field else {
+            self.report_unknown_field(ty, name, span);
+            return self.build_expression_error();
+        };
+
+        let result = self.create_type_inference_variable();
+        self.bind_type_arguments(field_ty, result, &type_arguments);
+        self.generic_calls.push(GenericCall {
+            type_arguments,
+            values: vec![(field_ty, result)],
+        });
+
+        FieldAccess {
+            base: Box::new(base),
+            field,
+            result,
+        }
+        .into()
+    }
+
+    fn report_unknown_field(&mut self, ty: TypeId, name: &str, span: Span<'file_name>) {
+        self.report(Diagnostic::error(
+            codes::UNKNOWN_FIELD,
+            format!("no field `{name}` on type `{}`", self.types.to_display(ty)),
+            Label::new(span, "unknown field"),
+        ));
+    }
+
+    /// Build a value of the enum `ty`, `variant` is the position of the variant
+    /// in the enum.
+    pub fn build_expression_enum_value(&mut self, ty: TypeId, variant: usize) -> Expression {
+        EnumValue {
+            variant,
+            ty: self.create_type_inference_resolved(ty),
+        }
+        .into()
+    }
+
+    /// Build a value of the tagged union `ty`, `variant` is the position of the
+    /// variant in the union and `fields` are the values it carries, in the
+    /// order they were declared.
+    ///
+    /// Reports an error if the number of values does not match the variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ty` is not a tagged union or `variant` is not one of its
+    /// variants.
+    pub fn build_expression_union(
+        &mut self,
+        ty: TypeId,
+        variant: usize,
+        fields: Vec<Expression>,
+        span: Span<'file_name>,
+    ) -> Expression {
+        let field_types = self
+            .get_union(ty)
+            .expect("expected type to be a tagged union")
+            .variants[variant]
+            .fields
+            .iter()
+            .map(|field| field.ty)
+            .collect::<Vec<_>>();
+
+        let expected = field_types.len();
+        if fields.len() != expected {
+            let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+            self.report(Diagnostic::error(
+                codes::ARGUMENT_COUNT_MISMATCH,
+                format!(
+                    "this variant takes {expected} value{} but {} value{} {} supplied",
+                    plural(expected),
+                    fields.len(),
+                    plural(fields.len()),
+                    if fields.len() == 1 { "was" } else { "were" },
+                ),
+                Label::new(
+                    span,
+                    format!("expected {expected} value{}", plural(expected)),
+                ),
+            ));
+        }
+
+        // The fields of a generic tagged union may be given any type
+        for (field, field_ty) in fields.iter().zip(&field_types) {
+            if self.types.contains_parameters(*field_ty) {
+                self.constrain_value(field);
+            }
+        }
+
+        let result = self.create_type_inference_variable();
+        let field_types = self.bind_nominal_value(ty, result, &field_types, Some(span));
+
+        for (field, field_ty) in fields.iter().zip(field_types) {
+            let value_ty = field.ty(self);
+            self.unify(field_ty, value_ty);
+        }
+
+        UnionValue {
+            variant,
+            fields,
+            ty: result,
+        }
+        .into()
+    }
+
+    /// Build a check of whether `value` holds the variant at position
+    /// `variant` of the enum or tagged union `ty`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `bool` type is not found in the package.
+    pub fn build_expression_variant_check(
+        &mut self,
+        ty: TypeId,
+        value: Expression,
+        variant: usize,
+    ) -> Expression {
+        let value_ty = value.ty(self);
+        self.bind_nominal_value(ty, value_ty, &[], None);
+
+        let bool = self
+            .types
+            .get(&Path::new(vec![PathSegment::from("bool".to_string())]))
+            .expect("expected bool type to exist in package");
+
+        VariantCheck {
+            value: Box::new(value),
+            variant,
+            result: self.create_type_inference_resolved(bool),
+        }
+        .into()
+    }
+
+    /// Build a conversion of `expression` to the type `ty`.
     pub fn build_expression_type_cast(&mut self, ty: TypeId, expression: Expression) -> Expression {
         let target_type = self.create_type_inference_resolved(ty);
         let expression_ty = expression.ty(self);
 
-        self.type_inference_environment
-            .unify_with(expression_ty, ty, &self.types);
+        self.constrain(expression_ty, TypeConstraint::CastTo(ty));
 
         Expression::TypeCast(TypeCast {
             target_type,
@@ -779,13 +3503,152 @@ impl HLIRPackage {
         })
     }
 
+    // TODO: Implement a building interface for patterns
+
+    pub fn build_pattern_wildcard(&mut self) -> Pattern {
+        Pattern::Wildcard
+    }
+
+    /// Builds a pattern in place of one that could not be lowered, after the
+    /// problem has been reported.
+    pub fn build_pattern_error(&mut self) -> Pattern {
+        Pattern::Error
+    }
+
+    /// Build a pattern that stores the value it is matched against, whose type
+    /// is `scrutinee`, in a new variable.
+    pub fn build_pattern_binding(&mut self, name: &str, scrutinee: InferenceId) -> Pattern {
+        let ty = self.create_type_inference_variable();
+        self.unify(scrutinee, ty);
+
+        Pattern::Binding(self.create_variable(name, ty))
+    }
+
+    /// Build a pattern that matches `literal`, whose value is `value`, against
+    /// a value of type `scrutinee`.
+    pub fn build_pattern_literal(
+        &mut self,
+        literal: &Literal,
+        value: i128,
+        scrutinee: InferenceId,
+    ) -> Pattern {
+        self.unify(scrutinee, literal.ty());
+
+        Pattern::Range {
+            start: value,
+            end: value,
+        }
+    }
+
+    /// Build a pattern that matches the values from `start` to `end`, both
+    /// included, each given as a literal along with its value.
+    pub fn build_pattern_range(
+        &mut self,
+        start: (&Literal, i128),
+        end: (&Literal, i128),
+        scrutinee: InferenceId,
+    ) -> Pattern {
+        self.unify(scrutinee, start.0.ty());
+        self.unify(scrutinee, end.0.ty());
+
+        Pattern::Range {
+            start: start.1,
+            end: end.1,
+        }
+    }
+
+    /// Get the types of the values carried by the variant at position
+    /// `variant` of the enum or tagged union `ty`, which the patterns of the
+    /// fields of a variant pattern matched against `scrutinee` are matched
+    /// against.
+    ///
+    /// For a generic tagged union, these are inferred along with the type
+    /// arguments of the scrutinee.
+    pub fn build_pattern_field_types(
+        &mut self,
+        ty: TypeId,
+        variant: usize,
+        scrutinee: InferenceId,
+        span: Span<'file_name>,
+    ) -> Vec<InferenceId> {
+        let fields = self
+            .get_union(ty)
+            .map(|union| {
+                union.variants[variant]
+                    .fields
+                    .iter()
+                    .map(|field| field.ty)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if self.types.type_parameters(ty).is_empty() {
+            // The scrutinee is unified with the type once the pattern is built
+            fields
+                .into_iter()
+                .map(|field| self.create_type_inference_resolved(field))
+                .collect()
+        } else {
+            self.bind_nominal_value(ty, scrutinee, &fields, Some(span))
+        }
+    }
+
+    /// Build a pattern that matches the variant at position `variant` of the
+    /// enum or tagged union `ty`, and the values it carries against `fields`,
+    /// whose types are given by [`HLIRPackage::build_pattern_field_types`].
+    ///
+    /// Reports an error if the number of fields does not match the variant.
+    pub fn build_pattern_variant(
+        &mut self,
+        ty: TypeId,
+        variant: usize,
+        fields: Vec<Pattern>,
+        scrutinee: InferenceId,
+        span: Span<'file_name>,
+    ) -> Pattern {
+        if self.types.type_parameters(ty).is_empty() {
+            self.unify_with(scrutinee, ty);
+        }
+
+        let expected = self
+            .get_union(ty)
+            .map_or(0, |union| union.variants[variant].fields.len());
+        if fields.len() != expected {
+            let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+            self.report(Diagnostic::error(
+                codes::ARGUMENT_COUNT_MISMATCH,
+                format!(
+                    "this variant has {expected} field{} but the pattern has {}",
+                    plural(expected),
+                    fields.len(),
+                ),
+                Label::new(
+                    span,
+                    format!("expected {expected} field{}", plural(expected)),
+                ),
+            ));
+
+            return Pattern::Error;
+        }
+
+        Pattern::Variant { variant, fields }
+    }
+
+    pub fn build_pattern_or(&mut self, alternatives: Vec<Pattern>) -> Pattern {
+        Pattern::Or(alternatives)
+    }
+
     // TODO: Implement a building interface for literals
 
     pub fn build_integer_literal(&mut self, value: String, radix: u32) -> Literal {
         let type_inference_id = self.create_type_inference_variable();
 
-        self.type_inference_environment
-            .add_constraint(type_inference_id, TypeConstraint::FromIntegerLiteral);
+        let parsed = LiteralValue::parse_integer(&value, radix).ok();
+        self.type_inference_environment.add_constraint(
+            type_inference_id,
+            TypeConstraint::FromIntegerLiteral(parsed),
+        );
 
         Literal::new(LiteralValue::Integer { value, radix }, type_inference_id)
     }
@@ -854,7 +3717,7 @@ impl HLIRPackage {
     }
 }
 
-impl Default for HLIRPackage {
+impl Default for HLIRPackage<'_> {
     fn default() -> Self {
         Self::new()
     }
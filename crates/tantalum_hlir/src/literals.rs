@@ -26,3 +26,22 @@ pub enum LiteralValue {
     Character { value: String },
     String { value: String },
 }
+
+impl LiteralValue {
+    /// Get the value of the lexeme of an integer literal written in `radix`,
+    /// which starts with a `0b`, `0o` or `0x` prefix unless it is decimal.
+    /// The lexeme of a negated literal starts with a `-`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not fit in an `i128`.
+    pub fn parse_integer(value: &str, radix: u32) -> Result<i128, core::num::ParseIntError> {
+        let (sign, value) = match value.strip_prefix('-') {
+            Some(value) => ("-", value),
+            None => ("", value),
+        };
+        let digits = if radix == 10 { value } else { &value[2..] };
+
+        i128::from_str_radix(&format!("{sign}{digits}"), radix)
+    }
+}
@@ -20,12 +20,37 @@ impl Path {
         &self.0
     }
 
+    /// Get `path` relative to this path, e.g. `::shapes::Circle` for
+    /// `Circle` relative to `::shapes`.
+    #[must_use]
+    pub fn join(&self, path: &Path) -> Self {
+        Self(self.0.iter().chain(path.segments()).cloned().collect())
+    }
+
+    /// Get the name of the item at the path as a symbol, e.g. `__max$LT$__i32$GT$`
+    /// for `max<i32>`.
+    ///
+    /// Type arguments are wrapped in `$LT$` and `$GT$` and separated by `$C$`,
+    /// so that every instantiation of a generic item gets its own symbol.
+    /// Pointers and arrays given as arguments are already named with `$P$`
+    /// and `$A$N$`, see [`crate::types::Types::to_path`].
     #[must_use]
     pub fn to_mangled_string(&self) -> String {
         let mut result = String::new();
         for segment in self.segments() {
             result.push_str("__");
             result.push_str(&segment.name);
+
+            if !segment.arguments.is_empty() {
+                result.push_str("$LT$");
+                for (i, argument) in segment.arguments.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str("$C$");
+                    }
+                    result.push_str(&argument.to_mangled_string());
+                }
+                result.push_str("$GT$");
+            }
         }
         result
     }
@@ -61,12 +86,23 @@ impl core::fmt::Display for Path {
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PathSegment {
     pub name: String,
+    /// The type arguments of an instantiation of a generic item, each given
+    /// as the path of the type
+    pub arguments: Vec<Path>,
 }
 
 impl PathSegment {
     #[must_use]
     pub fn new(name: String) -> Self {
-        Self { name }
+        Self {
+            name,
+            arguments: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_arguments(name: String, arguments: Vec<Path>) -> Self {
+        Self { name, arguments }
     }
 }
 
@@ -84,12 +120,25 @@ impl From<String> for PathSegment {
 
 impl core::fmt::Debug for PathSegment {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "{}", self.name)
+        write!(f, "{self}")
     }
 }
 
 impl core::fmt::Display for PathSegment {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "{}", self.name)
+        write!(f, "{}", self.name)?;
+
+        if !self.arguments.is_empty() {
+            write!(f, "<")?;
+            for (i, argument) in self.arguments.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{argument}")?;
+            }
+            write!(f, ">")?;
+        }
+
+        Ok(())
     }
 }
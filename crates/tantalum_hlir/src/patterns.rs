@@ -0,0 +1,408 @@
+use crate::inference::InferenceId;
+use crate::types::{PrimitiveType, Type, TypeId, Types, VariantKind};
+use crate::variables::VariableId;
+use tantalum_span::Span;
+
+/// A pattern that the scrutinee of a `match` is compared against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    /// Matches any value
+    Wildcard,
+    /// Matches any value and stores it in a variable
+    Binding(VariableId),
+    /// Matches the integers, characters or booleans between `start` and `end`,
+    /// both included. A single value is a range where both are the same, and
+    /// booleans are `0` for `false` and `1` for `true`
+    Range { start: i128, end: i128 },
+    /// Matches the variant at position `variant` of an enum or a tagged union,
+    /// along with the values it carries in the order they were declared
+    Variant {
+        variant: usize,
+        fields: Vec<Pattern>,
+    },
+    /// Matches a value that any of the patterns match
+    Or(Vec<Pattern>),
+    /// A pattern that could not be lowered because of an error that has
+    /// already been reported
+    Error,
+}
+
+impl Pattern {
+    /// Whether the pattern, or a pattern inside of it, has an error.
+    #[must_use]
+    pub fn has_error(&self) -> bool {
+        match self {
+            Pattern::Error => true,
+            Pattern::Variant {
+                fields: patterns, ..
+            }
+            | Pattern::Or(patterns) => patterns.iter().any(Pattern::has_error),
+            Pattern::Wildcard | Pattern::Binding(_) | Pattern::Range { .. } => false,
+        }
+    }
+
+    /// Whether the pattern matches every value, without looking at it.
+    #[must_use]
+    pub fn is_irrefutable(&self) -> bool {
+        matches!(
+            self,
+            Pattern::Wildcard | Pattern::Binding(_) | Pattern::Error
+        )
+    }
+}
+
+/// A match whose patterns are checked once the function it is in is finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MatchCheck<'file_name> {
+    pub(crate) scrutinee: InferenceId,
+    pub(crate) span: Span<'file_name>,
+    pub(crate) arms: Vec<(Pattern, Span<'file_name>)>,
+}
+
+const WILDCARD: Pattern = Pattern::Wildcard;
+
+/// The values that a column of patterns is matched against.
+enum Domain {
+    /// Integers, characters and booleans, which are matched by ranges
+    Integers(i128, i128),
+    /// The field types of every variant of an enum or tagged union
+    Variants(Vec<Vec<TypeId>>),
+    /// Values that patterns cannot look into, only wildcards match them
+    Opaque,
+}
+
+/// Finds the arms of a `match` that can never be reached, and the values that
+/// no arm matches, using the usefulness algorithm: a pattern is useful if it
+/// matches a value that none of the patterns before it match.
+pub(crate) struct PatternChecker<'a> {
+    types: &'a Types,
+}
+
+impl<'a> PatternChecker<'a> {
+    pub(crate) fn new(types: &'a Types) -> Self {
+        Self { types }
+    }
+
+    /// Whether `pattern` matches a value of type `ty` that none of `previous`
+    /// match.
+    pub(crate) fn is_reachable(
+        &self,
+        previous: &[&Pattern],
+        pattern: &Pattern,
+        ty: TypeId,
+    ) -> bool {
+        let rows = previous
+            .iter()
+            .map(|pattern| vec![*pattern])
+            .collect::<Vec<_>>();
+
+        self.is_useful(&rows, &[pattern], &[ty])
+    }
+
+    /// Describe the values of type `ty` that none of `patterns` match, or
+    /// nothing if they are exhaustive.
+    ///
+    /// Only the outermost part of each value is described, e.g. a variant
+    /// whose values are not all matched is described as `Shape::Circle(..)`.
+    pub(crate) fn missing(&self, patterns: &[&Pattern], ty: TypeId) -> Vec<String> {
+        let rows = patterns
+            .iter()
+            .map(|pattern| vec![*pattern])
+            .collect::<Vec<_>>();
+        let rows = Self::expand_or(&rows);
+
+        match self.domain(ty) {
+            Domain::Integers(min, max) => {
+                let mut missing: Vec<(i128, i128)> = Vec::new();
+
+                for (start, end) in Self::split(&rows, min, max) {
+                    let specialized = Self::specialize_range(&rows, start, end);
+                    if !self.is_useful(&specialized, &[], &[]) {
+                        continue;
+                    }
+
+                    // Merge the pieces that follow on from each other
+                    match missing.last_mut() {
+                        Some(last) if last.1 + 1 == start => last.1 = end,
+                        _ => missing.push((start, end)),
+                    }
+                }
+
+                missing
+                    .into_iter()
+                    .map(|(start, end)| self.describe_range(ty, start, end))
+                    .collect()
+            }
+            Domain::Variants(variants) => variants
+                .iter()
+                .enumerate()
+                .filter(|(variant, fields)| {
+                    let specialized = Self::specialize_variant(&rows, *variant, fields.len());
+                    self.is_useful(&specialized, &vec![&WILDCARD; fields.len()], fields)
+                })
+                .map(|(variant, _)| self.describe_variant(ty, variant))
+                .collect(),
+            Domain::Opaque if rows.iter().any(|row| row[0].is_irrefutable()) => Vec::new(),
+            Domain::Opaque => vec!["_".to_string()],
+        }
+    }
+
+    fn domain(&self, ty: TypeId) -> Domain {
+        let Some(resolved) = self.types.get_by_id(ty) else {
+            return Domain::Opaque;
+        };
+
+        match *resolved {
+            Type::Primitive(PrimitiveType::Bool) => Domain::Integers(0, 1),
+            // Characters are single bytes
+            Type::Primitive(PrimitiveType::Char) => Domain::Integers(0, 255),
+            Type::Primitive(primitive) => {
+                primitive.integer_range().map_or(Domain::Opaque, |range| {
+                    Domain::Integers(*range.start(), *range.end())
+                })
+            }
+            Type::Enum(id) => Domain::Variants(
+                self.types
+                    .get_enum(id)
+                    .map(|definition| vec![Vec::new(); definition.variants.len()])
+                    .unwrap_or_default(),
+            ),
+            Type::Union(id) => Domain::Variants(
+                self.types
+                    .get_union(id)
+                    .map(|definition| {
+                        definition
+                            .variants
+                            .iter()
+                            .map(|variant| variant.fields.iter().map(|field| field.ty).collect())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ),
+            _ => Domain::Opaque,
+        }
+    }
+
+    /// Whether `vector`, a row of patterns for values of types `tys`, matches
+    /// values that none of `rows` match.
+    fn is_useful(&self, rows: &[Vec<&Pattern>], vector: &[&Pattern], tys: &[TypeId]) -> bool {
+        let Some((head, tail)) = vector.split_first() else {
+            return rows.is_empty();
+        };
+
+        let rows = Self::expand_or(rows);
+
+        match head {
+            Pattern::Or(alternatives) => alternatives.iter().any(|alternative| {
+                let vector = [&[alternative][..], tail].concat();
+                self.is_useful(&rows, &vector, tys)
+            }),
+            Pattern::Variant { variant, fields } => {
+                let specialized = Self::specialize_variant(&rows, *variant, fields.len());
+                let vector = fields
+                    .iter()
+                    .chain(tail.iter().copied())
+                    .collect::<Vec<_>>();
+                let tys = [&self.field_types(tys[0], *variant)[..], &tys[1..]].concat();
+
+                self.is_useful(&specialized, &vector, &tys)
+            }
+            Pattern::Range { start, end } => {
+                Self::split(&rows, *start, *end)
+                    .into_iter()
+                    .any(|(start, end)| {
+                        let specialized = Self::specialize_range(&rows, start, end);
+                        self.is_useful(&specialized, tail, &tys[1..])
+                    })
+            }
+            Pattern::Wildcard | Pattern::Binding(_) | Pattern::Error => match self.domain(tys[0]) {
+                Domain::Integers(min, max) => {
+                    Self::split(&rows, min, max)
+                        .into_iter()
+                        .any(|(start, end)| {
+                            let specialized = Self::specialize_range(&rows, start, end);
+                            self.is_useful(&specialized, tail, &tys[1..])
+                        })
+                }
+                Domain::Variants(variants) => {
+                    variants.iter().enumerate().any(|(variant, fields)| {
+                        let specialized = Self::specialize_variant(&rows, variant, fields.len());
+                        let vector = vec![&WILDCARD; fields.len()]
+                            .into_iter()
+                            .chain(tail.iter().copied())
+                            .collect::<Vec<_>>();
+                        let tys = [&fields[..], &tys[1..]].concat();
+
+                        self.is_useful(&specialized, &vector, &tys)
+                    })
+                }
+                Domain::Opaque => {
+                    let default = rows
+                        .iter()
+                        .filter(|row| row[0].is_irrefutable())
+                        .map(|row| row[1..].to_vec())
+                        .collect::<Vec<_>>();
+
+                    self.is_useful(&default, tail, &tys[1..])
+                }
+            },
+        }
+    }
+
+    fn field_types(&self, ty: TypeId, variant: usize) -> Vec<TypeId> {
+        match self.domain(ty) {
+            Domain::Variants(mut variants) if variant < variants.len() => {
+                variants.swap_remove(variant)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Replace the rows starting with an or-pattern by a row for each of its
+    /// alternatives.
+    fn expand_or<'p>(rows: &[Vec<&'p Pattern>]) -> Vec<Vec<&'p Pattern>> {
+        let mut expanded = Vec::new();
+
+        for row in rows {
+            match row.first() {
+                Some(Pattern::Or(alternatives)) => {
+                    let alternatives = alternatives
+                        .iter()
+                        .map(|alternative| [&[alternative][..], &row[1..]].concat())
+                        .collect::<Vec<_>>();
+                    expanded.extend(Self::expand_or(&alternatives));
+                }
+                _ => expanded.push(row.clone()),
+            }
+        }
+
+        expanded
+    }
+
+    /// Split the values from `start` to `end` into ranges that every range at
+    /// the start of `rows` either covers entirely or not at all.
+    fn split(rows: &[Vec<&Pattern>], start: i128, end: i128) -> Vec<(i128, i128)> {
+        let mut boundaries = vec![start, end.saturating_add(1)];
+
+        for row in rows {
+            if let Pattern::Range {
+                start: row_start,
+                end: row_end,
+            } = row[0]
+            {
+                for boundary in [*row_start, row_end.saturating_add(1)] {
+                    if start < boundary && boundary <= end {
+                        boundaries.push(boundary);
+                    }
+                }
+            }
+        }
+
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        boundaries
+            .windows(2)
+            .map(|pair| (pair[0], pair[1] - 1))
+            .collect()
+    }
+
+    /// Keep the rows whose first pattern matches every value from `start` to
+    /// `end`, without that pattern.
+    fn specialize_range<'p>(
+        rows: &[Vec<&'p Pattern>],
+        start: i128,
+        end: i128,
+    ) -> Vec<Vec<&'p Pattern>> {
+        rows.iter()
+            .filter(|row| match row[0] {
+                Pattern::Range {
+                    start: row_start,
+                    end: row_end,
+                } => *row_start <= start && end <= *row_end,
+                pattern => pattern.is_irrefutable(),
+            })
+            .map(|row| row[1..].to_vec())
+            .collect()
+    }
+
+    /// Keep the rows whose first pattern matches the variant at position
+    /// `variant`, replacing that pattern with the patterns of the `arity`
+    /// values of the variant.
+    fn specialize_variant<'p>(
+        rows: &[Vec<&'p Pattern>],
+        variant: usize,
+        arity: usize,
+    ) -> Vec<Vec<&'p Pattern>> {
+        rows.iter()
+            .filter_map(|row| match row[0] {
+                Pattern::Variant {
+                    variant: row_variant,
+                    fields,
+                } if *row_variant == variant => {
+                    Some(fields.iter().chain(row[1..].iter().copied()).collect())
+                }
+                pattern if pattern.is_irrefutable() => Some(
+                    vec![&WILDCARD; arity]
+                        .into_iter()
+                        .chain(row[1..].iter().copied())
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn describe_range(&self, ty: TypeId, start: i128, end: i128) -> String {
+        let describe = |value: i128| match self.types.get_by_id(ty).as_deref() {
+            Some(Type::Primitive(PrimitiveType::Bool)) => (value != 0).to_string(),
+            Some(Type::Primitive(PrimitiveType::Char)) => u8::try_from(value).map_or_else(
+                |_| value.to_string(),
+                |byte| format!("'{}'", byte.escape_ascii()),
+            ),
+            _ => value.to_string(),
+        };
+
+        if start == end {
+            describe(start)
+        } else {
+            format!("{}..={}", describe(start), describe(end))
+        }
+    }
+
+    fn describe_variant(&self, ty: TypeId, variant: usize) -> String {
+        let Some(resolved) = self.types.get_by_id(ty) else {
+            return "_".to_string();
+        };
+
+        match *resolved {
+            Type::Enum(id) => {
+                let definition = self.types.get_enum(id).expect("expected enum to exist");
+                format!(
+                    "{}::{}",
+                    self.types.to_display(ty),
+                    definition.variants[variant].name
+                )
+            }
+            Type::Union(id) => {
+                let definition = self.types.get_union(id).expect("expected union to exist");
+                let variant = &definition.variants[variant];
+                let fields = match variant.kind {
+                    VariantKind::Unit => "",
+                    VariantKind::Tuple => "(..)",
+                    VariantKind::Struct => " { .. }",
+                };
+
+                // Variants are named after the generic enum, without the type
+                // arguments of the scrutinee
+                let ty = self
+                    .types
+                    .generic_arguments(ty)
+                    .map_or(ty, |(generic, _)| generic);
+
+                format!("{}::{}{fields}", self.types.to_display(ty), variant.name)
+            }
+            _ => "_".to_string(),
+        }
+    }
+}
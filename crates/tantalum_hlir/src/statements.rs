@@ -1,4 +1,6 @@
 use crate::expressions::Expression;
+use crate::literals::{Literal, LiteralValue};
+use crate::patterns::Pattern;
 use crate::types::TypeScopeId;
 use crate::variables::{VariableId, VariableScopeBlockId};
 use std::fmt::Formatter;
@@ -9,6 +11,12 @@ pub enum Statement {
     Let(Let),
     If(If),
     While(While),
+    For(For),
+    Match(Match),
+    /// Exit the innermost loop
+    Break,
+    /// Skip to the next iteration of the innermost loop
+    Continue,
     Return(Return),
     Expression(Expression),
 }
@@ -20,12 +28,83 @@ impl core::fmt::Debug for Statement {
             Statement::Let(let_) => let_.fmt(f),
             Statement::If(if_) => if_.fmt(f),
             Statement::While(while_) => while_.fmt(f),
+            Statement::For(for_) => for_.fmt(f),
+            Statement::Match(match_) => match_.fmt(f),
+            Statement::Break => write!(f, "Break"),
+            Statement::Continue => write!(f, "Continue"),
             Statement::Return(return_) => return_.fmt(f),
             Statement::Expression(expression) => expression.fmt(f),
         }
     }
 }
 
+impl Statement {
+    /// Whether running the statement never goes on to the statement after
+    /// it, because each path through it returns or loops forever.
+    #[must_use]
+    pub fn diverges(&self) -> bool {
+        match self {
+            Statement::Return(_) => true,
+            Statement::Block(block) => block.statements.iter().any(Statement::diverges),
+            Statement::If(If {
+                then_branch,
+                else_branch: Some(else_branch),
+                ..
+            }) => then_branch.diverges() && else_branch.diverges(),
+            // The arms are checked to cover every value of the scrutinee
+            Statement::Match(match_statement) => {
+                match_statement.arms.iter().all(|arm| arm.body.diverges())
+            }
+            Statement::While(While { condition, body })
+            | Statement::For(For {
+                condition, body, ..
+            }) => is_true(condition) && !body.breaks(),
+            Statement::If(_)
+            | Statement::Let(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Expression(_) => false,
+        }
+    }
+
+    /// Whether the statement may exit the loop it is in.
+    fn breaks(&self) -> bool {
+        match self {
+            Statement::Break => true,
+            Statement::Block(block) => block.statements.iter().any(Statement::breaks),
+            Statement::If(if_statement) => {
+                if_statement.then_branch.breaks()
+                    || if_statement
+                        .else_branch
+                        .as_ref()
+                        .is_some_and(|branch| branch.breaks())
+            }
+            Statement::Match(match_statement) => {
+                match_statement.arms.iter().any(|arm| arm.body.breaks())
+            }
+            // A `break` inside of another loop exits that loop instead
+            Statement::While(_)
+            | Statement::For(_)
+            | Statement::Let(_)
+            | Statement::Continue
+            | Statement::Return(_)
+            | Statement::Expression(_) => false,
+        }
+    }
+}
+
+/// Whether `condition` is the literal `true`, making a loop endless unless it
+/// is exited with a `break`.
+fn is_true(condition: &Expression) -> bool {
+    matches!(
+        condition,
+        Expression::Literal(Literal {
+            value: LiteralValue::Boolean { value: true },
+            ..
+        })
+    )
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Block {
     pub variable_scope: VariableScopeBlockId,
@@ -76,6 +155,47 @@ impl From<While> for Statement {
     }
 }
 
+/// A loop that runs `update` after every iteration of `body`, including those
+/// ended by a `continue`.
+///
+/// The initializer of a `for` loop is placed before it in an enclosing block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct For {
+    pub condition: Expression,
+    pub update: Box<Statement>,
+    pub body: Box<Statement>,
+}
+
+impl From<For> for Statement {
+    fn from(for_statement: For) -> Self {
+        Self::For(for_statement)
+    }
+}
+
+/// Run the body of the first arm whose pattern matches the scrutinee.
+///
+/// The arms are checked to cover every value of the scrutinee when the function
+/// is finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub scrutinee: Expression,
+    pub arms: Vec<MatchArm>,
+}
+
+/// An arm of a `match`, whose body is a block holding the variables bound by
+/// the pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Statement,
+}
+
+impl From<Match> for Statement {
+    fn from(match_statement: Match) -> Self {
+        Self::Match(match_statement)
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Return {
     pub value: Option<Expression>,
@@ -1,5 +1,4 @@
-use crate::inference::TypeConstraint;
-use crate::items::Function;
+use crate::functions::FunctionId;
 use crate::path::Path;
 use crate::types::TypeId;
 use std::collections::HashMap;
@@ -14,16 +13,6 @@ impl core::fmt::Debug for TraitId {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(transparent)]
-pub struct TraitTypeParameterId(usize);
-
-impl core::fmt::Debug for TraitTypeParameterId {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "TraitTypeParameterId({})", self.0)
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct TraitMethodId(usize);
@@ -36,9 +25,7 @@ impl core::fmt::Debug for TraitMethodId {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Trait {
-    // pub name: Path,
-    next_parameter_id: TraitTypeParameterId,
-    pub type_parameters: HashMap<String, TraitTypeParameterId>,
+    pub name: Path,
     next_method_id: TraitMethodId,
     pub methods: HashMap<String, TraitMethodId>,
     pub method_definitions: HashMap<TraitMethodId, TraitMethod>,
@@ -46,22 +33,15 @@ pub struct Trait {
 
 impl Trait {
     #[must_use]
-    pub fn new() -> Self {
+    pub fn new(name: Path) -> Self {
         Self {
-            next_parameter_id: TraitTypeParameterId(0),
-            type_parameters: HashMap::new(),
+            name,
             next_method_id: TraitMethodId(0),
             methods: HashMap::new(),
             method_definitions: HashMap::new(),
         }
     }
 
-    fn next_parameter_id(&mut self) -> TraitTypeParameterId {
-        let id = self.next_parameter_id;
-        self.next_parameter_id = TraitTypeParameterId(id.0 + 1);
-        id
-    }
-
     fn next_method_id(&mut self) -> TraitMethodId {
         let id = self.next_method_id;
         self.next_method_id = TraitMethodId(id.0 + 1);
@@ -69,82 +49,44 @@ impl Trait {
     }
 
     #[must_use]
-    pub fn get_type_parameter(&self, name: &str) -> Option<TraitTypeParameterId> {
-        self.type_parameters.get(name).copied()
-    }
-
-    pub fn add_type_parameter(&mut self, name: String) -> TraitTypeParameterId {
-        let id = self.next_parameter_id();
-        self.type_parameters.insert(name, id);
-        id
+    pub fn get_method(&self, name: &str) -> Option<TraitMethodId> {
+        self.methods.get(name).copied()
     }
 
-    /// # Panics
-    ///
-    /// Panics if the type parameter ID is not found for the current trait.
-    pub fn add_type_parameter_constraint(
-        &mut self,
-        id: TraitTypeParameterId,
-        constraint: TypeConstraint,
-    ) {
-        debug_assert!(
-            self.type_parameters.values().any(|&x| x == id),
-            "Type parameter ID not found for current trait"
-        );
-
-        let name = self
-            .type_parameters
-            .iter()
-            .find_map(|(name, &x)| if x == id { Some(name.clone()) } else { None })
-            .unwrap();
-
-        todo!(
-            "add type parameter constraint to trait: {} -> {:?}",
-            name,
-            constraint
-        );
+    /// Get the methods of the trait in the order they were declared.
+    #[must_use]
+    pub fn method_ids(&self) -> Vec<TraitMethodId> {
+        let mut ids = self.method_definitions.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
     }
 
-    pub fn add_method(
-        &mut self,
-        name: String,
-        parameters: Vec<TraitTypeParameterId>,
-        return_type: TraitTypeParameterId,
-    ) -> TraitMethodId {
+    pub fn add_method(&mut self, name: String, function: FunctionId) -> TraitMethodId {
         let id = self.next_method_id();
         self.methods.insert(name.clone(), id);
-        self.method_definitions.insert(
-            id,
-            TraitMethod {
-                name,
-                parameters,
-                return_type,
-            },
-        );
+        self.method_definitions
+            .insert(id, TraitMethod { name, function });
         id
     }
 }
 
-impl Default for Trait {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TraitImpl {
     pub trait_id: TraitId,
     pub type_id: TypeId,
-    pub type_constraints: HashMap<TraitTypeParameterId, Vec<TypeConstraint>>,
-    pub method_impls: HashMap<TraitMethodId, Function>,
+    /// The function defining each method of the trait for the type
+    pub method_impls: HashMap<TraitMethodId, FunctionId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TraitMethod {
     pub name: String,
-    // TODO: add other fields for a trait method
-    pub parameters: Vec<TraitTypeParameterId>,
-    pub return_type: TraitTypeParameterId,
+    /// The prototype of the method, a generic function whose only type
+    /// parameter is `Self`, the type implementing the trait.
+    ///
+    /// It has no implementation, each implementation of the trait defines
+    /// its own function instead.
+    pub function: FunctionId,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -152,6 +94,9 @@ pub struct Traits {
     next_id: TraitId,
     known: HashMap<Path, TraitId>,
     trait_defs: HashMap<TraitId, Trait>,
+    /// The trait and method declared by each generic function that declares
+    /// a method of a trait
+    methods: HashMap<FunctionId, (TraitId, TraitMethodId)>,
     trait_impls: HashMap<TraitId, HashMap<TypeId, TraitImpl>>,
 }
 
@@ -162,6 +107,7 @@ impl Traits {
             next_id: TraitId(0),
             known: HashMap::new(),
             trait_defs: HashMap::new(),
+            methods: HashMap::new(),
             trait_impls: HashMap::new(),
         }
     }
@@ -172,18 +118,31 @@ impl Traits {
         id
     }
 
+    #[must_use]
+    pub fn get(&self, name: &Path) -> Option<TraitId> {
+        self.known.get(name).copied()
+    }
+
     pub fn get_or_insert(&mut self, name: Path) -> TraitId {
         if let Some(id) = self.known.get(&name) {
             return *id;
         }
 
         let id = self.next_id();
-        self.known.insert(name, id);
-        self.trait_defs.insert(id, Trait::new());
+        self.known.insert(name.clone(), id);
+        self.trait_defs.insert(id, Trait::new(name));
         self.trait_impls.insert(id, HashMap::new());
         id
     }
 
+    /// Get every trait in the order they were declared.
+    #[must_use]
+    pub fn ids(&self) -> Vec<TraitId> {
+        let mut ids = self.trait_defs.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+
     #[must_use]
     pub fn get_trait(&self, id: TraitId) -> Option<&Trait> {
         self.trait_defs.get(&id)
@@ -193,6 +152,48 @@ impl Traits {
         self.trait_defs.get_mut(&id)
     }
 
+    /// The name of the trait `id` as it is written in the source code.
+    #[must_use]
+    pub fn to_display(&self, id: TraitId) -> String {
+        self.get_trait(id)
+            .and_then(|trait_| trait_.name.segments().last())
+            .map(|segment| segment.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Add a method to the trait `id`, declared by the generic `function`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait does not exist.
+    pub fn add_method(&mut self, id: TraitId, name: String, function: FunctionId) -> TraitMethodId {
+        let method = self
+            .trait_defs
+            .get_mut(&id)
+            .expect("trait not found")
+            .add_method(name, function);
+        self.methods.insert(function, (id, method));
+        method
+    }
+
+    /// Get the trait and method `function` declares, if it declares one.
+    #[must_use]
+    pub fn get_method_of(&self, function: FunctionId) -> Option<(TraitId, TraitMethodId)> {
+        self.methods.get(&function).copied()
+    }
+
+    /// Get every trait with a method called `name`, along with the method.
+    #[must_use]
+    pub fn with_method(&self, name: &str) -> Vec<(TraitId, TraitMethodId)> {
+        let mut found = self
+            .trait_defs
+            .iter()
+            .filter_map(|(id, trait_)| Some((*id, trait_.get_method(name)?)))
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        found
+    }
+
     #[must_use]
     pub fn get_trait_impls(&self, id: TraitId) -> Option<&HashMap<TypeId, TraitImpl>> {
         self.trait_impls.get(&id)
@@ -203,6 +204,14 @@ impl Traits {
         self.trait_impls.get(&trait_id)?.get(&type_id)
     }
 
+    pub fn get_trait_impl_mut(
+        &mut self,
+        trait_id: TraitId,
+        type_id: TypeId,
+    ) -> Option<&mut TraitImpl> {
+        self.trait_impls.get_mut(&trait_id)?.get_mut(&type_id)
+    }
+
     pub fn insert_trait_impl(&mut self, impl_: TraitImpl) {
         self.trait_impls
             .entry(impl_.trait_id)
@@ -1,4 +1,9 @@
-use crate::{path::Path, types::scope::TypeScope};
+use crate::{
+    generics::RECURSION_LIMIT,
+    path::{Path, PathSegment},
+    traits::TraitId,
+    types::scope::TypeScope,
+};
 use std::{collections::HashMap, rc::Rc};
 
 mod scope;
@@ -17,6 +22,39 @@ impl core::fmt::Debug for TypeId {
     }
 }
 
+/// A unique identifier for a struct definition.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct StructId(usize);
+
+impl core::fmt::Debug for StructId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "StructId({})", self.0)
+    }
+}
+
+/// A unique identifier for an enum definition.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct EnumId(usize);
+
+impl core::fmt::Debug for EnumId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "EnumId({})", self.0)
+    }
+}
+
+/// A unique identifier for a tagged union definition.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct UnionId(usize);
+
+impl core::fmt::Debug for UnionId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "UnionId({})", self.0)
+    }
+}
+
 /// Represents a type in the HLIR.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Type {
@@ -40,6 +78,38 @@ pub enum Type {
     ///
     /// Contains the type of the elements in the array.
     UnsizedArray(TypeId),
+    /// A struct with named fields.
+    ///
+    /// Structs are nominal, so the type only refers to its definition which is
+    /// kept alongside the other types.
+    Struct(StructId),
+    /// An enum whose values are one of its variants.
+    ///
+    /// Like structs, enums are nominal and their definition is kept alongside
+    /// the other types.
+    Enum(EnumId),
+    /// An enum whose variants carry values, stored as a tag followed by the
+    /// values of the variant it holds.
+    ///
+    /// Like enums, tagged unions are nominal and their definition is kept
+    /// alongside the other types.
+    Union(UnionId),
+    /// A type parameter of a generic function or type, which stands for the
+    /// type argument at the same position wherever it is instantiated.
+    ///
+    /// Contains the position of the parameter and its name.
+    Parameter(usize, String),
+    /// A pointer to a value of any type implementing a trait, written
+    /// `*dyn Trait`, stored as a pointer to the value along with a pointer to
+    /// the table of the methods of the trait for its type.
+    ///
+    /// Contains the trait and its name.
+    TraitObject(TraitId, String),
+    /// A type that could not be resolved because of an error that has already
+    /// been reported.
+    ///
+    /// Unifies with every other type to avoid reporting further errors.
+    Error,
 }
 
 impl core::fmt::Debug for Type {
@@ -50,6 +120,12 @@ impl core::fmt::Debug for Type {
             Type::Ptr(ty) => write!(f, "Ptr({ty:?})"),
             Type::SizedArray(ty, len) => write!(f, "Array({ty:?}; {len})"),
             Type::UnsizedArray(ty) => write!(f, "Array({ty:?})"),
+            Type::Struct(id) => write!(f, "Struct({id:?})"),
+            Type::Enum(id) => write!(f, "Enum({id:?})"),
+            Type::Union(id) => write!(f, "Union({id:?})"),
+            Type::Parameter(index, name) => write!(f, "Parameter({index}, {name})"),
+            Type::TraitObject(id, _) => write!(f, "TraitObject({id:?})"),
+            Type::Error => write!(f, "Error"),
         }
     }
 }
@@ -62,6 +138,12 @@ impl core::fmt::Display for Type {
             Type::Ptr(ty) => write!(f, "*{ty:?}"),
             Type::SizedArray(ty, len) => write!(f, "[{ty:?}; {len}]"),
             Type::UnsizedArray(ty) => write!(f, "[{ty:?}]"),
+            Type::Struct(id) => write!(f, "struct {id:?}"),
+            Type::Enum(id) => write!(f, "enum {id:?}"),
+            Type::Union(id) => write!(f, "union {id:?}"),
+            Type::Parameter(_, name) => write!(f, "{name}"),
+            Type::TraitObject(_, name) => write!(f, "*dyn {name}"),
+            Type::Error => write!(f, "{{error}}"),
         }
     }
 }
@@ -98,6 +180,55 @@ pub enum PrimitiveType {
     Str,
 }
 
+impl PrimitiveType {
+    /// Whether the type is a signed or unsigned integer.
+    #[must_use]
+    pub fn is_integer(self) -> bool {
+        matches!(
+            self,
+            PrimitiveType::I8
+                | PrimitiveType::I16
+                | PrimitiveType::I32
+                | PrimitiveType::I64
+                | PrimitiveType::U8
+                | PrimitiveType::U16
+                | PrimitiveType::U32
+                | PrimitiveType::U64
+        )
+    }
+
+    /// Whether the type is a signed integer.
+    #[must_use]
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64
+        )
+    }
+
+    /// The values that fit in the type, if it is an integer.
+    #[must_use]
+    pub fn integer_range(self) -> Option<core::ops::RangeInclusive<i128>> {
+        match self {
+            PrimitiveType::I8 => Some(i128::from(i8::MIN)..=i128::from(i8::MAX)),
+            PrimitiveType::I16 => Some(i128::from(i16::MIN)..=i128::from(i16::MAX)),
+            PrimitiveType::I32 => Some(i128::from(i32::MIN)..=i128::from(i32::MAX)),
+            PrimitiveType::I64 => Some(i128::from(i64::MIN)..=i128::from(i64::MAX)),
+            PrimitiveType::U8 => Some(0..=i128::from(u8::MAX)),
+            PrimitiveType::U16 => Some(0..=i128::from(u16::MAX)),
+            PrimitiveType::U32 => Some(0..=i128::from(u32::MAX)),
+            PrimitiveType::U64 => Some(0..=i128::from(u64::MAX)),
+            _ => None,
+        }
+    }
+
+    /// Whether the type is a floating point number.
+    #[must_use]
+    pub fn is_float(self) -> bool {
+        matches!(self, PrimitiveType::F32 | PrimitiveType::F64)
+    }
+}
+
 impl core::fmt::Display for PrimitiveType {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -119,6 +250,155 @@ impl core::fmt::Display for PrimitiveType {
     }
 }
 
+/// The definition of a struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Struct {
+    /// The path the struct was declared at.
+    pub path: Path,
+    /// The fields of the struct, in the order they were declared.
+    pub fields: Vec<StructField>,
+    /// The type parameters of a generic struct, each a [`Type::Parameter`].
+    pub type_parameters: Vec<TypeId>,
+    /// The generic struct this is an instantiation of, along with the type
+    /// arguments replacing its parameters.
+    pub instance_of: Option<(TypeId, Vec<TypeId>)>,
+}
+
+impl Struct {
+    /// Find a field by name, along with its position in the struct.
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<(usize, &StructField)> {
+        self.fields
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructField {
+    pub name: String,
+    pub ty: TypeId,
+}
+
+/// The definition of an enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enum {
+    /// The path the enum was declared at.
+    pub path: Path,
+    /// The integer type that values of the enum are stored as.
+    pub representation: PrimitiveType,
+    /// The variants of the enum, in the order they were declared.
+    pub variants: Vec<EnumVariant>,
+}
+
+impl Enum {
+    /// Find a variant by name, along with its position in the enum.
+    #[must_use]
+    pub fn variant(&self, name: &str) -> Option<(usize, &EnumVariant)> {
+        self.variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumVariant {
+    pub name: String,
+    /// The value that the variant is stored as, which fits in the
+    /// representation of the enum.
+    pub discriminant: i128,
+}
+
+/// The definition of an enum whose variants carry values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Union {
+    /// The path the enum was declared at.
+    pub path: Path,
+    /// The integer type of the tag that tells which variant a value holds.
+    pub tag: PrimitiveType,
+    /// The variants of the enum, in the order they were declared.
+    pub variants: Vec<UnionVariant>,
+    /// The type parameters of a generic enum, each a [`Type::Parameter`].
+    pub type_parameters: Vec<TypeId>,
+    /// The generic enum this is an instantiation of, along with the type
+    /// arguments replacing its parameters.
+    pub instance_of: Option<(TypeId, Vec<TypeId>)>,
+}
+
+impl Union {
+    /// Find a variant by name, along with its position in the enum.
+    #[must_use]
+    pub fn variant(&self, name: &str) -> Option<(usize, &UnionVariant)> {
+        self.variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionVariant {
+    pub name: String,
+    /// The value of the tag for the variant, which fits in the tag type of the
+    /// enum.
+    pub discriminant: i128,
+    pub kind: VariantKind,
+    /// The values carried by the variant. The fields of tuple variants are
+    /// named after their position, e.g. `0`.
+    pub fields: Vec<StructField>,
+}
+
+impl UnionVariant {
+    /// Find a field by name, along with its position in the variant.
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<(usize, &StructField)> {
+        self.fields
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.name == name)
+    }
+}
+
+/// How the values of a variant are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantKind {
+    /// No values, e.g. `Empty`
+    Unit,
+    /// Unnamed values, e.g. `Circle(f32)`
+    Tuple,
+    /// Named values, e.g. `Rect { w: f32, h: f32 }`
+    Struct,
+}
+
+/// The size and alignment of a type in memory, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub size: u64,
+    pub align: u64,
+}
+
+impl Layout {
+    const EMPTY: Layout = Layout { size: 0, align: 1 };
+
+    /// The layout of a value made of `fields` laid out one after the other,
+    /// with padding to keep each of them aligned, as in C.
+    fn sequence(fields: impl IntoIterator<Item = Layout>) -> Layout {
+        let layout = fields
+            .into_iter()
+            .fold(Layout::EMPTY, |layout, field| Layout {
+                size: layout.size.next_multiple_of(field.align) + field.size,
+                align: layout.align.max(field.align),
+            });
+
+        Layout {
+            size: layout.size.next_multiple_of(layout.align),
+            align: layout.align,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Types {
     /// The next ID to assign to a type.
@@ -130,6 +410,20 @@ pub struct Types {
     /// The current scope of types. This is used to resolve types by path as they are
     /// written in the source code (e.g. `u8` or `::u8`
     scope: TypeScope,
+    /// The definitions of every struct, indexed by their [`StructId`].
+    structs: Vec<Struct>,
+    /// The definitions of every enum, indexed by their [`EnumId`].
+    enums: Vec<Enum>,
+    /// The definitions of every tagged union, indexed by their [`UnionId`].
+    unions: Vec<Union>,
+    /// The instantiations of generic structs and tagged unions, by the
+    /// generic type and the type arguments.
+    instances: HashMap<(TypeId, Vec<TypeId>), TypeId>,
+    /// The number of instantiations being created inside one another.
+    instantiation_depth: usize,
+    /// The generic type whose instantiation reached the recursion limit, if
+    /// one did since it was last taken.
+    overflowed: Option<TypeId>,
 }
 
 impl Types {
@@ -140,6 +434,12 @@ impl Types {
             known: HashMap::new(),
             type_ids: HashMap::new(),
             scope: TypeScope::new(),
+            structs: Vec::new(),
+            enums: Vec::new(),
+            unions: Vec::new(),
+            instances: HashMap::new(),
+            instantiation_depth: 0,
+            overflowed: None,
         }
     }
 
@@ -157,8 +457,17 @@ impl Types {
         id
     }
 
+    /// Get every type, in the order they were created, so that the type a
+    /// pointer or an array refers to comes before it.
     pub fn iter(&self) -> impl Iterator<Item = (TypeId, &Type)> + '_ {
-        self.known.iter().map(|(&id, ty)| (id, ty.as_ref()))
+        let mut types: Vec<_> = self
+            .known
+            .iter()
+            .map(|(&id, ty)| (id, ty.as_ref()))
+            .collect();
+        types.sort_by_key(|(id, _)| *id);
+
+        types.into_iter()
     }
 
     /// Get the ID of a type, inserting it if it does not already exist.
@@ -189,6 +498,342 @@ impl Types {
         self.known.get(&id).cloned()
     }
 
+    /// Whether the type is, or is built from, the placeholder for a type with
+    /// an error.
+    #[must_use]
+    pub fn is_error(&self, id: TypeId) -> bool {
+        self.known.get(&id).is_some_and(|ty| match ty.as_ref() {
+            Type::Error => true,
+            Type::Ptr(inner) | Type::SizedArray(inner, _) | Type::UnsizedArray(inner) => {
+                self.is_error(*inner)
+            }
+            Type::Struct(_) | Type::Union(_) => {
+                self.generic_arguments(id).is_some_and(|(_, arguments)| {
+                    arguments.iter().any(|argument| self.is_error(*argument))
+                })
+            }
+            Type::Unresolved(_)
+            | Type::Primitive(_)
+            | Type::Enum(_)
+            | Type::Parameter(_, _)
+            | Type::TraitObject(_, _) => false,
+        })
+    }
+
+    /// Whether the type is, or is built from, a type parameter.
+    ///
+    /// Generic structs and tagged unions are built from their own parameters.
+    #[must_use]
+    pub fn contains_parameters(&self, id: TypeId) -> bool {
+        self.known.get(&id).is_some_and(|ty| match ty.as_ref() {
+            Type::Parameter(_, _) => true,
+            Type::Ptr(inner) | Type::SizedArray(inner, _) | Type::UnsizedArray(inner) => {
+                self.contains_parameters(*inner)
+            }
+            Type::Struct(_) | Type::Union(_) => {
+                self.generic_arguments(id).is_some_and(|(_, arguments)| {
+                    arguments
+                        .iter()
+                        .any(|argument| self.contains_parameters(*argument))
+                })
+            }
+            Type::Unresolved(_)
+            | Type::Primitive(_)
+            | Type::Enum(_)
+            | Type::TraitObject(_, _)
+            | Type::Error => false,
+        })
+    }
+
+    /// The type parameters of a generic struct or tagged union, empty for
+    /// every other type.
+    #[must_use]
+    pub fn type_parameters(&self, id: TypeId) -> &[TypeId] {
+        match self.known.get(&id).map(AsRef::as_ref) {
+            Some(Type::Struct(id)) => &self.structs[id.0].type_parameters,
+            Some(Type::Union(id)) => &self.unions[id.0].type_parameters,
+            _ => &[],
+        }
+    }
+
+    /// The generic struct or tagged union a type is an instantiation of, along
+    /// with its type arguments.
+    ///
+    /// A generic type is its own instantiation, with its type parameters as
+    /// the arguments.
+    #[must_use]
+    pub fn generic_arguments(&self, id: TypeId) -> Option<(TypeId, &[TypeId])> {
+        let (type_parameters, instance_of) = match self.known.get(&id)?.as_ref() {
+            Type::Struct(struct_id) => {
+                let definition = &self.structs[struct_id.0];
+                (&definition.type_parameters, &definition.instance_of)
+            }
+            Type::Union(union_id) => {
+                let definition = &self.unions[union_id.0];
+                (&definition.type_parameters, &definition.instance_of)
+            }
+            _ => return None,
+        };
+
+        match instance_of {
+            Some((generic, arguments)) => Some((*generic, arguments)),
+            None if !type_parameters.is_empty() => Some((id, type_parameters)),
+            None => None,
+        }
+    }
+
+    /// Get the instantiation of the generic struct or tagged union `generic`
+    /// with `arguments`, if it already exists.
+    #[must_use]
+    pub fn get_instance(&self, generic: TypeId, arguments: &[TypeId]) -> Option<TypeId> {
+        if self.type_parameters(generic) == arguments {
+            return Some(generic);
+        }
+
+        self.instances.get(&(generic, arguments.to_vec())).copied()
+    }
+
+    /// Get the instantiation of the generic struct or tagged union `generic`
+    /// with `arguments`, creating it if it does not exist yet.
+    ///
+    /// The fields of an instantiation are those of the generic type with the
+    /// arguments in place of the parameters. A type that contains itself with
+    /// other arguments, e.g. through a `*Node<*T>` field, has no end of
+    /// instantiations, so those nested too deep are errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path of `generic` is empty.
+    pub fn instantiate(&mut self, generic: TypeId, arguments: Vec<TypeId>) -> TypeId {
+        if let Some(instance) = self.get_instance(generic, &arguments) {
+            return instance;
+        }
+
+        if self.instantiation_depth == RECURSION_LIMIT {
+            self.overflowed.get_or_insert(generic);
+            return self.get_or_insert(Type::Error);
+        }
+
+        let Some(ty) = self.get_by_id(generic) else {
+            return generic;
+        };
+
+        let path = |types: &Self, path: &Path| {
+            let mut path = path.clone();
+            let name = path.pop().expect("expected path to have a name").name;
+            path.push(PathSegment::with_arguments(
+                name,
+                arguments.iter().map(|ty| types.to_path(*ty)).collect(),
+            ));
+            path
+        };
+
+        let instance = match *ty {
+            Type::Struct(id) => {
+                let instance = StructId(self.structs.len());
+                self.structs.push(Struct {
+                    path: path(self, &self.structs[id.0].path),
+                    fields: Vec::new(),
+                    type_parameters: Vec::new(),
+                    instance_of: Some((generic, arguments.clone())),
+                });
+                self.get_or_insert(Type::Struct(instance))
+            }
+            Type::Union(id) => {
+                let instance = UnionId(self.unions.len());
+                self.unions.push(Union {
+                    path: path(self, &self.unions[id.0].path),
+                    tag: self.unions[id.0].tag,
+                    variants: Vec::new(),
+                    type_parameters: Vec::new(),
+                    instance_of: Some((generic, arguments.clone())),
+                });
+                self.get_or_insert(Type::Union(instance))
+            }
+            _ => return generic,
+        };
+
+        // The instantiation is known before its fields are substituted, so
+        // that fields can refer to it
+        self.instances.insert((generic, arguments), instance);
+
+        self.instantiation_depth += 1;
+        self.substitute_definition(instance);
+        self.instantiation_depth -= 1;
+
+        instance
+    }
+
+    /// Take the generic type whose instantiation reached the recursion limit,
+    /// if one did since this was last called.
+    pub fn take_overflowed(&mut self) -> Option<TypeId> {
+        self.overflowed.take()
+    }
+
+    /// Set the fields of an instantiation to those of its generic type, with
+    /// the type arguments in place of the parameters.
+    fn substitute_definition(&mut self, instance: TypeId) {
+        let Some((generic, arguments)) = self
+            .generic_arguments(instance)
+            .map(|(generic, arguments)| (generic, arguments.to_vec()))
+        else {
+            return;
+        };
+
+        let (Some(generic), Some(instance)) = (self.get_by_id(generic), self.get_by_id(instance))
+        else {
+            return;
+        };
+
+        match (generic.as_ref(), instance.as_ref()) {
+            (Type::Struct(generic), Type::Struct(instance)) => {
+                let fields = self.structs[generic.0]
+                    .fields
+                    .clone()
+                    .into_iter()
+                    .map(|field| StructField {
+                        ty: self.substitute(field.ty, &arguments),
+                        ..field
+                    })
+                    .collect();
+                self.structs[instance.0].fields = fields;
+            }
+            (Type::Union(generic), Type::Union(instance)) => {
+                let mut variants = self.unions[generic.0].variants.clone();
+                for field in variants.iter_mut().flat_map(|variant| &mut variant.fields) {
+                    field.ty = self.substitute(field.ty, &arguments);
+                }
+                self.unions[instance.0].variants = variants;
+            }
+            _ => {}
+        }
+    }
+
+    /// Update the fields of the instantiations of `generic` once its own are
+    /// set.
+    fn substitute_instances(&mut self, generic: TypeId) {
+        let mut instances = self
+            .instances
+            .iter()
+            .filter(|((of, _), _)| *of == generic)
+            .map(|(_, instance)| *instance)
+            .collect::<Vec<_>>();
+        instances.sort_unstable();
+
+        for instance in instances {
+            self.substitute_definition(instance);
+        }
+    }
+
+    /// Replace the type parameters in a type by the type arguments at their
+    /// positions, inserting the types built along the way.
+    ///
+    /// Parameters without an argument are kept.
+    pub fn substitute(&mut self, id: TypeId, arguments: &[TypeId]) -> TypeId {
+        let Some(ty) = self.get_by_id(id) else {
+            return id;
+        };
+
+        match *ty {
+            Type::Parameter(index, _) => arguments.get(index).copied().unwrap_or(id),
+            Type::Ptr(inner) => {
+                let inner = self.substitute(inner, arguments);
+                self.get_or_insert(Type::Ptr(inner))
+            }
+            Type::SizedArray(inner, len) => {
+                let inner = self.substitute(inner, arguments);
+                self.get_or_insert(Type::SizedArray(inner, len))
+            }
+            Type::UnsizedArray(inner) => {
+                let inner = self.substitute(inner, arguments);
+                self.get_or_insert(Type::UnsizedArray(inner))
+            }
+            Type::Struct(_) | Type::Union(_) => {
+                let Some((generic, type_arguments)) = self
+                    .generic_arguments(id)
+                    .map(|(generic, type_arguments)| (generic, type_arguments.to_vec()))
+                else {
+                    return id;
+                };
+
+                let type_arguments = type_arguments
+                    .into_iter()
+                    .map(|argument| self.substitute(argument, arguments))
+                    .collect();
+                self.instantiate(generic, type_arguments)
+            }
+            Type::Unresolved(_)
+            | Type::Primitive(_)
+            | Type::Enum(_)
+            | Type::TraitObject(_, _)
+            | Type::Error => id,
+        }
+    }
+
+    /// Get the type that [`Types::substitute`] gives, if it already exists.
+    #[must_use]
+    pub fn get_substituted(&self, id: TypeId, arguments: &[TypeId]) -> Option<TypeId> {
+        let ty = self.get_by_id(id)?;
+
+        let substituted = match *ty {
+            Type::Parameter(index, _) => return Some(arguments.get(index).copied().unwrap_or(id)),
+            Type::Ptr(inner) => Type::Ptr(self.get_substituted(inner, arguments)?),
+            Type::SizedArray(inner, len) => {
+                Type::SizedArray(self.get_substituted(inner, arguments)?, len)
+            }
+            Type::UnsizedArray(inner) => {
+                Type::UnsizedArray(self.get_substituted(inner, arguments)?)
+            }
+            Type::Struct(_) | Type::Union(_) => {
+                let Some((generic, type_arguments)) = self.generic_arguments(id) else {
+                    return Some(id);
+                };
+
+                let type_arguments = type_arguments
+                    .iter()
+                    .map(|argument| self.get_substituted(*argument, arguments))
+                    .collect::<Option<Vec<_>>>()?;
+                return self.get_instance(generic, &type_arguments);
+            }
+            Type::Unresolved(_)
+            | Type::Primitive(_)
+            | Type::Enum(_)
+            | Type::TraitObject(_, _)
+            | Type::Error => return Some(id),
+        };
+
+        self.type_ids.get(&substituted).copied()
+    }
+
+    /// Get a path that identifies the type, used to name the instantiations of
+    /// generic functions and types.
+    ///
+    /// Types without a name of their own are named after what they are built
+    /// from, behind a marker that is not an identifier so that they cannot
+    /// collide with a type declared with the same name, e.g. `*i32` is
+    /// `$P$<i32>` and `[i32; 4]` is `$A$4$<i32>`.
+    #[must_use]
+    pub fn to_path(&self, id: TypeId) -> Path {
+        let Some(ty) = self.get_by_id(id) else {
+            return Path::from(self.to_display(id));
+        };
+
+        let (name, arguments) = match *ty {
+            Type::Struct(id) => return self.structs[id.0].path.clone(),
+            Type::Enum(id) => return self.enums[id.0].path.clone(),
+            Type::Union(id) => return self.unions[id.0].path.clone(),
+            Type::Ptr(inner) => ("$P$".to_string(), vec![self.to_path(inner)]),
+            Type::SizedArray(inner, len) => (format!("$A${len}$"), vec![self.to_path(inner)]),
+            Type::UnsizedArray(inner) => ("$S$".to_string(), vec![self.to_path(inner)]),
+            Type::TraitObject(_, ref name) => ("$D$".to_string(), vec![Path::from(name.as_str())]),
+            Type::Unresolved(_) | Type::Primitive(_) | Type::Parameter(_, _) | Type::Error => {
+                return Path::from(self.to_display(id));
+            }
+        };
+
+        Path::new(vec![PathSegment::with_arguments(name, arguments)])
+    }
+
     #[must_use]
     pub fn to_display(&self, id: TypeId) -> String {
         match self.get_by_id(id) {
@@ -199,6 +844,18 @@ impl Types {
                 Type::Ptr(inner) => format!("*{}", self.to_display(inner)),
                 Type::SizedArray(inner, len) => format!("[{}; {}]", self.to_display(inner), len),
                 Type::UnsizedArray(inner) => format!("[{}]", self.to_display(inner)),
+                Type::Struct(id) => self.nominal_display(
+                    &self.structs[id.0].path,
+                    self.structs[id.0].instance_of.as_ref(),
+                ),
+                Type::Enum(id) => Self::path_display(&self.enums[id.0].path),
+                Type::Union(id) => self.nominal_display(
+                    &self.unions[id.0].path,
+                    self.unions[id.0].instance_of.as_ref(),
+                ),
+                Type::Parameter(_, ref name) => name.clone(),
+                Type::TraitObject(_, ref name) => format!("*dyn {name}"),
+                Type::Error => "{error}".to_string(),
             },
         }
     }
@@ -209,6 +866,213 @@ impl Types {
         id
     }
 
+    /// The name of a type declared at `path`, without the leading `::`.
+    fn path_display(path: &Path) -> String {
+        path.segments()
+            .iter()
+            .map(|segment| segment.name.as_str())
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// The name of a struct or tagged union declared at `path`, followed by
+    /// its type arguments if it is an instantiation, e.g. `Pair<i32, f32>`.
+    fn nominal_display(&self, path: &Path, instance_of: Option<&(TypeId, Vec<TypeId>)>) -> String {
+        let name = Self::path_display(path);
+
+        match instance_of {
+            Some((_, arguments)) => format!(
+                "{name}<{}>",
+                arguments
+                    .iter()
+                    .map(|argument| self.to_display(*argument))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            None => name,
+        }
+    }
+
+    /// Declare a new struct at `path` without any fields, they are set with
+    /// [`Self::set_struct_fields`] once the types they refer to are known.
+    pub fn create_struct(&mut self, path: Path) -> TypeId {
+        let id = StructId(self.structs.len());
+        self.structs.push(Struct {
+            path: path.clone(),
+            fields: Vec::new(),
+            type_parameters: Vec::new(),
+            instance_of: None,
+        });
+
+        self.create_type(path, Type::Struct(id))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the struct does not exist.
+    pub fn set_struct_fields(&mut self, id: StructId, fields: Vec<StructField>) {
+        self.structs[id.0].fields = fields;
+
+        if let Some(generic) = self.type_ids.get(&Type::Struct(id)).copied() {
+            self.substitute_instances(generic);
+        }
+    }
+
+    /// Make a struct generic over `type_parameters`, which must be set before
+    /// the struct is instantiated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the struct does not exist.
+    pub fn set_struct_type_parameters(&mut self, id: StructId, type_parameters: Vec<TypeId>) {
+        self.structs[id.0].type_parameters = type_parameters;
+    }
+
+    #[must_use]
+    pub fn get_struct(&self, id: StructId) -> Option<&Struct> {
+        self.structs.get(id.0)
+    }
+
+    /// Declare a new enum at `path` without any variants, they are set with
+    /// [`Self::set_enum_variants`].
+    pub fn create_enum(&mut self, path: Path, representation: PrimitiveType) -> TypeId {
+        let id = EnumId(self.enums.len());
+        self.enums.push(Enum {
+            path: path.clone(),
+            representation,
+            variants: Vec::new(),
+        });
+
+        self.create_type(path, Type::Enum(id))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the enum does not exist.
+    pub fn set_enum_variants(&mut self, id: EnumId, variants: Vec<EnumVariant>) {
+        self.enums[id.0].variants = variants;
+    }
+
+    #[must_use]
+    pub fn get_enum(&self, id: EnumId) -> Option<&Enum> {
+        self.enums.get(id.0)
+    }
+
+    /// Declare a new tagged union at `path` without any variants, they are set
+    /// with [`Self::set_union_variants`] once the types they refer to are known.
+    pub fn create_union(&mut self, path: Path, tag: PrimitiveType) -> TypeId {
+        let id = UnionId(self.unions.len());
+        self.unions.push(Union {
+            path: path.clone(),
+            tag,
+            variants: Vec::new(),
+            type_parameters: Vec::new(),
+            instance_of: None,
+        });
+
+        self.create_type(path, Type::Union(id))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the tagged union does not exist.
+    pub fn set_union_variants(&mut self, id: UnionId, variants: Vec<UnionVariant>) {
+        self.unions[id.0].variants = variants;
+
+        if let Some(generic) = self.type_ids.get(&Type::Union(id)).copied() {
+            self.substitute_instances(generic);
+        }
+    }
+
+    /// Make a tagged union generic over `type_parameters`, which must be set
+    /// before the union is instantiated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tagged union does not exist.
+    pub fn set_union_type_parameters(&mut self, id: UnionId, type_parameters: Vec<TypeId>) {
+        self.unions[id.0].type_parameters = type_parameters;
+    }
+
+    #[must_use]
+    pub fn get_union(&self, id: UnionId) -> Option<&Union> {
+        self.unions.get(id.0)
+    }
+
+    /// The size and alignment of values of the type on a 64-bit target.
+    ///
+    /// Types that are not known yet, and types with errors, take no space.
+    #[must_use]
+    pub fn layout(&self, id: TypeId) -> Layout {
+        let Some(ty) = self.get_by_id(id) else {
+            return Layout::EMPTY;
+        };
+
+        match *ty {
+            // Type parameters only have a layout once they are instantiated
+            Type::Unresolved(_) | Type::Parameter(_, _) | Type::Error => Layout::EMPTY,
+            Type::Primitive(primitive) => Self::primitive_layout(primitive),
+            Type::Ptr(_) | Type::UnsizedArray(_) => Layout { size: 8, align: 8 },
+            // A pointer to the value and a pointer to the table of methods
+            Type::TraitObject(_, _) => Layout { size: 16, align: 8 },
+            Type::SizedArray(element, len) => {
+                let element = self.layout(element);
+                Layout {
+                    size: element.size * len as u64,
+                    align: element.align,
+                }
+            }
+            Type::Struct(id) => Layout::sequence(
+                self.structs[id.0]
+                    .fields
+                    .iter()
+                    .map(|field| self.layout(field.ty)),
+            ),
+            Type::Enum(id) => Self::primitive_layout(self.enums[id.0].representation),
+            Type::Union(id) => Layout::sequence([
+                Self::primitive_layout(self.unions[id.0].tag),
+                self.payload_layout(id),
+            ]),
+        }
+    }
+
+    /// The size and alignment of the area holding the values of the variants
+    /// of a tagged union, which fits the values of any of them.
+    #[must_use]
+    pub fn payload_layout(&self, id: UnionId) -> Layout {
+        let payload = self.unions[id.0]
+            .variants
+            .iter()
+            .map(|variant| {
+                Layout::sequence(variant.fields.iter().map(|field| self.layout(field.ty)))
+            })
+            .fold(Layout::EMPTY, |payload, variant| Layout {
+                size: payload.size.max(variant.size),
+                align: payload.align.max(variant.align),
+            });
+
+        // The largest variant may be less aligned than the most aligned one
+        Layout {
+            size: payload.size.next_multiple_of(payload.align),
+            align: payload.align,
+        }
+    }
+
+    fn primitive_layout(primitive: PrimitiveType) -> Layout {
+        let size = match primitive {
+            PrimitiveType::Void => 0,
+            PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::Bool | PrimitiveType::Char => 1,
+            PrimitiveType::I16 | PrimitiveType::U16 => 2,
+            PrimitiveType::I32 | PrimitiveType::U32 | PrimitiveType::F32 => 4,
+            PrimitiveType::I64 | PrimitiveType::U64 | PrimitiveType::F64 | PrimitiveType::Str => 8,
+        };
+
+        Layout {
+            size,
+            align: size.max(1),
+        }
+    }
+
     /// Inserts a new path reference to an existing type.
     ///
     /// # Panics
@@ -238,6 +1102,16 @@ impl core::fmt::Debug for Types {
             .field_with("known", |f| f.debug_map().entries(known).finish())
             .field_with("type_ids", |f| f.debug_map().entries(type_ids).finish())
             .field("scope", &self.scope)
+            .field("structs", &self.structs)
+            .field("enums", &self.enums)
+            .field("unions", &self.unions)
+            .field_with("instances", |f| {
+                let mut instances: Vec<_> = self.instances.iter().collect();
+                instances.sort_by_key(|(_, id)| **id);
+                f.debug_map().entries(instances).finish()
+            })
+            .field("instantiation_depth", &self.instantiation_depth)
+            .field("overflowed", &self.overflowed)
             .finish()
     }
 }
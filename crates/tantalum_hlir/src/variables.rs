@@ -6,6 +6,7 @@ mod scope;
 
 use crate::functions::FunctionId;
 use crate::inference::InferenceId;
+use crate::path::Path;
 pub use scope::VariableScopeBlockId;
 
 /// A unique identifier for a variable.
@@ -70,6 +71,8 @@ pub struct Variables {
     known: HashMap<VariableId, VariableType>,
     /// The current scope of all variables.
     scope: VariableScope,
+    /// The constants and statics declared in modules, by their path.
+    globals: HashMap<Path, VariableId>,
 }
 
 impl Variables {
@@ -79,6 +82,7 @@ impl Variables {
             next_id: VariableId(0),
             known: HashMap::new(),
             scope: VariableScope::new(),
+            globals: HashMap::new(),
         }
     }
 
@@ -151,6 +155,33 @@ impl Variables {
         }
     }
 
+    /// Add a variable declared in a module at `path`, unless one is already
+    /// declared there.
+    pub fn create_global(&mut self, path: Path, ty: InferenceId) -> Option<VariableId> {
+        if self.globals.contains_key(&path) {
+            return None;
+        }
+
+        let id = self.next_id();
+        let name = path.segments().last().map_or("", |segment| &segment.name);
+        self.known.insert(
+            id,
+            VariableType::Variable(Variable {
+                name: name.to_string(),
+                ty,
+            }),
+        );
+
+        self.globals.insert(path, id);
+
+        Some(id)
+    }
+
+    #[must_use]
+    pub fn get_global(&self, path: &Path) -> Option<VariableId> {
+        self.globals.get(path).copied()
+    }
+
     pub fn get_or_insert_function(&mut self, name: &str, function_id: FunctionId) -> VariableId {
         if let Some(id) = self.get(name) {
             return id;
@@ -182,6 +213,12 @@ impl core::fmt::Debug for Variables {
                 f.debug_map().entries(known).finish()
             })
             .field("scope", &self.scope)
+            .field_with("globals", |f| {
+                let mut globals: Vec<_> = self.globals.iter().collect();
+                globals.sort_by_key(|(_, id)| **id);
+
+                f.debug_map().entries(globals).finish()
+            })
             .finish()
     }
 }
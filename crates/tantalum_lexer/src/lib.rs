@@ -9,6 +9,7 @@ mod tests;
 pub mod token;
 pub mod token_kind;
 
+use tantalum_diagnostics::{codes, Diagnostic, Label};
 use tantalum_span::{Location, Span, Spanned};
 
 use crate::token::Token;
@@ -23,6 +24,8 @@ pub struct Lexer<'file_name, 'source> {
     location: Location<'file_name>,
     /// The source code to lexed into tokens
     source: &'source str,
+    /// The problems found in the source code so far
+    diagnostics: Vec<Diagnostic<'file_name>>,
 }
 
 impl<'file_name, 'source> Lexer<'file_name, 'source> {
@@ -44,6 +47,7 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
         return Self {
             location: Location::new(file_name),
             source,
+            diagnostics: Vec::new(),
         };
     }
 
@@ -65,11 +69,27 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
         return self.location.file_name();
     }
 
+    /// The problems found in the source code so far
+    ///
+    /// Tokens that could not be lexed are still produced (e.g. as
+    /// [`TokenKind::Unknown`]) so that a parser can continue past them.
+    #[must_use]
+    #[inline]
+    pub fn diagnostics(&self) -> &[Diagnostic<'file_name>] {
+        return &self.diagnostics;
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn into_diagnostics(self) -> Vec<Diagnostic<'file_name>> {
+        return self.diagnostics;
+    }
+
     #[must_use]
     #[inline]
     #[expect(clippy::too_many_lines, reason = "Long match statement")]
     pub fn next_token(&mut self) -> Option<Spanned<'file_name, Token<'source>>> {
-        self.skip_whitespace();
+        self.skip_trivia();
 
         let start = self.location;
 
@@ -105,25 +125,47 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
             '.' => match self.peek_characters(2) {
                 Some('*') => lex!(TokenKind::DotStar, 2),
                 Some('&') => lex!(TokenKind::DotAmpersand, 2),
-                Some('.')
-                    if self
-                        .peek_characters(3)
-                        .is_some_and(|character| return character == '.') =>
-                {
-                    lex!(TokenKind::DotDotDot, 3)
-                }
+                Some('.') => match self.peek_characters(3) {
+                    Some('.') => lex!(TokenKind::DotDotDot, 3),
+                    Some('=') => lex!(TokenKind::DotDotEqual, 3),
+                    _ => lex!(TokenKind::DotDot, 2),
+                },
                 _ => lex!(TokenKind::Dot, 1),
             },
-            '=' => {
-                if let Some('=') = self.peek_characters(2) {
-                    lex!(TokenKind::EqualEqual, 2)
-                }
-                lex!(TokenKind::Equal, 1)
-            }
+            '=' => match self.peek_characters(2) {
+                Some('=') => lex!(TokenKind::EqualEqual, 2),
+                Some('>') => lex!(TokenKind::FatArrow, 2),
+                _ => lex!(TokenKind::Equal, 1),
+            },
             '+' => lex!(TokenKind::Plus, 1),
             '-' => lex!(TokenKind::Minus, 1),
             '*' => lex!(TokenKind::Star, 1),
-            '/' => lex!(TokenKind::Slash, 1),
+            '/' => match self.peek_characters(2) {
+                // Only doc comments are left after skipping trivia
+                Some('/') => {
+                    self.skip_line();
+                    lex!(TokenKind::DocComment, 0)
+                }
+                // Only unterminated block comments are left after skipping trivia
+                Some('*') => {
+                    let _: char = self.next_characters(2)?;
+                    let opening = Span::new(start, self.location);
+
+                    while self.next_characters(1).is_some() {}
+
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            codes::UNTERMINATED_BLOCK_COMMENT,
+                            "unterminated block comment",
+                            Label::new(opening, "comment starts here"),
+                        )
+                        .with_note("block comments nest, each `/*` needs a matching `*/`"),
+                    );
+
+                    lex!(TokenKind::UnterminatedBlockComment, 0)
+                }
+                _ => lex!(TokenKind::Slash, 1),
+            },
             '%' => lex!(TokenKind::Percent, 1),
             '&' => {
                 if let Some('&') = self.peek_characters(2) {
@@ -177,6 +219,18 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
                     "break" => lex!(TokenKind::KeywordBreak, 0),
                     "continue" => lex!(TokenKind::KeywordContinue, 0),
                     "const" => lex!(TokenKind::KeywordConst, 0),
+                    "struct" => lex!(TokenKind::KeywordStruct, 0),
+                    "enum" => lex!(TokenKind::KeywordEnum, 0),
+                    "is" => lex!(TokenKind::KeywordIs, 0),
+                    "match" => lex!(TokenKind::KeywordMatch, 0),
+                    "trait" => lex!(TokenKind::KeywordTrait, 0),
+                    "impl" => lex!(TokenKind::KeywordImpl, 0),
+                    "dyn" => lex!(TokenKind::KeywordDyn, 0),
+                    "mod" => lex!(TokenKind::KeywordMod, 0),
+                    "use" => lex!(TokenKind::KeywordUse, 0),
+                    "as" => lex!(TokenKind::KeywordAs, 0),
+                    "type" => lex!(TokenKind::KeywordType, 0),
+                    "static" => lex!(TokenKind::KeywordStatic, 0),
                     "true" => lex!(TokenKind::KeywordTrue, 0),
                     "false" => lex!(TokenKind::KeywordFalse, 0),
                     _ => lex!(TokenKind::Identifier, 0),
@@ -291,6 +345,8 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
 
             '"' => {
                 let _: char = self.next_characters(1)?;
+                let opening = Span::new(start, self.location);
+                let mut terminated = false;
 
                 while let Some(character) = self.peek_characters(1) {
                     match character {
@@ -300,6 +356,7 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
                         }
                         '"' => {
                             let _: char = self.next_characters(1)?;
+                            terminated = true;
                             break;
                         }
                         _ => {
@@ -308,6 +365,14 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
                     }
                 }
 
+                if !terminated {
+                    self.diagnostics.push(Diagnostic::error(
+                        codes::UNTERMINATED_STRING_LITERAL,
+                        "unterminated string literal",
+                        Label::new(opening, "string starts here"),
+                    ));
+                }
+
                 lex!(TokenKind::StringLiteral, 0);
             }
 
@@ -324,19 +389,35 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
                     }
                 }
 
-                match self.peek_characters(1) {
-                    Some('\'') => {
-                        let _: char = self.next_characters(1)?;
-                    }
-                    _ => {
-                        return None;
-                    }
+                if self.peek_characters(1) == Some('\'') {
+                    let _: char = self.next_characters(1)?;
+                } else {
+                    self.diagnostics.push(Diagnostic::error(
+                        codes::UNTERMINATED_CHARACTER_LITERAL,
+                        "unterminated character literal",
+                        Label::new(Span::new(start, self.location), "expected a closing `'`"),
+                    ));
+
+                    lex!(TokenKind::Unknown, 0);
                 }
 
                 lex!(TokenKind::CharacterLiteral, 0);
             }
 
-            _ => lex!(TokenKind::Unknown, 1),
+            _ => {
+                let character = self.next_characters(1)?;
+
+                self.diagnostics.push(Diagnostic::error(
+                    codes::UNKNOWN_CHARACTER,
+                    format!("unknown character `{}`", character.escape_debug()),
+                    Label::new(
+                        Span::new(start, self.location),
+                        "not the start of any token",
+                    ),
+                ));
+
+                lex!(TokenKind::Unknown, 0)
+            }
         }
     }
 
@@ -354,6 +435,83 @@ impl<'file_name, 'source> Lexer<'file_name, 'source> {
         ));
     }
 
+    /// Skip any whitespace and comments in the source code
+    ///
+    /// Doc comments and unterminated block comments are left in place so that
+    /// they can be produced as tokens.
+    fn skip_trivia(&mut self) {
+        loop {
+            self.skip_whitespace();
+
+            match (self.peek_characters(1), self.peek_characters(2)) {
+                (Some('/'), Some('/')) if !self.is_at_doc_comment() => self.skip_line(),
+                (Some('/'), Some('*')) => {
+                    let mut lexer = self.clone();
+
+                    if !lexer.skip_block_comment() {
+                        break;
+                    }
+
+                    *self = lexer;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Check if the lexer is at the start of a `///` doc comment
+    ///
+    /// Comments starting with four or more slashes are ordinary comments.
+    fn is_at_doc_comment(&self) -> bool {
+        return self.peek_characters(1) == Some('/')
+            && self.peek_characters(2) == Some('/')
+            && self.peek_characters(3) == Some('/')
+            && self.peek_characters(4) != Some('/');
+    }
+
+    /// Skip to the end of the current line, leaving the newline in place
+    fn skip_line(&mut self) {
+        while let Some(character) = self.peek_characters(1) {
+            if character == '\n' {
+                break;
+            }
+
+            let _: char = self
+                .next_characters(1)
+                .expect("Failed to unwrap character already shown to exist");
+        }
+    }
+
+    /// Skip a block comment starting at the current position, block comments
+    /// may be nested
+    ///
+    /// Returns `false` if the end of the source is reached before the comment
+    /// is closed.
+    fn skip_block_comment(&mut self) -> bool {
+        let mut depth = 0_usize;
+
+        loop {
+            match (self.peek_characters(1), self.peek_characters(2)) {
+                (Some('/'), Some('*')) => {
+                    let _: Option<char> = self.next_characters(2);
+                    depth += 1;
+                }
+                (Some('*'), Some('/')) => {
+                    let _: Option<char> = self.next_characters(2);
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return true;
+                    }
+                }
+                (Some(_), _) => {
+                    let _: Option<char> = self.next_characters(1);
+                }
+                (None, _) => return false,
+            }
+        }
+    }
+
     /// Skip any whitespace characters in the source code
     fn skip_whitespace(&mut self) {
         while let Some(character) = self.peek_characters(1) {
@@ -2,6 +2,8 @@ use rstest::rstest;
 
 use crate::Lexer;
 
+mod comments;
+mod diagnostics;
 mod identifiers;
 mod keywords;
 mod literals;
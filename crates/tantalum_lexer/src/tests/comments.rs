@@ -0,0 +1,48 @@
+use rstest::rstest;
+
+use crate::single_token_test_case;
+use crate::token_kind::TokenKind;
+use crate::Lexer;
+
+single_token_test_case! {
+    DocComment : "/// A doc comment" => "/// A doc comment" (0..17, 1, 1),
+    UnterminatedBlockComment : "/* never closed" => "/* never closed" (0..15, 1, 1)
+}
+
+#[rstest]
+#[case("// a comment", &[])]
+#[case("// a comment\nfn", &[TokenKind::KeywordFn])]
+#[case("fn // a comment\nmain", &[TokenKind::KeywordFn, TokenKind::Identifier])]
+#[case("//// not a doc comment\nfn", &[TokenKind::KeywordFn])]
+#[case("/// doc\nfn", &[TokenKind::DocComment, TokenKind::KeywordFn])]
+#[case("/* a comment */", &[])]
+#[case("fn /* a comment */ main", &[TokenKind::KeywordFn, TokenKind::Identifier])]
+#[case("/* a\nmultiline\ncomment */fn", &[TokenKind::KeywordFn])]
+#[case("/* outer /* inner */ still a comment */fn", &[TokenKind::KeywordFn])]
+#[case("/**/ // two comments\n/* */ fn", &[TokenKind::KeywordFn])]
+#[case("a / b", &[TokenKind::Identifier, TokenKind::Slash, TokenKind::Identifier])]
+#[case("fn /* outer /* inner */", &[TokenKind::KeywordFn, TokenKind::UnterminatedBlockComment])]
+fn comments(#[case] source: &str, #[case] expected: &[TokenKind]) {
+    let lexer = Lexer::new("main.ta", source);
+
+    let kinds = lexer
+        .map(|token| return token.data().kind())
+        .collect::<Vec<_>>();
+
+    pretty_assertions::assert_eq!(kinds, expected);
+}
+
+#[test]
+fn unterminated_nested_block_comment_spans_to_end() {
+    let source = "fn /* outer /* inner */\nmain";
+    let mut lexer = Lexer::new("main.ta", source);
+
+    let _ = lexer.next_token();
+    let token = lexer
+        .next_token()
+        .expect("expected an unterminated comment");
+
+    assert_eq!(token.data().kind(), TokenKind::UnterminatedBlockComment);
+    assert_eq!(token.data().lexeme(), "/* outer /* inner */\nmain");
+    assert_eq!(lexer.next_token(), None);
+}
@@ -0,0 +1,42 @@
+use rstest::rstest;
+use tantalum_diagnostics::{codes, Code};
+
+use crate::token_kind::TokenKind;
+use crate::Lexer;
+
+#[rstest]
+#[case("fn", &[TokenKind::KeywordFn], &[])]
+#[case("$", &[TokenKind::Unknown], &[codes::UNKNOWN_CHARACTER])]
+#[case("a @ b", &[TokenKind::Identifier, TokenKind::Unknown, TokenKind::Identifier], &[codes::UNKNOWN_CHARACTER])]
+#[case("/* never closed", &[TokenKind::UnterminatedBlockComment], &[codes::UNTERMINATED_BLOCK_COMMENT])]
+#[case("\"never closed", &[TokenKind::StringLiteral], &[codes::UNTERMINATED_STRING_LITERAL])]
+#[case("'ab", &[TokenKind::Unknown, TokenKind::Identifier], &[codes::UNTERMINATED_CHARACTER_LITERAL])]
+fn diagnostics(#[case] source: &str, #[case] kinds: &[TokenKind], #[case] expected: &[Code]) {
+    let mut lexer = Lexer::new("main.ta", source);
+
+    let tokens = lexer
+        .by_ref()
+        .map(|token| return token.data().kind())
+        .collect::<Vec<_>>();
+
+    let diagnostics = lexer
+        .diagnostics()
+        .iter()
+        .map(tantalum_diagnostics::Diagnostic::code)
+        .collect::<Vec<_>>();
+
+    pretty_assertions::assert_eq!(tokens, kinds);
+    pretty_assertions::assert_eq!(diagnostics, expected);
+}
+
+#[test]
+fn unterminated_block_comment_points_at_opening() {
+    let mut lexer = Lexer::new("main.ta", "fn /* never closed\nmain");
+
+    while lexer.next_token().is_some() {}
+
+    let diagnostic = &lexer.diagnostics()[0];
+
+    assert_eq!(diagnostic.primary().span().range(), 3..5);
+    assert_eq!(diagnostic.primary().message(), "comment starts here");
+}
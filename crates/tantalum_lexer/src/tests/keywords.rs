@@ -11,6 +11,18 @@ single_token_test_case! {
     KeywordBreak : "break" => "break" (0..5, 1, 1),
     KeywordContinue : "continue" => "continue" (0..8, 1, 1),
     KeywordConst : "const" => "const" (0..5, 1, 1),
+    KeywordStruct : "struct" => "struct" (0..6, 1, 1),
+    KeywordEnum : "enum" => "enum" (0..4, 1, 1),
+    KeywordIs : "is" => "is" (0..2, 1, 1),
+    KeywordMatch : "match" => "match" (0..5, 1, 1),
+    KeywordTrait : "trait" => "trait" (0..5, 1, 1),
+    KeywordImpl : "impl" => "impl" (0..4, 1, 1),
+    KeywordDyn : "dyn" => "dyn" (0..3, 1, 1),
+    KeywordMod : "mod" => "mod" (0..3, 1, 1),
+    KeywordUse : "use" => "use" (0..3, 1, 1),
+    KeywordAs : "as" => "as" (0..2, 1, 1),
+    KeywordType : "type" => "type" (0..4, 1, 1),
+    KeywordStatic : "static" => "static" (0..6, 1, 1),
     KeywordTrue : "true" => "true" (0..4, 1, 1),
     KeywordFalse : "false" => "false" (0..5, 1, 1)
 }
@@ -6,6 +6,9 @@ single_token_test_case! {
     Dot : "." => "." (0..1, 1, 1),
     DotStar : ".*" => ".*" (0..2, 1, 1),
     DotAmpersand : ".&" => ".&" (0..2, 1, 1),
+    DotDot : ".." => ".." (0..2, 1, 1),
+    DotDotEqual : "..=" => "..=" (0..3, 1, 1),
+    FatArrow : "=>" => "=>" (0..2, 1, 1),
     Equal : "=" => "=" (0..1, 1, 1),
     Plus : "+" => "+" (0..1, 1, 1),
     Minus : "-" => "-" (0..1, 1, 1),
@@ -18,6 +18,9 @@ pub enum TokenKind {
     DotStar,
     DotAmpersand,
     DotDotDot,
+    DotDot,
+    DotDotEqual,
+    FatArrow,
 
     Equal,
 
@@ -56,6 +59,18 @@ pub enum TokenKind {
     KeywordBreak,
     KeywordContinue,
     KeywordConst,
+    KeywordStruct,
+    KeywordEnum,
+    KeywordIs,
+    KeywordMatch,
+    KeywordTrait,
+    KeywordImpl,
+    KeywordDyn,
+    KeywordMod,
+    KeywordUse,
+    KeywordAs,
+    KeywordType,
+    KeywordStatic,
     KeywordTrue,
     KeywordFalse,
 
@@ -72,6 +87,17 @@ pub enum TokenKind {
 
     CharacterLiteral,
 
+    /// A `///` documentation comment, running to the end of the line
+    ///
+    /// Ordinary `//` and `/* */` comments are skipped by the lexer, doc
+    /// comments are kept so they can be attached to the item that follows.
+    DocComment,
+
+    /// A `/*` block comment that is not closed before the end of the source
+    ///
+    /// The token spans from the opening `/*` to the end of the source.
+    UnterminatedBlockComment,
+
     /// Any unknown character found in the source code
     ///
     /// This will likely cause a syntax error to be raised, but can also be
@@ -80,3 +106,85 @@ pub enum TokenKind {
     #[default]
     Unknown,
 }
+
+impl core::fmt::Display for TokenKind {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return match self {
+            TokenKind::LeftParen => write!(f, "`(`"),
+            TokenKind::RightParen => write!(f, "`)`"),
+            TokenKind::LeftBrace => write!(f, "`{{`"),
+            TokenKind::RightBrace => write!(f, "`}}`"),
+            TokenKind::LeftBracket => write!(f, "`[`"),
+            TokenKind::RightBracket => write!(f, "`]`"),
+            TokenKind::Comma => write!(f, "`,`"),
+            TokenKind::Semicolon => write!(f, "`;`"),
+            TokenKind::Colon => write!(f, "`:`"),
+            TokenKind::ColonColon => write!(f, "`::`"),
+            TokenKind::Dot => write!(f, "`.`"),
+            TokenKind::DotStar => write!(f, "`.*`"),
+            TokenKind::DotAmpersand => write!(f, "`.&`"),
+            TokenKind::DotDotDot => write!(f, "`...`"),
+            TokenKind::DotDot => write!(f, "`..`"),
+            TokenKind::DotDotEqual => write!(f, "`..=`"),
+            TokenKind::FatArrow => write!(f, "`=>`"),
+            TokenKind::Equal => write!(f, "`=`"),
+            TokenKind::Plus => write!(f, "`+`"),
+            TokenKind::Minus => write!(f, "`-`"),
+            TokenKind::Star => write!(f, "`*`"),
+            TokenKind::Slash => write!(f, "`/`"),
+            TokenKind::Percent => write!(f, "`%`"),
+            TokenKind::AmpersandAmpersand => write!(f, "`&&`"),
+            TokenKind::PipePipe => write!(f, "`||`"),
+            TokenKind::Exclamation => write!(f, "`!`"),
+            TokenKind::Ampersand => write!(f, "`&`"),
+            TokenKind::Pipe => write!(f, "`|`"),
+            TokenKind::Caret => write!(f, "`^`"),
+            TokenKind::Tilde => write!(f, "`~`"),
+            TokenKind::LeftAngleLeftAngle => write!(f, "`<<`"),
+            TokenKind::RightAngleRightAngle => write!(f, "`>>`"),
+            TokenKind::EqualEqual => write!(f, "`==`"),
+            TokenKind::ExclamationEqual => write!(f, "`!=`"),
+            TokenKind::LeftAngle => write!(f, "`<`"),
+            TokenKind::LeftAngleEqual => write!(f, "`<=`"),
+            TokenKind::RightAngle => write!(f, "`>`"),
+            TokenKind::RightAngleEqual => write!(f, "`>=`"),
+            TokenKind::KeywordFn => write!(f, "`fn`"),
+            TokenKind::KeywordExtern => write!(f, "`extern`"),
+            TokenKind::KeywordLet => write!(f, "`let`"),
+            TokenKind::KeywordIf => write!(f, "`if`"),
+            TokenKind::KeywordElse => write!(f, "`else`"),
+            TokenKind::KeywordWhile => write!(f, "`while`"),
+            TokenKind::KeywordFor => write!(f, "`for`"),
+            TokenKind::KeywordReturn => write!(f, "`return`"),
+            TokenKind::KeywordBreak => write!(f, "`break`"),
+            TokenKind::KeywordContinue => write!(f, "`continue`"),
+            TokenKind::KeywordConst => write!(f, "`const`"),
+            TokenKind::KeywordStruct => write!(f, "`struct`"),
+            TokenKind::KeywordEnum => write!(f, "`enum`"),
+            TokenKind::KeywordIs => write!(f, "`is`"),
+            TokenKind::KeywordMatch => write!(f, "`match`"),
+            TokenKind::KeywordTrait => write!(f, "`trait`"),
+            TokenKind::KeywordImpl => write!(f, "`impl`"),
+            TokenKind::KeywordDyn => write!(f, "`dyn`"),
+            TokenKind::KeywordMod => write!(f, "`mod`"),
+            TokenKind::KeywordUse => write!(f, "`use`"),
+            TokenKind::KeywordAs => write!(f, "`as`"),
+            TokenKind::KeywordType => write!(f, "`type`"),
+            TokenKind::KeywordStatic => write!(f, "`static`"),
+            TokenKind::KeywordTrue => write!(f, "`true`"),
+            TokenKind::KeywordFalse => write!(f, "`false`"),
+            TokenKind::Identifier => write!(f, "identifier"),
+            TokenKind::BinaryIntegerLiteral => write!(f, "binary integer literal"),
+            TokenKind::OctalIntegerLiteral => write!(f, "octal integer literal"),
+            TokenKind::DecimalIntegerLiteral => write!(f, "integer literal"),
+            TokenKind::HexadecimalIntegerLiteral => write!(f, "hexadecimal integer literal"),
+            TokenKind::FloatLiteral => write!(f, "float literal"),
+            TokenKind::StringLiteral => write!(f, "string literal"),
+            TokenKind::CharacterLiteral => write!(f, "character literal"),
+            TokenKind::DocComment => write!(f, "doc comment"),
+            TokenKind::UnterminatedBlockComment => write!(f, "unterminated block comment"),
+            TokenKind::Unknown => write!(f, "unknown character"),
+        };
+    }
+}
@@ -1,5 +1,6 @@
+use tantalum_diagnostics::{codes, Diagnostic, Label, Renderer, SourceMap};
 use tantalum_lexer::token_kind::TokenKind;
-use tantalum_span::Location;
+use tantalum_span::{Location, Span};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -39,6 +40,29 @@ impl<'file_name, 'source> ParseError<'file_name, 'source> {
         };
     }
 
+    pub(crate) fn expected_expression(
+        source: &'source str,
+        location: Location<'file_name>,
+        kind: TokenKind,
+    ) -> ParseError<'file_name, 'source> {
+        return Self {
+            source,
+            location,
+            kind: ParseErrorKind::ExpectedExpression { kind },
+        };
+    }
+
+    pub(crate) fn invalid_array_length(
+        source: &'source str,
+        location: Location<'file_name>,
+    ) -> ParseError<'file_name, 'source> {
+        Self {
+            source,
+            location,
+            kind: ParseErrorKind::InvalidArrayLength,
+        }
+    }
+
     pub(crate) fn unexpected_token_set(
         source: &'source str,
         location: Location<'file_name>,
@@ -56,32 +80,43 @@ impl<'file_name, 'source> ParseError<'file_name, 'source> {
     }
 }
 
+impl<'file_name> From<ParseError<'file_name, '_>> for Diagnostic<'file_name> {
+    fn from(error: ParseError<'file_name, '_>) -> Self {
+        let span = Span::new(error.location, error.location);
+
+        match &error.kind {
+            ParseErrorKind::UnexpectedEof => Diagnostic::error(
+                codes::UNEXPECTED_END_OF_FILE,
+                error.kind.to_string(),
+                Label::new(span, "the file ends here"),
+            ),
+            ParseErrorKind::UnexpectedToken { kind, .. } => Diagnostic::error(
+                codes::UNEXPECTED_TOKEN,
+                error.kind.to_string(),
+                Label::new(span, format!("unexpected {kind}")),
+            ),
+            ParseErrorKind::ExpectedExpression { .. } => Diagnostic::error(
+                codes::UNEXPECTED_TOKEN,
+                error.kind.to_string(),
+                Label::new(span, "expected an expression"),
+            ),
+            ParseErrorKind::InvalidArrayLength => Diagnostic::error(
+                codes::INVALID_ARRAY_LENGTH,
+                error.kind.to_string(),
+                Label::new(span, "too large to be the length of an array"),
+            ),
+        }
+    }
+}
+
 impl core::fmt::Display for ParseError<'_, '_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let lines = self.source.lines().collect::<Vec<_>>();
-
-        writeln!(f, "error: {}", self.kind)?;
-        writeln!(
-            f,
-            " --> {}:{}",
-            self.location.file_name(),
-            self.location.line()
-        )?;
-        writeln!(f)?;
+        let mut sources = SourceMap::new();
+        sources.add(self.location.file_name(), self.source);
 
-        for (i, line) in lines
-            .iter()
-            .enumerate()
-            .skip(self.location.line() - 2)
-            .take(3)
-        {
-            writeln!(f, "{:>4} | {}", i + 1, line)?;
-            if i + 1 == self.location.line() {
-                writeln!(f, "{:>4} | {:>1$}^", "", self.location.column())?;
-            }
-        }
+        let diagnostic = Diagnostic::from(self.clone());
 
-        Ok(())
+        write!(f, "{}", Renderer::new(&sources).render(&diagnostic))
     }
 }
 
@@ -94,15 +129,36 @@ pub enum ParseErrorKind {
         kind: TokenKind,
         set: Box<[TokenKind]>,
     },
+    /// An expression was missing, `kind` is the token found in its place
+    ExpectedExpression {
+        kind: TokenKind,
+    },
+    /// The length of an array does not fit in a `usize`
+    InvalidArrayLength,
 }
 
 impl core::fmt::Display for ParseErrorKind {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of file"),
-            ParseErrorKind::UnexpectedToken { kind, set } => {
-                write!(f, "unexpected token {kind:?}, expected one of {set:?}")
+            ParseErrorKind::UnexpectedToken { kind, set } => match &**set {
+                [] => write!(f, "unexpected {kind}"),
+                [expected] => write!(f, "expected {expected}, found {kind}"),
+                [first, second] => {
+                    write!(f, "expected {first} or {second}, found {kind}")
+                }
+                [expected @ .., last] => {
+                    write!(f, "expected one of ")?;
+                    for expected in expected {
+                        write!(f, "{expected}, ")?;
+                    }
+                    write!(f, "or {last}, found {kind}")
+                }
+            },
+            ParseErrorKind::ExpectedExpression { kind } => {
+                write!(f, "expected an expression, found {kind}")
             }
+            ParseErrorKind::InvalidArrayLength => write!(f, "array length is too large"),
         }
     }
 }
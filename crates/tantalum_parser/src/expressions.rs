@@ -1,9 +1,10 @@
 use tantalum_ast::{
-    BinaryOperation, BinaryOperator, Expression, FunctionCall, Index, MemberAccess, TypeCast,
-    UnaryOperation, UnaryOperator, Variable,
+    ArrayLiteral, ArrayRepeat, BinaryOperation, BinaryOperator, Expression, FieldInitializer,
+    FunctionCall, Index, MemberAccess, Path, StructLiteral, TypeCast, UnaryOperation,
+    UnaryOperator, Variable, VariantCheck,
 };
 use tantalum_lexer::{token::Token, token_kind::TokenKind};
-use tantalum_span::Spanned;
+use tantalum_span::{Span, Spanned};
 
 use crate::{ParseError, Parser};
 
@@ -23,6 +24,7 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         TokenKind::KeywordTrue,
         TokenKind::KeywordFalse,
         TokenKind::LeftParen,
+        TokenKind::LeftBracket,
         // Unary Expression Start
         TokenKind::Minus,
         TokenKind::Exclamation,
@@ -44,6 +46,19 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         TokenKind::KeywordTrue,
         TokenKind::KeywordFalse,
         TokenKind::LeftParen,
+        TokenKind::LeftBracket,
+    ];
+
+    /// Tokens that close the construct an expression appears in. Finding one
+    /// where an expression should start means the expression is missing.
+    ///
+    /// A `}` is left out as it closes a block rather than the expression, so
+    /// the statement is recovered from instead.
+    const EXPRESSION_END: &'static [TokenKind] = &[
+        TokenKind::Semicolon,
+        TokenKind::Comma,
+        TokenKind::RightParen,
+        TokenKind::RightBracket,
     ];
 
     const POSTFIX_START: &'static [TokenKind] = &[
@@ -54,6 +69,7 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         TokenKind::DotStar,
         TokenKind::Colon,
         TokenKind::ColonColon,
+        TokenKind::KeywordIs,
     ];
 
     const BINARY_OPERATOR: &'static [TokenKind] = &[
@@ -119,7 +135,27 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         &mut self,
     ) -> Result<Spanned<'file_name, Expression<'file_name, 'source>>, ParseError<'file_name, 'source>>
     {
-        self.parse_expression_binary(0)
+        // Struct literals are allowed again inside of delimiters, such as in
+        // `if (Point { x: 1, y: 2 }).x > 0 {}`
+        let restrict_struct_literals =
+            core::mem::replace(&mut self.restrict_struct_literals, false);
+        let expression = self.parse_expression_binary(0);
+        self.restrict_struct_literals = restrict_struct_literals;
+
+        expression
+    }
+
+    /// Parse the condition of an `if` or `while`, where the `{` after a name
+    /// starts the body rather than a struct literal.
+    pub(crate) fn parse_condition(
+        &mut self,
+    ) -> Result<Spanned<'file_name, Expression<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let restrict_struct_literals = core::mem::replace(&mut self.restrict_struct_literals, true);
+        let expression = self.parse_expression_binary(0);
+        self.restrict_struct_literals = restrict_struct_literals;
+
+        expression
     }
 
     fn parse_expression_primary(
@@ -155,9 +191,45 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         &mut self,
     ) -> Result<Spanned<'file_name, Expression<'file_name, 'source>>, ParseError<'file_name, 'source>>
     {
-        let token = self.expect_any(Self::PRIMARY_START)?;
+        // Report a missing expression without consuming the closing token, so
+        // the enclosing statement can carry on parsing
+        if let Some(token) = self.is_at_any(Self::EXPRESSION_END) {
+            self.errors.push(ParseError::expected_expression(
+                self.source,
+                token.start(),
+                token.data().kind(),
+            ));
+
+            return Ok(Spanned::new(
+                Span::new(token.start(), token.start()),
+                Expression::Error,
+            ));
+        }
+
+        let token = match self.peek() {
+            Some(token) if Self::PRIMARY_START.contains(&token.data().kind()) => {
+                self.next();
+                token
+            }
+            Some(token) => {
+                return Err(ParseError::expected_expression(
+                    self.source,
+                    token.start(),
+                    token.data().kind(),
+                ));
+            }
+            None => return Err(ParseError::unexpected_eof(self.source, self.eof)),
+        };
 
         let expr = match token.data().kind() {
+            TokenKind::Identifier
+                if !self.restrict_struct_literals && self.is_at(TokenKind::LeftBrace).is_some() =>
+            {
+                let name = token.map(|_| Path {
+                    segments: vec![token.map(|name| name.lexeme())],
+                });
+                self.parse_struct_literal(name)?
+            }
             TokenKind::Identifier => token.map(|_| {
                 Expression::Variable(Variable {
                     name: token.map(|name| name.lexeme()),
@@ -178,6 +250,7 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
 
                 expr
             }
+            TokenKind::LeftBracket => self.parse_array_literal(token)?,
             _ => unreachable!(
                 "Already expected in set of primary expression starts ({:?})",
                 Self::PRIMARY_START
@@ -187,6 +260,103 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         Ok(expr)
     }
 
+    /// Parse a path such as `Shape::Circle`.
+    pub(crate) fn parse_path(
+        &mut self,
+    ) -> Result<Spanned<'file_name, Path<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let first = self.expect(TokenKind::Identifier)?;
+        let mut segments = vec![first.map(|segment| segment.lexeme())];
+
+        while self.advance_if(TokenKind::ColonColon).is_some() {
+            let segment = self.expect(TokenKind::Identifier)?;
+            segments.push(segment.map(|segment| segment.lexeme()));
+        }
+
+        let last = segments.last().map_or(first.span(), Spanned::span);
+
+        Ok(Spanned::join_spans(first.span(), last, Path { segments }))
+    }
+
+    /// Parse a struct literal, `name` has already been consumed.
+    fn parse_struct_literal(
+        &mut self,
+        name: Spanned<'file_name, Path<'file_name, 'source>>,
+    ) -> Result<Spanned<'file_name, Expression<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        self.expect(TokenKind::LeftBrace)?;
+
+        let mut fields = Vec::new();
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            let field_name = self.expect(TokenKind::Identifier)?;
+            self.expect(TokenKind::Colon)?;
+            let value = self.parse_expression()?;
+
+            fields.push(Spanned::join_spans(
+                field_name.span(),
+                value.span(),
+                FieldInitializer {
+                    name: field_name.map(|name| name.lexeme()),
+                    value,
+                },
+            ));
+
+            if self.advance_if(TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+
+        let r_brace = self.expect(TokenKind::RightBrace)?;
+
+        Ok(Spanned::join_spans(
+            name.span(),
+            r_brace.span(),
+            Expression::StructLiteral(StructLiteral { name, fields }),
+        ))
+    }
+
+    /// Parse an array literal, either a list of elements such as `[1, 2, 3]`
+    /// or a value repeated a number of times such as `[0; 8]`. The opening
+    /// `[` has already been consumed.
+    fn parse_array_literal(
+        &mut self,
+        l_bracket: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Expression<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let mut elements = Vec::new();
+        while self.is_at(TokenKind::RightBracket).is_none() {
+            let element = self.parse_expression()?;
+
+            if elements.is_empty() && self.advance_if(TokenKind::Semicolon).is_some() {
+                let length = self.parse_array_length()?;
+                let r_bracket = self.expect(TokenKind::RightBracket)?;
+
+                return Ok(Spanned::join_spans(
+                    l_bracket.span(),
+                    r_bracket.span(),
+                    Expression::ArrayRepeat(ArrayRepeat {
+                        value: Box::new(element),
+                        length,
+                    }),
+                ));
+            }
+
+            elements.push(element);
+
+            if self.advance_if(TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+
+        let r_bracket = self.expect(TokenKind::RightBracket)?;
+
+        Ok(Spanned::join_spans(
+            l_bracket.span(),
+            r_bracket.span(),
+            Expression::ArrayLiteral(ArrayLiteral { elements }),
+        ))
+    }
+
     #[allow(clippy::too_many_lines)]
     fn parse_expression_binary(
         &mut self,
@@ -274,7 +444,47 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
                             }),
                         );
                     }
-                    TokenKind::ColonColon => todo!("Parse Path Expression"),
+                    TokenKind::ColonColon => {
+                        let segment = self.expect(TokenKind::Identifier)?;
+
+                        // Only names can be joined into a path
+                        let mut segments = match lhs.data() {
+                            Expression::Variable(variable) => vec![variable.name],
+                            Expression::Path(path) => path.segments.clone(),
+                            _ => {
+                                return Err(ParseError::unexpected_token_set(
+                                    self.source,
+                                    operator.start(),
+                                    operator.data().kind(),
+                                    &[],
+                                ));
+                            }
+                        };
+                        segments.push(segment.map(|segment| segment.lexeme()));
+
+                        let path =
+                            Spanned::join_spans(lhs.span(), segment.span(), Path { segments });
+
+                        lhs = if !self.restrict_struct_literals
+                            && self.is_at(TokenKind::LeftBrace).is_some()
+                        {
+                            self.parse_struct_literal(path)?
+                        } else {
+                            path.map(Expression::Path)
+                        };
+                    }
+                    TokenKind::KeywordIs => {
+                        let variant = self.parse_path()?;
+
+                        lhs = Spanned::join_spans(
+                            lhs.span(),
+                            variant.span(),
+                            Expression::VariantCheck(VariantCheck {
+                                value: Box::new(lhs),
+                                variant,
+                            }),
+                        );
+                    }
                     _ => {
                         lhs = Spanned::join_spans(
                             lhs.span(),
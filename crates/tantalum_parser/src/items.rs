@@ -1,13 +1,29 @@
-use tantalum_ast::{ExternalFunction, Function, Item, NamedParameter, Parameter};
+use tantalum_ast::{
+    Constant, Enum, EnumVariant, ExternalFunction, ExternalStatic, Function, GenericParameter,
+    Impl, Item, Module, NamedParameter, Parameter, Path, Static, Struct, StructField, Trait,
+    TraitMethod, TypeAlias, Use, UseImport, VariantFields,
+};
 use tantalum_lexer::{token::Token, token_kind::TokenKind};
 use tantalum_span::Spanned;
 
 use crate::{error::ParseError, Parser};
 
 impl<'file_name, 'source> Parser<'file_name, 'source> {
-    pub const ITEM_START: &'static [TokenKind] = &[TokenKind::KeywordFn, TokenKind::KeywordExtern];
+    pub const ITEM_START: &'static [TokenKind] = &[
+        TokenKind::KeywordFn,
+        TokenKind::KeywordExtern,
+        TokenKind::KeywordStruct,
+        TokenKind::KeywordEnum,
+        TokenKind::KeywordTrait,
+        TokenKind::KeywordImpl,
+        TokenKind::KeywordMod,
+        TokenKind::KeywordUse,
+        TokenKind::KeywordType,
+        TokenKind::KeywordConst,
+        TokenKind::KeywordStatic,
+    ];
 
-    const EXTERN_START: &'static [TokenKind] = &[TokenKind::KeywordFn];
+    const EXTERN_START: &'static [TokenKind] = &[TokenKind::KeywordFn, TokenKind::KeywordStatic];
 
     pub(crate) fn parse_item(
         &mut self,
@@ -20,9 +36,42 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
                 let function = self.parse_top_level_function(token)?;
                 Ok(function.map(Item::Function))
             }
-            TokenKind::KeywordExtern => {
-                let extern_function = self.parse_top_level_extern(token)?;
-                Ok(extern_function.map(Item::ExternalFunction))
+            TokenKind::KeywordExtern => self.parse_top_level_extern(token),
+            TokenKind::KeywordStruct => {
+                let structure = self.parse_top_level_struct(token)?;
+                Ok(structure.map(Item::Struct))
+            }
+            TokenKind::KeywordEnum => {
+                let enumeration = self.parse_top_level_enum(token)?;
+                Ok(enumeration.map(Item::Enum))
+            }
+            TokenKind::KeywordTrait => {
+                let trait_ = self.parse_top_level_trait(token)?;
+                Ok(trait_.map(Item::Trait))
+            }
+            TokenKind::KeywordImpl => {
+                let impl_ = self.parse_top_level_impl(token)?;
+                Ok(impl_.map(Item::Impl))
+            }
+            TokenKind::KeywordMod => {
+                let module = self.parse_top_level_module(token)?;
+                Ok(module.map(Item::Module))
+            }
+            TokenKind::KeywordUse => {
+                let use_ = self.parse_top_level_use(token)?;
+                Ok(use_.map(Item::Use))
+            }
+            TokenKind::KeywordType => {
+                let alias = self.parse_type_alias(token)?;
+                Ok(alias.map(Item::TypeAlias))
+            }
+            TokenKind::KeywordConst => {
+                let constant = self.parse_top_level_constant(token)?;
+                Ok(constant.map(Item::Constant))
+            }
+            TokenKind::KeywordStatic => {
+                let static_ = self.parse_top_level_static(token)?;
+                Ok(static_.map(Item::Static))
             }
             _ => unimplemented!(
                 "Token {:?} is not in the set {:?}",
@@ -38,7 +87,40 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
     ) -> Result<Spanned<'file_name, Function<'file_name, 'source>>, ParseError<'file_name, 'source>>
     {
         let name = self.expect(TokenKind::Identifier)?;
+        let generics = self.parse_generic_parameters()?;
+
+        let parameters = self.parse_parameters()?;
+
+        let return_type = if self.is_at(TokenKind::Colon).is_some() {
+            self.expect(TokenKind::Colon)?;
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        let body = self.parse_statement()?;
 
+        Ok(Spanned::join_spans(
+            fn_token.span(),
+            body.span(),
+            Function {
+                name: name.map(|name| name.lexeme()),
+                generics,
+                parameters,
+                return_type,
+                body,
+            },
+        ))
+    }
+
+    /// Parse the parameters of a function, along with the parentheses around
+    /// them.
+    fn parse_parameters(
+        &mut self,
+    ) -> Result<
+        Spanned<'file_name, Vec<Spanned<'file_name, Parameter<'file_name, 'source>>>>,
+        ParseError<'file_name, 'source>,
+    > {
         let mut parameters = Vec::new();
         let l_paren = self.expect(TokenKind::LeftParen)?;
         while self.is_at(TokenKind::RightParen).is_none() {
@@ -75,34 +157,439 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         }
         let r_paren = self.expect(TokenKind::RightParen)?;
 
-        let return_type = if self.is_at(TokenKind::Colon).is_some() {
+        Ok(Spanned::join_spans(
+            l_paren.span(),
+            r_paren.span(),
+            parameters,
+        ))
+    }
+
+    /// Parse the type parameters of a generic item, e.g. `<A, B>`, if there
+    /// are any.
+    fn parse_generic_parameters(
+        &mut self,
+    ) -> Result<
+        Vec<Spanned<'file_name, GenericParameter<'file_name, 'source>>>,
+        ParseError<'file_name, 'source>,
+    > {
+        let mut generics = Vec::new();
+
+        if self.is_at(TokenKind::LeftAngle).is_none() {
+            return Ok(generics);
+        }
+
+        self.expect(TokenKind::LeftAngle)?;
+        while self.is_at(TokenKind::RightAngle).is_none() {
+            let name = self
+                .expect(TokenKind::Identifier)?
+                .map(|name| name.lexeme());
+
+            let mut bounds = Vec::new();
+            if self.advance_if(TokenKind::Colon).is_some() {
+                loop {
+                    let bound = self.expect(TokenKind::Identifier)?;
+                    bounds.push(bound.map(|bound| bound.lexeme()));
+
+                    if self.advance_if(TokenKind::Plus).is_none() {
+                        break;
+                    }
+                }
+            }
+
+            let end = bounds.last().map_or(name.span(), Spanned::span);
+            generics.push(Spanned::join_spans(
+                name.span(),
+                end,
+                GenericParameter { name, bounds },
+            ));
+
+            match self.nth(0) {
+                Some(token) if token.data().kind() == TokenKind::Comma => {
+                    self.expect(TokenKind::Comma)?;
+                }
+                Some(token) if token.data().kind() == TokenKind::RightAngle => break,
+                Some(token) => {
+                    return Err(ParseError::unexpected_token_set(
+                        self.source,
+                        token.start(),
+                        token.data().kind(),
+                        &[TokenKind::Comma, TokenKind::RightAngle],
+                    ));
+                }
+                None => {
+                    return Err(ParseError::unexpected_eof(self.source, self.eof));
+                }
+            }
+        }
+        self.expect(TokenKind::RightAngle)?;
+
+        Ok(generics)
+    }
+
+    fn parse_top_level_struct(
+        &mut self,
+        struct_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Struct<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+        let generics = self.parse_generic_parameters()?;
+        let fields = self.parse_struct_fields()?;
+
+        Ok(Spanned::join_spans(
+            struct_token.span(),
+            fields.span(),
+            Struct {
+                name: name.map(|name| name.lexeme()),
+                generics,
+                fields,
+            },
+        ))
+    }
+
+    /// Parse the fields of a struct or of a variant of an enum, along with the
+    /// braces around them.
+    fn parse_struct_fields(
+        &mut self,
+    ) -> Result<
+        Spanned<'file_name, Vec<Spanned<'file_name, StructField<'file_name, 'source>>>>,
+        ParseError<'file_name, 'source>,
+    > {
+        let mut fields = Vec::new();
+        let l_brace = self.expect(TokenKind::LeftBrace)?;
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            let field_name = self.expect(TokenKind::Identifier)?;
             self.expect(TokenKind::Colon)?;
+            let field_type = self.parse_type()?;
+
+            fields.push(Spanned::join_spans(
+                field_name.span(),
+                field_type.span(),
+                StructField {
+                    name: field_name.map(|name| name.lexeme()),
+                    ty: field_type,
+                },
+            ));
+
+            // The comma after the last field is optional
+            if self.advance_if(TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+        let r_brace = self.expect(TokenKind::RightBrace)?;
+
+        Ok(Spanned::join_spans(l_brace.span(), r_brace.span(), fields))
+    }
+
+    /// Parse the values carried by a variant of an enum, if it has any.
+    fn parse_variant_fields(
+        &mut self,
+    ) -> Result<VariantFields<'file_name, 'source>, ParseError<'file_name, 'source>> {
+        if self.is_at(TokenKind::LeftBrace).is_some() {
+            return Ok(VariantFields::Struct(self.parse_struct_fields()?));
+        }
+
+        let Some(l_paren) = self.advance_if(TokenKind::LeftParen) else {
+            return Ok(VariantFields::Unit);
+        };
+
+        let mut fields = Vec::new();
+        while self.is_at(TokenKind::RightParen).is_none() {
+            fields.push(self.parse_type()?);
+
+            // The comma after the last field is optional
+            if self.advance_if(TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+        let r_paren = self.expect(TokenKind::RightParen)?;
+
+        Ok(VariantFields::Tuple(Spanned::join_spans(
+            l_paren.span(),
+            r_paren.span(),
+            fields,
+        )))
+    }
+
+    fn parse_top_level_enum(
+        &mut self,
+        enum_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Enum<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+        let generics = self.parse_generic_parameters()?;
+
+        let ty = if self.advance_if(TokenKind::Colon).is_some() {
             Some(self.parse_type()?)
         } else {
             None
         };
 
-        let body = self.parse_statement()?;
+        let mut variants = Vec::new();
+        let l_brace = self.expect(TokenKind::LeftBrace)?;
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            let variant_name = self.expect(TokenKind::Identifier)?;
+            let fields = self.parse_variant_fields()?;
+
+            let discriminant = if self.advance_if(TokenKind::Equal).is_some() {
+                Some(self.parse_expression()?)
+            } else {
+                None
+            };
+
+            let end = match (&discriminant, &fields) {
+                (Some(discriminant), _) => discriminant.span(),
+                (None, VariantFields::Tuple(fields)) => fields.span(),
+                (None, VariantFields::Struct(fields)) => fields.span(),
+                (None, VariantFields::Unit) => variant_name.span(),
+            };
+
+            variants.push(Spanned::join_spans(
+                variant_name.span(),
+                end,
+                EnumVariant {
+                    name: variant_name.map(|name| name.lexeme()),
+                    fields,
+                    discriminant,
+                },
+            ));
+
+            // The comma after the last variant is optional
+            if self.advance_if(TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+        let r_brace = self.expect(TokenKind::RightBrace)?;
 
         Ok(Spanned::join_spans(
-            fn_token.span(),
-            body.span(),
-            Function {
+            enum_token.span(),
+            r_brace.span(),
+            Enum {
                 name: name.map(|name| name.lexeme()),
-                parameters: Spanned::join_spans(l_paren.span(), r_paren.span(), parameters),
-                return_type,
-                body,
+                generics,
+                ty,
+                variants: Spanned::join_spans(l_brace.span(), r_brace.span(), variants),
             },
         ))
     }
 
+    fn parse_top_level_trait(
+        &mut self,
+        trait_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Trait<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+
+        let mut methods = Vec::new();
+        let l_brace = self.expect(TokenKind::LeftBrace)?;
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            let fn_token = self.expect(TokenKind::KeywordFn)?;
+            let method_name = self.expect(TokenKind::Identifier)?;
+            let parameters = self.parse_parameters()?;
+
+            let return_type = if self.advance_if(TokenKind::Colon).is_some() {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+
+            let semicolon = self.expect(TokenKind::Semicolon)?;
+
+            methods.push(Spanned::join_spans(
+                fn_token.span(),
+                semicolon.span(),
+                TraitMethod {
+                    name: method_name.map(|name| name.lexeme()),
+                    parameters,
+                    return_type,
+                },
+            ));
+        }
+        let r_brace = self.expect(TokenKind::RightBrace)?;
+
+        Ok(Spanned::join_spans(
+            trait_token.span(),
+            r_brace.span(),
+            Trait {
+                name: name.map(|name| name.lexeme()),
+                methods: Spanned::join_spans(l_brace.span(), r_brace.span(), methods),
+            },
+        ))
+    }
+
+    fn parse_top_level_impl(
+        &mut self,
+        impl_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Impl<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        // `impl Trait for Type` implements a trait, `impl Type` defines
+        // methods of the type itself
+        let trait_name = if self
+            .nth(1)
+            .is_some_and(|token| token.data().kind() == TokenKind::KeywordFor)
+        {
+            let trait_name = self.expect(TokenKind::Identifier)?;
+            self.expect(TokenKind::KeywordFor)?;
+            Some(trait_name.map(|name| name.lexeme()))
+        } else {
+            None
+        };
+        let ty = self.parse_type()?;
+
+        let mut methods = Vec::new();
+        let l_brace = self.expect(TokenKind::LeftBrace)?;
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            let fn_token = self.expect(TokenKind::KeywordFn)?;
+            methods.push(self.parse_top_level_function(fn_token)?);
+        }
+        let r_brace = self.expect(TokenKind::RightBrace)?;
+
+        Ok(Spanned::join_spans(
+            impl_token.span(),
+            r_brace.span(),
+            Impl {
+                trait_name,
+                ty,
+                methods: Spanned::join_spans(l_brace.span(), r_brace.span(), methods),
+            },
+        ))
+    }
+
+    fn parse_top_level_module(
+        &mut self,
+        mod_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Module<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+
+        // `mod name;` declares a module whose items are in a file of its own
+        if let Some(semicolon) = self.advance_if(TokenKind::Semicolon) {
+            return Ok(Spanned::join_spans(
+                mod_token.span(),
+                semicolon.span(),
+                Module {
+                    name: name.map(|name| name.lexeme()),
+                    items: None,
+                },
+            ));
+        }
+
+        let mut items = Vec::new();
+        let l_brace = self.expect(TokenKind::LeftBrace)?;
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            items.push(self.parse_item()?);
+        }
+        let r_brace = self.expect(TokenKind::RightBrace)?;
+
+        Ok(Spanned::join_spans(
+            mod_token.span(),
+            r_brace.span(),
+            Module {
+                name: name.map(|name| name.lexeme()),
+                items: Some(Spanned::join_spans(l_brace.span(), r_brace.span(), items)),
+            },
+        ))
+    }
+
+    /// Parse `use a::b::c;`, `use a::b::c as d;`, or `use a::b::{c, d as e};`.
+    fn parse_top_level_use(
+        &mut self,
+        use_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Use<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let mut segments = Vec::new();
+        let mut imports = Vec::new();
+
+        loop {
+            if self.advance_if(TokenKind::LeftBrace).is_some() {
+                while self.is_at(TokenKind::RightBrace).is_none() {
+                    imports.push(self.parse_use_import()?);
+
+                    if self.advance_if(TokenKind::Comma).is_none() {
+                        break;
+                    }
+                }
+                self.expect(TokenKind::RightBrace)?;
+                break;
+            }
+
+            // The last segment is the item being imported
+            if self
+                .nth(1)
+                .is_none_or(|token| token.data().kind() != TokenKind::ColonColon)
+            {
+                imports.push(self.parse_use_import()?);
+                break;
+            }
+
+            let segment = self.expect(TokenKind::Identifier)?;
+            self.expect(TokenKind::ColonColon)?;
+            segments.push(segment.map(|segment| segment.lexeme()));
+        }
+
+        let semicolon = self.expect(TokenKind::Semicolon)?;
+
+        Ok(Spanned::join_spans(
+            use_token.span(),
+            semicolon.span(),
+            Use {
+                prefix: Path { segments },
+                imports,
+            },
+        ))
+    }
+
+    /// Parse `type Name = Type;`, which is also a statement.
+    pub(crate) fn parse_type_alias(
+        &mut self,
+        type_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, TypeAlias<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+        self.expect(TokenKind::Equal)?;
+        let ty = self.parse_type()?;
+        let semicolon = self.expect(TokenKind::Semicolon)?;
+
+        Ok(Spanned::join_spans(
+            type_token.span(),
+            semicolon.span(),
+            TypeAlias {
+                name: name.map(|name| name.lexeme()),
+                ty,
+            },
+        ))
+    }
+
+    fn parse_use_import(
+        &mut self,
+    ) -> Result<Spanned<'file_name, UseImport<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self
+            .expect(TokenKind::Identifier)?
+            .map(|name| name.lexeme());
+
+        let alias = if self.advance_if(TokenKind::KeywordAs).is_some() {
+            Some(
+                self.expect(TokenKind::Identifier)?
+                    .map(|alias| alias.lexeme()),
+            )
+        } else {
+            None
+        };
+
+        let end = alias.as_ref().map_or(name.span(), Spanned::span);
+
+        Ok(Spanned::join_spans(
+            name.span(),
+            end,
+            UseImport { name, alias },
+        ))
+    }
+
     fn parse_top_level_extern(
         &mut self,
         extern_token: Spanned<'file_name, Token<'source>>,
-    ) -> Result<
-        Spanned<'file_name, ExternalFunction<'file_name, 'source>>,
-        ParseError<'file_name, 'source>,
-    > {
+    ) -> Result<Spanned<'file_name, Item<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
         match self.is_at_any(Self::EXTERN_START) {
             None => {
                 return Err(ParseError::unexpected_token(
@@ -113,7 +600,14 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
                 ));
             }
             Some(token) => match token.data().kind() {
-                TokenKind::KeywordFn => self.parse_top_level_extern_function(extern_token),
+                TokenKind::KeywordFn => {
+                    let extern_function = self.parse_top_level_extern_function(extern_token)?;
+                    Ok(extern_function.map(Item::ExternalFunction))
+                }
+                TokenKind::KeywordStatic => {
+                    let extern_static = self.parse_top_level_extern_static(extern_token)?;
+                    Ok(extern_static.map(Item::ExternalStatic))
+                }
                 _ => unimplemented!(
                     "Token {:?} is not in the set {:?}",
                     token.data().kind(),
@@ -199,4 +693,79 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
             },
         ))
     }
+
+    fn parse_top_level_extern_static(
+        &mut self,
+        extern_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<
+        Spanned<'file_name, ExternalStatic<'file_name, 'source>>,
+        ParseError<'file_name, 'source>,
+    > {
+        self.expect(TokenKind::KeywordStatic)?;
+
+        let name = self.expect(TokenKind::Identifier)?;
+        self.expect(TokenKind::Colon)?;
+        let ty = self.parse_type()?;
+
+        let semicolon = self.expect(TokenKind::Semicolon)?;
+
+        Ok(Spanned::join_spans(
+            extern_token.span(),
+            semicolon.span(),
+            ExternalStatic {
+                name: name.map(|name| name.lexeme()),
+                ty,
+            },
+        ))
+    }
+
+    /// Parse `const NAME: Type = value;`.
+    fn parse_top_level_constant(
+        &mut self,
+        const_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Constant<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+        self.expect(TokenKind::Colon)?;
+        let ty = self.parse_type()?;
+
+        self.expect(TokenKind::Equal)?;
+        let value = self.parse_expression()?;
+        let semicolon = self.expect(TokenKind::Semicolon)?;
+
+        Ok(Spanned::join_spans(
+            const_token.span(),
+            semicolon.span(),
+            Constant {
+                name: name.map(|name| name.lexeme()),
+                ty,
+                value,
+            },
+        ))
+    }
+
+    /// Parse `static NAME: Type = value;`.
+    fn parse_top_level_static(
+        &mut self,
+        static_token: Spanned<'file_name, Token<'source>>,
+    ) -> Result<Spanned<'file_name, Static<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let name = self.expect(TokenKind::Identifier)?;
+        self.expect(TokenKind::Colon)?;
+        let ty = self.parse_type()?;
+
+        self.expect(TokenKind::Equal)?;
+        let value = self.parse_expression()?;
+        let semicolon = self.expect(TokenKind::Semicolon)?;
+
+        Ok(Spanned::join_spans(
+            static_token.span(),
+            semicolon.span(),
+            Static {
+                name: name.map(|name| name.lexeme()),
+                ty,
+                value,
+            },
+        ))
+    }
 }
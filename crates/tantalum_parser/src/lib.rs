@@ -1,13 +1,16 @@
 use error::ParseError;
-use tantalum_ast::AST;
+use tantalum_ast::{Item, AST};
+use tantalum_diagnostics::Diagnostic;
 use tantalum_lexer::{token::Token, token_kind::TokenKind, Lexer};
-use tantalum_span::{Location, Spanned};
+use tantalum_span::{Location, Span, Spanned};
 
 pub mod error;
+pub mod package;
 
 mod expressions;
 mod items;
 mod literals;
+mod patterns;
 mod statements;
 mod types;
 
@@ -23,34 +26,130 @@ pub struct Parser<'file_name, 'source> {
     tokens: Vec<Spanned<'file_name, Token<'source>>>,
     eof: Location<'file_name>,
     position: usize,
+    diagnostics: Vec<Diagnostic<'file_name>>,
+    /// The syntax errors recovered from so far
+    errors: Vec<ParseError<'file_name, 'source>>,
+    /// Whether a `{` after a name starts a block rather than a struct literal,
+    /// as it does after the condition of an `if` or `while`
+    restrict_struct_literals: bool,
 }
 
 impl<'file_name, 'source> Parser<'file_name, 'source> {
     #[must_use]
     #[inline]
     pub fn new(mut lexer: Lexer<'file_name, 'source>) -> Self {
+        // Doc comments are not yet attached to items, and invalid tokens have
+        // already been reported by the lexer, so they are dropped here
+        let tokens = lexer
+            .by_ref()
+            .filter(|token| {
+                !matches!(
+                    token.data().kind(),
+                    TokenKind::DocComment
+                        | TokenKind::Unknown
+                        | TokenKind::UnterminatedBlockComment
+                )
+            })
+            .collect();
+
         Self {
             source: lexer.source(),
             file_name: lexer.file_name(),
-            tokens: lexer.by_ref().collect(),
+            tokens,
             eof: lexer.location(),
             position: 0,
+            diagnostics: lexer.into_diagnostics(),
+            errors: Vec::new(),
+            restrict_struct_literals: false,
         }
     }
 
-    /// Parse the entire source file.
+    /// The problems found while lexing the source file.
     ///
-    /// # Errors
+    /// These do not prevent parsing, the invalid tokens are skipped.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic<'file_name>] {
+        &self.diagnostics
+    }
+
+    /// Parse the entire source file.
     ///
-    /// Returns an error if the parser encounters an unexpected token or the end of the file.
-    pub fn parse(&mut self) -> Result<AST, error::ParseError<'file_name, 'source>> {
+    /// Parsing does not stop at the first syntax error. The parser skips ahead
+    /// to the end of the broken statement or item and carries on, so every
+    /// error in the file is returned alongside the partial AST. The parts that
+    /// could not be parsed are replaced with `Error` placeholders.
+    pub fn parse(
+        &mut self,
+    ) -> (
+        AST<'file_name, 'source>,
+        Vec<error::ParseError<'file_name, 'source>>,
+    ) {
         let mut items = Vec::new();
 
         while !self.is_eof() {
-            items.push(self.parse_item()?);
+            let start = self.position;
+
+            match self.parse_item() {
+                Ok(item) => items.push(item),
+                Err(error) => {
+                    self.errors.push(error);
+
+                    // Always make progress, even if the error was at the very
+                    // first token of the item
+                    if self.position == start {
+                        self.position += 1;
+                    }
+                    self.synchronize_item();
+
+                    items.push(Spanned::new(self.span_since(start), Item::Error));
+                }
+            }
+        }
+
+        (AST(items), core::mem::take(&mut self.errors))
+    }
+
+    /// Skip tokens until the end of the current statement. A `;` is consumed
+    /// as part of the statement, while a `}` or the start of an item is left
+    /// for the enclosing block or item to handle.
+    fn synchronize_statement(&mut self) {
+        while let Some(token) = self.peek() {
+            match token.data().kind() {
+                TokenKind::Semicolon => {
+                    self.position += 1;
+                    break;
+                }
+                TokenKind::RightBrace => break,
+                kind if Self::ITEM_START.contains(&kind) => break,
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    /// Skip tokens until the start of the next item.
+    fn synchronize_item(&mut self) {
+        while !self.is_eof() && self.is_at_any(Self::ITEM_START).is_none() {
+            self.position += 1;
         }
+    }
+
+    /// The span covering the tokens from `start` up to the current position.
+    fn span_since(&self, start: usize) -> Span<'file_name> {
+        let end = self.position.min(self.tokens.len());
 
-        Ok(AST(items))
+        if end > start {
+            Span::new(
+                self.tokens[start].span().start(),
+                self.tokens[end - 1].span().end(),
+            )
+        } else {
+            let location = self
+                .tokens
+                .get(start)
+                .map_or(self.eof, |token| token.span().start());
+
+            Span::new(location, location)
+        }
     }
 
     fn is_eof(&self) -> bool {
@@ -0,0 +1,307 @@
+//! Loading every source file of a package, which is the root file along with
+//! the file of each module declared with `mod name;`.
+//!
+//! The module `name` declared in a file is loaded from `name.ta`, or else from
+//! `name/mod.ta`, next to that file. A `mod name;` nested in inline modules,
+//! e.g. `mod shapes { mod circle; }`, is looked for in the directories named
+//! after them, e.g. `shapes/circle.ta`.
+//!
+//! The files are read before any of them is parsed into the AST of the
+//! package, since the AST borrows from every one of them.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tantalum_ast::{Item, Module, AST};
+use tantalum_diagnostics::{codes, Diagnostic, Label};
+use tantalum_lexer::Lexer;
+use tantalum_span::{Location, Spanned};
+
+use crate::Parser;
+
+/// A source file of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    name: String,
+    source: String,
+    /// The file of each `mod name;` declared in this file, in the order they
+    /// are declared
+    modules: Vec<ModuleFile>,
+}
+
+impl SourceFile {
+    /// The path the file was read from, which is its name in diagnostics.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// What was found when loading the file of a `mod name;`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ModuleFile {
+    /// The file was read, contains its index among the files of the package
+    Loaded(usize),
+    /// None of the files the module can be in could be read, contains the
+    /// paths that were tried
+    Missing(Vec<String>),
+    /// The file is already being loaded, contains its path
+    Cycle(String),
+    /// The module is declared again in the same file, so its file is only
+    /// loaded for the first declaration
+    Duplicate,
+}
+
+/// Every source file of a package, the first one being the root file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFiles {
+    files: Vec<SourceFile>,
+}
+
+impl SourceFiles {
+    /// Read the root file of a package at `root`, along with the files of the
+    /// modules it declares.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the root file cannot be read. The files of modules
+    /// that cannot be read are reported by [`SourceFiles::parse`] instead.
+    pub fn load(root: &Path) -> io::Result<Self> {
+        Self::load_with(root, |path| std::fs::read_to_string(path))
+    }
+
+    /// Same as [`SourceFiles::load`], with every file read by `read`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `read` fails for the root file.
+    pub fn load_with(
+        root: &Path,
+        mut read: impl FnMut(&Path) -> io::Result<String>,
+    ) -> io::Result<Self> {
+        let source = read(root)?;
+
+        let mut files = Self { files: Vec::new() };
+        files.add(root.to_path_buf(), source, &mut read, &mut Vec::new());
+
+        Ok(files)
+    }
+
+    /// Every file of the package, the first one being the root file.
+    #[must_use]
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// Add the file at `path` along with the files of the modules it
+    /// declares, `loading` being the files whose modules are being loaded,
+    /// and get its index.
+    fn add(
+        &mut self,
+        path: PathBuf,
+        source: String,
+        read: &mut impl FnMut(&Path) -> io::Result<String>,
+        loading: &mut Vec<PathBuf>,
+    ) -> usize {
+        let index = self.files.len();
+        self.files.push(SourceFile {
+            name: path.display().to_string(),
+            source,
+            modules: Vec::new(),
+        });
+
+        let declarations = self.module_declarations(index);
+        let directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        loading.push(path);
+
+        let mut modules = Vec::new();
+        for (position, declaration) in declarations.iter().enumerate() {
+            if declarations[..position].contains(declaration) {
+                modules.push(ModuleFile::Duplicate);
+                continue;
+            }
+
+            let base = directory.join(declaration.iter().collect::<PathBuf>());
+            let candidates = [base.with_extension("ta"), base.join("mod.ta")];
+
+            let mut module = ModuleFile::Missing(
+                candidates
+                    .iter()
+                    .map(|candidate| candidate.display().to_string())
+                    .collect(),
+            );
+
+            for candidate in candidates {
+                if loading.contains(&candidate) {
+                    module = ModuleFile::Cycle(candidate.display().to_string());
+                    break;
+                }
+
+                if let Ok(source) = read(&candidate) {
+                    module = ModuleFile::Loaded(self.add(candidate, source, read, loading));
+                    break;
+                }
+            }
+
+            modules.push(module);
+        }
+
+        loading.pop();
+        self.files[index].modules = modules;
+
+        index
+    }
+
+    /// Find every `mod name;` in the file at `index`, getting the names of
+    /// the inline modules it is in followed by its own name.
+    ///
+    /// Syntax errors are ignored here, they are reported when the file is
+    /// parsed again along with the rest of the package.
+    fn module_declarations(&self, index: usize) -> Vec<Vec<String>> {
+        fn collect(
+            items: &[Spanned<'_, Item<'_, '_>>],
+            path: &[String],
+            found: &mut Vec<Vec<String>>,
+        ) {
+            for item in items {
+                let Item::Module(module) = item.data() else {
+                    continue;
+                };
+
+                let mut path = path.to_vec();
+                path.push((*module.name.data()).to_string());
+
+                match &module.items {
+                    Some(items) => collect(items.data(), &path, found),
+                    None => found.push(path),
+                }
+            }
+        }
+
+        let file = &self.files[index];
+        let (ast, _) = Parser::new(Lexer::new(&file.name, &file.source)).parse();
+
+        let mut found = Vec::new();
+        collect(&ast.0, &[], &mut found);
+        found
+    }
+
+    /// Parse every file of the package into a single AST, in which the items
+    /// of each `mod name;` are the ones in its file.
+    ///
+    /// Parsing carries on past every problem found, which are returned along
+    /// with the AST: invalid tokens, syntax errors, and the modules whose file
+    /// is missing or would be loaded into itself.
+    #[must_use]
+    pub fn parse(&self) -> (AST<'_, '_>, Vec<Diagnostic<'_>>) {
+        let mut diagnostics = Vec::new();
+        let items = self.parse_file(0, &mut diagnostics);
+
+        (AST(items), diagnostics)
+    }
+
+    fn parse_file<'a>(
+        &'a self,
+        index: usize,
+        diagnostics: &mut Vec<Diagnostic<'a>>,
+    ) -> Vec<Spanned<'a, Item<'a, 'a>>> {
+        let file = &self.files[index];
+        let mut parser = Parser::new(Lexer::new(&file.name, &file.source));
+        let (ast, errors) = parser.parse();
+
+        diagnostics.extend(parser.diagnostics().iter().cloned());
+        diagnostics.extend(errors.into_iter().map(Diagnostic::from));
+
+        self.load_modules(ast.0, &mut file.modules.iter(), diagnostics)
+    }
+
+    /// Fill in the items of each `mod name;` among `items`, whose files are
+    /// the next ones of `modules`.
+    fn load_modules<'a>(
+        &'a self,
+        items: Vec<Spanned<'a, Item<'a, 'a>>>,
+        modules: &mut core::slice::Iter<'_, ModuleFile>,
+        diagnostics: &mut Vec<Diagnostic<'a>>,
+    ) -> Vec<Spanned<'a, Item<'a, 'a>>> {
+        items
+            .into_iter()
+            .map(|item| {
+                item.map(|item| match item {
+                    Item::Module(module) => {
+                        Item::Module(self.load_module(module, modules, diagnostics))
+                    }
+                    item => item,
+                })
+            })
+            .collect()
+    }
+
+    fn load_module<'a>(
+        &'a self,
+        mut module: Module<'a, 'a>,
+        modules: &mut core::slice::Iter<'_, ModuleFile>,
+        diagnostics: &mut Vec<Diagnostic<'a>>,
+    ) -> Module<'a, 'a> {
+        module.items = if let Some(items) = module.items {
+            Some(items.map(|items| self.load_modules(items, modules, diagnostics)))
+        } else {
+            let file = modules
+                .next()
+                .expect("expected the file of every module to have been looked for");
+            self.load_module_file(module.name, file, diagnostics)
+        };
+
+        module
+    }
+
+    /// Get the items of the module `name` from its `file`, reporting it if
+    /// the file could not be loaded.
+    fn load_module_file<'a>(
+        &'a self,
+        name: Spanned<'a, &str>,
+        file: &ModuleFile,
+        diagnostics: &mut Vec<Diagnostic<'a>>,
+    ) -> Option<Spanned<'a, Vec<Spanned<'a, Item<'a, 'a>>>>> {
+        match file {
+            ModuleFile::Loaded(index) => {
+                let items = self.parse_file(*index, diagnostics);
+
+                let start = Location::new(&self.files[*index].name);
+                let end = items.last().map_or(start, Spanned::end);
+                Some(Spanned::spanning(start, end, items))
+            }
+            ModuleFile::Missing(candidates) => {
+                let candidates = candidates
+                    .iter()
+                    .map(|candidate| format!("`{candidate}`"))
+                    .collect::<Vec<_>>();
+
+                diagnostics.push(
+                    Diagnostic::error(
+                        codes::MODULE_NOT_FOUND,
+                        format!("cannot find the file of module `{}`", name.data()),
+                        Label::new(name.span(), "declared here"),
+                    )
+                    .with_help(format!("create {}", candidates.join(" or "))),
+                );
+                None
+            }
+            ModuleFile::Cycle(path) => {
+                diagnostics.push(Diagnostic::error(
+                    codes::MODULE_CYCLE,
+                    format!("module `{}` is loaded into itself", name.data()),
+                    Label::new(name.span(), format!("`{path}` is already being loaded")),
+                ));
+                None
+            }
+            // Reported when the package is lowered, along with the other
+            // items defined more than once
+            ModuleFile::Duplicate => None,
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use tantalum_ast::{
+    FieldPattern, LiteralPattern, Pattern, RangePattern, VariantPattern, VariantPatternFields,
+};
+use tantalum_lexer::token_kind::TokenKind;
+use tantalum_span::Spanned;
+
+use crate::{error::ParseError, Parser};
+
+impl<'file_name, 'source> Parser<'file_name, 'source> {
+    pub const PATTERN_START: &'static [TokenKind] = &[
+        TokenKind::Identifier,
+        TokenKind::Minus,
+        TokenKind::BinaryIntegerLiteral,
+        TokenKind::OctalIntegerLiteral,
+        TokenKind::DecimalIntegerLiteral,
+        TokenKind::HexadecimalIntegerLiteral,
+        TokenKind::KeywordTrue,
+        TokenKind::KeywordFalse,
+        TokenKind::CharacterLiteral,
+    ];
+
+    /// Parse a pattern, including or-patterns such as `1 | 2`.
+    pub(crate) fn parse_pattern(
+        &mut self,
+    ) -> Result<Spanned<'file_name, Pattern<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let first = self.parse_pattern_alternative()?;
+
+        if self.is_at(TokenKind::Pipe).is_none() {
+            return Ok(first);
+        }
+
+        let mut alternatives = vec![first];
+        while self.advance_if(TokenKind::Pipe).is_some() {
+            alternatives.push(self.parse_pattern_alternative()?);
+        }
+
+        let start = alternatives[0].span();
+        let end = alternatives[alternatives.len() - 1].span();
+
+        Ok(Spanned::join_spans(start, end, Pattern::Or(alternatives)))
+    }
+
+    /// Parse a pattern that is not an or-pattern.
+    fn parse_pattern_alternative(
+        &mut self,
+    ) -> Result<Spanned<'file_name, Pattern<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let Some(token) = self.peek() else {
+            return Err(ParseError::unexpected_eof(self.source, self.eof));
+        };
+
+        match token.data().kind() {
+            TokenKind::Identifier
+                if self.nth(1).map(|token| token.data().kind()) == Some(TokenKind::ColonColon) =>
+            {
+                self.parse_pattern_variant()
+            }
+            TokenKind::Identifier => {
+                self.next();
+
+                if token.data().lexeme() == "_" {
+                    Ok(token.map(|_| Pattern::Wildcard))
+                } else {
+                    Ok(token.map(|_| Pattern::Binding(token.map(|name| name.lexeme()))))
+                }
+            }
+            kind if Self::PATTERN_START.contains(&kind) => {
+                let start = self.parse_pattern_literal()?;
+
+                let inclusive = if self.advance_if(TokenKind::DotDotEqual).is_some() {
+                    true
+                } else if self.advance_if(TokenKind::DotDot).is_some() {
+                    false
+                } else {
+                    return Ok(start.map(Pattern::Literal));
+                };
+
+                let end = self.parse_pattern_literal()?;
+
+                Ok(Spanned::join_spans(
+                    start.span(),
+                    end.span(),
+                    Pattern::Range(RangePattern {
+                        start,
+                        end,
+                        inclusive,
+                    }),
+                ))
+            }
+            kind => Err(ParseError::unexpected_token_set(
+                self.source,
+                token.start(),
+                kind,
+                Self::PATTERN_START,
+            )),
+        }
+    }
+
+    /// Parse a literal in a pattern, which may be negative.
+    fn parse_pattern_literal(
+        &mut self,
+    ) -> Result<
+        Spanned<'file_name, LiteralPattern<'file_name, 'source>>,
+        ParseError<'file_name, 'source>,
+    > {
+        let minus = self.advance_if(TokenKind::Minus);
+
+        let Some(token) = self.next() else {
+            return Err(ParseError::unexpected_eof(self.source, self.eof));
+        };
+        let literal = self.parse_literal(token)?;
+
+        let start = minus.map_or(literal.span(), |minus| minus.span());
+
+        Ok(Spanned::join_spans(
+            start,
+            literal.span(),
+            LiteralPattern {
+                negative: minus.is_some(),
+                literal: literal.data().clone(),
+            },
+        ))
+    }
+
+    /// Parse a pattern of a variant, such as `Shape::Circle(radius)`.
+    fn parse_pattern_variant(
+        &mut self,
+    ) -> Result<Spanned<'file_name, Pattern<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let path = self.parse_path()?;
+
+        let (fields, end) = if let Some(l_paren) = self.advance_if(TokenKind::LeftParen) {
+            let mut patterns = Vec::new();
+            while self.is_at(TokenKind::RightParen).is_none() {
+                patterns.push(self.parse_pattern()?);
+
+                // The comma after the last pattern is optional
+                if self.advance_if(TokenKind::Comma).is_none() {
+                    break;
+                }
+            }
+            let r_paren = self.expect(TokenKind::RightParen)?;
+
+            (
+                VariantPatternFields::Tuple(Spanned::join_spans(
+                    l_paren.span(),
+                    r_paren.span(),
+                    patterns,
+                )),
+                r_paren.span(),
+            )
+        } else if let Some(l_brace) = self.advance_if(TokenKind::LeftBrace) {
+            let mut fields = Vec::new();
+            let mut rest = false;
+            while self.is_at(TokenKind::RightBrace).is_none() {
+                // Nothing can follow the `..` allowing the other fields
+                if self.advance_if(TokenKind::DotDot).is_some() {
+                    rest = true;
+                    break;
+                }
+
+                let name = self.expect(TokenKind::Identifier)?;
+                let pattern = if self.advance_if(TokenKind::Colon).is_some() {
+                    Some(self.parse_pattern()?)
+                } else {
+                    None
+                };
+
+                fields.push(Spanned::join_spans(
+                    name.span(),
+                    pattern.as_ref().map_or(name.span(), Spanned::span),
+                    FieldPattern {
+                        name: name.map(|name| name.lexeme()),
+                        pattern,
+                    },
+                ));
+
+                // The comma after the last field is optional
+                if self.advance_if(TokenKind::Comma).is_none() {
+                    break;
+                }
+            }
+            let r_brace = self.expect(TokenKind::RightBrace)?;
+
+            (
+                VariantPatternFields::Struct {
+                    fields: Spanned::join_spans(l_brace.span(), r_brace.span(), fields),
+                    rest,
+                },
+                r_brace.span(),
+            )
+        } else {
+            (VariantPatternFields::Unit, path.span())
+        };
+
+        Ok(Spanned::join_spans(
+            path.span(),
+            end,
+            Pattern::Variant(VariantPattern { path, fields }),
+        ))
+    }
+}
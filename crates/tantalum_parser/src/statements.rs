@@ -1,4 +1,6 @@
-use tantalum_ast::{Block, ForInitCondUpdate, If, Return, Statement, VariableDeclaration, While};
+use tantalum_ast::{
+    Block, ForInitCondUpdate, If, Match, MatchArm, Return, Statement, VariableDeclaration, While,
+};
 use tantalum_lexer::token_kind::TokenKind;
 use tantalum_span::Spanned;
 
@@ -10,9 +12,11 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         TokenKind::KeywordIf,
         TokenKind::KeywordFor,
         TokenKind::KeywordWhile,
+        TokenKind::KeywordMatch,
         TokenKind::KeywordReturn,
         TokenKind::KeywordBreak,
         TokenKind::KeywordContinue,
+        TokenKind::KeywordType,
         TokenKind::LeftBrace,
     ];
 
@@ -35,11 +39,19 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
                 TokenKind::KeywordWhile => self
                     .parse_statement_while()
                     .map(|statement| statement.map(Statement::While)),
+                TokenKind::KeywordMatch => self
+                    .parse_statement_match()
+                    .map(|statement| statement.map(Statement::Match)),
                 TokenKind::KeywordReturn => self
                     .parse_statement_return()
                     .map(|statement| statement.map(Statement::Return)),
                 TokenKind::KeywordBreak => self.parse_statement_break(),
                 TokenKind::KeywordContinue => self.parse_statement_continue(),
+                TokenKind::KeywordType => {
+                    let type_token = self.expect(TokenKind::KeywordType)?;
+                    let alias = self.parse_type_alias(type_token)?;
+                    Ok(alias.map(Statement::TypeAlias))
+                }
                 TokenKind::LeftBrace => self
                     .parse_statement_block()
                     .map(|statement| statement.map(Statement::Block)),
@@ -76,6 +88,24 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         }
     }
 
+    /// Parse a statement, recording any syntax error and skipping to the end
+    /// of the statement so the rest of the block can still be parsed.
+    fn parse_statement_or_recover(
+        &mut self,
+    ) -> Spanned<'file_name, Statement<'file_name, 'source>> {
+        let start = self.position;
+
+        match self.parse_statement() {
+            Ok(statement) => statement,
+            Err(error) => {
+                self.errors.push(error);
+                self.synchronize_statement();
+
+                Spanned::new(self.span_since(start), Statement::Error)
+            }
+        }
+    }
+
     fn parse_statement_let(
         &mut self,
     ) -> Result<
@@ -113,7 +143,7 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
     ) -> Result<Spanned<'file_name, If<'file_name, 'source>>, ParseError<'file_name, 'source>> {
         let if_token = self.expect(TokenKind::KeywordIf)?;
 
-        let condition = self.parse_expression()?;
+        let condition = self.parse_condition()?;
         let body = self.parse_statement()?;
 
         let else_branch = if self.advance_if(TokenKind::KeywordElse).is_some() {
@@ -167,7 +197,7 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
     {
         let while_token = self.expect(TokenKind::KeywordWhile)?;
 
-        let condition = self.parse_expression()?;
+        let condition = self.parse_condition()?;
         let body = self.parse_statement()?;
 
         Ok(Spanned::join_spans(
@@ -180,6 +210,41 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         ))
     }
 
+    fn parse_statement_match(
+        &mut self,
+    ) -> Result<Spanned<'file_name, Match<'file_name, 'source>>, ParseError<'file_name, 'source>>
+    {
+        let match_token = self.expect(TokenKind::KeywordMatch)?;
+
+        let scrutinee = self.parse_condition()?;
+
+        self.expect(TokenKind::LeftBrace)?;
+
+        let mut arms = Vec::new();
+        while self.is_at(TokenKind::RightBrace).is_none() {
+            let pattern = self.parse_pattern()?;
+            self.expect(TokenKind::FatArrow)?;
+            let body = self.parse_statement()?;
+
+            arms.push(Spanned::join_spans(
+                pattern.span(),
+                body.span(),
+                MatchArm { pattern, body },
+            ));
+
+            // Arms may be separated by commas
+            self.advance_if(TokenKind::Comma);
+        }
+
+        let right_brace = self.expect(TokenKind::RightBrace)?;
+
+        Ok(Spanned::join_spans(
+            match_token.span(),
+            right_brace.span(),
+            Match { scrutinee, arms },
+        ))
+    }
+
     fn parse_statement_return(
         &mut self,
     ) -> Result<Spanned<'file_name, Return<'file_name, 'source>>, ParseError<'file_name, 'source>>
@@ -235,9 +300,15 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
     {
         let left_brace = self.expect(TokenKind::LeftBrace)?;
 
+        // A statement can never start with an item keyword other than `type`,
+        // so reaching one means the block is missing its closing brace
         let mut statements = Vec::new();
-        while self.is_at(TokenKind::RightBrace).is_none() {
-            statements.push(self.parse_statement()?);
+        while self.is_at(TokenKind::RightBrace).is_none()
+            && (self.is_at_any(Self::ITEM_START).is_none()
+                || self.is_at(TokenKind::KeywordType).is_some())
+            && !self.is_eof()
+        {
+            statements.push(self.parse_statement_or_recover());
         }
 
         let right_brace = self.expect(TokenKind::RightBrace)?;
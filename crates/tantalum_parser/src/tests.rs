@@ -1,3 +1,5 @@
 mod expression;
+mod package;
+mod recovery;
 mod statement;
 mod top_level;
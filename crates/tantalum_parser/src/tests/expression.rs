@@ -173,3 +173,56 @@ fn type_cast_with_binary_expression() {
 
     insta::assert_ron_snapshot!(result);
 }
+
+#[test]
+fn struct_literal() {
+    let lexer = Lexer::new("struct_literal", "Point { x: 1, y: a.b }.x");
+    let mut parser = Parser::new(lexer);
+
+    let result = parser.parse_expression();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn path_expression() {
+    let lexer = Lexer::new("path_expression", "Color::Red:u8");
+    let mut parser = Parser::new(lexer);
+
+    let result = parser.parse_expression();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn variant_literal_and_check() {
+    let lexer = Lexer::new(
+        "variant_literal_and_check",
+        "Shape::Rect { w: 1.0, h: 2.0 } is Shape::Rect && !(shape is Shape::Circle)",
+    );
+    let mut parser = Parser::new(lexer);
+
+    let result = parser.parse_expression();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn array_literals() {
+    let lexer = Lexer::new("array_literals", "[[1, 2], [x; 3], []][0]");
+    let mut parser = Parser::new(lexer);
+
+    let result = parser.parse_expression();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn array_length_too_large() {
+    let lexer = Lexer::new("array_length_too_large", "[0; 99999999999999999999999]");
+    let mut parser = Parser::new(lexer);
+
+    let result = parser.parse_expression();
+
+    insta::assert_ron_snapshot!(result);
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use tantalum_ast::Item;
+use tantalum_diagnostics::{Renderer, SourceMap};
+use tantalum_span::Spanned;
+
+use crate::package::SourceFiles;
+
+/// Load the package whose root file is `main.ta` from the in-memory `files`
+fn load(files: &[(&str, &str)]) -> SourceFiles {
+    let files = files.iter().copied().collect::<HashMap<_, _>>();
+
+    SourceFiles::load_with(Path::new("main.ta"), |path| {
+        path.to_str()
+            .and_then(|path| files.get(path))
+            .map(|source| (*source).to_string())
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    })
+    .expect("expected the root file to exist")
+}
+
+/// The names of the modules, structs, and functions among `items`
+fn outline(items: &[Spanned<'_, Item<'_, '_>>]) -> String {
+    items
+        .iter()
+        .map(|item| match item.data() {
+            Item::Module(module) => match &module.items {
+                Some(items) => {
+                    format!("mod {} {{ {} }}", module.name.data(), outline(items.data()))
+                }
+                None => format!("mod {};", module.name.data()),
+            },
+            Item::Struct(structure) => format!("struct {}", structure.name.data()),
+            Item::Function(function) => format!("fn {}", function.name.data()),
+            _ => "item".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn loads_module_files() {
+    let files = load(&[
+        (
+            "main.ta",
+            "mod shapes; mod util; mod nested { mod inner; } fn main() {}",
+        ),
+        ("shapes.ta", "mod circle; struct Point {}"),
+        ("circle.ta", "struct Circle {}"),
+        ("util/mod.ta", "mod math; fn helper() {}"),
+        ("util/math.ta", "fn add() {}"),
+        ("nested/inner.ta", "fn inner() {}"),
+    ]);
+
+    let (ast, diagnostics) = files.parse();
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    assert_eq!(files.files().len(), 6);
+    assert_eq!(
+        outline(&ast.0),
+        "mod shapes { mod circle { struct Circle } struct Point } \
+         mod util { mod math { fn add } fn helper } \
+         mod nested { mod inner { fn inner } } fn main"
+    );
+}
+
+#[test]
+fn missing_and_cyclic_module_files() {
+    let files = load(&[
+        ("main.ta", "mod missing;\nmod a;\nfn main() {}"),
+        ("a.ta", "mod main;\nfn a() {}"),
+    ]);
+
+    let (ast, diagnostics) = files.parse();
+
+    assert_eq!(
+        outline(&ast.0),
+        "mod missing; mod a { mod main; fn a } fn main"
+    );
+
+    let mut sources = SourceMap::new();
+    for file in files.files() {
+        sources.add(file.name(), file.source());
+    }
+    let renderer = Renderer::new(&sources);
+
+    insta::assert_snapshot!(diagnostics
+        .iter()
+        .map(|diagnostic| renderer.render(diagnostic))
+        .collect::<Vec<_>>()
+        .join("\n"));
+}
+
+#[test]
+fn duplicate_module_declarations_are_loaded_once() {
+    let files = load(&[
+        ("main.ta", "mod b; mod b; fn main() {}"),
+        ("b.ta", "fn f() {}"),
+    ]);
+
+    let (ast, diagnostics) = files.parse();
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    assert_eq!(files.files().len(), 2);
+    assert_eq!(outline(&ast.0), "mod b { fn f } mod b; fn main");
+}
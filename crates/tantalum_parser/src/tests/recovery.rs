@@ -0,0 +1,72 @@
+use tantalum_lexer::Lexer;
+
+use crate::Parser;
+
+#[test]
+fn recovers_at_statement_boundaries() {
+    let source = r"
+fn main(): i32 {
+    let x = 1 +;
+    let y = 2;
+    return ) y;
+}
+";
+    let mut parser = Parser::new(Lexer::new("recovers_at_statement_boundaries", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn recovers_at_block_end() {
+    let source = r"fn main() { foo( }";
+    let mut parser = Parser::new(Lexer::new("recovers_at_block_end", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn recovers_at_item_boundaries() {
+    let source = r"
+extern fn puts(s: *u8 i32;
+
+let x = 1;
+
+fn main() {}
+";
+    let mut parser = Parser::new(Lexer::new("recovers_at_item_boundaries", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn unclosed_block_before_item() {
+    let source = r"
+fn first() {
+    let x = 1;
+
+fn second() {}
+";
+    let mut parser = Parser::new(Lexer::new("unclosed_block_before_item", source));
+
+    let (ast, errors) = parser.parse();
+
+    assert_eq!(ast.0.len(), 2);
+    assert_eq!(errors.len(), 1);
+    insta::assert_snapshot!(errors[0].to_string());
+}
+
+#[test]
+fn missing_expression_placeholder() {
+    let source = r"fn main() { let x = ; }";
+    let mut parser = Parser::new(Lexer::new("missing_expression_placeholder", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!(result);
+}
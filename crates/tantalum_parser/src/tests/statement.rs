@@ -32,6 +32,26 @@ fn let_statement_with_type() {
     insta::assert_ron_snapshot!(result);
 }
 
+#[test]
+fn let_statement_with_sized_array() {
+    let source = "let grid: [[i32; 3]; 2] = [[0; 3]; 2];";
+    let mut parser = Parser::new(Lexer::new("let_statement_with_sized_array", source));
+
+    let result = parser.parse_statement();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn type_alias_statement() {
+    let source = "type Scale = f32;";
+    let mut parser = Parser::new(Lexer::new("type_alias_statement", source));
+
+    let result = parser.parse_statement();
+
+    insta::assert_ron_snapshot!(result);
+}
+
 #[test]
 fn if_statement() {
     let source = "if true { return 42; }";
@@ -132,3 +152,31 @@ fn block_statement() {
 
     insta::assert_ron_snapshot!(result);
 }
+
+#[test]
+fn if_statement_condition_is_not_struct_literal() {
+    let source = "if ready { (Point { x: 1 }).x; }";
+    let mut parser = Parser::new(Lexer::new(
+        "if_statement_condition_is_not_struct_literal",
+        source,
+    ));
+
+    let result = parser.parse_statement();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn match_statement() {
+    let source = r"match shape {
+    Shape::Circle(r) => return r;
+    Shape::Rect { w, h: 0 | -1, .. } => {}
+    'a'..='z' | 0..10 => x = 1;
+    _ => {}
+}";
+    let mut parser = Parser::new(Lexer::new("match_statement", source));
+
+    let result = parser.parse_statement();
+
+    insta::assert_ron_snapshot!(result);
+}
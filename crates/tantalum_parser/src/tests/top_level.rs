@@ -32,6 +32,26 @@ fn function_declaration_with_return_type() {
     insta::assert_ron_snapshot!(result);
 }
 
+#[test]
+fn generic_function_declaration() {
+    let source = r"fn max<T, U>(a: T, b: U): T {}";
+    let mut parser = Parser::new(Lexer::new("generic_function_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn generic_function_with_bounds() {
+    let source = r"fn show<T: Display + Debug>(value: T) {}";
+    let mut parser = Parser::new(Lexer::new("generic_function_with_bounds", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
 #[test]
 fn external_function_declaration() {
     let source = r"extern fn puts(s: *const u8): i32;";
@@ -54,3 +74,182 @@ fn external_function_declaration_with_variadic() {
 
     insta::assert_ron_snapshot!(result);
 }
+
+#[test]
+fn struct_declaration() {
+    let source = r"struct Point { x: i32, next: *Point, }";
+    let mut parser = Parser::new(Lexer::new("struct_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn enum_declaration() {
+    let source = r"enum Color: u8 { Red = 1, Green, Blue = -1 }";
+    let mut parser = Parser::new(Lexer::new("enum_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn enum_with_payloads() {
+    let source = r"enum Shape { Empty, Circle(f32), Rect { w: f32, h: f32 } }";
+    let mut parser = Parser::new(Lexer::new("enum_with_payloads", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn generic_struct_declaration() {
+    let source = r"struct Pair<A, B> { first: A, second: Option<Option<B>> }";
+    let mut parser = Parser::new(Lexer::new("generic_struct_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn generic_enum_declaration() {
+    let source = r"enum Option<T>: u8 { None, Some(T) }";
+    let mut parser = Parser::new(Lexer::new("generic_enum_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn trait_declaration() {
+    let source = r"trait Shape { fn area(self: *Self): f32; fn scale(self: *Self, by: f32); }";
+    let mut parser = Parser::new(Lexer::new("trait_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn impl_declaration() {
+    let source = r"impl Shape for Circle { fn area(self: *Self): f32 { return 0.0; } }";
+    let mut parser = Parser::new(Lexer::new("impl_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn inherent_impl_declaration() {
+    let source = r"impl Point { fn origin(): Point { return Point { x: 0, y: 0 }; } }";
+    let mut parser = Parser::new(Lexer::new("inherent_impl_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn trait_object_parameter() {
+    let source = r"fn draw(shape: *dyn Shape) {}";
+    let mut parser = Parser::new(Lexer::new("trait_object_parameter", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn module_declaration() {
+    let source = r"mod shapes { struct Circle { radius: f32 } fn unit(): shapes::Circle {} }";
+    let mut parser = Parser::new(Lexer::new("module_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn use_declaration() {
+    let source = r"use shapes::{Circle, Square as Quad};";
+    let mut parser = Parser::new(Lexer::new("use_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn type_alias_declaration() {
+    let source = r"type Points = *[Point];";
+    let mut parser = Parser::new(Lexer::new("type_alias_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn constant_declaration() {
+    let source = r"const LIMIT: i32 = 4 * 16;";
+    let mut parser = Parser::new(Lexer::new("constant_declaration", source));
+
+    let result = parser.parse_item();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn static_declarations() {
+    let source = r"
+static COUNTER: u32 = 0;
+extern static errno: i32;
+";
+    let mut parser = Parser::new(Lexer::new("static_declarations", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn comments_are_skipped() {
+    let source = r"
+/// The entry point
+fn main() {
+    // a line comment
+    /* a /* nested */ block comment */
+}
+";
+    let mut parser = Parser::new(Lexer::new("comments_are_skipped", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!(result);
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let source = r"fn main() {} /* never closed";
+    let mut parser = Parser::new(Lexer::new("unterminated_block_comment", source));
+
+    let result = parser.parse();
+
+    insta::assert_ron_snapshot!((result, parser.diagnostics()));
+}
+
+#[test]
+fn unexpected_token_diagnostic() {
+    let source = "fn main() {\n    return 0\n}\n";
+    let mut parser = Parser::new(Lexer::new("main.ta", source));
+
+    let (_, errors) = parser.parse();
+
+    insta::assert_snapshot!(errors[0].to_string());
+}
@@ -1,6 +1,8 @@
-use tantalum_ast::{ConstType, NamedType, PointerType, Type, UnsizedArrayType};
-use tantalum_lexer::token_kind::TokenKind;
-use tantalum_span::Spanned;
+use tantalum_ast::{
+    ConstType, DynType, NamedType, Path, PointerType, SizedArrayType, Type, UnsizedArrayType,
+};
+use tantalum_lexer::{token::Token, token_kind::TokenKind};
+use tantalum_span::{Location, Span, Spanned};
 
 use crate::{error::ParseError, Parser};
 
@@ -10,6 +12,7 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         TokenKind::LeftBracket,
         TokenKind::Star,
         TokenKind::KeywordConst,
+        TokenKind::KeywordDyn,
     ];
 
     pub(crate) fn parse_type(
@@ -19,22 +22,63 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
         let token = self.expect_any(Self::TYPE_START_SET)?;
 
         match token.data().kind() {
-            TokenKind::Identifier => Ok(token.map(|_| {
-                Type::Named(NamedType {
-                    name: token.map(|name| name.lexeme()),
-                })
-            })),
+            TokenKind::Identifier => {
+                // A type in a module is named by its path, e.g. `shapes::Circle`
+                let mut segments = vec![token.map(|segment| segment.lexeme())];
+                while self.advance_if(TokenKind::ColonColon).is_some() {
+                    let segment = self.expect(TokenKind::Identifier)?;
+                    segments.push(segment.map(|segment| segment.lexeme()));
+                }
+
+                let last = segments.last().map_or(token.span(), Spanned::span);
+                let name = Spanned::join_spans(token.span(), last, Path { segments });
+
+                if self.advance_if(TokenKind::LeftAngle).is_none() {
+                    return Ok(Spanned::new(
+                        name.span(),
+                        Type::Named(NamedType {
+                            name,
+                            arguments: Vec::new(),
+                        }),
+                    ));
+                }
+
+                let mut arguments = Vec::new();
+                while self.is_at_right_angle().is_none() {
+                    arguments.push(self.parse_type()?);
+
+                    if self.advance_if(TokenKind::Comma).is_none() {
+                        break;
+                    }
+                }
+                let r_angle = self.expect_right_angle()?;
+
+                Ok(Spanned::join_spans(
+                    token.span(),
+                    r_angle,
+                    Type::Named(NamedType { name, arguments }),
+                ))
+            }
             TokenKind::LeftBracket => {
                 let element_type = self.parse_type()?;
 
+                // `[T; N]` is an array of `N` elements, `[T]` one of any length
+                let size = if self.advance_if(TokenKind::Semicolon).is_some() {
+                    Some(self.parse_array_length()?)
+                } else {
+                    None
+                };
+
                 let r_bracket = self.expect_any(&[TokenKind::RightBracket])?;
 
+                let ty = Box::new(element_type);
                 Ok(Spanned::join_spans(
                     token.span(),
                     r_bracket.span(),
-                    Type::UnsizedArray(UnsizedArrayType {
-                        ty: Box::new(element_type),
-                    }),
+                    match size {
+                        Some(size) => Type::SizedArray(SizedArrayType { ty, size }),
+                        None => Type::UnsizedArray(UnsizedArrayType { ty }),
+                    },
                 ))
             }
             TokenKind::Star => {
@@ -59,7 +103,62 @@ impl<'file_name, 'source> Parser<'file_name, 'source> {
                     }),
                 ))
             }
+            TokenKind::KeywordDyn => {
+                let trait_name = self.expect(TokenKind::Identifier)?;
+
+                Ok(Spanned::join_spans(
+                    token.span(),
+                    trait_name.span(),
+                    Type::Dyn(DynType {
+                        trait_name: trait_name.map(|name| name.lexeme()),
+                    }),
+                ))
+            }
             _ => todo!(),
         }
     }
+
+    /// Parse the number of elements of an array, which is written as a
+    /// decimal integer.
+    pub(crate) fn parse_array_length(
+        &mut self,
+    ) -> Result<Spanned<'file_name, usize>, ParseError<'file_name, 'source>> {
+        let length = self.expect(TokenKind::DecimalIntegerLiteral)?;
+
+        match length.data().lexeme().parse() {
+            Ok(value) => Ok(Spanned::new(length.span(), value)),
+            Err(_) => Err(ParseError::invalid_array_length(
+                self.source,
+                length.start(),
+            )),
+        }
+    }
+
+    fn is_at_right_angle(&self) -> Option<Spanned<'file_name, Token<'source>>> {
+        self.is_at(TokenKind::RightAngle)
+            .or_else(|| self.is_at(TokenKind::RightAngleRightAngle))
+    }
+
+    /// Expect the `>` closing a list of type arguments, splitting a `>>` in
+    /// two so that nested lists like `Option<Option<i32>>` can be closed.
+    fn expect_right_angle(&mut self) -> Result<Span<'file_name>, ParseError<'file_name, 'source>> {
+        let Some(token) = self.is_at(TokenKind::RightAngleRightAngle) else {
+            return self.expect(TokenKind::RightAngle).map(|token| token.span());
+        };
+
+        let start = token.start();
+        let middle = Location::new_at(
+            start.file_name(),
+            start.position() + 1,
+            start.line(),
+            start.column() + 1,
+        );
+
+        self.tokens[self.position] = Spanned::new(
+            Span::new(middle, token.end()),
+            Token::new(&token.data().lexeme()[1..], TokenKind::RightAngle),
+        );
+
+        Ok(Span::new(start, middle))
+    }
 }
@@ -12,7 +12,8 @@ macro_rules! snapshot_from_file {
 
             let mut parser = Parser::new(lexer);
 
-            let ast = parser.parse();
+            let (ast, errors) = parser.parse();
+            assert!(errors.is_empty(), "failed to parse source: {errors:?}");
 
             assert_ron_snapshot!(ast);
         }